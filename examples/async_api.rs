@@ -15,7 +15,7 @@ async fn main() -> FalkorResult<()> {
         .build()
         .await?;
 
-    let mut graph = client.select_graph("imdb");
+    let graph = client.select_graph("imdb");
     let mut res = graph.query("MATCH (a:actor) return a").execute().await?;
     assert_eq!(res.data.len(), 1317);
 
@@ -11,9 +11,9 @@ fn main() -> FalkorResult<()> {
         .build()?;
 
     // Dataset is available in the 'resources' directory
-    let mut graph = client.select_graph("imdb");
+    let graph = client.select_graph("imdb");
 
-    let mut cloned_graph = client.copy_graph("imdb", "imdb_clone")?;
+    let cloned_graph = client.copy_graph("imdb", "imdb_clone")?;
 
     let mut res = graph.query("MATCH (a:actor) return a").execute()?;
     let mut clone_graph_res = cloned_graph.query("MATCH (a:actor) return a").execute()?;
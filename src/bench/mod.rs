@@ -0,0 +1,381 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! A small load-testing harness, driving a configurable concurrent query mix against a graph,
+//! and reporting latency percentiles and throughput.
+//!
+//! ```no_run
+//! use falkordb::{bench, FalkorClientBuilder};
+//! use std::time::Duration;
+//!
+//! let client = FalkorClientBuilder::new().build().expect("Could not create client");
+//! let scenario = bench::Scenario::new(client, "social")
+//!     .with_ro_query("lookup", "MATCH (p:Person {id: 1}) RETURN p", 9)
+//!     .with_query("insert", "CREATE (:Person {id: -1})", 1)
+//!     .with_concurrency(8)
+//!     .with_duration(Duration::from_secs(30));
+//!
+//! let report = bench::run(&scenario);
+//! println!("{:.0} queries/s, p99 {:?}", report.throughput, report.latency.p99);
+//! ```
+
+use crate::FalkorSyncClient;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A single query in a [`Scenario`]'s mix
+#[derive(Clone, Debug)]
+pub struct ScenarioQuery {
+    /// The name this query is reported under
+    pub name: String,
+    /// The query text
+    pub query: String,
+    /// The relative frequency of this query in the mix
+    pub weight: u32,
+    /// Whether this query is sent using GRAPH.RO_QUERY
+    pub read_only: bool,
+}
+
+/// Describes a load test: which queries to run, how often, and with how much concurrency
+#[derive(Clone)]
+pub struct Scenario {
+    client: FalkorSyncClient,
+    graph_name: String,
+    queries: Vec<ScenarioQuery>,
+    concurrency: usize,
+    duration: Option<Duration>,
+    iterations: usize,
+}
+
+impl Scenario {
+    /// Creates a new scenario for the specified graph, with no queries, a concurrency of 1, and 1000 iterations
+    ///
+    /// # Arguments
+    /// * `client`: The client used to send the queries, its connection pool size limits the effective concurrency
+    /// * `graph_name`: The graph to run the queries against
+    pub fn new<T: ToString>(
+        client: FalkorSyncClient,
+        graph_name: T,
+    ) -> Self {
+        Self {
+            client,
+            graph_name: graph_name.to_string(),
+            queries: vec![],
+            concurrency: 1,
+            duration: None,
+            iterations: 1000,
+        }
+    }
+
+    fn push_query(
+        mut self,
+        name: &str,
+        query: &str,
+        weight: u32,
+        read_only: bool,
+    ) -> Self {
+        self.queries.push(ScenarioQuery {
+            name: name.to_string(),
+            query: query.to_string(),
+            weight,
+            read_only,
+        });
+        self
+    }
+
+    /// Adds a query to the mix
+    ///
+    /// # Arguments
+    /// * `name`: The name this query is reported under
+    /// * `query`: The query text
+    /// * `weight`: The relative frequency of this query in the mix
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_query(
+        self,
+        name: &str,
+        query: &str,
+        weight: u32,
+    ) -> Self {
+        self.push_query(name, query, weight, false)
+    }
+
+    /// Adds a read-only query to the mix
+    ///
+    /// # Arguments
+    /// * `name`: The name this query is reported under
+    /// * `query`: The query text
+    /// * `weight`: The relative frequency of this query in the mix
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_ro_query(
+        self,
+        name: &str,
+        query: &str,
+        weight: u32,
+    ) -> Self {
+        self.push_query(name, query, weight, true)
+    }
+
+    /// Sets the number of worker threads sending queries concurrently
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_concurrency(
+        self,
+        concurrency: usize,
+    ) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            ..self
+        }
+    }
+
+    /// Runs the scenario for the specified duration, instead of a fixed number of iterations
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_duration(
+        self,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            duration: Some(duration),
+            ..self
+        }
+    }
+
+    /// Sets the total number of queries to send, across all workers
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_iterations(
+        self,
+        iterations: usize,
+    ) -> Self {
+        Self {
+            iterations,
+            duration: None,
+            ..self
+        }
+    }
+}
+
+/// Expands the weighted mix into a deterministic schedule of query indices, interleaving the queries
+fn schedule(queries: &[ScenarioQuery]) -> Vec<usize> {
+    let mut schedule = vec![];
+    let max_weight = queries.iter().map(|query| query.weight).max();
+    for round in 0..max_weight.unwrap_or_default() {
+        schedule.extend(
+            queries
+                .iter()
+                .enumerate()
+                .filter(|(_, query)| query.weight > round)
+                .map(|(idx, _)| idx),
+        );
+    }
+    schedule
+}
+
+/// Latency statistics for a set of queries
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LatencySummary {
+    /// The number of measured queries
+    pub count: usize,
+    /// The fastest query
+    pub min: Duration,
+    /// The mean latency
+    pub mean: Duration,
+    /// The median latency
+    pub p50: Duration,
+    /// The 90th percentile latency
+    pub p90: Duration,
+    /// The 99th percentile latency
+    pub p99: Duration,
+    /// The slowest query
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+
+        let percentile = |pct: usize| samples[((samples.len() * pct).div_ceil(100)).max(1) - 1];
+        Self {
+            count: samples.len(),
+            min: samples[0],
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// The results of a [`run`]
+#[derive(Clone, Debug, Default)]
+pub struct BenchReport {
+    /// The total number of queries sent, including failed ones
+    pub total_queries: usize,
+    /// The number of queries which returned an error
+    pub errors: usize,
+    /// The wall-clock duration of the run
+    pub elapsed: Duration,
+    /// Successful queries per second
+    pub throughput: f64,
+    /// Latency statistics over all successful queries
+    pub latency: LatencySummary,
+    /// Latency statistics per query name
+    pub per_query: HashMap<String, LatencySummary>,
+}
+
+/// Runs the scenario, blocking until it is complete
+///
+/// # Arguments
+/// * `scenario`: The [`Scenario`] to run
+///
+/// # Returns
+/// A [`BenchReport`] with the measured latencies and throughput
+pub fn run(scenario: &Scenario) -> BenchReport {
+    let schedule = schedule(&scenario.queries);
+    if schedule.is_empty() {
+        return BenchReport::default();
+    }
+
+    let counter = AtomicUsize::new(0);
+    let start = Instant::now();
+    let deadline = scenario.duration.map(|duration| start + duration);
+
+    let worker_results: Vec<(Vec<(usize, Duration)>, usize)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..scenario.concurrency)
+            .map(|_| {
+                scope.spawn(|| {
+                    let graph = scenario.client.select_graph(&scenario.graph_name);
+                    let mut samples = vec![];
+                    let mut errors = 0;
+                    loop {
+                        let iteration = counter.fetch_add(1, Ordering::Relaxed);
+                        let done = match deadline {
+                            Some(deadline) => Instant::now() >= deadline,
+                            None => iteration >= scenario.iterations,
+                        };
+                        if done {
+                            break;
+                        }
+
+                        let query_idx = schedule[iteration % schedule.len()];
+                        let query = &scenario.queries[query_idx];
+                        let query_start = Instant::now();
+                        let res = match query.read_only {
+                            true => graph.ro_query(&query.query).execute(),
+                            false => graph.query(query.query.as_str()).execute(),
+                        }
+                        .map(|res| res.data.count());
+
+                        match res {
+                            Ok(_) => samples.push((query_idx, query_start.elapsed())),
+                            Err(_) => errors += 1,
+                        }
+                    }
+                    (samples, errors)
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .filter_map(|worker| worker.join().ok())
+            .collect()
+    });
+    let elapsed = start.elapsed();
+
+    let errors = worker_results.iter().map(|(_, errors)| errors).sum();
+    let samples: Vec<(usize, Duration)> = worker_results
+        .into_iter()
+        .flat_map(|(samples, _)| samples)
+        .collect();
+
+    let mut per_query: HashMap<String, Vec<Duration>> = HashMap::new();
+    for (query_idx, latency) in &samples {
+        per_query
+            .entry(scenario.queries[*query_idx].name.clone())
+            .or_default()
+            .push(*latency);
+    }
+
+    BenchReport {
+        total_queries: samples.len() + errors,
+        errors,
+        elapsed,
+        throughput: samples.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        latency: LatencySummary::from_samples(samples.into_iter().map(|(_, l)| l).collect()),
+        per_query: per_query
+            .into_iter()
+            .map(|(name, samples)| (name, LatencySummary::from_samples(samples)))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_client;
+
+    #[test]
+    fn test_latency_summary() {
+        let summary =
+            LatencySummary::from_samples((1..=100).rev().map(Duration::from_millis).collect());
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p90, Duration::from_millis(90));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.max, Duration::from_millis(100));
+        assert_eq!(summary.mean, Duration::from_micros(50500));
+
+        assert_eq!(
+            LatencySummary::from_samples(vec![]),
+            LatencySummary::default()
+        );
+    }
+
+    #[test]
+    fn test_schedule() {
+        let query = |weight| ScenarioQuery {
+            name: String::new(),
+            query: String::new(),
+            weight,
+            read_only: true,
+        };
+        assert_eq!(schedule(&[query(3), query(1)]), vec![0, 1, 0, 0]);
+        assert_eq!(schedule(&[query(0), query(2)]), vec![1, 1]);
+        assert!(schedule(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_run() {
+        let scenario = Scenario::new(create_test_client(), "imdb")
+            .with_ro_query("count", "MATCH (n) RETURN count(n)", 3)
+            .with_ro_query("invalid", "NOT CYPHER", 1)
+            .with_concurrency(4)
+            .with_iterations(40);
+
+        let report = run(&scenario);
+        assert_eq!(report.total_queries, 40);
+        assert_eq!(report.errors, 10);
+        assert_eq!(report.per_query["count"].count, 30);
+        assert!(report.throughput > 0.0);
+    }
+}
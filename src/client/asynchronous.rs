@@ -4,20 +4,63 @@
  */
 
 use crate::{
-    client::{FalkorClientProvider, ProvidesSyncConnections},
+    client::{
+        capabilities::{
+            parse_client_protocol, parse_supported_commands, supported_index_types, Capabilities,
+            OPTIONAL_COMMANDS,
+        },
+        circuit::{CircuitBreaker, CircuitStats, ConnectionCircuitPass, GraphCircuits},
+        events::{ConnectionEvent, ConnectionEventListener, ConnectionEvents},
+        interceptor::{CommandInterceptor, InterceptorChain},
+        metrics::MetricsRecorder,
+        parse_query_memory_limit,
+        pool::{
+            AcquisitionDeadline, PoolMetrics, PoolSize, PoolStats, CLOSE_POLL_INTERVAL,
+            MAX_POOL_CAPACITY,
+        },
+        query_memory_limit_config_value,
+        rate_limit::{RateLimit, RateLimiter, RatePermit},
+        self_test::{
+            check_round_trip, parse_module_version, self_test_graph_name, SelfTestCheck,
+            SelfTestReport, SELF_TEST_CREATE_QUERY, SELF_TEST_INDEX_TIMEOUT,
+            SELF_TEST_ROUND_TRIP_QUERY,
+        },
+        sentinel::{spawn_failover_watcher, FailoverTarget},
+        slow_query::SlowQueryLog,
+        warm_up::WarmUpReport,
+        FalkorClientProvider, ProvidesSyncConnections, QUERY_MEMORY_LIMIT_CONFIG,
+    },
     connection::{
         asynchronous::{BorrowedAsyncConnection, FalkorAsyncConnection},
         blocking::FalkorSyncConnection,
     },
-    parser::{parse_config_hashmap, redis_value_as_untyped_string_vec},
-    AsyncGraph, ConfigValue, FalkorConnectionInfo, FalkorDBError, FalkorResult,
+    graph_schema::SchemaCache,
+    parser::{parse_config_hashmap, redis_value_as_string, redis_value_as_untyped_string_vec},
+    runtime, AsyncGraph, ConfigValue, EntityType, FalkorConnectionInfo, FalkorDBError,
+    FalkorResult, GraphSchema, IndexType, QueryRegistry, RetryPolicy,
 };
-use std::{collections::HashMap, sync::Arc};
-use tokio::{
-    runtime::{Handle, RuntimeFlavor},
-    sync::{mpsc, Mutex},
-    task,
+use std::{
+    collections::HashMap,
+    num::{NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, Instant},
 };
+use tokio::sync::{mpsc, Mutex};
+
+/// Custom transports can't be shared between concurrent queries, so only Redis connections can be multiplexed
+fn into_multiplexed(
+    conn: FalkorAsyncConnection
+) -> FalkorResult<redis::aio::MultiplexedConnection> {
+    match conn {
+        FalkorAsyncConnection::Redis(conn) => Ok(conn),
+        FalkorAsyncConnection::Transport(_) => Err(FalkorDBError::InvalidConnectionInfo(
+            "Multiplexing is only supported for Redis connections".to_string(),
+        )),
+    }
+}
 
 /// A user-opaque inner struct, containing the actual implementation of the asynchronous client
 /// The idea is that each member here is either Copy, or locked in some form, and the public struct only has an Arc to this struct
@@ -25,12 +68,116 @@ use tokio::{
 pub struct FalkorAsyncClientInner {
     _inner: Mutex<FalkorClientProvider>,
 
-    connection_pool_size: u8,
+    connection_pool_size: PoolSize,
+    pool_metrics: PoolMetrics,
     connection_pool_tx: mpsc::Sender<FalkorAsyncConnection>,
     connection_pool_rx: Mutex<mpsc::Receiver<FalkorAsyncConnection>>,
+    multiplexed: bool,
+    shared_connection: parking_lot::Mutex<Option<redis::aio::MultiplexedConnection>>,
+    test_on_borrow: AtomicBool,
+    single_connection: AtomicBool,
+    closed: AtomicBool,
+    pool_timeout: parking_lot::Mutex<Option<Duration>>,
+    log_redaction: AtomicBool,
+    query_registry: parking_lot::Mutex<Arc<QueryRegistry>>,
+    retry_policy: parking_lot::Mutex<Option<RetryPolicy>>,
+    strict_typing: AtomicBool,
+    schema_cache: SchemaCache,
+    graph_circuits: GraphCircuits,
+    connection_circuit: parking_lot::Mutex<Option<Arc<CircuitBreaker>>>,
+    rate_limiter: parking_lot::Mutex<Option<Arc<RateLimiter>>>,
+    metrics_recorder: parking_lot::Mutex<Option<Arc<dyn MetricsRecorder>>>,
+    interceptors: parking_lot::Mutex<Option<Arc<InterceptorChain>>>,
+    slow_query_log: parking_lot::Mutex<Option<Arc<SlowQueryLog>>>,
+    connection_events: ConnectionEvents,
+    address: String,
 }
 
 impl FalkorAsyncClientInner {
+    /// Returns the schema cache of the given graph, shared by all its handles
+    pub(crate) fn graph_schema(
+        self: &Arc<Self>,
+        graph_name: &str,
+    ) -> Arc<GraphSchema> {
+        self.schema_cache.get_or_create(graph_name, self.clone())
+    }
+
+    /// Returns the circuit breaker of the given graph, shared by all its handles, if per-graph circuit breakers are enabled
+    pub(crate) fn graph_circuit(
+        &self,
+        graph_name: &str,
+    ) -> Option<Arc<CircuitBreaker>> {
+        self.graph_circuits.get_or_create(graph_name)
+    }
+
+    /// Passes through the connection circuit breaker, if it is enabled, failing fast if it is open
+    fn pass_connection_circuit(&self) -> FalkorResult<ConnectionCircuitPass> {
+        ConnectionCircuitPass::acquire(self.connection_circuit.lock().clone())
+    }
+
+    pub(crate) fn pool_metrics(&self) -> &PoolMetrics {
+        &self.pool_metrics
+    }
+
+    /// The address of the server, as reported in the spans of queries
+    #[cfg(feature = "otel")]
+    pub(crate) fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub(crate) fn log_redaction(&self) -> bool {
+        self.log_redaction.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn strict_typing(&self) -> bool {
+        self.strict_typing.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn query_registry(&self) -> Arc<QueryRegistry> {
+        self.query_registry.lock().clone()
+    }
+
+    pub(crate) fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy.lock().clone()
+    }
+
+    pub(crate) fn metrics_recorder(&self) -> Option<Arc<dyn MetricsRecorder>> {
+        self.metrics_recorder.lock().clone()
+    }
+
+    pub(crate) fn interceptors(&self) -> Option<Arc<InterceptorChain>> {
+        self.interceptors.lock().clone()
+    }
+
+    pub(crate) fn slow_query_log(&self) -> Option<Arc<SlowQueryLog>> {
+        self.slow_query_log.lock().clone()
+    }
+
+    /// Replaces a connection which failed, reporting the loss and the reconnection attempt to the connection event listeners
+    pub(crate) async fn reconnect(
+        &self,
+        err: &FalkorDBError,
+    ) -> FalkorResult<FalkorAsyncConnection> {
+        self.report_lost_connection(err);
+        let res = self.get_async_connection().await;
+        self.connection_events
+            .emit(|| ConnectionEvent::ReconnectAttempted {
+                address: self.address.clone(),
+                succeeded: res.is_ok(),
+            });
+        res
+    }
+
+    fn report_lost_connection(
+        &self,
+        err: &FalkorDBError,
+    ) {
+        self.connection_events.emit(|| ConnectionEvent::Lost {
+            address: self.address.clone(),
+            reason: err.to_string(),
+        });
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -43,18 +190,220 @@ impl FalkorAsyncClientInner {
         &self,
         pool_owner: Arc<Self>,
     ) -> FalkorResult<BorrowedAsyncConnection> {
-        Ok(BorrowedAsyncConnection::new(
-            self.connection_pool_rx
-                .lock()
+        self.borrow_connection_with_deadline(pool_owner, None).await
+    }
+
+    /// Borrows a connection, if all are in use, waits for one to be returned until the deadline passes,
+    /// failing with [`FalkorDBError::PoolExhausted`] if none was, or with [`FalkorDBError::PoolTimeout`]
+    /// if the pool's acquisition timeout passed first
+    pub(crate) async fn borrow_connection_with_deadline(
+        &self,
+        pool_owner: Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<BorrowedAsyncConnection> {
+        let rate_limiter = self.rate_limiter.lock().clone();
+        self.borrow_connection_with_limiter(pool_owner, deadline, rate_limiter)
+            .await
+    }
+
+    /// Borrows a connection without taking a permit from the rate limiter, for maintenance such as warming up the pool,
+    /// which would otherwise stall whenever fewer commands may be in flight than there are connections
+    pub(crate) async fn borrow_connection_unlimited(
+        &self,
+        pool_owner: Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<BorrowedAsyncConnection> {
+        self.borrow_connection_with_limiter(pool_owner, deadline, None)
+            .await
+    }
+
+    async fn borrow_connection_with_limiter(
+        &self,
+        pool_owner: Arc<Self>,
+        deadline: Option<Instant>,
+        rate_limiter: Option<Arc<RateLimiter>>,
+    ) -> FalkorResult<BorrowedAsyncConnection> {
+        let started = Instant::now();
+        let mut circuit_pass = self.pass_connection_circuit()?;
+        let acquisition = AcquisitionDeadline::new(deadline, *self.pool_timeout.lock());
+        let rate_permit = RatePermit::acquire_async(rate_limiter, acquisition.at()).await?;
+        if self.multiplexed {
+            return self
+                .borrow_shared_connection(pool_owner, circuit_pass, rate_permit)
+                .await
+                .inspect(|_| self.pool_metrics.record_checkout(started));
+        }
+
+        let wait_for_idle = async {
+            let mut connection_pool_rx = self.connection_pool_rx.lock().await;
+            // Checked while holding the receiver, so no connection is opened after the pool was drained
+            if self.closed.load(Ordering::Relaxed) {
+                return Err(FalkorDBError::ClientClosed);
+            }
+            match connection_pool_rx.try_recv() {
+                Ok(conn) => Ok(Some(conn)),
+                // The pool was resized or had connections closed, so open a new one instead of waiting
+                Err(_) if self.connection_pool_size.try_reserve() => Ok(None),
+                Err(_) => {
+                    self.pool_metrics.record_wait();
+                    let wait_started = Instant::now();
+                    let conn = connection_pool_rx
+                        .recv()
+                        .await
+                        .ok_or(FalkorDBError::EmptyConnection)?;
+                    if let Some(recorder) = self.metrics_recorder() {
+                        recorder.record_pool_wait(wait_started.elapsed());
+                    }
+                    Ok(Some(conn))
+                }
+            }
+        };
+
+        let idle_conn = match acquisition.at() {
+            Some(deadline) => runtime::timeout_at(deadline, wait_for_idle)
                 .await
-                .recv()
+                .ok_or_else(|| {
+                    self.pool_metrics.record_timeout();
+                    if let Some(recorder) = self.metrics_recorder() {
+                        recorder.record_pool_timeout();
+                    }
+                    self.connection_events
+                        .emit(|| ConnectionEvent::PoolExhausted {
+                            waited: started.elapsed(),
+                        });
+                    acquisition.error()
+                })??,
+            None => wait_for_idle.await?,
+        };
+
+        let conn = match idle_conn {
+            Some(conn) if self.test_on_borrow.load(Ordering::Relaxed) => {
+                self.validate_idle_connection(conn).await
+            }
+            Some(conn) => Ok(conn),
+            None => self
+                .get_async_connection()
                 .await
-                .ok_or(FalkorDBError::EmptyConnection)?,
+                .inspect_err(|_| self.connection_pool_size.release()),
+        };
+        if conn.is_err() {
+            circuit_pass.record(&conn);
+        }
+
+        // Only counted once the borrow succeeded, as failed borrows are never checked back in
+        let conn = conn?;
+        self.pool_metrics.record_checkout(started);
+        Ok(BorrowedAsyncConnection::new(
+            conn,
             self.connection_pool_tx.clone(),
             pool_owner,
+            circuit_pass,
+            rate_permit,
         ))
     }
 
+    /// Pings an idle connection before handing it out, replacing it if it is broken, E.g. after the server restarted
+    async fn validate_idle_connection(
+        &self,
+        mut conn: FalkorAsyncConnection,
+    ) -> FalkorResult<FalkorAsyncConnection> {
+        let Err(err) = conn.execute_command(None, "PING", None, None).await else {
+            return Ok(conn);
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Replacing a broken pooled connection");
+        self.reconnect(&err)
+            .await
+            .inspect_err(|_| self.connection_pool_size.release())
+    }
+
+    /// Hands out a clone of the shared multiplexed connection, opening it if it was closed
+    async fn borrow_shared_connection(
+        &self,
+        pool_owner: Arc<Self>,
+        mut circuit_pass: ConnectionCircuitPass,
+        rate_permit: RatePermit,
+    ) -> FalkorResult<BorrowedAsyncConnection> {
+        if self.closed.load(Ordering::Relaxed) {
+            return Err(FalkorDBError::ClientClosed);
+        }
+        let shared_conn = self.shared_connection.lock().clone();
+        let conn = match shared_conn {
+            Some(conn) => conn,
+            None => {
+                let conn = self.get_async_connection().await;
+                if conn.is_err() {
+                    circuit_pass.record(&conn);
+                }
+                let conn = into_multiplexed(conn?)?;
+                self.shared_connection.lock().get_or_insert(conn).clone()
+            }
+        };
+
+        Ok(BorrowedAsyncConnection::shared(
+            FalkorAsyncConnection::Redis(conn),
+            pool_owner,
+            circuit_pass,
+            rate_permit,
+        ))
+    }
+
+    /// Closes the shared multiplexed connection after it went down, so the next borrow opens a new one
+    pub(crate) fn close_shared_connection(
+        &self,
+        err: &FalkorDBError,
+    ) {
+        if self.shared_connection.lock().take().is_some() {
+            self.report_lost_connection(err);
+        }
+    }
+
+    /// Gives up the pool slot of a connection which was closed instead of being returned, so it is reopened on demand
+    pub(crate) fn discard_connection(&self) {
+        self.connection_pool_size.release();
+    }
+
+    /// The current generation of the pool's connections, see [`PoolSize::generation`]
+    pub(crate) fn connection_generation(&self) -> u64 {
+        self.connection_pool_size.generation()
+    }
+
+    /// Returns whether a connection being returned should be closed instead, because the pool was shrunk
+    pub(crate) fn should_close_returned_connection(&self) -> bool {
+        self.connection_pool_size.try_shed()
+    }
+
+    fn close_excess_idle_connections(&self) {
+        // If the receiver is locked, someone is waiting for a connection, so none are idle
+        let Ok(mut connection_pool_rx) = self.connection_pool_rx.try_lock() else {
+            return;
+        };
+
+        while self.connection_pool_size.live() > self.connection_pool_size.target() {
+            let Ok(conn) = connection_pool_rx.try_recv() else {
+                break;
+            };
+            if !self.connection_pool_size.try_shed() {
+                self.connection_pool_tx.try_send(conn).ok();
+                break;
+            }
+        }
+    }
+
+    fn close_idle_connections(&self) -> usize {
+        let Ok(mut connection_pool_rx) = self.connection_pool_rx.try_lock() else {
+            return 0;
+        };
+
+        let mut closed = 0;
+        while connection_pool_rx.try_recv().is_ok() {
+            self.connection_pool_size.release();
+            closed += 1;
+        }
+        closed
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -64,7 +413,26 @@ impl FalkorAsyncClientInner {
         )
     )]
     pub(crate) async fn get_async_connection(&self) -> FalkorResult<FalkorAsyncConnection> {
-        self._inner.lock().await.get_async_connection().await
+        let conn = self._inner.lock().await.get_async_connection().await?;
+        self.connection_events
+            .emit(|| ConnectionEvent::Established {
+                address: self.address.clone(),
+            });
+        Ok(conn)
+    }
+}
+
+impl FailoverTarget for FalkorAsyncClientInner {
+    fn on_failover(
+        &self,
+        promoted: Option<String>,
+    ) {
+        // Borrowed connections are closed once returned, idle ones are closed right away
+        self.connection_pool_size.invalidate();
+        self.close_idle_connections();
+        self.shared_connection.lock().take();
+        self.connection_events
+            .emit(|| ConnectionEvent::FailoverCompleted { promoted });
     }
 }
 
@@ -78,11 +446,11 @@ impl ProvidesSyncConnections for FalkorAsyncClientInner {
         )
     )]
     fn get_connection(&self) -> FalkorResult<FalkorSyncConnection> {
-        let handle = Handle::try_current().map_err(|_| FalkorDBError::NoRuntime)?;
-        match handle.runtime_flavor() {
-            RuntimeFlavor::CurrentThread => Err(FalkorDBError::SingleThreadedRuntime),
-            _ => task::block_in_place(|| handle.block_on(self._inner.lock())).get_connection(),
-        }
+        runtime::block_on(self._inner.lock())?.get_connection()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy.lock().clone()
     }
 }
 
@@ -103,15 +471,24 @@ impl FalkorAsyncClient {
         mut client: FalkorClientProvider,
         connection_info: FalkorConnectionInfo,
         num_connections: u8,
+        multiplexed: bool,
+        lazy: bool,
     ) -> FalkorResult<Self> {
-        let (connection_pool_tx, connection_pool_rx) = mpsc::channel(num_connections as usize);
+        let (connection_pool_tx, connection_pool_rx) = mpsc::channel(MAX_POOL_CAPACITY);
 
-        // One already exists
-        for _ in 0..num_connections {
-            let new_conn = client
-                .get_async_connection()
-                .await
-                .map_err(|err| FalkorDBError::RedisError(err.to_string()))?;
+        // A multiplexed client shares a single connection instead of pooling them,
+        // a lazy client opens its connections on demand, the shared one is opened by the first borrower
+        let (shared_connection, pooled_connections) = match (multiplexed, lazy) {
+            (true, false) => (
+                Some(into_multiplexed(client.get_async_connection().await?)?),
+                0,
+            ),
+            (false, false) => (None, num_connections),
+            (_, true) => (None, 0),
+        };
+
+        for _ in 0..pooled_connections {
+            let new_conn = client.get_async_connection().await?;
 
             connection_pool_tx
                 .send(new_conn)
@@ -123,23 +500,347 @@ impl FalkorAsyncClient {
             inner: Arc::new(FalkorAsyncClientInner {
                 _inner: client.into(),
 
-                connection_pool_size: num_connections,
+                pool_metrics: PoolMetrics::default(),
+                connection_pool_size: PoolSize::new(
+                    match multiplexed {
+                        true => 1,
+                        false => num_connections,
+                    },
+                    pooled_connections,
+                ),
                 connection_pool_tx,
                 connection_pool_rx: Mutex::new(connection_pool_rx),
+                multiplexed,
+                shared_connection: parking_lot::Mutex::new(shared_connection),
+                test_on_borrow: AtomicBool::new(false),
+                single_connection: AtomicBool::new(false),
+                closed: AtomicBool::new(false),
+                pool_timeout: parking_lot::Mutex::new(None),
+                log_redaction: AtomicBool::new(false),
+                query_registry: parking_lot::Mutex::new(Arc::default()),
+                retry_policy: parking_lot::Mutex::new(None),
+                strict_typing: AtomicBool::new(false),
+                schema_cache: SchemaCache::default(),
+                graph_circuits: GraphCircuits::default(),
+                connection_circuit: parking_lot::Mutex::new(None),
+                rate_limiter: parking_lot::Mutex::new(None),
+                metrics_recorder: parking_lot::Mutex::new(None),
+                interceptors: parking_lot::Mutex::new(None),
+                slow_query_log: parking_lot::Mutex::new(None),
+                connection_events: ConnectionEvents::default(),
+                address: connection_info.address(),
             }),
             _connection_info: connection_info,
         })
     }
 
-    /// Get the max number of connections in the client's connection pool
+    /// Enables or disables masking of string and number literals in queries recorded by tracing spans,
+    /// this applies immediately to all graphs selected from this client, and to all of its clones
+    ///
+    /// # Arguments
+    /// * `enabled`: Whether query literals should be redacted, see [`redact_query_literals`](crate::redact_query_literals)
+    pub fn set_log_redaction(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner.log_redaction.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Enables or disables strict typing, failing property writes whose value the server coerced to another type,
+    /// this applies immediately to all graphs selected from this client, and to all of its clones
+    ///
+    /// # Arguments
+    /// * `enabled`: Whether coerced property types are treated as errors, see [`find_type_coercions`](crate::find_type_coercions)
+    pub fn set_strict_typing(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner.strict_typing.store(enabled, Ordering::Relaxed);
+    }
+
+    /// How long to wait for a pooled connection, see [`FalkorClientBuilder::with_pool_timeout`](crate::FalkorClientBuilder::with_pool_timeout)
+    pub(crate) fn set_pool_timeout(
+        &self,
+        timeout: Option<Duration>,
+    ) {
+        *self.inner.pool_timeout.lock() = timeout;
+    }
+
+    /// The named queries graphs selected from this client can run, see [`FalkorClientBuilder::with_query_registry`](crate::FalkorClientBuilder::with_query_registry)
+    pub(crate) fn set_query_registry(
+        &self,
+        registry: QueryRegistry,
+    ) {
+        *self.inner.query_registry.lock() = Arc::new(registry);
+    }
+
+    /// The policy idempotent operations are retried with, see [`FalkorClientBuilder::with_retry_policy`](crate::FalkorClientBuilder::with_retry_policy)
+    pub(crate) fn set_retry_policy(
+        &self,
+        policy: Option<RetryPolicy>,
+    ) {
+        *self.inner.retry_policy.lock() = policy;
+    }
+
+    /// Whether idle connections are pinged before being borrowed, see [`FalkorClientBuilder::with_test_on_borrow`](crate::FalkorClientBuilder::with_test_on_borrow)
+    pub(crate) fn set_test_on_borrow(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner.test_on_borrow.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether the client owns a single connection, see [`FalkorClientBuilder::with_single_connection`](crate::FalkorClientBuilder::with_single_connection)
+    pub(crate) fn set_single_connection(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner
+            .single_connection
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// Fails fast once connecting to the server failed repeatedly, see [`FalkorClientBuilder::with_connection_circuit_breaker`](crate::FalkorClientBuilder::with_connection_circuit_breaker)
+    pub(crate) fn set_connection_circuit_breaker(
+        &self,
+        failure_threshold: NonZeroU32,
+        cool_down: Duration,
+    ) {
+        *self.inner.connection_circuit.lock() =
+            Some(Arc::new(CircuitBreaker::new(failure_threshold, cool_down)));
+    }
+
+    /// Limits the commands sent concurrently and per second, see [`FalkorClientBuilder::with_rate_limit`](crate::FalkorClientBuilder::with_rate_limit)
+    pub(crate) fn set_rate_limit(
+        &self,
+        limit: RateLimit,
+    ) {
+        *self.inner.rate_limiter.lock() = Some(Arc::new(RateLimiter::new(limit)));
+    }
+
+    /// Reports the client's metrics to a recorder, see [`FalkorClientBuilder::with_metrics_recorder`](crate::FalkorClientBuilder::with_metrics_recorder)
+    pub(crate) fn set_metrics_recorder(
+        &self,
+        recorder: Arc<dyn MetricsRecorder>,
+    ) {
+        *self.inner.metrics_recorder.lock() = Some(recorder);
+    }
+
+    /// Passes every command through the interceptors, see [`FalkorClientBuilder::with_interceptor`](crate::FalkorClientBuilder::with_interceptor)
+    pub(crate) fn set_interceptors(
+        &self,
+        interceptors: Vec<Arc<dyn CommandInterceptor>>,
+    ) {
+        *self.inner.interceptors.lock() = InterceptorChain::new(interceptors);
+    }
+
+    /// Reports queries slower than the log's threshold, see [`FalkorClientBuilder::with_slow_query_log`](crate::FalkorClientBuilder::with_slow_query_log)
+    pub(crate) fn set_slow_query_log(
+        &self,
+        log: SlowQueryLog,
+    ) {
+        *self.inner.slow_query_log.lock() = Some(Arc::new(log));
+    }
+
+    /// Reports the client's connection events to a listener, see [`FalkorClientBuilder::with_connection_event_listener`](crate::FalkorClientBuilder::with_connection_event_listener)
+    pub(crate) fn add_connection_event_listener(
+        &self,
+        listener: ConnectionEventListener,
+    ) {
+        self.inner.connection_events.subscribe(listener);
+    }
+
+    /// Isolates the failures of each graph selected from now on, see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker)
+    pub(crate) fn set_graph_circuit_breaker(
+        &self,
+        failure_threshold: NonZeroU32,
+        cool_down: Duration,
+    ) {
+        self.inner
+            .graph_circuits
+            .configure(failure_threshold, cool_down);
+    }
+
+    /// Bounds the number of ids cached per schema type, in the schemas of graphs selected from now on
+    pub(crate) fn set_schema_cache_max_ids(
+        &self,
+        max_ids: Option<NonZeroUsize>,
+    ) {
+        self.inner.schema_cache.set_max_ids(max_ids);
+    }
+
+    /// Replaces the pool's connections whenever the sentinel announces that the master failed over
+    pub(crate) fn watch_sentinel_failovers(
+        &self,
+        sentinel_info: redis::ConnectionInfo,
+        master_name: String,
+    ) {
+        let target: Weak<dyn FailoverTarget> = Arc::downgrade(&self.inner) as _;
+        spawn_failover_watcher(sentinel_info, master_name, target);
+    }
+
+    /// Returns whether query literals are currently redacted when queries are logged
+    pub fn log_redaction(&self) -> bool {
+        self.inner.log_redaction()
+    }
+
+    /// Returns whether property writes currently fail when the server coerces the written value's type
+    pub fn strict_typing(&self) -> bool {
+        self.inner.strict_typing()
+    }
+
+    /// Subscribes to the client's connection events, such as connections being lost or the pool being exhausted,
+    /// E.g. to surface connectivity issues in a health endpoint.
+    /// The listener is invoked on the task which observed the event, so it should not block.
+    ///
+    /// # Arguments
+    /// * `listener`: The callback to invoke with every [`ConnectionEvent`] from now on
+    pub fn on_connection_event<F: Fn(&ConnectionEvent) + Send + Sync + 'static>(
+        &self,
+        listener: F,
+    ) {
+        self.add_connection_event_listener(Arc::new(listener));
+    }
+
+    /// Returns whether this client shares a single multiplexed connection between all queries, instead of pooling connections
+    /// See [`FalkorClientBuilder::with_multiplexing`](crate::FalkorClientBuilder::with_multiplexing)
+    pub fn is_multiplexed(&self) -> bool {
+        self.inner.multiplexed
+    }
+
+    /// Returns the state and counters of a graph's circuit breaker, see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker)
+    ///
+    /// # Arguments
+    /// * `graph_name`: The name of the graph
+    ///
+    /// # Returns
+    /// The [`CircuitStats`] of the graph, or [`None`] if circuit breakers are disabled, or the graph was never selected
+    pub fn graph_circuit_stats(
+        &self,
+        graph_name: &str,
+    ) -> Option<CircuitStats> {
+        self.inner.graph_circuits.stats(graph_name)
+    }
+
+    /// Returns a snapshot of the connection pool's occupancy and counters, E.g. to alert on pool exhaustion before it causes an outage
+    /// A multiplexed client counts its concurrent queries as connections in use.
+    ///
+    /// # Returns
+    /// The pool's [`PoolStats`]
+    pub fn pool_stats(&self) -> PoolStats {
+        PoolStats {
+            connection_circuit: self
+                .inner
+                .connection_circuit
+                .lock()
+                .as_ref()
+                .map(|circuit| circuit.stats()),
+            ..self
+                .inner
+                .pool_metrics
+                .stats(&self.inner.connection_pool_size)
+        }
+    }
+
+    /// Get the max number of connections in the client's connection pool, a multiplexed client always has a single one
     pub fn connection_pool_size(&self) -> u8 {
-        self.inner.connection_pool_size
+        self.inner.connection_pool_size.target()
+    }
+
+    /// Changes the number of connections in the client's connection pool at runtime
+    /// When growing, the new connections are opened immediately,
+    /// when shrinking, idle connections are closed immediately, and borrowed connections are closed once they are returned.
+    /// This has no effect on a multiplexed client, which shares a single connection.
+    ///
+    /// # Arguments
+    /// * `num_connections`: The new size of the connection pool
+    ///
+    /// # Returns
+    /// Nothing, or [`FalkorDBError::SingleConnectionMode`] if the client owns a single connection
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Resize Connection Pool", skip_all, level = "info")
+    )]
+    pub async fn resize_pool(
+        &self,
+        num_connections: NonZeroU8,
+    ) -> FalkorResult<()> {
+        if self.inner.single_connection.load(Ordering::Relaxed) {
+            return Err(FalkorDBError::SingleConnectionMode);
+        }
+        if self.inner.multiplexed {
+            return Ok(());
+        }
+        self.inner
+            .connection_pool_size
+            .set_target(num_connections.get());
+
+        while self.inner.connection_pool_size.try_reserve() {
+            let new_conn = self
+                .inner
+                .get_async_connection()
+                .await
+                .inspect_err(|_| self.inner.connection_pool_size.release())?;
+            self.inner
+                .connection_pool_tx
+                .send(new_conn)
+                .await
+                .map_err(|_| FalkorDBError::EmptyConnection)?;
+        }
+
+        self.inner.close_excess_idle_connections();
+        Ok(())
+    }
+
+    /// Closes all the connections currently idle in the connection pool, without changing its size
+    /// Connections are reopened on demand, once they are needed again.
+    ///
+    /// # Returns
+    /// The number of connections closed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Close Idle Connections", skip_all, level = "info")
+    )]
+    pub fn close_idle(&self) -> usize {
+        self.inner.close_idle_connections()
     }
 
     pub(crate) async fn borrow_connection(&self) -> FalkorResult<BorrowedAsyncConnection> {
         self.inner.borrow_connection(self.inner.clone()).await
     }
 
+    /// Closes the client gracefully, instead of relying on it being dropped during process exit:
+    /// borrowing connections fails with [`FalkorDBError::ClientClosed`] from now on, in-flight operations are waited for,
+    /// and every pooled connection is closed using `QUIT` once it is returned.
+    /// A multiplexed client sends `QUIT` over its shared connection right away, after the queries already sent over it.
+    /// This applies to all clones of the client, and waits for as long as connections are borrowed,
+    /// so result sets or other handles holding on to a connection must be dropped first.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Close Client", skip_all, level = "info")
+    )]
+    pub async fn close(&self) {
+        self.inner.closed.store(true, Ordering::Relaxed);
+
+        let shared_conn = self.inner.shared_connection.lock().take();
+        if let Some(conn) = shared_conn {
+            FalkorAsyncConnection::Redis(conn)
+                .execute_command(None, "QUIT", None, None)
+                .await
+                .ok();
+        }
+
+        let mut connection_pool_rx = self.inner.connection_pool_rx.lock().await;
+        while self.inner.connection_pool_size.live() > 0 {
+            if let Some(Some(mut conn)) =
+                runtime::timeout(CLOSE_POLL_INTERVAL, connection_pool_rx.recv()).await
+            {
+                conn.execute_command(None, "QUIT", None, None).await.ok();
+                self.inner.connection_pool_size.release();
+            }
+        }
+    }
+
     /// Return a list of graphs currently residing in the database
     ///
     /// # Returns
@@ -160,7 +861,7 @@ impl FalkorAsyncClient {
     ///
     /// # Arguments
     /// * `config_Key`: A [`String`] representation of a configuration's key.
-    /// The config key can also be "*", which will return ALL the configuration options.
+    ///   The config key can also be "*", which will return ALL the configuration options.
     ///
     /// # Returns
     /// A [`HashMap`] comprised of [`String`] keys, and [`ConfigValue`] values.
@@ -183,7 +884,7 @@ impl FalkorAsyncClient {
     ///
     /// # Arguments
     /// * `config_Key`: A [`String`] representation of a configuration's key.
-    /// The config key can also be "*", which will return ALL the configuration options.
+    ///   The config key can also be "*", which will return ALL the configuration options.
     /// * `value`: The new value to set, which is anything that can be converted into a [`ConfigValue`], namely string types and i64.
     #[cfg_attr(
         feature = "tracing",
@@ -205,6 +906,43 @@ impl FalkorAsyncClient {
             .await
     }
 
+    /// Returns the memory limit of each query, past which the server aborts the query
+    /// with [`FalkorDBError::QueryMemoryLimitExceeded`]
+    ///
+    /// # Returns
+    /// The limit in bytes, or [`None`] if queries are unlimited
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Get Query Memory Limit", skip_all, level = "info")
+    )]
+    pub async fn query_memory_limit(&self) -> FalkorResult<Option<NonZeroU64>> {
+        self.config_get(QUERY_MEMORY_LIMIT_CONFIG)
+            .await
+            .and_then(parse_query_memory_limit)
+    }
+
+    /// Sets the memory limit of each query, so heavy analytical queries can't exhaust the server's memory,
+    /// queries exceeding it are aborted with [`FalkorDBError::QueryMemoryLimitExceeded`]
+    /// The server enforces the limit for every query on its own, but only supports configuring it server-wide.
+    ///
+    /// # Arguments
+    /// * `limit`: The limit in bytes, or [`None`] to not limit queries
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Set Query Memory Limit", skip_all, level = "info")
+    )]
+    pub async fn set_query_memory_limit(
+        &self,
+        limit: Option<NonZeroU64>,
+    ) -> FalkorResult<()> {
+        self.config_set(
+            QUERY_MEMORY_LIMIT_CONFIG,
+            query_memory_limit_config_value(limit)?,
+        )
+        .await
+        .map(|_| ())
+    }
+
     /// Opens a graph context for queries and operations
     ///
     /// # Arguments
@@ -248,6 +986,182 @@ impl FalkorAsyncClient {
         Ok(self.select_graph(new_graph_name))
     }
 
+    /// Runs a battery of compatibility checks against the server, useful as a deployment smoke test
+    /// The checks ping the server, read the FalkorDB module version, and create a scratch graph,
+    /// on which they verify values are parsed as expected, and that an index can be created, before deleting it.
+    ///
+    /// # Returns
+    /// A [`SelfTestReport`] with the outcome of every check, failed checks do not stop the following ones
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Client Self Test", skip_all, level = "info")
+    )]
+    pub async fn self_test(&self) -> SelfTestReport {
+        let mut report = SelfTestReport::default();
+
+        let start = Instant::now();
+        let res = match self.borrow_connection().await {
+            Ok(conn) => conn
+                .execute_command(None, "PING", None, None)
+                .await
+                .and_then(redis_value_as_string),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::Ping, start, res);
+
+        let start = Instant::now();
+        let res = match self.borrow_connection().await {
+            Ok(conn) => conn
+                .execute_command(None, "MODULE", Some("LIST"), None)
+                .await
+                .and_then(parse_module_version),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::ModuleVersion, start, res);
+
+        let graph_name = self_test_graph_name();
+        let graph = self.select_graph(&graph_name);
+
+        let start = Instant::now();
+        let res = graph
+            .query(SELF_TEST_CREATE_QUERY)
+            .execute()
+            .await
+            .map(|_| format!("Created graph {graph_name}"));
+        report.record(SelfTestCheck::CreateGraph, start, res);
+
+        let start = Instant::now();
+        let res = match graph.ro_query(SELF_TEST_ROUND_TRIP_QUERY).execute().await {
+            Ok(mut res) => check_round_trip(res.data.next()),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::CompactRoundTrip, start, res);
+
+        let start = Instant::now();
+        let res = match graph
+            .create_index(
+                IndexType::Range,
+                EntityType::Node,
+                "SelfTest",
+                &["name"],
+                None,
+            )
+            .await
+        {
+            Ok(_) => graph
+                .wait_for_index("SelfTest", &["name"], SELF_TEST_INDEX_TIMEOUT)
+                .await
+                .map(|index| format!("Index on {} is operational", index.index_label)),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::IndexCreation, start, res);
+
+        let start = Instant::now();
+        let res = graph
+            .delete()
+            .await
+            .map(|_| format!("Deleted graph {graph_name}"));
+        report.record(SelfTestCheck::DropGraph, start, res);
+
+        report
+    }
+
+    /// Reports what the server supports, so callers can adapt their behavior up front, instead of probing with failing calls
+    /// This includes the negotiated protocol, the FalkorDB module version, the kinds of indices which can be created,
+    /// and which of the optional commands, such as `GRAPH.COPY` or `GRAPH.UDF`, are available.
+    ///
+    /// # Returns
+    /// The server's [`Capabilities`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Client Capabilities", skip_all, level = "info")
+    )]
+    pub async fn capabilities(&self) -> FalkorResult<Capabilities> {
+        let mut conn = self.borrow_connection().await?;
+        let res = async {
+            let inner = conn.as_inner()?;
+            let module_version = inner
+                .execute_command(None, "MODULE", Some("LIST"), None)
+                .await
+                .and_then(parse_module_version)?;
+            let commands = inner
+                .execute_command(None, "COMMAND", Some("INFO"), Some(OPTIONAL_COMMANDS))
+                .await
+                .and_then(parse_supported_commands)?;
+            let protocol = inner
+                .execute_command(None, "CLIENT", Some("INFO"), None)
+                .await
+                .ok()
+                .and_then(parse_client_protocol);
+
+            Ok(Capabilities {
+                protocol,
+                index_types: supported_index_types(&module_version),
+                module_version,
+                commands,
+            })
+        }
+        .await;
+
+        conn.return_to_pool().await;
+
+        res
+    }
+
+    /// Opens and validates every pooled connection, for services which want to verify connectivity at startup
+    /// Each connection is borrowed, opening it if needed, and pinged, all of them are held until every one was checked,
+    /// so each check uses a distinct connection.
+    /// Every connection is attempted and reported, those left once the timeout passed fail right away,
+    /// and the rate limiter is bypassed, as it would otherwise hold back connections beyond its limit.
+    ///
+    /// # Arguments
+    /// * `timeout`: How long to wait overall for pooled connections and their replies, so a busy pool or an unresponsive server fails fast,
+    ///   opening each connection is also bounded by the connect timeout, see [`FalkorClientBuilder::with_connect_timeout`](crate::FalkorClientBuilder::with_connect_timeout)
+    ///
+    /// # Returns
+    /// A [`WarmUpReport`] with the outcome of every connection,
+    /// or [`FalkorDBError::WarmUpFailed`] carrying the same report if any of them failed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Warm Up Connection Pool", skip_all, level = "info")
+    )]
+    pub async fn warm_up(
+        &self,
+        timeout: Duration,
+    ) -> FalkorResult<WarmUpReport> {
+        let deadline = Instant::now() + timeout;
+        let mut report = WarmUpReport::new(self.connection_pool_size() as usize);
+        let mut borrowed = Vec::with_capacity(self.connection_pool_size() as usize);
+
+        for _ in 0..self.connection_pool_size() {
+            let start = Instant::now();
+            let res = match self
+                .inner
+                .borrow_connection_unlimited(self.inner.clone(), Some(deadline))
+                .await
+            {
+                Ok(mut conn) => match conn.as_inner() {
+                    Ok(inner) => runtime::timeout_at(
+                        deadline,
+                        inner.execute_command(None, "PING", None, None),
+                    )
+                    .await
+                    .unwrap_or(Err(FalkorDBError::DeadlineExceeded))
+                    .map(|_| ()),
+                    Err(err) => Err(err),
+                }
+                .map(|_| borrowed.push(conn)),
+                Err(err) => Err(err),
+            };
+            report.record(start, res);
+        }
+
+        for conn in borrowed {
+            conn.return_to_pool().await;
+        }
+        report.into_result()
+    }
+
     /// Retrieves redis information
     #[cfg_attr(
         feature = "tracing",
@@ -274,8 +1188,8 @@ mod tests {
         test_utils::{create_async_test_client, TestAsyncGraphHandle},
         FalkorClientBuilder,
     };
-    use std::{mem, num::NonZeroU8, thread};
-    use tokio::sync::mpsc::error::TryRecvError;
+    use std::{mem, thread};
+    use tokio::{sync::mpsc::error::TryRecvError, task};
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_borrow_connection() {
@@ -301,6 +1215,72 @@ mod tests {
         };
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_resize_pool_and_close_idle() {
+        let client = FalkorClientBuilder::new_async()
+            .with_num_connections(NonZeroU8::new(4).expect("Could not create a perfectly valid u8"))
+            .build()
+            .await
+            .expect("Could not create client for this test");
+
+        client
+            .resize_pool(NonZeroU8::new(6).expect("Could not create a perfectly valid u8"))
+            .await
+            .expect("Could not grow pool");
+        assert_eq!(client.connection_pool_size(), 6);
+        assert_eq!(client.inner.connection_pool_size.live(), 6);
+
+        client
+            .resize_pool(NonZeroU8::new(2).expect("Could not create a perfectly valid u8"))
+            .await
+            .expect("Could not shrink pool");
+        assert_eq!(client.connection_pool_size(), 2);
+        assert_eq!(client.inner.connection_pool_size.live(), 2);
+
+        assert_eq!(client.close_idle(), 2);
+        assert_eq!(client.inner.connection_pool_size.live(), 0);
+
+        // Connections are reopened on demand
+        assert!(client.list_graphs().await.is_ok());
+        assert_eq!(client.inner.connection_pool_size.live(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_multiplexed_client() {
+        let client = FalkorClientBuilder::new_async()
+            .with_multiplexing(true)
+            .build()
+            .await
+            .expect("Could not create client for this test");
+        assert!(client.is_multiplexed());
+        assert_eq!(client.connection_pool_size(), 1);
+
+        // Far more concurrent borrowers than a pool would hold, all sharing the same socket
+        let mut conn_vec = Vec::with_capacity(64);
+        for _ in 0..64 {
+            conn_vec.push(
+                client
+                    .borrow_connection()
+                    .await
+                    .expect("Could not borrow connection"),
+            );
+        }
+        let mut tasks = task::JoinSet::new();
+        for conn in conn_vec {
+            tasks.spawn(async move { conn.execute_command(None, "PING", None, None).await });
+        }
+        while let Some(reply) = tasks.join_next().await {
+            assert!(reply.expect("Task panicked").is_ok());
+        }
+        assert!(client
+            .inner
+            .connection_pool_rx
+            .lock()
+            .await
+            .try_recv()
+            .is_err());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_list_graphs() {
         let client = create_async_test_client().await;
@@ -315,7 +1295,7 @@ mod tests {
     async fn test_select_graph_and_query() {
         let client = create_async_test_client().await;
 
-        let mut graph = client.select_graph("imdb");
+        let graph = client.select_graph("imdb");
         assert_eq!(graph.graph_name(), "imdb".to_string());
 
         let res = graph
@@ -340,11 +1320,11 @@ mod tests {
         let graph = client.copy_graph("imdb", "imdb_ro_copy_async").await;
         assert!(graph.is_ok());
 
-        let mut graph = TestAsyncGraphHandle {
+        let graph = TestAsyncGraphHandle {
             inner: graph.unwrap(),
         };
 
-        let mut original_graph = client.select_graph("imdb");
+        let original_graph = client.select_graph("imdb");
 
         assert_eq!(
             graph
@@ -4,15 +4,48 @@
  */
 
 use crate::{
-    client::{FalkorClientProvider, ProvidesSyncConnections},
+    client::{
+        capabilities::{
+            parse_client_protocol, parse_supported_commands, supported_index_types, Capabilities,
+            OPTIONAL_COMMANDS,
+        },
+        circuit::{CircuitBreaker, CircuitStats, ConnectionCircuitPass, GraphCircuits},
+        events::{ConnectionEvent, ConnectionEventListener, ConnectionEvents},
+        interceptor::{CommandInterceptor, InterceptorChain},
+        metrics::MetricsRecorder,
+        parse_query_memory_limit,
+        pool::{
+            AcquisitionDeadline, PoolMetrics, PoolSize, PoolStats, CLOSE_POLL_INTERVAL,
+            MAX_POOL_CAPACITY,
+        },
+        query_memory_limit_config_value,
+        rate_limit::{RateLimit, RateLimiter, RatePermit},
+        self_test::{
+            check_round_trip, parse_module_version, self_test_graph_name, SelfTestCheck,
+            SelfTestReport, SELF_TEST_CREATE_QUERY, SELF_TEST_INDEX_TIMEOUT,
+            SELF_TEST_ROUND_TRIP_QUERY,
+        },
+        sentinel::{spawn_failover_watcher, FailoverTarget},
+        slow_query::SlowQueryLog,
+        warm_up::WarmUpReport,
+        ConnectionTimeouts, FalkorClientProvider, ProvidesSyncConnections,
+        QUERY_MEMORY_LIMIT_CONFIG,
+    },
     connection::blocking::{BorrowedSyncConnection, FalkorSyncConnection},
-    parser::{parse_config_hashmap, redis_value_as_untyped_string_vec},
-    ConfigValue, FalkorConnectionInfo, FalkorDBError, FalkorResult, SyncGraph,
+    graph_schema::SchemaCache,
+    parser::{parse_config_hashmap, redis_value_as_string, redis_value_as_untyped_string_vec},
+    ConfigValue, EntityType, FalkorConnectionInfo, FalkorDBError, FalkorResult, GraphSchema,
+    IndexType, QueryRegistry, RetryPolicy, SyncGraph,
 };
 use parking_lot::Mutex;
 use std::{
     collections::HashMap,
-    sync::{mpsc, Arc},
+    num::{NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Weak,
+    },
+    time::{Duration, Instant},
 };
 
 /// A user-opaque inner struct, containing the actual implementation of the blocking client
@@ -21,12 +54,113 @@ use std::{
 pub(crate) struct FalkorSyncClientInner {
     _inner: Mutex<FalkorClientProvider>,
 
-    connection_pool_size: u8,
+    connection_pool_size: PoolSize,
+    pool_metrics: PoolMetrics,
     connection_pool_tx: mpsc::SyncSender<FalkorSyncConnection>,
     connection_pool_rx: Mutex<mpsc::Receiver<FalkorSyncConnection>>,
+    socket_timeouts: ConnectionTimeouts,
+    test_on_borrow: AtomicBool,
+    single_connection: AtomicBool,
+    closed: AtomicBool,
+    pool_timeout: Mutex<Option<Duration>>,
+    log_redaction: AtomicBool,
+    query_registry: Mutex<Arc<QueryRegistry>>,
+    retry_policy: Mutex<Option<RetryPolicy>>,
+    strict_typing: AtomicBool,
+    schema_cache: SchemaCache,
+    graph_circuits: GraphCircuits,
+    connection_circuit: Mutex<Option<Arc<CircuitBreaker>>>,
+    rate_limiter: Mutex<Option<Arc<RateLimiter>>>,
+    metrics_recorder: Mutex<Option<Arc<dyn MetricsRecorder>>>,
+    interceptors: Mutex<Option<Arc<InterceptorChain>>>,
+    slow_query_log: Mutex<Option<Arc<SlowQueryLog>>>,
+    connection_events: ConnectionEvents,
+    address: String,
 }
 
 impl FalkorSyncClientInner {
+    /// Returns the schema cache of the given graph, shared by all its handles
+    pub(crate) fn graph_schema(
+        self: &Arc<Self>,
+        graph_name: &str,
+    ) -> Arc<GraphSchema> {
+        self.schema_cache.get_or_create(graph_name, self.clone())
+    }
+
+    /// Returns the circuit breaker of the given graph, shared by all its handles, if per-graph circuit breakers are enabled
+    pub(crate) fn graph_circuit(
+        &self,
+        graph_name: &str,
+    ) -> Option<Arc<CircuitBreaker>> {
+        self.graph_circuits.get_or_create(graph_name)
+    }
+
+    /// Passes through the connection circuit breaker, if it is enabled, failing fast if it is open
+    fn pass_connection_circuit(&self) -> FalkorResult<ConnectionCircuitPass> {
+        ConnectionCircuitPass::acquire(self.connection_circuit.lock().clone())
+    }
+
+    pub(crate) fn pool_metrics(&self) -> &PoolMetrics {
+        &self.pool_metrics
+    }
+
+    /// The address of the server, as reported in the spans of queries
+    #[cfg(feature = "otel")]
+    pub(crate) fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub(crate) fn log_redaction(&self) -> bool {
+        self.log_redaction.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn strict_typing(&self) -> bool {
+        self.strict_typing.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn query_registry(&self) -> Arc<QueryRegistry> {
+        self.query_registry.lock().clone()
+    }
+
+    pub(crate) fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy.lock().clone()
+    }
+
+    pub(crate) fn metrics_recorder(&self) -> Option<Arc<dyn MetricsRecorder>> {
+        self.metrics_recorder.lock().clone()
+    }
+
+    pub(crate) fn interceptors(&self) -> Option<Arc<InterceptorChain>> {
+        self.interceptors.lock().clone()
+    }
+
+    pub(crate) fn slow_query_log(&self) -> Option<Arc<SlowQueryLog>> {
+        self.slow_query_log.lock().clone()
+    }
+
+    /// Replaces a connection which failed, reporting the loss and the reconnection attempt to the connection event listeners
+    pub(crate) fn reconnect(
+        &self,
+        err: &FalkorDBError,
+    ) -> FalkorResult<FalkorSyncConnection> {
+        self.connection_events.emit(|| ConnectionEvent::Lost {
+            address: self.address.clone(),
+            reason: err.to_string(),
+        });
+        let res = self.get_connection();
+        self.connection_events
+            .emit(|| ConnectionEvent::ReconnectAttempted {
+                address: self.address.clone(),
+                succeeded: res.is_ok(),
+            });
+        res
+    }
+
+    /// The timeouts configured for the pool's connections, restored after commands that use shorter ones
+    pub(crate) fn socket_timeouts(&self) -> ConnectionTimeouts {
+        self.socket_timeouts
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -39,15 +173,191 @@ impl FalkorSyncClientInner {
         &self,
         pool_owner: Arc<Self>,
     ) -> FalkorResult<BorrowedSyncConnection> {
+        self.borrow_connection_with_deadline(pool_owner, None)
+    }
+
+    /// Borrows a connection, if all are in use, waits for one to be returned until the deadline passes,
+    /// failing with [`FalkorDBError::PoolExhausted`] if none was, or with [`FalkorDBError::PoolTimeout`]
+    /// if the pool's acquisition timeout passed first
+    pub(crate) fn borrow_connection_with_deadline(
+        &self,
+        pool_owner: Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<BorrowedSyncConnection> {
+        let rate_limiter = self.rate_limiter.lock().clone();
+        self.borrow_connection_with_limiter(pool_owner, deadline, rate_limiter)
+    }
+
+    /// Borrows a connection without taking a permit from the rate limiter, for maintenance such as warming up the pool,
+    /// which would otherwise stall whenever fewer commands may be in flight than there are connections
+    pub(crate) fn borrow_connection_unlimited(
+        &self,
+        pool_owner: Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<BorrowedSyncConnection> {
+        self.borrow_connection_with_limiter(pool_owner, deadline, None)
+    }
+
+    fn borrow_connection_with_limiter(
+        &self,
+        pool_owner: Arc<Self>,
+        deadline: Option<Instant>,
+        rate_limiter: Option<Arc<RateLimiter>>,
+    ) -> FalkorResult<BorrowedSyncConnection> {
+        let started = Instant::now();
+        let mut circuit_pass = self.pass_connection_circuit()?;
+        let acquisition = AcquisitionDeadline::new(deadline, *self.pool_timeout.lock());
+        let deadline = acquisition.at();
+        let rate_permit = RatePermit::acquire(rate_limiter, deadline)?;
+        let exhausted = || {
+            self.pool_metrics.record_timeout();
+            if let Some(recorder) = self.metrics_recorder() {
+                recorder.record_pool_timeout();
+            }
+            self.connection_events
+                .emit(|| ConnectionEvent::PoolExhausted {
+                    waited: started.elapsed(),
+                });
+            acquisition.error()
+        };
+
+        let idle_conn = {
+            let connection_pool_rx = match deadline {
+                Some(deadline) => self
+                    .connection_pool_rx
+                    .try_lock_until(deadline)
+                    .ok_or_else(exhausted)?,
+                None => self.connection_pool_rx.lock(),
+            };
+            // Checked while holding the receiver, so no connection is opened after the pool was drained
+            if self.closed.load(Ordering::Relaxed) {
+                return Err(FalkorDBError::ClientClosed);
+            }
+            match connection_pool_rx.try_recv() {
+                Ok(conn) => Some(conn),
+                // The pool was resized or had connections closed, so open a new one instead of waiting
+                Err(_) if self.connection_pool_size.try_reserve() => None,
+                Err(_) => Some({
+                    self.pool_metrics.record_wait();
+                    let wait_started = Instant::now();
+                    let conn = match deadline {
+                        Some(deadline) => connection_pool_rx
+                            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                            .map_err(|err| match err {
+                                mpsc::RecvTimeoutError::Timeout => exhausted(),
+                                mpsc::RecvTimeoutError::Disconnected => {
+                                    FalkorDBError::EmptyConnection
+                                }
+                            })?,
+                        None => connection_pool_rx
+                            .recv()
+                            .map_err(|_| FalkorDBError::EmptyConnection)?,
+                    };
+                    if let Some(recorder) = self.metrics_recorder() {
+                        recorder.record_pool_wait(wait_started.elapsed());
+                    }
+                    conn
+                }),
+            }
+        };
+
+        let conn = match idle_conn {
+            Some(conn) if self.test_on_borrow.load(Ordering::Relaxed) => {
+                self.validate_idle_connection(conn)
+            }
+            Some(conn) => Ok(conn),
+            None => self
+                .get_connection()
+                .inspect_err(|_| self.connection_pool_size.release()),
+        };
+        if conn.is_err() {
+            circuit_pass.record(&conn);
+        }
+
+        // Only counted once the borrow succeeded, as failed borrows are never checked back in
+        let conn = conn?;
+        self.pool_metrics.record_checkout(started);
         Ok(BorrowedSyncConnection::new(
-            self.connection_pool_rx
-                .lock()
-                .recv()
-                .map_err(|_| FalkorDBError::EmptyConnection)?,
+            conn,
             self.connection_pool_tx.clone(),
             pool_owner,
+            circuit_pass,
+            rate_permit,
         ))
     }
+
+    /// Pings an idle connection before handing it out, replacing it if it is broken, E.g. after the server restarted
+    fn validate_idle_connection(
+        &self,
+        mut conn: FalkorSyncConnection,
+    ) -> FalkorResult<FalkorSyncConnection> {
+        let Err(err) = conn.execute_command(None, "PING", None, None) else {
+            return Ok(conn);
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Replacing a broken pooled connection");
+        self.reconnect(&err)
+            .inspect_err(|_| self.connection_pool_size.release())
+    }
+
+    /// The current generation of the pool's connections, see [`PoolSize::generation`]
+    pub(crate) fn connection_generation(&self) -> u64 {
+        self.connection_pool_size.generation()
+    }
+
+    /// Gives up the pool slot of a connection which was closed instead of being returned, so it is reopened on demand
+    pub(crate) fn discard_connection(&self) {
+        self.connection_pool_size.release();
+    }
+
+    /// Returns whether a connection being returned should be closed instead, because the pool was shrunk
+    pub(crate) fn should_close_returned_connection(&self) -> bool {
+        self.connection_pool_size.try_shed()
+    }
+
+    fn close_excess_idle_connections(&self) {
+        // If the receiver is locked, someone is waiting for a connection, so none are idle
+        let Some(connection_pool_rx) = self.connection_pool_rx.try_lock() else {
+            return;
+        };
+
+        while self.connection_pool_size.live() > self.connection_pool_size.target() {
+            let Ok(conn) = connection_pool_rx.try_recv() else {
+                break;
+            };
+            if !self.connection_pool_size.try_shed() {
+                self.connection_pool_tx.send(conn).ok();
+                break;
+            }
+        }
+    }
+
+    fn close_idle_connections(&self) -> usize {
+        let Some(connection_pool_rx) = self.connection_pool_rx.try_lock() else {
+            return 0;
+        };
+
+        let mut closed = 0;
+        while connection_pool_rx.try_recv().is_ok() {
+            self.connection_pool_size.release();
+            closed += 1;
+        }
+        closed
+    }
+}
+
+impl FailoverTarget for FalkorSyncClientInner {
+    fn on_failover(
+        &self,
+        promoted: Option<String>,
+    ) {
+        // Borrowed connections are closed once returned, idle ones are closed right away
+        self.connection_pool_size.invalidate();
+        self.close_idle_connections();
+        self.connection_events
+            .emit(|| ConnectionEvent::FailoverCompleted { promoted });
+    }
 }
 
 impl ProvidesSyncConnections for FalkorSyncClientInner {
@@ -60,7 +370,16 @@ impl ProvidesSyncConnections for FalkorSyncClientInner {
         )
     )]
     fn get_connection(&self) -> FalkorResult<FalkorSyncConnection> {
-        self._inner.lock().get_connection()
+        let conn = self._inner.lock().get_connection()?;
+        self.connection_events
+            .emit(|| ConnectionEvent::Established {
+                address: self.address.clone(),
+            });
+        Ok(conn)
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy.lock().clone()
     }
 }
 
@@ -86,14 +405,17 @@ impl FalkorSyncClient {
         mut client: FalkorClientProvider,
         connection_info: FalkorConnectionInfo,
         num_connections: u8,
+        lazy: bool,
     ) -> FalkorResult<Self> {
-        let (connection_pool_tx, connection_pool_rx) = mpsc::sync_channel(num_connections as usize);
+        let (connection_pool_tx, connection_pool_rx) = mpsc::sync_channel(MAX_POOL_CAPACITY);
 
-        // One already exists
-        for _ in 0..num_connections {
-            let new_conn = client
-                .get_connection()
-                .map_err(|err| FalkorDBError::RedisError(err.to_string()))?;
+        // A lazy pool opens its connections on demand, as borrows find it holding fewer than requested
+        let initial_connections = match lazy {
+            true => 0,
+            false => num_connections,
+        };
+        for _ in 0..initial_connections {
+            let new_conn = client.get_connection()?;
 
             connection_pool_tx
                 .send(new_conn)
@@ -102,24 +424,317 @@ impl FalkorSyncClient {
 
         Ok(Self {
             inner: Arc::new(FalkorSyncClientInner {
+                socket_timeouts: client.timeouts(),
+                test_on_borrow: AtomicBool::new(false),
+                single_connection: AtomicBool::new(false),
+                closed: AtomicBool::new(false),
+                pool_timeout: Mutex::new(None),
                 _inner: client.into(),
-                connection_pool_size: num_connections,
+                pool_metrics: PoolMetrics::default(),
+                connection_pool_size: PoolSize::new(num_connections, initial_connections),
                 connection_pool_tx,
                 connection_pool_rx: Mutex::new(connection_pool_rx),
+                log_redaction: AtomicBool::new(false),
+                query_registry: Mutex::new(Arc::default()),
+                retry_policy: Mutex::new(None),
+                strict_typing: AtomicBool::new(false),
+                schema_cache: SchemaCache::default(),
+                graph_circuits: GraphCircuits::default(),
+                connection_circuit: Mutex::new(None),
+                rate_limiter: Mutex::new(None),
+                metrics_recorder: Mutex::new(None),
+                interceptors: Mutex::new(None),
+                slow_query_log: Mutex::new(None),
+                connection_events: ConnectionEvents::default(),
+                address: connection_info.address(),
             }),
             _connection_info: connection_info,
         })
     }
 
+    /// Enables or disables masking of string and number literals in queries recorded by tracing spans,
+    /// this applies immediately to all graphs selected from this client, and to all of its clones
+    ///
+    /// # Arguments
+    /// * `enabled`: Whether query literals should be redacted, see [`redact_query_literals`](crate::redact_query_literals)
+    pub fn set_log_redaction(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner.log_redaction.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Enables or disables strict typing, failing property writes whose value the server coerced to another type,
+    /// this applies immediately to all graphs selected from this client, and to all of its clones
+    ///
+    /// # Arguments
+    /// * `enabled`: Whether coerced property types are treated as errors, see [`find_type_coercions`](crate::find_type_coercions)
+    pub fn set_strict_typing(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner.strict_typing.store(enabled, Ordering::Relaxed);
+    }
+
+    /// How long to wait for a pooled connection, see [`FalkorClientBuilder::with_pool_timeout`](crate::FalkorClientBuilder::with_pool_timeout)
+    pub(crate) fn set_pool_timeout(
+        &self,
+        timeout: Option<Duration>,
+    ) {
+        *self.inner.pool_timeout.lock() = timeout;
+    }
+
+    /// The named queries graphs selected from this client can run, see [`FalkorClientBuilder::with_query_registry`](crate::FalkorClientBuilder::with_query_registry)
+    pub(crate) fn set_query_registry(
+        &self,
+        registry: QueryRegistry,
+    ) {
+        *self.inner.query_registry.lock() = Arc::new(registry);
+    }
+
+    /// The policy idempotent operations are retried with, see [`FalkorClientBuilder::with_retry_policy`](crate::FalkorClientBuilder::with_retry_policy)
+    pub(crate) fn set_retry_policy(
+        &self,
+        policy: Option<RetryPolicy>,
+    ) {
+        *self.inner.retry_policy.lock() = policy;
+    }
+
+    /// Whether idle connections are pinged before being borrowed, see [`FalkorClientBuilder::with_test_on_borrow`](crate::FalkorClientBuilder::with_test_on_borrow)
+    pub(crate) fn set_test_on_borrow(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner.test_on_borrow.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether the client owns a single connection, see [`FalkorClientBuilder::with_single_connection`](crate::FalkorClientBuilder::with_single_connection)
+    pub(crate) fn set_single_connection(
+        &self,
+        enabled: bool,
+    ) {
+        self.inner
+            .single_connection
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// Fails fast once connecting to the server failed repeatedly, see [`FalkorClientBuilder::with_connection_circuit_breaker`](crate::FalkorClientBuilder::with_connection_circuit_breaker)
+    pub(crate) fn set_connection_circuit_breaker(
+        &self,
+        failure_threshold: NonZeroU32,
+        cool_down: Duration,
+    ) {
+        *self.inner.connection_circuit.lock() =
+            Some(Arc::new(CircuitBreaker::new(failure_threshold, cool_down)));
+    }
+
+    /// Limits the commands sent concurrently and per second, see [`FalkorClientBuilder::with_rate_limit`](crate::FalkorClientBuilder::with_rate_limit)
+    pub(crate) fn set_rate_limit(
+        &self,
+        limit: RateLimit,
+    ) {
+        *self.inner.rate_limiter.lock() = Some(Arc::new(RateLimiter::new(limit)));
+    }
+
+    /// Reports the client's metrics to a recorder, see [`FalkorClientBuilder::with_metrics_recorder`](crate::FalkorClientBuilder::with_metrics_recorder)
+    pub(crate) fn set_metrics_recorder(
+        &self,
+        recorder: Arc<dyn MetricsRecorder>,
+    ) {
+        *self.inner.metrics_recorder.lock() = Some(recorder);
+    }
+
+    /// Passes every command through the interceptors, see [`FalkorClientBuilder::with_interceptor`](crate::FalkorClientBuilder::with_interceptor)
+    pub(crate) fn set_interceptors(
+        &self,
+        interceptors: Vec<Arc<dyn CommandInterceptor>>,
+    ) {
+        *self.inner.interceptors.lock() = InterceptorChain::new(interceptors);
+    }
+
+    /// Reports queries slower than the log's threshold, see [`FalkorClientBuilder::with_slow_query_log`](crate::FalkorClientBuilder::with_slow_query_log)
+    pub(crate) fn set_slow_query_log(
+        &self,
+        log: SlowQueryLog,
+    ) {
+        *self.inner.slow_query_log.lock() = Some(Arc::new(log));
+    }
+
+    /// Reports the client's connection events to a listener, see [`FalkorClientBuilder::with_connection_event_listener`](crate::FalkorClientBuilder::with_connection_event_listener)
+    pub(crate) fn add_connection_event_listener(
+        &self,
+        listener: ConnectionEventListener,
+    ) {
+        self.inner.connection_events.subscribe(listener);
+    }
+
+    /// Isolates the failures of each graph selected from now on, see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker)
+    pub(crate) fn set_graph_circuit_breaker(
+        &self,
+        failure_threshold: NonZeroU32,
+        cool_down: Duration,
+    ) {
+        self.inner
+            .graph_circuits
+            .configure(failure_threshold, cool_down);
+    }
+
+    /// Bounds the number of ids cached per schema type, in the schemas of graphs selected from now on
+    pub(crate) fn set_schema_cache_max_ids(
+        &self,
+        max_ids: Option<NonZeroUsize>,
+    ) {
+        self.inner.schema_cache.set_max_ids(max_ids);
+    }
+
+    /// Replaces the pool's connections whenever the sentinel announces that the master failed over
+    pub(crate) fn watch_sentinel_failovers(
+        &self,
+        sentinel_info: redis::ConnectionInfo,
+        master_name: String,
+    ) {
+        let target: Weak<dyn FailoverTarget> = Arc::downgrade(&self.inner) as _;
+        spawn_failover_watcher(sentinel_info, master_name, target);
+    }
+
+    /// Returns whether query literals are currently redacted when queries are logged
+    pub fn log_redaction(&self) -> bool {
+        self.inner.log_redaction()
+    }
+
+    /// Returns whether property writes currently fail when the server coerces the written value's type
+    pub fn strict_typing(&self) -> bool {
+        self.inner.strict_typing()
+    }
+
+    /// Subscribes to the client's connection events, such as connections being lost or the pool being exhausted,
+    /// E.g. to surface connectivity issues in a health endpoint.
+    /// The listener is invoked on the thread which observed the event, so it should return quickly.
+    ///
+    /// # Arguments
+    /// * `listener`: The callback to invoke with every [`ConnectionEvent`] from now on
+    pub fn on_connection_event<F: Fn(&ConnectionEvent) + Send + Sync + 'static>(
+        &self,
+        listener: F,
+    ) {
+        self.add_connection_event_listener(Arc::new(listener));
+    }
+
+    /// Returns the state and counters of a graph's circuit breaker, see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker)
+    ///
+    /// # Arguments
+    /// * `graph_name`: The name of the graph
+    ///
+    /// # Returns
+    /// The [`CircuitStats`] of the graph, or [`None`] if circuit breakers are disabled, or the graph was never selected
+    pub fn graph_circuit_stats(
+        &self,
+        graph_name: &str,
+    ) -> Option<CircuitStats> {
+        self.inner.graph_circuits.stats(graph_name)
+    }
+
+    /// Returns a snapshot of the connection pool's occupancy and counters, E.g. to alert on pool exhaustion before it causes an outage
+    ///
+    /// # Returns
+    /// The pool's [`PoolStats`]
+    pub fn pool_stats(&self) -> PoolStats {
+        PoolStats {
+            connection_circuit: self
+                .inner
+                .connection_circuit
+                .lock()
+                .as_ref()
+                .map(|circuit| circuit.stats()),
+            ..self
+                .inner
+                .pool_metrics
+                .stats(&self.inner.connection_pool_size)
+        }
+    }
+
     ///  Get the max number of connections in the client's connection pool
     pub fn connection_pool_size(&self) -> u8 {
-        self.inner.connection_pool_size
+        self.inner.connection_pool_size.target()
+    }
+
+    /// Changes the number of connections in the client's connection pool at runtime
+    /// When growing, the new connections are opened immediately,
+    /// when shrinking, idle connections are closed immediately, and borrowed connections are closed once they are returned.
+    ///
+    /// # Arguments
+    /// * `num_connections`: The new size of the connection pool
+    ///
+    /// # Returns
+    /// Nothing, or [`FalkorDBError::SingleConnectionMode`] if the client owns a single connection
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Resize Connection Pool", skip_all, level = "info")
+    )]
+    pub fn resize_pool(
+        &self,
+        num_connections: NonZeroU8,
+    ) -> FalkorResult<()> {
+        if self.inner.single_connection.load(Ordering::Relaxed) {
+            return Err(FalkorDBError::SingleConnectionMode);
+        }
+        self.inner
+            .connection_pool_size
+            .set_target(num_connections.get());
+
+        while self.inner.connection_pool_size.try_reserve() {
+            let new_conn = self
+                .inner
+                .get_connection()
+                .inspect_err(|_| self.inner.connection_pool_size.release())?;
+            self.inner
+                .connection_pool_tx
+                .send(new_conn)
+                .map_err(|_| FalkorDBError::EmptyConnection)?;
+        }
+
+        self.inner.close_excess_idle_connections();
+        Ok(())
+    }
+
+    /// Closes all the connections currently idle in the connection pool, without changing its size
+    /// Connections are reopened on demand, once they are needed again.
+    ///
+    /// # Returns
+    /// The number of connections closed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Close Idle Connections", skip_all, level = "info")
+    )]
+    pub fn close_idle(&self) -> usize {
+        self.inner.close_idle_connections()
     }
 
     pub(crate) fn borrow_connection(&self) -> FalkorResult<BorrowedSyncConnection> {
         self.inner.borrow_connection(self.inner.clone())
     }
 
+    /// Closes the client gracefully, instead of relying on it being dropped during process exit:
+    /// borrowing connections fails with [`FalkorDBError::ClientClosed`] from now on, in-flight operations are waited for,
+    /// and every pooled connection is closed using `QUIT` once it is returned.
+    /// This applies to all clones of the client, and waits for as long as connections are borrowed,
+    /// so result sets or other handles holding on to a connection must be dropped first.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Close Client", skip_all, level = "info")
+    )]
+    pub fn close(&self) {
+        self.inner.closed.store(true, Ordering::Relaxed);
+
+        let connection_pool_rx = self.inner.connection_pool_rx.lock();
+        while self.inner.connection_pool_size.live() > 0 {
+            if let Ok(mut conn) = connection_pool_rx.recv_timeout(CLOSE_POLL_INTERVAL) {
+                conn.execute_command(None, "QUIT", None, None).ok();
+                self.inner.connection_pool_size.release();
+            }
+        }
+    }
+
     /// Return a list of graphs currently residing in the database
     ///
     /// # Returns
@@ -138,7 +753,7 @@ impl FalkorSyncClient {
     ///
     /// # Arguments
     /// * `config_Key`: A [`String`] representation of a configuration's key.
-    ///    The config key can also be "*", which will return ALL the configuration options.
+    ///   The config key can also be "*", which will return ALL the configuration options.
     ///
     /// # Returns
     /// A [`HashMap`] comprised of [`String`] keys, and [`ConfigValue`] values.
@@ -161,7 +776,7 @@ impl FalkorSyncClient {
     ///
     /// # Arguments
     /// * `config_Key`: A [`String`] representation of a configuration's key.
-    ///    The config key can also be "*", which will return ALL the configuration options.
+    ///   The config key can also be "*", which will return ALL the configuration options.
     /// * `value`: The new value to set, which is anything that can be converted into a [`ConfigValue`], namely string types and i64.
     #[cfg_attr(
         feature = "tracing",
@@ -182,6 +797,41 @@ impl FalkorSyncClient {
         })
     }
 
+    /// Returns the memory limit of each query, past which the server aborts the query
+    /// with [`FalkorDBError::QueryMemoryLimitExceeded`]
+    ///
+    /// # Returns
+    /// The limit in bytes, or [`None`] if queries are unlimited
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Get Query Memory Limit", skip_all, level = "info")
+    )]
+    pub fn query_memory_limit(&self) -> FalkorResult<Option<NonZeroU64>> {
+        self.config_get(QUERY_MEMORY_LIMIT_CONFIG)
+            .and_then(parse_query_memory_limit)
+    }
+
+    /// Sets the memory limit of each query, so heavy analytical queries can't exhaust the server's memory,
+    /// queries exceeding it are aborted with [`FalkorDBError::QueryMemoryLimitExceeded`]
+    /// The server enforces the limit for every query on its own, but only supports configuring it server-wide.
+    ///
+    /// # Arguments
+    /// * `limit`: The limit in bytes, or [`None`] to not limit queries
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Set Query Memory Limit", skip_all, level = "info")
+    )]
+    pub fn set_query_memory_limit(
+        &self,
+        limit: Option<NonZeroU64>,
+    ) -> FalkorResult<()> {
+        self.config_set(
+            QUERY_MEMORY_LIMIT_CONFIG,
+            query_memory_limit_config_value(limit)?,
+        )
+        .map(|_| ())
+    }
+
     /// Opens a graph context for queries and operations
     ///
     /// # Arguments
@@ -222,6 +872,149 @@ impl FalkorSyncClient {
         Ok(self.select_graph(new_graph_name))
     }
 
+    /// Runs a battery of compatibility checks against the server, useful as a deployment smoke test
+    /// The checks ping the server, read the FalkorDB module version, and create a scratch graph,
+    /// on which they verify values are parsed as expected, and that an index can be created, before deleting it.
+    ///
+    /// # Returns
+    /// A [`SelfTestReport`] with the outcome of every check, failed checks do not stop the following ones
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Client Self Test", skip_all, level = "info")
+    )]
+    pub fn self_test(&self) -> SelfTestReport {
+        let mut report = SelfTestReport::default();
+
+        let start = Instant::now();
+        let res = match self.borrow_connection() {
+            Ok(mut conn) => conn
+                .execute_command(None, "PING", None, None)
+                .and_then(redis_value_as_string),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::Ping, start, res);
+
+        let start = Instant::now();
+        let res = match self.borrow_connection() {
+            Ok(mut conn) => conn
+                .execute_command(None, "MODULE", Some("LIST"), None)
+                .and_then(parse_module_version),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::ModuleVersion, start, res);
+
+        let graph_name = self_test_graph_name();
+        let graph = self.select_graph(&graph_name);
+
+        let start = Instant::now();
+        let res = graph
+            .query(SELF_TEST_CREATE_QUERY)
+            .execute()
+            .map(|_| format!("Created graph {graph_name}"));
+        report.record(SelfTestCheck::CreateGraph, start, res);
+
+        let start = Instant::now();
+        let res = match graph.ro_query(SELF_TEST_ROUND_TRIP_QUERY).execute() {
+            Ok(mut res) => check_round_trip(res.data.next()),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::CompactRoundTrip, start, res);
+
+        let start = Instant::now();
+        let res = match graph.create_index(
+            IndexType::Range,
+            EntityType::Node,
+            "SelfTest",
+            &["name"],
+            None,
+        ) {
+            Ok(_) => graph
+                .wait_for_index("SelfTest", &["name"], SELF_TEST_INDEX_TIMEOUT)
+                .map(|index| format!("Index on {} is operational", index.index_label)),
+            Err(err) => Err(err),
+        };
+        report.record(SelfTestCheck::IndexCreation, start, res);
+
+        let start = Instant::now();
+        let res = graph
+            .delete()
+            .map(|_| format!("Deleted graph {graph_name}"));
+        report.record(SelfTestCheck::DropGraph, start, res);
+
+        report
+    }
+
+    /// Reports what the server supports, so callers can adapt their behavior up front, instead of probing with failing calls
+    /// This includes the negotiated protocol, the FalkorDB module version, the kinds of indices which can be created,
+    /// and which of the optional commands, such as `GRAPH.COPY` or `GRAPH.UDF`, are available.
+    ///
+    /// # Returns
+    /// The server's [`Capabilities`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Client Capabilities", skip_all, level = "info")
+    )]
+    pub fn capabilities(&self) -> FalkorResult<Capabilities> {
+        let mut conn = self.borrow_connection()?;
+        let module_version = conn
+            .execute_command(None, "MODULE", Some("LIST"), None)
+            .and_then(parse_module_version)?;
+        let commands = conn
+            .execute_command(None, "COMMAND", Some("INFO"), Some(OPTIONAL_COMMANDS))
+            .and_then(parse_supported_commands)?;
+        let protocol = conn
+            .execute_command(None, "CLIENT", Some("INFO"), None)
+            .ok()
+            .and_then(parse_client_protocol);
+
+        Ok(Capabilities {
+            protocol,
+            index_types: supported_index_types(&module_version),
+            module_version,
+            commands,
+        })
+    }
+
+    /// Opens and validates every pooled connection, for services which want to verify connectivity at startup
+    /// Each connection is borrowed, opening it if needed, and pinged, all of them are held until every one was checked,
+    /// so each check uses a distinct connection.
+    /// Every connection is attempted and reported, those left once the timeout passed fail right away,
+    /// and the rate limiter is bypassed, as it would otherwise hold back connections beyond its limit.
+    ///
+    /// # Arguments
+    /// * `timeout`: How long to wait overall for pooled connections and their replies, so a busy pool or an unresponsive server fails fast,
+    ///   opening each connection is also bounded by the connect timeout, see [`FalkorClientBuilder::with_connect_timeout`](crate::FalkorClientBuilder::with_connect_timeout)
+    ///
+    /// # Returns
+    /// A [`WarmUpReport`] with the outcome of every connection,
+    /// or [`FalkorDBError::WarmUpFailed`] carrying the same report if any of them failed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Warm Up Connection Pool", skip_all, level = "info")
+    )]
+    pub fn warm_up(
+        &self,
+        timeout: Duration,
+    ) -> FalkorResult<WarmUpReport> {
+        let deadline = Some(Instant::now() + timeout);
+        let mut report = WarmUpReport::new(self.connection_pool_size() as usize);
+        let mut borrowed = Vec::with_capacity(self.connection_pool_size() as usize);
+
+        for _ in 0..self.connection_pool_size() {
+            let start = Instant::now();
+            let res = self
+                .inner
+                .borrow_connection_unlimited(self.inner.clone(), deadline)
+                .and_then(|mut conn| {
+                    conn.execute_command_with_deadline(None, "PING", None, None, deadline)
+                        .map(|_| borrowed.push(conn))
+                });
+            report.record(start, res);
+        }
+
+        report.into_result()
+    }
+
     /// Retrieves redis information
     #[cfg_attr(
         feature = "tracing",
@@ -243,9 +1036,28 @@ pub(crate) fn create_empty_inner_sync_client() -> Arc<FalkorSyncClientInner> {
     tx.send(FalkorSyncConnection::None).ok();
     Arc::new(FalkorSyncClientInner {
         _inner: Mutex::new(FalkorClientProvider::None),
-        connection_pool_size: 0,
+        pool_metrics: PoolMetrics::default(),
+        connection_pool_size: PoolSize::new(1, 1),
         connection_pool_tx: tx,
         connection_pool_rx: Mutex::new(rx),
+        socket_timeouts: ConnectionTimeouts::default(),
+        test_on_borrow: AtomicBool::new(false),
+        single_connection: AtomicBool::new(false),
+        closed: AtomicBool::new(false),
+        pool_timeout: Mutex::new(None),
+        log_redaction: AtomicBool::new(false),
+        query_registry: Mutex::new(Arc::default()),
+        retry_policy: Mutex::new(None),
+        strict_typing: AtomicBool::new(false),
+        schema_cache: SchemaCache::default(),
+        graph_circuits: GraphCircuits::default(),
+        connection_circuit: Mutex::new(None),
+        rate_limiter: Mutex::new(None),
+        metrics_recorder: Mutex::new(None),
+        interceptors: Mutex::new(None),
+        slow_query_log: Mutex::new(None),
+        connection_events: ConnectionEvents::default(),
+        address: "127.0.0.1:6379".to_string(),
     })
 }
 
@@ -254,11 +1066,190 @@ mod tests {
     use super::*;
     use crate::FalkorValue::Node;
     use crate::{
-        test_utils::{create_test_client, TestSyncGraphHandle},
-        FalkorClientBuilder, FalkorValue, LazyResultSet, QueryResult,
+        test_utils::{create_test_client, MockTransport, TestSyncGraphHandle},
+        ConnectionWarmUp, Credentials, FalkorClientBuilder, FalkorValue, LazyResultSet,
+        QueryResult,
     };
     use approx::assert_relative_eq;
-    use std::{mem, num::NonZeroU8, sync::mpsc::TryRecvError, thread};
+    use std::{mem, sync::mpsc::TryRecvError, thread, time::Duration};
+
+    #[test]
+    fn test_close() {
+        let client = FalkorSyncClient {
+            inner: create_empty_inner_sync_client(),
+            _connection_info: "falkor://127.0.0.1:6379"
+                .try_into()
+                .expect("Could not parse connection info"),
+        };
+        let borrowed = client
+            .borrow_connection()
+            .expect("Could not borrow the only connection");
+
+        // Closing waits for the borrowed connection to be returned
+        let closing = thread::spawn({
+            let client = client.clone();
+            move || client.close()
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert!(!closing.is_finished());
+
+        drop(borrowed);
+        closing.join().expect("Could not close client");
+        assert_eq!(client.pool_stats().total, 0);
+        assert!(matches!(
+            client.borrow_connection(),
+            Err(FalkorDBError::ClientClosed)
+        ));
+    }
+
+    #[test]
+    fn test_borrow_connection_pool_exhausted() {
+        let inner = create_empty_inner_sync_client();
+        let _borrowed = inner
+            .borrow_connection(inner.clone())
+            .expect("Could not borrow the only connection");
+
+        let res = inner.borrow_connection_with_deadline(
+            inner.clone(),
+            Some(Instant::now() + Duration::from_millis(50)),
+        );
+        assert!(matches!(
+            res,
+            Err(FalkorDBError::PoolExhausted { waited }) if waited >= Duration::from_millis(40)
+        ));
+    }
+
+    #[test]
+    fn test_connection_events() {
+        let inner = create_empty_inner_sync_client();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        inner.connection_events.subscribe({
+            let events = events.clone();
+            Arc::new(move |event: &ConnectionEvent| events.lock().push(event.clone()))
+        });
+        let _borrowed = inner
+            .borrow_connection(inner.clone())
+            .expect("Could not borrow the only connection");
+        assert!(inner
+            .borrow_connection_with_deadline(
+                inner.clone(),
+                Some(Instant::now() + Duration::from_millis(10))
+            )
+            .is_err());
+        assert!(inner.reconnect(&FalkorDBError::ConnectionDown).is_err());
+        inner.on_failover(Some("10.0.0.2:6379".to_string()));
+
+        let events = events.lock();
+        assert!(matches!(
+            events[0],
+            ConnectionEvent::PoolExhausted { waited } if waited >= Duration::from_millis(10)
+        ));
+        assert_eq!(
+            events[1..],
+            [
+                ConnectionEvent::Lost {
+                    address: "127.0.0.1:6379".to_string(),
+                    reason: FalkorDBError::ConnectionDown.to_string(),
+                },
+                ConnectionEvent::ReconnectAttempted {
+                    address: "127.0.0.1:6379".to_string(),
+                    succeeded: false,
+                },
+                ConnectionEvent::FailoverCompleted {
+                    promoted: Some("10.0.0.2:6379".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failed_borrow_is_not_in_use() {
+        let unreachable = MockTransport::new("mock://unreachable", |_| Ok(redis::Value::Nil));
+        unreachable.set_down(true);
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(FalkorConnectionInfo::Transport(Arc::new(unreachable)))
+            .with_lazy_connections(true)
+            .build()
+            .expect("Could not build lazy client");
+
+        for _ in 0..3 {
+            assert!(client.borrow_connection().is_err());
+        }
+        let stats = client.pool_stats();
+        assert_eq!((stats.total, stats.in_use, stats.checkouts), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_pool_stats() {
+        let inner = create_empty_inner_sync_client();
+        let borrowed = inner
+            .borrow_connection(inner.clone())
+            .expect("Could not borrow the only connection");
+        assert!(inner
+            .borrow_connection_with_deadline(
+                inner.clone(),
+                Some(Instant::now() + Duration::from_millis(10))
+            )
+            .is_err());
+
+        let stats = inner.pool_metrics().stats(&inner.connection_pool_size);
+        assert_eq!((stats.total, stats.idle, stats.in_use), (1, 0, 1));
+        assert_eq!((stats.checkouts, stats.waits, stats.timeouts), (1, 1, 1));
+
+        drop(borrowed);
+        let stats = inner.pool_metrics().stats(&inner.connection_pool_size);
+        assert_eq!((stats.idle, stats.in_use), (1, 0));
+    }
+
+    #[test]
+    fn test_borrow_connection_pool_timeout() {
+        let inner = create_empty_inner_sync_client();
+        *inner.pool_timeout.lock() = Some(Duration::from_millis(50));
+        let _borrowed = inner
+            .borrow_connection(inner.clone())
+            .expect("Could not borrow the only connection");
+
+        let started = Instant::now();
+        assert_eq!(
+            inner.borrow_connection(inner.clone()).err(),
+            Some(FalkorDBError::PoolTimeout {
+                timeout: Duration::from_millis(50)
+            })
+        );
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_borrow_connection_test_on_borrow() {
+        let client = FalkorClientBuilder::new()
+            .with_num_connections(NonZeroU8::new(1).expect("Could not create a perfectly valid u8"))
+            .with_test_on_borrow(true)
+            .build()
+            .expect("Could not create client for this test");
+
+        let client_id = client
+            .borrow_connection()
+            .and_then(|mut conn| conn.execute_command(None, "CLIENT", Some("ID"), None))
+            .expect("Could not get client id");
+        let redis::Value::Int(client_id) = client_id else {
+            panic!("Unexpected CLIENT ID reply: {client_id:?}");
+        };
+
+        // Simulates the server closing the idle connection, E.g. after a restart
+        create_test_client()
+            .borrow_connection()
+            .and_then(|mut conn| {
+                conn.execute_command(
+                    None,
+                    "CLIENT",
+                    Some("KILL"),
+                    Some(&["ID", client_id.to_string().as_str()]),
+                )
+            })
+            .expect("Could not kill the pooled connection");
+
+        assert!(client.list_graphs().is_ok());
+    }
 
     #[test]
     fn test_borrow_connection() {
@@ -284,6 +1275,40 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_resize_pool_and_close_idle() {
+        let client = FalkorClientBuilder::new()
+            .with_num_connections(NonZeroU8::new(4).expect("Could not create a perfectly valid u8"))
+            .build()
+            .expect("Could not create client for this test");
+
+        client
+            .resize_pool(NonZeroU8::new(6).expect("Could not create a perfectly valid u8"))
+            .expect("Could not grow pool");
+        assert_eq!(client.connection_pool_size(), 6);
+        assert_eq!(client.inner.connection_pool_size.live(), 6);
+
+        let borrowed = client
+            .borrow_connection()
+            .expect("Could not borrow connection");
+        client
+            .resize_pool(NonZeroU8::new(2).expect("Could not create a perfectly valid u8"))
+            .expect("Could not shrink pool");
+        assert_eq!(client.connection_pool_size(), 2);
+        assert_eq!(client.inner.connection_pool_size.live(), 3);
+
+        // The borrowed connection is excess, so it should be closed once returned
+        drop(borrowed);
+        assert_eq!(client.inner.connection_pool_size.live(), 2);
+
+        assert_eq!(client.close_idle(), 2);
+        assert_eq!(client.inner.connection_pool_size.live(), 0);
+
+        // Connections are reopened on demand
+        assert!(client.list_graphs().is_ok());
+        assert_eq!(client.inner.connection_pool_size.live(), 1);
+    }
+
     #[test]
     fn test_list_graphs() {
         let client = create_test_client();
@@ -297,7 +1322,7 @@ mod tests {
     #[test]
     fn test_read_only_query() {
         let client = create_test_client();
-        let mut graph = client.select_graph("test_read_only_query");
+        let graph = client.select_graph("test_read_only_query");
         graph
             .query("CREATE (n:Person {name: 'John Doe', age: 30})")
             .execute()
@@ -332,7 +1357,7 @@ mod tests {
     #[test]
     fn test_read_vec32() {
         let client = create_test_client();
-        let mut graph = client.select_graph("test_read_vec32");
+        let graph = client.select_graph("test_read_vec32");
         graph
             .query("CREATE (p:Document {embedding: vecf32([2.1, 0.82, 1.3]), id: '1'})")
             .execute()
@@ -341,7 +1366,7 @@ mod tests {
             .query("MATCH (p:Document) RETURN p")
             .execute()
             .expect("Could not get document");
-        while let Some(falkor_value) = res.data.next() {
+        for falkor_value in res.data.by_ref() {
             // iterate on a node value
             for value in falkor_value {
                 if let Node(node) = value {
@@ -367,7 +1392,7 @@ mod tests {
     fn test_select_graph_and_query() {
         let client = create_test_client();
 
-        let mut graph = client.select_graph("imdb");
+        let graph = client.select_graph("imdb");
         assert_eq!(graph.graph_name(), "imdb".to_string());
 
         let res = graph
@@ -387,11 +1412,11 @@ mod tests {
         let graph = client.copy_graph("imdb", "imdb_ro_copy");
         assert!(graph.is_ok());
 
-        let mut graph = TestSyncGraphHandle {
+        let graph = TestSyncGraphHandle {
             inner: graph.unwrap(),
         };
 
-        let mut original_graph = client.select_graph("imdb");
+        let original_graph = client.select_graph("imdb");
 
         assert_eq!(
             graph
@@ -410,6 +1435,157 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_self_test() {
+        let client = create_test_client();
+        let report = client.self_test();
+        assert!(report.passed(), "Self-test failed: {report:?}");
+        assert_eq!(report.checks.len(), 6);
+    }
+
+    #[test]
+    fn test_credentials_provider() {
+        let invocations = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = invocations.clone();
+        let client = FalkorClientBuilder::new()
+            .with_num_connections(NonZeroU8::new(2).expect("Could not create a perfectly valid u8"))
+            // The default user accepts any password, unless one was configured
+            .with_credentials_provider(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Ok(Credentials::with_username("default", "rotated"))
+            })
+            .build()
+            .expect("Could not create client");
+
+        // Once per pooled connection, and once more for the connection detecting a Sentinel
+        assert_eq!(invocations.load(Ordering::Relaxed), 3);
+        assert!(client.list_graphs().is_ok());
+    }
+
+    #[test]
+    fn test_client_name() {
+        let client = FalkorClientBuilder::new()
+            .with_client_name("falkordb-rs-test")
+            .build()
+            .expect("Could not create client");
+
+        let name = client
+            .borrow_connection()
+            .and_then(|mut conn| conn.execute_command(None, "CLIENT", Some("GETNAME"), None))
+            .and_then(redis_value_as_string);
+        assert_eq!(name, Ok("falkordb-rs-test".to_string()));
+    }
+
+    #[test]
+    fn test_warm_up() {
+        let client = create_test_client();
+        let report = client
+            .warm_up(Duration::from_secs(5))
+            .expect("Could not warm up the pool");
+        assert_eq!(
+            report.connections.len(),
+            client.connection_pool_size() as usize
+        );
+        assert_eq!(client.pool_stats().idle, client.connection_pool_size());
+    }
+
+    #[test]
+    fn test_warm_up_unreachable() {
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(
+                "falkor://10.255.255.1:6379"
+                    .try_into()
+                    .expect("Could not parse connection info"),
+            )
+            .with_num_connections(NonZeroU8::new(2).expect("Could not create a perfectly valid u8"))
+            .with_connect_timeout(Duration::from_millis(100))
+            .with_lazy_connections(true)
+            .build()
+            .expect("Could not build lazy client");
+
+        let Err(FalkorDBError::WarmUpFailed(report)) = client.warm_up(Duration::from_secs(5))
+        else {
+            panic!("Warming up an unreachable pool should fail");
+        };
+        assert_eq!(report.pool_size, 2);
+        assert_eq!(report.connections.len(), 2);
+        assert_eq!(report.failures().count(), 2);
+    }
+
+    #[test]
+    fn test_warm_up_bypasses_rate_limit() {
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(
+                "falkor://10.255.255.1:6379"
+                    .try_into()
+                    .expect("Could not parse connection info"),
+            )
+            .with_num_connections(NonZeroU8::new(2).expect("Could not create a perfectly valid u8"))
+            .with_connect_timeout(Duration::from_millis(100))
+            .with_lazy_connections(true)
+            .with_rate_limit(RateLimit::new().with_max_in_flight(
+                NonZeroUsize::new(1).expect("Could not create a perfectly valid usize"),
+            ))
+            .build()
+            .expect("Could not build lazy client");
+        let limiter = client
+            .inner
+            .rate_limiter
+            .lock()
+            .clone()
+            .expect("Rate limiter should be set");
+        let _permit = limiter
+            .acquire(None)
+            .expect("Could not take the only permit");
+
+        let Err(FalkorDBError::WarmUpFailed(report)) = client.warm_up(Duration::from_secs(5))
+        else {
+            panic!("Warming up an unreachable pool should fail");
+        };
+        assert_eq!(report.connections.len(), 2);
+        assert!(!report
+            .failures()
+            .any(|conn| matches!(conn.result, Err(FalkorDBError::Throttled(_)))));
+    }
+
+    #[test]
+    fn test_warm_up_busy_pool() {
+        let client = FalkorSyncClient {
+            inner: create_empty_inner_sync_client(),
+            _connection_info: "falkor://127.0.0.1:6379"
+                .try_into()
+                .expect("Could not parse connection info"),
+        };
+        let _borrowed = client
+            .borrow_connection()
+            .expect("Could not borrow the only connection");
+
+        let started = Instant::now();
+        let Err(FalkorDBError::WarmUpFailed(report)) = client.warm_up(Duration::from_millis(50))
+        else {
+            panic!("Warming up a pool whose connections are all in use should fail");
+        };
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(matches!(
+            report.connections[..],
+            [ConnectionWarmUp {
+                result: Err(FalkorDBError::PoolExhausted { .. }),
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let client = create_test_client();
+        let capabilities = client
+            .capabilities()
+            .expect("Could not get server capabilities");
+        assert!(!capabilities.module_version.is_empty());
+        assert!(capabilities.supports_index(IndexType::Range));
+        assert!(capabilities.supports_command("GRAPH.COPY"));
+    }
+
     #[test]
     fn test_get_config() {
         let client = create_test_client();
@@ -436,6 +1612,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_query_memory_limit() {
+        let client = create_test_client();
+        let previous = client
+            .query_memory_limit()
+            .expect("Could not get query memory limit");
+
+        let desired = NonZeroU64::new(1 << 30);
+        client
+            .set_query_memory_limit(desired)
+            .expect("Could not set query memory limit");
+        assert_eq!(client.query_memory_limit(), Ok(desired));
+
+        client
+            .set_query_memory_limit(previous)
+            .expect("Could not restore query memory limit");
+    }
+
     #[test]
     fn test_set_config() {
         let client = create_test_client();
@@ -0,0 +1,159 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    parser::{redis_value_as_string, redis_value_as_vec},
+    FalkorResult, IndexType,
+};
+
+/// The optional commands probed for using `COMMAND INFO`, which are missing from some server releases or deployments
+pub(crate) const OPTIONAL_COMMANDS: &[&str] = &[
+    "GRAPH.CONSTRAINT",
+    "GRAPH.COPY",
+    "GRAPH.INFO",
+    "GRAPH.MEMORY",
+    "GRAPH.UDF",
+];
+
+/// The first module version supporting vector indices, as reported by `MODULE LIST`
+const VECTOR_INDEX_MODULE_VERSION: u64 = 40000;
+
+/// The RESP protocol version negotiated for the client's connections
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display)]
+pub enum RespProtocol {
+    /// RESP2, the default protocol
+    #[strum(serialize = "RESP2")]
+    Resp2,
+    /// RESP3, which adds maps, sets and out-of-band pushes
+    #[strum(serialize = "RESP3")]
+    Resp3,
+}
+
+/// A structured report of what the server the client is connected to supports,
+/// allowing downstream libraries to adapt their behavior, instead of probing with failing calls,
+/// see [`FalkorSyncClient::capabilities`](crate::FalkorSyncClient::capabilities)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The protocol negotiated for the client's connections, [`None`] if the server does not report it, E.g. behind a custom transport
+    pub protocol: Option<RespProtocol>,
+    /// The version of the FalkorDB module, as reported by `MODULE LIST`, E.g. `40200` for v4.2.0
+    pub module_version: String,
+    /// The kinds of indices the server can create
+    pub index_types: Vec<IndexType>,
+    /// The optional commands the server supports, out of those the client knows of, such as `GRAPH.COPY`
+    pub commands: Vec<String>,
+}
+
+impl Capabilities {
+    /// Returns whether the server supports an optional command
+    ///
+    /// # Arguments
+    /// * `command`: The name of the command, E.g. `GRAPH.COPY`, compared case-insensitively
+    ///
+    /// # Returns
+    /// Whether the command was found, always false for commands which are not probed
+    pub fn supports_command(
+        &self,
+        command: &str,
+    ) -> bool {
+        self.commands
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(command))
+    }
+
+    /// Returns whether the server can create indices of the provided kind
+    ///
+    /// # Arguments
+    /// * `index_type`: The kind of index
+    ///
+    /// # Returns
+    /// Whether indices of this kind are supported
+    pub fn supports_index(
+        &self,
+        index_type: IndexType,
+    ) -> bool {
+        self.index_types.contains(&index_type)
+    }
+}
+
+/// Finds the negotiated protocol in the reply of `CLIENT INFO`, a single line of space separated `key=value` fields
+pub(crate) fn parse_client_protocol(value: redis::Value) -> Option<RespProtocol> {
+    let info = redis_value_as_string(value).ok()?;
+    info.split_whitespace()
+        .find_map(|field| field.strip_prefix("resp="))
+        .and_then(|resp| match resp {
+            "2" => Some(RespProtocol::Resp2),
+            "3" => Some(RespProtocol::Resp3),
+            _ => None,
+        })
+}
+
+/// Returns the probed commands the server knows, `COMMAND INFO` replies with a nil entry for each unknown command
+pub(crate) fn parse_supported_commands(value: redis::Value) -> FalkorResult<Vec<String>> {
+    Ok(redis_value_as_vec(value)?
+        .into_iter()
+        .zip(OPTIONAL_COMMANDS)
+        .filter(|(info, _)| !matches!(info, redis::Value::Nil))
+        .map(|(_, command)| command.to_string())
+        .collect())
+}
+
+pub(crate) fn supported_index_types(module_version: &str) -> Vec<IndexType> {
+    let mut index_types = vec![IndexType::Range, IndexType::Fulltext];
+    if module_version
+        .parse::<u64>()
+        .is_ok_and(|version| version >= VECTOR_INDEX_MODULE_VERSION)
+    {
+        index_types.push(IndexType::Vector);
+    }
+    index_types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_protocol() {
+        let info = |info: &str| redis::Value::VerbatimString {
+            format: redis::VerbatimFormat::Text,
+            text: info.to_string(),
+        };
+        assert_eq!(
+            parse_client_protocol(info("id=3 addr=127.0.0.1:6379 db=0 resp=3 lib-name=")),
+            Some(RespProtocol::Resp3)
+        );
+        assert_eq!(
+            parse_client_protocol(redis::Value::BulkString(b"id=3 resp=2\n".to_vec())),
+            Some(RespProtocol::Resp2)
+        );
+        assert_eq!(parse_client_protocol(info("id=3 db=0")), None);
+        assert_eq!(parse_client_protocol(redis::Value::Nil), None);
+    }
+
+    #[test]
+    fn test_parse_supported_commands() {
+        let mut infos = vec![redis::Value::Nil; OPTIONAL_COMMANDS.len()];
+        infos[1] = redis::Value::Array(vec![]);
+        assert_eq!(
+            parse_supported_commands(redis::Value::Array(infos)),
+            Ok(vec!["GRAPH.COPY".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = Capabilities {
+            protocol: None,
+            module_version: "40200".to_string(),
+            index_types: supported_index_types("40200"),
+            commands: vec!["GRAPH.COPY".to_string()],
+        };
+        assert!(capabilities.supports_index(IndexType::Vector));
+        assert!(capabilities.supports_command("graph.copy"));
+        assert!(!capabilities.supports_command("GRAPH.UDF"));
+        assert!(!supported_index_types("21000").contains(&IndexType::Vector));
+    }
+}
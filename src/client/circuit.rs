@@ -0,0 +1,481 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{FalkorDBError, FalkorResult};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The state of a circuit breaker
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests pass through normally
+    Closed,
+    /// Requests fail fast, until the cool-down period passes
+    Open,
+    /// The cool-down period passed, the next request is let through as a trial, closing the circuit if it succeeds
+    HalfOpen,
+}
+
+/// A snapshot of a circuit breaker's state and counters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// The current state of the circuit
+    pub state: CircuitState,
+    /// How many requests failed in a row, reset by any successful request
+    pub consecutive_failures: u32,
+    /// How many requests failed in total
+    pub failures: u64,
+    /// How many requests were rejected without being sent, while the circuit was open
+    pub rejected: u64,
+    /// How many times the circuit opened
+    pub times_opened: u64,
+}
+
+#[derive(Default)]
+struct CircuitCounters {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_in_flight: bool,
+    failures: u64,
+    rejected: u64,
+    times_opened: u64,
+}
+
+/// Opens after a number of consecutive failures, failing requests fast until a cool-down period passes,
+/// after which a single trial request decides whether it closes again
+pub(crate) struct CircuitBreaker {
+    failure_threshold: NonZeroU32,
+    cool_down: Duration,
+    counters: Mutex<CircuitCounters>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(
+        failure_threshold: NonZeroU32,
+        cool_down: Duration,
+    ) -> Self {
+        Self {
+            failure_threshold,
+            cool_down,
+            counters: Mutex::default(),
+        }
+    }
+
+    /// Lets a request through, or returns how long until the circuit lets a trial request through
+    pub(crate) fn try_acquire(&self) -> Result<(), Duration> {
+        let mut counters = self.counters.lock();
+        let Some(opened_at) = counters.opened_at else {
+            return Ok(());
+        };
+
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.cool_down || counters.trial_in_flight {
+            counters.rejected += 1;
+            return Err(self.cool_down.saturating_sub(elapsed));
+        }
+        counters.trial_in_flight = true;
+        Ok(())
+    }
+
+    pub(crate) fn record_success(&self) {
+        let mut counters = self.counters.lock();
+        counters.consecutive_failures = 0;
+        counters.opened_at = None;
+        counters.trial_in_flight = false;
+    }
+
+    pub(crate) fn record_failure(&self) {
+        let mut counters = self.counters.lock();
+        counters.failures += 1;
+        counters.consecutive_failures = counters.consecutive_failures.saturating_add(1);
+
+        let trip = match counters.opened_at {
+            Some(_) => counters.trial_in_flight,
+            None => counters.consecutive_failures >= self.failure_threshold.get(),
+        };
+        if trip {
+            counters.opened_at = Some(Instant::now());
+            counters.times_opened += 1;
+        }
+        counters.trial_in_flight = false;
+    }
+
+    /// Records a request whose outcome says nothing about what the circuit protects, E.g. a connection error,
+    /// allowing another trial request if this one was a trial
+    pub(crate) fn record_neutral(&self) {
+        self.counters.lock().trial_in_flight = false;
+    }
+
+    pub(crate) fn stats(&self) -> CircuitStats {
+        let counters = self.counters.lock();
+        CircuitStats {
+            state: match counters.opened_at {
+                None => CircuitState::Closed,
+                Some(opened_at)
+                    if opened_at.elapsed() >= self.cool_down && !counters.trial_in_flight =>
+                {
+                    CircuitState::HalfOpen
+                }
+                Some(_) => CircuitState::Open,
+            },
+            consecutive_failures: counters.consecutive_failures,
+            failures: counters.failures,
+            rejected: counters.rejected,
+            times_opened: counters.times_opened,
+        }
+    }
+}
+
+/// Whether an error is attributable to the graph being queried, rather than to the connection or the server as a whole,
+/// E.g. a query which timed out or exceeded the memory limit, as opposed to a broken connection, or an exhausted pool.
+/// Errors caused by the caller, such as syntax errors or constraint violations, say nothing about the graph's health.
+fn is_graph_failure(err: &FalkorDBError) -> bool {
+    matches!(
+        err.without_context(),
+        FalkorDBError::QueryMemoryLimitExceeded(_)
+            | FalkorDBError::QueryTimedOut { .. }
+            | FalkorDBError::DeadlineExceeded
+            | FalkorDBError::SocketTimeout
+    )
+}
+
+/// Whether an error means the server could not be reached, as opposed to it rejecting a command
+fn is_connection_failure(err: &FalkorDBError) -> bool {
+    matches!(
+        err,
+        FalkorDBError::ConnectionDown
+            | FalkorDBError::NoConnection
+            | FalkorDBError::EndpointUnavailable { .. }
+    )
+}
+
+/// Whether an error was replied by the server, which proves it is reachable
+fn is_server_reply(err: &FalkorDBError) -> bool {
+    matches!(
+        err,
+        FalkorDBError::RedisError(_)
+            | FalkorDBError::QueryMemoryLimitExceeded(_)
+            | FalkorDBError::QueryTimedOut { .. }
+            | FalkorDBError::SyntaxError { .. }
+            | FalkorDBError::ConstraintViolation(_)
+            | FalkorDBError::AuthenticationFailed(_)
+            | FalkorDBError::ServerOutOfMemory(_)
+            | FalkorDBError::Redirected { .. }
+    )
+}
+
+/// A borrower's pass through the connection circuit, recording the outcome of the borrowed connection's commands,
+/// and releasing the trial it was let through as, if it is dropped without sending any
+pub(crate) struct ConnectionCircuitPass {
+    circuit: Option<Arc<CircuitBreaker>>,
+    pending: bool,
+}
+
+impl ConnectionCircuitPass {
+    /// Passes through the connection circuit, failing fast with [`FalkorDBError::ConnectionCircuitOpen`] if it is open
+    pub(crate) fn acquire(circuit: Option<Arc<CircuitBreaker>>) -> FalkorResult<Self> {
+        if let Some(Err(retry_in)) = circuit.as_deref().map(CircuitBreaker::try_acquire) {
+            return Err(FalkorDBError::ConnectionCircuitOpen { retry_in });
+        }
+        Ok(Self {
+            pending: circuit.is_some(),
+            circuit,
+        })
+    }
+
+    /// Records the outcome of a command, or of opening a connection
+    pub(crate) fn record<T>(
+        &mut self,
+        res: &FalkorResult<T>,
+    ) {
+        let Some(circuit) = self.circuit.as_deref() else {
+            return;
+        };
+        match res {
+            Ok(_) => circuit.record_success(),
+            Err(err) if is_connection_failure(err) => circuit.record_failure(),
+            Err(err) if is_server_reply(err) => circuit.record_success(),
+            Err(_) => circuit.record_neutral(),
+        }
+        self.pending = false;
+    }
+}
+
+impl Drop for ConnectionCircuitPass {
+    fn drop(&mut self) {
+        if let Some(circuit) = self.circuit.as_deref().filter(|_| self.pending) {
+            circuit.record_neutral();
+        }
+    }
+}
+
+/// The circuit breakers isolating the failures of each graph, so a single failing graph fails fast,
+/// without holding on to the pool's connections, while other graphs are queried normally
+#[derive(Default)]
+pub(crate) struct GraphCircuits {
+    config: Mutex<Option<(NonZeroU32, Duration)>>,
+    circuits: Mutex<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl GraphCircuits {
+    /// Enables per-graph circuit breakers, for graphs selected from now on
+    pub(crate) fn configure(
+        &self,
+        failure_threshold: NonZeroU32,
+        cool_down: Duration,
+    ) {
+        *self.config.lock() = Some((failure_threshold, cool_down));
+    }
+
+    /// Returns the circuit breaker of the given graph, shared by all its handles, if circuit breakers are enabled
+    pub(crate) fn get_or_create(
+        &self,
+        graph_name: &str,
+    ) -> Option<Arc<CircuitBreaker>> {
+        let (failure_threshold, cool_down) = (*self.config.lock())?;
+        Some(
+            self.circuits
+                .lock()
+                .entry(graph_name.to_string())
+                .or_insert_with(|| Arc::new(CircuitBreaker::new(failure_threshold, cool_down)))
+                .clone(),
+        )
+    }
+
+    pub(crate) fn stats(
+        &self,
+        graph_name: &str,
+    ) -> Option<CircuitStats> {
+        self.circuits
+            .lock()
+            .get(graph_name)
+            .map(|circuit| circuit.stats())
+    }
+}
+
+/// Fails fast if the graph's circuit is open
+pub(crate) fn acquire_graph_circuit(
+    circuit: Option<&CircuitBreaker>,
+    graph_name: &str,
+) -> FalkorResult<()> {
+    match circuit.map(CircuitBreaker::try_acquire) {
+        Some(Err(retry_in)) => Err(FalkorDBError::GraphCircuitOpen {
+            graph: graph_name.to_string(),
+            retry_in,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Records the outcome of a query in the graph's circuit
+pub(crate) fn record_graph_outcome<T>(
+    circuit: Option<&CircuitBreaker>,
+    res: &FalkorResult<T>,
+) {
+    let Some(circuit) = circuit else {
+        return;
+    };
+    match res {
+        Ok(_) => circuit.record_success(),
+        Err(err) if is_graph_failure(err) => circuit.record_failure(),
+        Err(_) => circuit.record_neutral(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransport, FalkorClientBuilder, FalkorConnectionInfo};
+
+    fn circuit(cool_down: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            NonZeroU32::new(2).expect("Could not create a perfectly valid u32"),
+            cool_down,
+        )
+    }
+
+    #[test]
+    fn test_circuit_opens_and_recovers() {
+        let circuit = circuit(Duration::from_millis(50));
+        circuit.record_failure();
+        circuit.record_success();
+        circuit.record_failure();
+        assert_eq!(circuit.stats().state, CircuitState::Closed);
+        assert!(circuit.try_acquire().is_ok());
+
+        circuit.record_failure();
+        assert_eq!(circuit.stats().state, CircuitState::Open);
+        assert!(circuit.try_acquire().is_err());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(circuit.stats().state, CircuitState::HalfOpen);
+        assert!(circuit.try_acquire().is_ok());
+        // Only a single trial request is let through
+        assert!(circuit.try_acquire().is_err());
+
+        circuit.record_success();
+        let stats = circuit.stats();
+        assert_eq!(stats.state, CircuitState::Closed);
+        assert_eq!(stats.failures, 3);
+        assert_eq!(stats.rejected, 2);
+        assert_eq!(stats.times_opened, 1);
+    }
+
+    #[test]
+    fn test_failed_trial_reopens_circuit() {
+        let circuit = circuit(Duration::ZERO);
+        circuit.record_failure();
+        circuit.record_failure();
+        assert!(circuit.try_acquire().is_ok());
+        circuit.record_failure();
+        assert_eq!(circuit.stats().times_opened, 2);
+
+        // A neutral outcome lets another trial through
+        assert!(circuit.try_acquire().is_ok());
+        circuit.record_neutral();
+        assert!(circuit.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_record_graph_outcome() {
+        let circuit = circuit(Duration::from_secs(60));
+        record_graph_outcome::<()>(Some(&circuit), &Err(FalkorDBError::ConnectionDown));
+        record_graph_outcome::<()>(Some(&circuit), &Err(FalkorDBError::ConnectionDown));
+        assert_eq!(circuit.stats().failures, 0);
+
+        record_graph_outcome::<()>(Some(&circuit), &Err(FalkorDBError::SocketTimeout));
+        record_graph_outcome::<()>(Some(&circuit), &Err(FalkorDBError::SocketTimeout));
+        assert!(matches!(
+            acquire_graph_circuit(Some(&circuit), "social"),
+            Err(FalkorDBError::GraphCircuitOpen { graph, .. }) if graph == "social"
+        ));
+        assert!(acquire_graph_circuit(None, "social").is_ok());
+    }
+
+    #[test]
+    fn test_client_errors_do_not_open_graph_circuit() {
+        let circuit = circuit(Duration::from_secs(60));
+        for _ in 0..5 {
+            record_graph_outcome::<()>(
+                Some(&circuit),
+                &Err(FalkorDBError::SyntaxError {
+                    message: "Invalid input".to_string(),
+                    offset: None,
+                }
+                .with_query_context("RETURN", false)),
+            );
+            record_graph_outcome::<()>(
+                Some(&circuit),
+                &Err(FalkorDBError::ConstraintViolation(
+                    "unique constraint violation".to_string(),
+                )),
+            );
+            record_graph_outcome::<()>(
+                Some(&circuit),
+                &Err(FalkorDBError::RedisError("ERR".to_string())),
+            );
+        }
+
+        let stats = circuit.stats();
+        assert_eq!(stats.state, CircuitState::Closed);
+        assert_eq!(stats.failures, 0);
+        assert!(acquire_graph_circuit(Some(&circuit), "social").is_ok());
+    }
+
+    #[test]
+    fn test_connection_circuit_pass() {
+        let circuit = Arc::new(circuit(Duration::from_secs(60)));
+        let mut pass = ConnectionCircuitPass::acquire(Some(circuit.clone())).unwrap();
+        pass.record::<()>(&Err(FalkorDBError::RedisError("ERR".to_string())));
+        pass.record::<()>(&Err(FalkorDBError::SocketTimeout));
+        pass.record::<()>(&Err(FalkorDBError::ConnectionDown));
+        assert_eq!(circuit.stats().consecutive_failures, 1);
+
+        pass.record::<()>(&Err(FalkorDBError::EndpointUnavailable {
+            endpoint: "127.0.0.1:6379".to_string(),
+            reason: "Connection refused".to_string(),
+        }));
+        assert_eq!(circuit.stats().state, CircuitState::Open);
+        assert!(matches!(
+            ConnectionCircuitPass::acquire(Some(circuit.clone())),
+            Err(FalkorDBError::ConnectionCircuitOpen { .. })
+        ));
+        assert!(ConnectionCircuitPass::acquire(None).is_ok());
+    }
+
+    #[test]
+    fn test_client_connection_circuit() {
+        let provider = MockTransport::new("mock://outage", |args| {
+            match args.first().map(String::as_str) {
+                Some("GRAPH.RO_QUERY") => {
+                    Ok(redis::Value::Array(vec![redis::Value::Array(vec![])]))
+                }
+                _ => Ok(redis::Value::Nil),
+            }
+        });
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(FalkorConnectionInfo::Transport(Arc::new(provider.clone())))
+            .with_connection_circuit_breaker(
+                NonZeroU32::new(2).expect("Could not create a perfectly valid u32"),
+                Duration::from_millis(50),
+            )
+            .build()
+            .expect("Could not create client over transport");
+        let graph = client.select_graph("social");
+        assert_eq!(
+            client
+                .pool_stats()
+                .connection_circuit
+                .map(|stats| stats.state),
+            Some(CircuitState::Closed)
+        );
+
+        provider.set_down(true);
+        for _ in 0..2 {
+            assert!(graph.ro_query("MATCH (n) RETURN n").execute().is_err());
+        }
+        assert_eq!(
+            client
+                .pool_stats()
+                .connection_circuit
+                .map(|stats| stats.state),
+            Some(CircuitState::Open)
+        );
+        assert!(matches!(
+            graph.ro_query("MATCH (n) RETURN n").execute(),
+            Err(FalkorDBError::ConnectionCircuitOpen { .. })
+        ));
+
+        // Once the server is back, the trial command closes the circuit
+        provider.set_down(false);
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(graph.ro_query("MATCH (n) RETURN n").execute().is_ok());
+        let stats = client
+            .pool_stats()
+            .connection_circuit
+            .expect("The connection circuit breaker is enabled");
+        assert_eq!(stats.state, CircuitState::Closed);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.times_opened, 1);
+    }
+
+    #[test]
+    fn test_unused_connection_circuit_pass_releases_trial() {
+        let circuit = Arc::new(circuit(Duration::ZERO));
+        circuit.record_failure();
+        circuit.record_failure();
+
+        let pass = ConnectionCircuitPass::acquire(Some(circuit.clone())).unwrap();
+        assert!(ConnectionCircuitPass::acquire(Some(circuit.clone())).is_err());
+        drop(pass);
+        assert!(ConnectionCircuitPass::acquire(Some(circuit)).is_ok());
+    }
+}
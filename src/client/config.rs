@@ -0,0 +1,78 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use std::num::{NonZeroU8, NonZeroUsize};
+
+/// A plain configuration struct, holding all the options of [`FalkorClientBuilder`](crate::FalkorClientBuilder)
+/// With the `serde` feature enabled this can be deserialized from any format serde supports, such as YAML, TOML or JSON,
+/// allowing the client to be configured from the application's config file using [`FalkorClientBuilder::from_config`](crate::FalkorClientBuilder::from_config).
+///
+/// Every field is optional, and falls back to the builder's default when omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct FalkorClientConfig {
+    /// The connection string of the database, E.g. `falkor://127.0.0.1:6379`, see [`FalkorConnectionInfo`](crate::FalkorConnectionInfo)
+    pub url: Option<String>,
+    /// How large a connection pool to maintain, between 1 and 32
+    pub num_connections: Option<NonZeroU8>,
+    /// Whether literals should be masked in logged queries, see [`FalkorClientBuilder::with_log_redaction`](crate::FalkorClientBuilder::with_log_redaction)
+    pub log_redaction: Option<bool>,
+    /// The maximal number of ids cached per schema type, see [`FalkorClientBuilder::with_schema_cache_max_ids`](crate::FalkorClientBuilder::with_schema_cache_max_ids)
+    pub schema_cache_max_ids: Option<NonZeroUsize>,
+    /// The master to connect to, when the url points at a Sentinel monitoring several masters, see [`FalkorClientBuilder::with_sentinel_master`](crate::FalkorClientBuilder::with_sentinel_master)
+    pub sentinel_master: Option<String>,
+    /// Whether idle connections are pinged before being borrowed, see [`FalkorClientBuilder::with_test_on_borrow`](crate::FalkorClientBuilder::with_test_on_borrow)
+    pub test_on_borrow: Option<bool>,
+    /// Whether coerced property types fail property writes, see [`FalkorClientBuilder::with_strict_typing`](crate::FalkorClientBuilder::with_strict_typing)
+    pub strict_typing: Option<bool>,
+    /// Whether connections are only opened once first used, see [`FalkorClientBuilder::with_lazy_connections`](crate::FalkorClientBuilder::with_lazy_connections)
+    pub lazy_connections: Option<bool>,
+    /// The name every pooled connection identifies with, see [`FalkorClientBuilder::with_client_name`](crate::FalkorClientBuilder::with_client_name)
+    pub client_name: Option<String>,
+    /// Whether the client owns a single connection instead of a pool, see [`FalkorClientBuilder::with_single_connection`](crate::FalkorClientBuilder::with_single_connection)
+    pub single_connection: Option<bool>,
+    /// The index of the logical database to select, see [`FalkorClientBuilder::with_database`](crate::FalkorClientBuilder::with_database)
+    pub database: Option<u32>,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_config() {
+        let config: FalkorClientConfig = serde_json::from_str(
+            r#"{"url": "falkor://10.0.0.1:6379", "num_connections": 4, "log_redaction": true, "schema_cache_max_ids": 1000, "sentinel_master": "mymaster", "test_on_borrow": true, "strict_typing": true, "lazy_connections": true, "client_name": "orders-service", "single_connection": true, "database": 2}"#,
+        )
+        .expect("Could not deserialize config");
+        assert_eq!(
+            config,
+            FalkorClientConfig {
+                url: Some("falkor://10.0.0.1:6379".to_string()),
+                num_connections: NonZeroU8::new(4),
+                log_redaction: Some(true),
+                schema_cache_max_ids: NonZeroUsize::new(1000),
+                sentinel_master: Some("mymaster".to_string()),
+                test_on_borrow: Some(true),
+                strict_typing: Some(true),
+                lazy_connections: Some(true),
+                client_name: Some("orders-service".to_string()),
+                single_connection: Some(true),
+                database: Some(2),
+            }
+        );
+
+        assert_eq!(
+            serde_json::from_str::<FalkorClientConfig>("{}").expect("Could not deserialize config"),
+            FalkorClientConfig::default()
+        );
+        assert!(serde_json::from_str::<FalkorClientConfig>(r#"{"num_connections": 0}"#).is_err());
+        assert!(serde_json::from_str::<FalkorClientConfig>(r#"{"hostname": "db"}"#).is_err());
+    }
+}
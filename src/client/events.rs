@@ -0,0 +1,110 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use parking_lot::RwLock;
+use std::{sync::Arc, time::Duration};
+
+/// A change in the client's connectivity, reported to the listeners registered with
+/// [`FalkorClientBuilder::with_connection_event_listener`](crate::FalkorClientBuilder::with_connection_event_listener)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// A new connection was opened to the server
+    Established {
+        /// The address of the server
+        address: String,
+    },
+    /// A connection went down or timed out, and is about to be replaced
+    Lost {
+        /// The address of the server
+        address: String,
+        /// The error the connection failed with
+        reason: String,
+    },
+    /// A new connection was opened, or failed to open, to replace one which was lost
+    ReconnectAttempted {
+        /// The address of the server
+        address: String,
+        /// Whether the new connection was opened
+        succeeded: bool,
+    },
+    /// Sentinel promoted a new master, so connections to the previous one are being replaced
+    FailoverCompleted {
+        /// The address of the promoted master, or [`None`] if the failover may have been missed,
+        /// E.g. while resubscribing to Sentinel's announcements
+        promoted: Option<String>,
+    },
+    /// A borrow gave up waiting for a pooled connection,
+    /// see [`FalkorDBError::PoolExhausted`](crate::FalkorDBError::PoolExhausted) and [`FalkorDBError::PoolTimeout`](crate::FalkorDBError::PoolTimeout)
+    PoolExhausted {
+        /// How long the borrow waited
+        waited: Duration,
+    },
+}
+
+/// A callback invoked with every [`ConnectionEvent`] of a client
+pub type ConnectionEventListener = Arc<dyn Fn(&ConnectionEvent) + Send + Sync>;
+
+/// The listeners registered with a client
+#[derive(Default)]
+pub(crate) struct ConnectionEvents(RwLock<Vec<ConnectionEventListener>>);
+
+impl ConnectionEvents {
+    pub(crate) fn subscribe(
+        &self,
+        listener: ConnectionEventListener,
+    ) {
+        self.0.write().push(listener);
+    }
+
+    /// Reports an event to every listener, creating it only if there are any
+    pub(crate) fn emit<F: FnOnce() -> ConnectionEvent>(
+        &self,
+        event: F,
+    ) {
+        // Clone the listeners so they run without holding any lock, allowing them to register other listeners
+        let listeners = self.0.read().clone();
+        if listeners.is_empty() {
+            return;
+        }
+
+        let event = event();
+        for listener in listeners {
+            listener(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[test]
+    fn test_connection_events() {
+        let events = ConnectionEvents::default();
+        events.emit(|| unreachable!());
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        for _ in 0..2 {
+            let received = received.clone();
+            events.subscribe(Arc::new(move |event: &ConnectionEvent| {
+                received.lock().push(event.clone())
+            }));
+        }
+        events.emit(|| ConnectionEvent::PoolExhausted {
+            waited: Duration::from_millis(50),
+        });
+
+        assert_eq!(
+            *received.lock(),
+            vec![
+                ConnectionEvent::PoolExhausted {
+                    waited: Duration::from_millis(50)
+                };
+                2
+            ]
+        );
+    }
+}
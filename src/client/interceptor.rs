@@ -0,0 +1,232 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::FalkorResult;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A command about to be sent to the server, as seen by a [`CommandInterceptor`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterceptedCommand {
+    /// The name of the command, E.g. `GRAPH.QUERY`
+    pub name: String,
+    /// The subcommand, sent right after the command's name, E.g. `SET` for `GRAPH.CONFIG SET`
+    pub subcommand: Option<String>,
+    /// The graph the command targets, if it targets one
+    pub graph: Option<String>,
+    /// The remaining arguments, E.g. the query followed by `--compact`
+    pub args: Vec<String>,
+}
+
+impl InterceptedCommand {
+    fn new(
+        graph_name: Option<&str>,
+        command: &str,
+        subcommand: Option<&str>,
+        params: Option<&[&str]>,
+    ) -> Self {
+        Self {
+            name: command.to_string(),
+            subcommand: subcommand.map(ToString::to_string),
+            graph: graph_name.map(ToString::to_string),
+            args: params
+                .unwrap_or_default()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    /// The arguments, borrowed as the connection layer sends them
+    pub(crate) fn params(&self) -> Vec<&str> {
+        self.args.iter().map(String::as_str).collect()
+    }
+}
+
+/// Sees every command the client sends, and its outcome, see [`FalkorClientBuilder::with_interceptor`](crate::FalkorClientBuilder::with_interceptor)
+/// Enables auditing, caching and custom telemetry, without forking the connection layer.
+///
+/// Interceptors are called in the order they were registered before a command is sent, and in reverse order once it completes.
+/// Both methods do nothing by default, and are called for every command, so they should not block.
+pub trait CommandInterceptor: Send + Sync {
+    /// Called before a command is sent, may modify its arguments, or reply to it instead of the server
+    ///
+    /// # Arguments
+    /// * `command`: The [`InterceptedCommand`] about to be sent, which may be modified
+    ///
+    /// # Returns
+    /// [`None`] to send the command, or a reply to short-circuit it with,
+    /// in which case neither the server nor the interceptors registered after this one see the command
+    fn before_command(
+        &self,
+        _command: &mut InterceptedCommand,
+    ) -> Option<FalkorResult<redis::Value>> {
+        None
+    }
+
+    /// Called once a command completed, or was short-circuited by this interceptor or one registered before it
+    ///
+    /// # Arguments
+    /// * `command`: The [`InterceptedCommand`] as it was sent
+    /// * `result`: The reply, or the error the command failed with
+    /// * `latency`: How long the command took, from the first interceptor being called
+    fn after_command(
+        &self,
+        _command: &InterceptedCommand,
+        _result: &FalkorResult<redis::Value>,
+        _latency: Duration,
+    ) {
+    }
+}
+
+/// The interceptors registered with a client, in registration order
+pub(crate) struct InterceptorChain(Vec<Arc<dyn CommandInterceptor>>);
+
+impl InterceptorChain {
+    /// Creates a chain of the interceptors, or [`None`] if there are none, so commands skip interception entirely
+    pub(crate) fn new(interceptors: Vec<Arc<dyn CommandInterceptor>>) -> Option<Arc<Self>> {
+        (!interceptors.is_empty()).then(|| Arc::new(Self(interceptors)))
+    }
+
+    /// Passes a command through the `before_command` hooks, until one of them short-circuits it
+    pub(crate) fn intercept(
+        self: Arc<Self>,
+        graph_name: Option<&str>,
+        command: &str,
+        subcommand: Option<&str>,
+        params: Option<&[&str]>,
+    ) -> Interception {
+        let started = Instant::now();
+        let mut command = InterceptedCommand::new(graph_name, command, subcommand, params);
+        let mut invoked = 0;
+        let mut reply = None;
+        for interceptor in &self.0 {
+            invoked += 1;
+            reply = interceptor.before_command(&mut command);
+            if reply.is_some() {
+                break;
+            }
+        }
+
+        Interception {
+            chain: self,
+            command,
+            invoked,
+            reply,
+            started,
+        }
+    }
+}
+
+/// A command which went through the `before_command` hooks of an [`InterceptorChain`]
+pub(crate) struct Interception {
+    chain: Arc<InterceptorChain>,
+    command: InterceptedCommand,
+    invoked: usize,
+    reply: Option<FalkorResult<redis::Value>>,
+    started: Instant,
+}
+
+impl Interception {
+    /// The command to send, as modified by the interceptors
+    pub(crate) fn command(&self) -> &InterceptedCommand {
+        &self.command
+    }
+
+    /// The reply an interceptor short-circuited the command with, in which case it must not be sent
+    pub(crate) fn take_reply(&mut self) -> Option<FalkorResult<redis::Value>> {
+        self.reply.take()
+    }
+
+    /// Passes the command's outcome through the `after_command` hooks of the interceptors which saw it
+    pub(crate) fn finish(
+        self,
+        result: FalkorResult<redis::Value>,
+    ) -> FalkorResult<redis::Value> {
+        let latency = self.started.elapsed();
+        for interceptor in self.chain.0[..self.invoked].iter().rev() {
+            interceptor.after_command(&self.command, &result, latency);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    struct Recorder {
+        name: &'static str,
+        events: Arc<Mutex<Vec<String>>>,
+        short_circuit: bool,
+    }
+
+    impl CommandInterceptor for Recorder {
+        fn before_command(
+            &self,
+            command: &mut InterceptedCommand,
+        ) -> Option<FalkorResult<redis::Value>> {
+            self.events
+                .lock()
+                .push(format!("{} before {}", self.name, command.args.join(" ")));
+            command.args.push(self.name.to_string());
+            self.short_circuit.then_some(Ok(redis::Value::Okay))
+        }
+
+        fn after_command(
+            &self,
+            _command: &InterceptedCommand,
+            result: &FalkorResult<redis::Value>,
+            _latency: Duration,
+        ) {
+            self.events
+                .lock()
+                .push(format!("{} after {}", self.name, result.is_ok()));
+        }
+    }
+
+    #[test]
+    fn test_interceptor_chain() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorder = |name, short_circuit| -> Arc<dyn CommandInterceptor> {
+            Arc::new(Recorder {
+                name,
+                events: events.clone(),
+                short_circuit,
+            })
+        };
+        assert!(InterceptorChain::new(Vec::new()).is_none());
+
+        let chain = InterceptorChain::new(vec![
+            recorder("audit", false),
+            recorder("cache", true),
+            recorder("unreached", false),
+        ])
+        .expect("Could not create a chain of interceptors");
+        let mut interception =
+            chain.intercept(Some("social"), "GRAPH.QUERY", None, Some(&["RETURN 1"]));
+        assert_eq!(
+            interception.command().params(),
+            ["RETURN 1", "audit", "cache"]
+        );
+        let reply = interception
+            .take_reply()
+            .expect("The command should have been short-circuited");
+        assert_eq!(interception.finish(reply), Ok(redis::Value::Okay));
+
+        assert_eq!(
+            *events.lock(),
+            [
+                "audit before RETURN 1",
+                "cache before RETURN 1 audit",
+                "cache after true",
+                "audit after true",
+            ]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    client::{ConnectionTimeouts, FalkorClientProvider},
+    connection::asynchronous::FalkorAsyncConnection,
+    FalkorClientBuilder, FalkorConnectionInfo, FalkorDBError, FalkorResult,
+};
+use tokio::sync::Mutex;
+
+/// A single asynchronous connection to FalkorDB, opened by a [`FalkorConnectionManager`]
+/// and owned by an external pool, such as `bb8` or `deadpool`.
+pub struct FalkorManagedConnection {
+    conn: FalkorAsyncConnection,
+    broken: bool,
+}
+
+impl FalkorManagedConnection {
+    /// Sends a single command over this connection, and waits for its reply
+    ///
+    /// # Arguments
+    /// * `command`: The command name, E.g. `GRAPH.QUERY`
+    /// * `args`: The arguments of the command, E.g. the graph name, followed by the query
+    ///
+    /// # Returns
+    /// The raw reply, as returned by the server
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Managed Connection Execute", skip_all, level = "debug")
+    )]
+    pub async fn execute(
+        &mut self,
+        command: &str,
+        args: &[&str],
+    ) -> FalkorResult<redis::Value> {
+        let res = self
+            .conn
+            .execute_command(None, command, None, Some(args))
+            .await;
+        if matches!(res, Err(FalkorDBError::ConnectionDown)) {
+            self.broken = true;
+        }
+        res
+    }
+
+    /// Whether a previous command found this connection down, in which case the pool should discard it
+    ///
+    /// # Returns
+    /// True if the connection can no longer be used
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+}
+
+/// Opens connections to FalkorDB for an external connection pool, for applications which already use `bb8` or `deadpool`,
+/// and would rather keep a single pooling strategy than use the client's built-in pool.
+///
+/// Enable the `bb8` feature for `bb8::ManageConnection`, or the `deadpool` feature for `deadpool::managed::Manager`.
+pub struct FalkorConnectionManager {
+    provider: Mutex<FalkorClientProvider>,
+}
+
+impl FalkorConnectionManager {
+    /// Creates a new [`FalkorConnectionManager`], which opens its connections using the provided connection info
+    ///
+    /// # Arguments
+    /// * `connection_info`: Anything convertible to [`FalkorConnectionInfo`], E.g. `falkor://127.0.0.1:6379`
+    ///
+    /// # Returns
+    /// The new [`FalkorConnectionManager`], or an error if the connection info could not be parsed
+    pub fn new<E: Into<FalkorDBError>, T: TryInto<FalkorConnectionInfo, Error = E>>(
+        connection_info: T
+    ) -> FalkorResult<Self> {
+        Ok(Self {
+            provider: Mutex::new(FalkorClientBuilder::<'A'>::get_client(
+                connection_info,
+                None,
+                ConnectionTimeouts::default(),
+            )?),
+        })
+    }
+
+    /// Opens a new connection
+    ///
+    /// # Returns
+    /// The new [`FalkorManagedConnection`]
+    pub async fn connect(&self) -> FalkorResult<FalkorManagedConnection> {
+        Ok(FalkorManagedConnection {
+            conn: self.provider.lock().await.get_async_connection().await?,
+            broken: false,
+        })
+    }
+
+    /// Checks a connection is still usable, by sending a PING over it
+    ///
+    /// # Arguments
+    /// * `conn`: The connection to check
+    ///
+    /// # Returns
+    /// Nothing, or the error the connection failed with
+    pub async fn validate(
+        &self,
+        conn: &mut FalkorManagedConnection,
+    ) -> FalkorResult<()> {
+        conn.execute("PING", &[]).await.map(|_| ())
+    }
+}
+
+#[cfg(feature = "bb8")]
+impl bb8::ManageConnection for FalkorConnectionManager {
+    type Connection = FalkorManagedConnection;
+    type Error = FalkorDBError;
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        FalkorConnectionManager::connect(self).await
+    }
+
+    async fn is_valid(
+        &self,
+        conn: &mut Self::Connection,
+    ) -> Result<(), Self::Error> {
+        self.validate(conn).await
+    }
+
+    fn has_broken(
+        &self,
+        conn: &mut Self::Connection,
+    ) -> bool {
+        conn.is_broken()
+    }
+}
+
+#[cfg(feature = "deadpool")]
+impl deadpool::managed::Manager for FalkorConnectionManager {
+    type Type = FalkorManagedConnection;
+    type Error = FalkorDBError;
+
+    async fn create(&self) -> Result<Self::Type, Self::Error> {
+        self.connect().await
+    }
+
+    async fn recycle(
+        &self,
+        conn: &mut Self::Type,
+        _: &deadpool::managed::Metrics,
+    ) -> deadpool::managed::RecycleResult<Self::Error> {
+        if conn.is_broken() {
+            return Err(deadpool::managed::RecycleError::message(
+                "The connection is down",
+            ));
+        }
+        self.validate(conn)
+            .await
+            .map_err(deadpool::managed::RecycleError::Backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransport;
+    use std::sync::{atomic::Ordering, Arc};
+
+    fn gateway() -> MockTransport {
+        MockTransport::new("mock://gateway", |args| {
+            match args.first().map(String::as_str) {
+                Some("PING") => Ok(redis::Value::SimpleString("PONG".to_string())),
+                _ => Err(FalkorDBError::ConnectionDown),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_connection_manager() {
+        let provider = Arc::new(gateway());
+        let manager =
+            FalkorConnectionManager::new(FalkorConnectionInfo::Transport(provider.clone()))
+                .expect("Could not create connection manager");
+
+        let mut conn = manager.connect().await.expect("Could not connect");
+        assert_eq!(provider.connections.load(Ordering::Relaxed), 1);
+        assert!(manager.validate(&mut conn).await.is_ok());
+        assert!(!conn.is_broken());
+
+        assert_eq!(
+            conn.execute("GRAPH.LIST", &[]).await,
+            Err(FalkorDBError::ConnectionDown)
+        );
+        assert!(conn.is_broken());
+    }
+
+    #[cfg(feature = "bb8")]
+    #[tokio::test]
+    async fn test_bb8_pool() {
+        let manager =
+            FalkorConnectionManager::new(FalkorConnectionInfo::Transport(Arc::new(gateway())))
+                .expect("Could not create connection manager");
+        let pool = bb8::Pool::builder()
+            .max_size(2)
+            .build(manager)
+            .await
+            .expect("Could not build pool");
+
+        let mut conn = pool.get().await.expect("Could not borrow connection");
+        assert!(conn.execute("PING", &[]).await.is_ok());
+    }
+
+    #[cfg(feature = "deadpool")]
+    #[tokio::test]
+    async fn test_deadpool_pool() {
+        let manager =
+            FalkorConnectionManager::new(FalkorConnectionInfo::Transport(Arc::new(gateway())))
+                .expect("Could not create connection manager");
+        let pool = deadpool::managed::Pool::<FalkorConnectionManager>::builder(manager)
+            .max_size(2)
+            .build()
+            .expect("Could not build pool");
+
+        let mut conn = pool.get().await.expect("Could not borrow connection");
+        assert!(conn.execute("PING", &[]).await.is_ok());
+    }
+}
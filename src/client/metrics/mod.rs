@@ -0,0 +1,207 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::FalkorResult;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "prometheus")]
+pub(crate) mod prometheus;
+
+/// Receives the client's metrics, so they can be exported to a monitoring system, see [`FalkorClientBuilder::with_metrics_recorder`](crate::FalkorClientBuilder::with_metrics_recorder)
+///
+/// Every method does nothing by default, so a recorder only implements the ones it exports.
+/// They are called on the path of every query, so they should not block, E.g. by only updating counters and histograms.
+pub trait MetricsRecorder: Send + Sync {
+    /// Called once a query is sent to a graph, retries of the query are not counted again
+    ///
+    /// # Arguments
+    /// * `graph`: The name of the graph
+    /// * `command`: The command the query is sent with, E.g. `GRAPH.QUERY` or `GRAPH.RO_QUERY`
+    fn record_query(
+        &self,
+        _graph: &str,
+        _command: &str,
+    ) {
+    }
+
+    /// Called once a query completes, whether it succeeded or failed
+    ///
+    /// # Arguments
+    /// * `graph`: The name of the graph
+    /// * `command`: The command the query was sent with
+    /// * `latency`: How long the query took, including borrowing a connection and retries
+    fn record_query_latency(
+        &self,
+        _graph: &str,
+        _command: &str,
+        _latency: Duration,
+    ) {
+    }
+
+    /// Called once a query fails
+    ///
+    /// # Arguments
+    /// * `graph`: The name of the graph
+    /// * `command`: The command the query was sent with
+    /// * `error_kind`: The name of the [`FalkorDBError`](crate::FalkorDBError) variant the query failed with, E.g. `SyntaxError`,
+    ///   the server's error rather than [`FalkorDBError::QueryFailed`](crate::FalkorDBError::QueryFailed)
+    fn record_query_error(
+        &self,
+        _graph: &str,
+        _command: &str,
+        _error_kind: &str,
+    ) {
+    }
+
+    /// Called once a borrow, which found no idle connection in the pool, was handed a returned one
+    ///
+    /// # Arguments
+    /// * `waited`: How long the borrow waited for a connection to be returned
+    fn record_pool_wait(
+        &self,
+        _waited: Duration,
+    ) {
+    }
+
+    /// Called once a borrow gives up waiting for a connection, see [`FalkorDBError::PoolTimeout`](crate::FalkorDBError::PoolTimeout)
+    /// and [`FalkorDBError::PoolExhausted`](crate::FalkorDBError::PoolExhausted)
+    fn record_pool_timeout(&self) {}
+}
+
+impl<M: MetricsRecorder + ?Sized> MetricsRecorder for Arc<M> {
+    fn record_query(
+        &self,
+        graph: &str,
+        command: &str,
+    ) {
+        (**self).record_query(graph, command)
+    }
+
+    fn record_query_latency(
+        &self,
+        graph: &str,
+        command: &str,
+        latency: Duration,
+    ) {
+        (**self).record_query_latency(graph, command, latency)
+    }
+
+    fn record_query_error(
+        &self,
+        graph: &str,
+        command: &str,
+        error_kind: &str,
+    ) {
+        (**self).record_query_error(graph, command, error_kind)
+    }
+
+    fn record_pool_wait(
+        &self,
+        waited: Duration,
+    ) {
+        (**self).record_pool_wait(waited)
+    }
+
+    fn record_pool_timeout(&self) {
+        (**self).record_pool_timeout()
+    }
+}
+
+/// Measures a query for the client's [`MetricsRecorder`], if it has one, from the moment it is sent until it completes
+pub(crate) struct QueryMetrics<'a> {
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+    graph: &'a str,
+    command: &'a str,
+    started: Instant,
+}
+
+impl<'a> QueryMetrics<'a> {
+    /// Records the query being sent
+    pub(crate) fn start(
+        recorder: Option<Arc<dyn MetricsRecorder>>,
+        graph: &'a str,
+        command: &'a str,
+    ) -> Self {
+        if let Some(recorder) = &recorder {
+            recorder.record_query(graph, command);
+        }
+        Self {
+            recorder,
+            graph,
+            command,
+            started: Instant::now(),
+        }
+    }
+
+    /// Records the query's latency, and the kind of error it failed with, if it did
+    pub(crate) fn finish<T>(
+        self,
+        res: &FalkorResult<T>,
+    ) {
+        let Some(recorder) = self.recorder else {
+            return;
+        };
+        recorder.record_query_latency(self.graph, self.command, self.started.elapsed());
+        if let Err(err) = res {
+            recorder.record_query_error(self.graph, self.command, err.without_context().into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FalkorDBError;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct EventRecorder(Mutex<Vec<String>>);
+
+    impl MetricsRecorder for EventRecorder {
+        fn record_query(
+            &self,
+            graph: &str,
+            command: &str,
+        ) {
+            self.0.lock().push(format!("query {graph} {command}"));
+        }
+
+        fn record_query_error(
+            &self,
+            graph: &str,
+            _command: &str,
+            error_kind: &str,
+        ) {
+            self.0.lock().push(format!("error {graph} {error_kind}"));
+        }
+    }
+
+    #[test]
+    fn test_query_metrics() {
+        let recorder = Arc::new(EventRecorder::default());
+        QueryMetrics::start(Some(Arc::new(recorder.clone())), "social", "GRAPH.QUERY")
+            .finish(&Ok(()));
+        QueryMetrics::start(Some(Arc::new(recorder.clone())), "social", "GRAPH.RO_QUERY")
+            .finish::<()>(&Err(FalkorDBError::SyntaxError {
+                message: "Invalid input".to_string(),
+                offset: None,
+            }
+            .with_query_context("RETURN", false)));
+        QueryMetrics::start(None, "social", "GRAPH.QUERY")
+            .finish::<()>(&Err(FalkorDBError::ConnectionDown));
+
+        assert_eq!(
+            *recorder.0.lock(),
+            [
+                "query social GRAPH.QUERY",
+                "query social GRAPH.RO_QUERY",
+                "error social SyntaxError"
+            ]
+        );
+    }
+}
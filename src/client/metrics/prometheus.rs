@@ -0,0 +1,145 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use super::MetricsRecorder;
+use prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry,
+};
+use std::time::Duration;
+
+/// A [`MetricsRecorder`] exporting the client's metrics to a [`prometheus::Registry`], under the `falkordb_` prefix:
+/// * `falkordb_queries_total`: The queries sent, by graph and command
+/// * `falkordb_query_errors_total`: The failed queries, by graph and error kind
+/// * `falkordb_query_duration_seconds`: The latency of queries, by graph and command
+/// * `falkordb_pool_wait_duration_seconds`: How long borrows waited for a pooled connection to be returned
+/// * `falkordb_pool_timeouts_total`: The borrows which gave up waiting for a pooled connection
+#[derive(Clone, Debug)]
+pub struct PrometheusRecorder {
+    queries: IntCounterVec,
+    query_errors: IntCounterVec,
+    query_duration: HistogramVec,
+    pool_wait_duration: Histogram,
+    pool_timeouts: IntCounter,
+}
+
+impl PrometheusRecorder {
+    /// Creates a new [`PrometheusRecorder`], registering its metrics
+    ///
+    /// # Arguments
+    /// * `registry`: The [`Registry`] to register the metrics with, E.g. [`prometheus::default_registry`]
+    ///
+    /// # Returns
+    /// The new [`PrometheusRecorder`], or an error if the registry already holds metrics of the same names
+    pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+        let recorder = Self {
+            queries: IntCounterVec::new(
+                Opts::new("falkordb_queries_total", "The queries sent to FalkorDB"),
+                &["graph", "command"],
+            )?,
+            query_errors: IntCounterVec::new(
+                Opts::new(
+                    "falkordb_query_errors_total",
+                    "The FalkorDB queries which failed",
+                ),
+                &["graph", "kind"],
+            )?,
+            query_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "falkordb_query_duration_seconds",
+                    "The latency of FalkorDB queries",
+                ),
+                &["graph", "command"],
+            )?,
+            pool_wait_duration: Histogram::with_opts(HistogramOpts::new(
+                "falkordb_pool_wait_duration_seconds",
+                "How long borrows waited for a pooled FalkorDB connection to be returned",
+            ))?,
+            pool_timeouts: IntCounter::new(
+                "falkordb_pool_timeouts_total",
+                "The borrows which gave up waiting for a pooled FalkorDB connection",
+            )?,
+        };
+
+        registry.register(Box::new(recorder.queries.clone()))?;
+        registry.register(Box::new(recorder.query_errors.clone()))?;
+        registry.register(Box::new(recorder.query_duration.clone()))?;
+        registry.register(Box::new(recorder.pool_wait_duration.clone()))?;
+        registry.register(Box::new(recorder.pool_timeouts.clone()))?;
+        Ok(recorder)
+    }
+}
+
+impl MetricsRecorder for PrometheusRecorder {
+    fn record_query(
+        &self,
+        graph: &str,
+        command: &str,
+    ) {
+        self.queries.with_label_values(&[graph, command]).inc();
+    }
+
+    fn record_query_latency(
+        &self,
+        graph: &str,
+        command: &str,
+        latency: Duration,
+    ) {
+        self.query_duration
+            .with_label_values(&[graph, command])
+            .observe(latency.as_secs_f64());
+    }
+
+    fn record_query_error(
+        &self,
+        graph: &str,
+        _command: &str,
+        error_kind: &str,
+    ) {
+        self.query_errors
+            .with_label_values(&[graph, error_kind])
+            .inc();
+    }
+
+    fn record_pool_wait(
+        &self,
+        waited: Duration,
+    ) {
+        self.pool_wait_duration.observe(waited.as_secs_f64());
+    }
+
+    fn record_pool_timeout(&self) {
+        self.pool_timeouts.inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::Encoder;
+
+    #[test]
+    fn test_prometheus_recorder() {
+        let registry = Registry::new();
+        let recorder = PrometheusRecorder::new(&registry).expect("Could not register the metrics");
+        assert!(PrometheusRecorder::new(&registry).is_err());
+
+        recorder.record_query("social", "GRAPH.QUERY");
+        recorder.record_query_latency("social", "GRAPH.QUERY", Duration::from_millis(5));
+        recorder.record_query_error("social", "GRAPH.QUERY", "SyntaxError");
+        recorder.record_pool_timeout();
+
+        let mut exported = Vec::new();
+        prometheus::TextEncoder::new()
+            .encode(&registry.gather(), &mut exported)
+            .expect("Could not encode the metrics");
+        let exported = String::from_utf8(exported).expect("The metrics are not valid UTF-8");
+        assert!(
+            exported.contains(r#"falkordb_queries_total{command="GRAPH.QUERY",graph="social"} 1"#)
+        );
+        assert!(exported
+            .contains(r#"falkordb_query_errors_total{graph="social",kind="SyntaxError"} 1"#));
+        assert!(exported.contains("falkordb_pool_timeouts_total 1"));
+    }
+}
@@ -4,21 +4,133 @@
  */
 
 use crate::{
-    connection::blocking::FalkorSyncConnection,
+    connection::{blocking::FalkorSyncConnection, map_connect_err},
     parser::{redis_value_as_string, redis_value_as_vec},
-    FalkorDBError, FalkorResult,
+    transport::FalkorTransportProvider,
+    ConfigValue, CredentialsProvider, FalkorDBError, FalkorResult, RetryPolicy,
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroU64, sync::Arc, time::Duration};
 
 #[cfg(feature = "tokio")]
 use crate::connection::asynchronous::FalkorAsyncConnection;
 
 pub(crate) mod blocking;
 pub(crate) mod builder;
+pub(crate) mod capabilities;
+pub(crate) mod circuit;
+pub(crate) mod config;
+pub(crate) mod events;
+pub(crate) mod interceptor;
+#[cfg(any(feature = "bb8", feature = "deadpool"))]
+pub(crate) mod managers;
+pub(crate) mod metrics;
+pub(crate) mod ops;
+pub(crate) mod pool;
+pub(crate) mod rate_limit;
+pub(crate) mod retry;
+pub(crate) mod self_test;
+pub(crate) mod sentinel;
+pub(crate) mod slow_query;
+pub(crate) mod warm_up;
 
 #[cfg(feature = "tokio")]
 pub(crate) mod asynchronous;
 
+/// The timeouts applied to every Redis connection the client opens, unset timeouts wait indefinitely
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ConnectionTimeouts {
+    /// How long to wait for a connection to be established
+    pub(crate) connect: Option<Duration>,
+    /// How long to wait for the server's reply, once a command was sent
+    pub(crate) read: Option<Duration>,
+    /// How long to wait for a command to be sent
+    pub(crate) write: Option<Duration>,
+}
+
+/// The TCP socket options applied to the client's connections, unset options keep the defaults of the redis crate
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TcpOptions {
+    /// Whether `TCP_NODELAY` is set, sending small commands right away instead of batching them
+    pub(crate) nodelay: Option<bool>,
+    /// How long a connection may be idle before keepalive probes are sent, enabling `SO_KEEPALIVE`
+    pub(crate) keepalive: Option<Duration>,
+}
+
+impl TcpOptions {
+    /// Applies the options to a socket opened by the client itself, rather than by the redis crate
+    #[cfg(feature = "socks5")]
+    pub(crate) fn apply(
+        &self,
+        socket: redis::io::tcp::socket2::SockRef<'_>,
+    ) -> std::io::Result<()> {
+        if let Some(nodelay) = self.nodelay {
+            socket.set_nodelay(nodelay)?;
+        }
+        if let Some(keepalive) = self.keepalive {
+            socket.set_tcp_keepalive(
+                &redis::io::tcp::socket2::TcpKeepalive::new().with_time(keepalive),
+            )?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    fn tcp_settings(&self) -> redis::io::tcp::TcpSettings {
+        let mut settings = redis::io::tcp::TcpSettings::default();
+        if let Some(nodelay) = self.nodelay {
+            settings = settings.set_nodelay(nodelay);
+        }
+        if let Some(keepalive) = self.keepalive {
+            settings = settings
+                .set_keepalive(redis::io::tcp::socket2::TcpKeepalive::new().with_time(keepalive));
+        }
+        settings
+    }
+}
+
+impl ConnectionTimeouts {
+    /// Multiplexed connections bound each round trip as a whole, so both socket timeouts add up
+    #[cfg(feature = "tokio")]
+    fn async_connection_config(
+        &self,
+        tcp_options: &TcpOptions,
+    ) -> redis::AsyncConnectionConfig {
+        let config =
+            redis::AsyncConnectionConfig::new().set_tcp_settings(tcp_options.tcp_settings());
+        match (self.read, self.write) {
+            (None, None) => config,
+            (read, write) => {
+                config.set_response_timeout(read.unwrap_or_default() + write.unwrap_or_default())
+            }
+        }
+    }
+}
+
+fn apply_socket_timeouts(
+    conn: redis::Connection,
+    timeouts: &ConnectionTimeouts,
+) -> FalkorResult<FalkorSyncConnection> {
+    let mut conn = FalkorSyncConnection::Redis(conn);
+    if timeouts.read.is_some() || timeouts.write.is_some() {
+        conn.set_timeouts(timeouts.read, timeouts.write)?;
+    }
+    Ok(conn)
+}
+
+/// Awaits a connection attempt, failing with [`FalkorDBError::NoConnection`] if it does not complete in time
+#[cfg(feature = "tokio")]
+async fn with_connect_timeout<T>(
+    connect_timeout: Option<Duration>,
+    connect: impl std::future::Future<Output = T>,
+) -> FalkorResult<T> {
+    match connect_timeout {
+        Some(connect_timeout) => crate::runtime::timeout(connect_timeout, connect)
+            .await
+            .ok_or(FalkorDBError::NoConnection),
+        None => Ok(connect.await),
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum FalkorClientProvider {
     #[cfg(test)]
@@ -27,52 +139,160 @@ pub(crate) enum FalkorClientProvider {
     Redis {
         client: redis::Client,
         sentinel: Option<redis::sentinel::SentinelClient>,
+        timeouts: ConnectionTimeouts,
+        client_name: Option<String>,
+        credentials: Option<Arc<dyn CredentialsProvider>>,
+        #[cfg(feature = "tokio")]
+        tcp_options: TcpOptions,
     },
+    Transport(Arc<dyn FalkorTransportProvider>),
 }
 
 impl FalkorClientProvider {
     pub(crate) fn get_connection(&mut self) -> FalkorResult<FalkorSyncConnection> {
-        Ok(match self {
+        let mut conn = match self {
             FalkorClientProvider::Redis {
                 sentinel: Some(sentinel),
+                timeouts,
                 ..
-            } => FalkorSyncConnection::Redis(
+            } => apply_socket_timeouts(
                 sentinel
                     .get_connection()
-                    .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
-            ),
+                    .map_err(|err| FalkorDBError::SentinelConnection(err.to_string()))?,
+                timeouts,
+            )?,
 
-            FalkorClientProvider::Redis { client, .. } => FalkorSyncConnection::Redis(
-                client
-                    .get_connection()
-                    .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
-            ),
+            FalkorClientProvider::Redis {
+                client, timeouts, ..
+            } => apply_socket_timeouts(
+                match timeouts.connect {
+                    Some(connect_timeout) => client.get_connection_with_timeout(connect_timeout),
+                    None => client.get_connection(),
+                }
+                .map_err(|err| map_connect_err(&client.get_connection_info().addr, err))?,
+                timeouts,
+            )?,
+            FalkorClientProvider::Transport(provider) => {
+                FalkorSyncConnection::Transport(provider.connect()?)
+            }
             #[cfg(test)]
             FalkorClientProvider::None => Err(FalkorDBError::UnavailableProvider)?,
-        })
+        };
+
+        if let Some(provider) = self.credentials_provider() {
+            let credentials = provider.credentials()?;
+            conn.execute_command(None, "AUTH", None, Some(&credentials.auth_args()))
+                .map_err(|err| FalkorDBError::AuthenticationFailed(err.to_string()))?;
+        }
+        if let Some(client_name) = self.client_name() {
+            conn.execute_command(None, "CLIENT", Some("SETNAME"), Some(&[client_name]))?;
+        }
+        Ok(conn)
     }
 
     #[cfg(feature = "tokio")]
     pub(crate) async fn get_async_connection(&mut self) -> FalkorResult<FalkorAsyncConnection> {
-        Ok(match self {
+        let mut conn = match self {
             FalkorClientProvider::Redis {
                 sentinel: Some(sentinel),
+                timeouts,
+                tcp_options,
                 ..
             } => FalkorAsyncConnection::Redis(
-                sentinel
-                    .get_async_connection()
-                    .await
-                    .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
+                with_connect_timeout(
+                    timeouts.connect,
+                    sentinel.get_async_connection_with_config(
+                        &timeouts.async_connection_config(tcp_options),
+                    ),
+                )
+                .await?
+                .map_err(|err| FalkorDBError::SentinelConnection(err.to_string()))?,
             ),
-            FalkorClientProvider::Redis { client, .. } => FalkorAsyncConnection::Redis(
-                client
-                    .get_multiplexed_tokio_connection()
-                    .await
-                    .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
+            FalkorClientProvider::Redis {
+                client,
+                timeouts,
+                tcp_options,
+                ..
+            } => FalkorAsyncConnection::Redis(
+                with_connect_timeout(
+                    timeouts.connect,
+                    client.get_multiplexed_async_connection_with_config(
+                        &timeouts.async_connection_config(tcp_options),
+                    ),
+                )
+                .await?
+                .map_err(|err| map_connect_err(&client.get_connection_info().addr, err))?,
             ),
+            FalkorClientProvider::Transport(provider) => {
+                FalkorAsyncConnection::Transport(provider.connect_async().await?)
+            }
             #[cfg(test)]
             FalkorClientProvider::None => Err(FalkorDBError::UnavailableProvider)?,
-        })
+        };
+
+        if let Some(provider) = self.credentials_provider() {
+            let credentials = provider.credentials_async().await?;
+            conn.execute_command(None, "AUTH", None, Some(&credentials.auth_args()))
+                .await
+                .map_err(|err| FalkorDBError::AuthenticationFailed(err.to_string()))?;
+        }
+        if let Some(client_name) = self.client_name() {
+            conn.execute_command(None, "CLIENT", Some("SETNAME"), Some(&[client_name]))
+                .await?;
+        }
+        Ok(conn)
+    }
+
+    /// The name the Redis connections this provider opens identify with, custom transports are not named
+    fn client_name(&self) -> Option<&str> {
+        match self {
+            FalkorClientProvider::Redis { client_name, .. } => client_name.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The provider of the credentials the Redis connections this provider opens authenticate with
+    fn credentials_provider(&self) -> Option<Arc<dyn CredentialsProvider>> {
+        match self {
+            FalkorClientProvider::Redis { credentials, .. } => credentials.clone(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_credentials_provider(
+        &mut self,
+        provider: Arc<dyn CredentialsProvider>,
+    ) {
+        if let FalkorClientProvider::Redis { credentials, .. } = self {
+            *credentials = Some(provider);
+        }
+    }
+
+    pub(crate) fn set_client_name(
+        &mut self,
+        name: String,
+    ) {
+        if let FalkorClientProvider::Redis { client_name, .. } = self {
+            *client_name = Some(name);
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) fn set_tcp_options(
+        &mut self,
+        options: TcpOptions,
+    ) {
+        if let FalkorClientProvider::Redis { tcp_options, .. } = self {
+            *tcp_options = options;
+        }
+    }
+
+    /// The timeouts applied to the Redis connections this provider opens
+    pub(crate) fn timeouts(&self) -> ConnectionTimeouts {
+        match self {
+            FalkorClientProvider::Redis { timeouts, .. } => *timeouts,
+            _ => ConnectionTimeouts::default(),
+        }
     }
 
     pub(crate) fn set_sentinel(
@@ -81,38 +301,52 @@ impl FalkorClientProvider {
     ) {
         match self {
             FalkorClientProvider::Redis { sentinel, .. } => *sentinel = Some(sentinel_client),
+            FalkorClientProvider::Transport(_) => {}
             #[cfg(test)]
             FalkorClientProvider::None => {}
         }
     }
 
+    /// Builds a client for the master monitored by the sentinel, returning it along with the master's name
+    /// Sentinels monitoring several masters require the master name to be specified.
     pub(crate) fn get_sentinel_client_common(
         &self,
         connection_info: &redis::ConnectionInfo,
         sentinel_masters: Vec<redis::Value>,
-    ) -> FalkorResult<Option<redis::sentinel::SentinelClient>> {
-        if sentinel_masters.len() != 1 {
-            return Err(FalkorDBError::SentinelMastersCount);
-        }
-
-        let sentinel_master: HashMap<_, _> = sentinel_masters
-            .into_iter()
-            .next()
-            .and_then(|master| master.into_sequence().ok())
-            .ok_or(FalkorDBError::SentinelMastersCount)?
-            .chunks_exact(2)
-            .flat_map(TryInto::<&[redis::Value; 2]>::try_into) // TODO: In the future, check if this can be done with no copying, but this should be a rare function call tbh
-            .flat_map(|[key, val]| {
-                redis_value_as_string(key.to_owned())
-                    .and_then(|key| redis_value_as_string(val.to_owned()).map(|val| (key, val)))
-            })
-            .collect();
+        master_name: Option<&str>,
+    ) -> FalkorResult<Option<(redis::sentinel::SentinelClient, String)>> {
+        let mut names = sentinel_masters.into_iter().map(|master| {
+            let sentinel_master: HashMap<_, _> = master
+                .into_sequence()
+                .map_err(|_| FalkorDBError::SentinelMastersCount)?
+                .chunks_exact(2)
+                .flat_map(TryInto::<&[redis::Value; 2]>::try_into) // TODO: In the future, check if this can be done with no copying, but this should be a rare function call tbh
+                .flat_map(|[key, val]| {
+                    redis_value_as_string(key.to_owned())
+                        .and_then(|key| redis_value_as_string(val.to_owned()).map(|val| (key, val)))
+                })
+                .collect();
+            sentinel_master
+                .get("name")
+                .cloned()
+                .ok_or(FalkorDBError::SentinelMastersCount)
+        });
 
-        let name = sentinel_master
-            .get("name")
-            .ok_or(FalkorDBError::SentinelMastersCount)?;
+        let name = match master_name {
+            Some(master_name) => names
+                .find(|name| name.as_deref().is_ok_and(|name| name == master_name))
+                .ok_or_else(|| {
+                    FalkorDBError::SentinelConnection(format!(
+                        "The sentinel does not monitor a master named {master_name}"
+                    ))
+                })??,
+            None => match (names.next(), names.next()) {
+                (Some(name), None) => name?,
+                _ => return Err(FalkorDBError::SentinelMastersCount),
+            },
+        };
 
-        Ok(Some(
+        Ok(Some((
             redis::sentinel::SentinelClient::build(
                 vec![connection_info.to_owned()],
                 name.to_string(),
@@ -131,7 +365,8 @@ impl FalkorClientProvider {
                 redis::sentinel::SentinelServerType::Master,
             )
             .map_err(|err| FalkorDBError::SentinelConnection(err.to_string()))?,
-        ))
+            name,
+        )))
     }
 
     #[cfg_attr(
@@ -141,7 +376,8 @@ impl FalkorClientProvider {
     pub(crate) fn get_sentinel_client(
         &mut self,
         connection_info: &redis::ConnectionInfo,
-    ) -> FalkorResult<Option<redis::sentinel::SentinelClient>> {
+        master_name: Option<&str>,
+    ) -> FalkorResult<Option<(redis::sentinel::SentinelClient, String)>> {
         let mut conn = self.get_connection()?;
         if !conn.check_is_redis_sentinel()? {
             return Ok(None);
@@ -150,7 +386,7 @@ impl FalkorClientProvider {
         conn.execute_command(None, "SENTINEL", Some("MASTERS"), None)
             .and_then(redis_value_as_vec)
             .and_then(|sentinel_masters| {
-                self.get_sentinel_client_common(connection_info, sentinel_masters)
+                self.get_sentinel_client_common(connection_info, sentinel_masters, master_name)
             })
     }
 
@@ -162,7 +398,8 @@ impl FalkorClientProvider {
     pub(crate) async fn get_sentinel_client_async(
         &mut self,
         connection_info: &redis::ConnectionInfo,
-    ) -> FalkorResult<Option<redis::sentinel::SentinelClient>> {
+        master_name: Option<&str>,
+    ) -> FalkorResult<Option<(redis::sentinel::SentinelClient, String)>> {
         let mut conn = self.get_async_connection().await?;
         if !conn.check_is_redis_sentinel().await? {
             return Ok(None);
@@ -172,11 +409,111 @@ impl FalkorClientProvider {
             .await
             .and_then(redis_value_as_vec)
             .and_then(|sentinel_masters| {
-                self.get_sentinel_client_common(connection_info, sentinel_masters)
+                self.get_sentinel_client_common(connection_info, sentinel_masters, master_name)
             })
     }
 }
 
+/// The configuration option holding the memory limit of each query, in bytes, where 0 means unlimited
+pub(crate) const QUERY_MEMORY_LIMIT_CONFIG: &str = "QUERY_MEM_CAPACITY";
+
+pub(crate) fn parse_query_memory_limit(
+    config: HashMap<String, ConfigValue>
+) -> FalkorResult<Option<NonZeroU64>> {
+    config
+        .get(QUERY_MEMORY_LIMIT_CONFIG)
+        .and_then(ConfigValue::as_i64)
+        .and_then(|limit| u64::try_from(limit).ok())
+        .map(NonZeroU64::new)
+        .ok_or(FalkorDBError::ParsingConfigValue)
+}
+
+pub(crate) fn query_memory_limit_config_value(limit: Option<NonZeroU64>) -> FalkorResult<i64> {
+    limit.map_or(Ok(0), |limit| {
+        i64::try_from(limit.get()).map_err(|_| FalkorDBError::ParsingConfigValue)
+    })
+}
+
 pub(crate) trait ProvidesSyncConnections: Sync + Send {
     fn get_connection(&self) -> FalkorResult<FalkorSyncConnection>;
+
+    /// The policy idempotent operations, such as schema refreshes, are retried with
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentinel_master(name: &str) -> redis::Value {
+        redis::Value::Array(
+            ["name", name, "ip", "10.0.0.1", "port", "6379"]
+                .into_iter()
+                .map(|field| redis::Value::BulkString(field.as_bytes().to_vec()))
+                .collect(),
+        )
+    }
+
+    #[cfg(feature = "socks5")]
+    #[test]
+    fn test_apply_tcp_options() {
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("Could not bind a listener");
+        let stream = std::net::TcpStream::connect(
+            listener
+                .local_addr()
+                .expect("Could not get the listener's address"),
+        )
+        .expect("Could not connect");
+
+        TcpOptions {
+            nodelay: Some(true),
+            keepalive: Some(Duration::from_secs(60)),
+        }
+        .apply((&stream).into())
+        .expect("Could not apply the TCP options");
+        assert_eq!(stream.nodelay().ok(), Some(true));
+        assert_eq!(
+            redis::io::tcp::socket2::SockRef::from(&stream)
+                .keepalive()
+                .ok(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_sentinel_master_selection() {
+        let provider = FalkorClientProvider::None;
+        let connection_info =
+            redis::IntoConnectionInfo::into_connection_info("redis://127.0.0.1:26379")
+                .expect("Could not parse connection info");
+        let select = |masters: &[&str], master_name: Option<&str>| {
+            provider
+                .get_sentinel_client_common(
+                    &connection_info,
+                    masters.iter().map(|name| sentinel_master(name)).collect(),
+                    master_name,
+                )
+                .map(|sentinel| sentinel.map(|(_, name)| name))
+        };
+
+        assert_eq!(
+            select(&["mymaster"], None),
+            Ok(Some("mymaster".to_string()))
+        );
+        assert_eq!(
+            select(&["first", "second"], Some("second")),
+            Ok(Some("second".to_string()))
+        );
+        assert_eq!(
+            select(&["first", "second"], None),
+            Err(FalkorDBError::SentinelMastersCount)
+        );
+        assert!(matches!(
+            select(&["first"], Some("second")),
+            Err(FalkorDBError::SentinelConnection(_))
+        ));
+    }
 }
@@ -0,0 +1,277 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{ConfigValue, FalkorResult, FalkorSyncClient, GraphOps};
+use std::collections::HashMap;
+
+#[cfg(feature = "tokio")]
+use crate::{AsyncGraphOps, FalkorAsyncClient, TransportFuture};
+
+/// The common operations of a client, implemented by [`FalkorSyncClient`],
+/// so application code can depend on this trait, and substitute a mock in unit tests without a live server.
+///
+/// The trait is object-safe, so clients can be passed around as `&dyn FalkorClientTrait` or `Arc<dyn FalkorClientTrait>`,
+/// graphs are selected as [`GraphOps`] trait objects, so mocked clients can hand out mocked graphs.
+pub trait FalkorClientTrait: Send + Sync {
+    /// Returns the names of the graphs in the database, see [`FalkorSyncClient::list_graphs`]
+    fn list_graphs(&self) -> FalkorResult<Vec<String>>;
+
+    /// Returns the values of configuration options, see [`FalkorSyncClient::config_get`]
+    fn config_get(
+        &self,
+        config_key: &str,
+    ) -> FalkorResult<HashMap<String, ConfigValue>>;
+
+    /// Sets the value of a configuration option, see [`FalkorSyncClient::config_set`]
+    fn config_set(
+        &self,
+        config_key: &str,
+        value: ConfigValue,
+    ) -> FalkorResult<redis::Value>;
+
+    /// Selects a graph to operate on, see [`FalkorSyncClient::select_graph`]
+    fn select_graph(
+        &self,
+        graph_name: &str,
+    ) -> Box<dyn GraphOps>;
+
+    /// Copies a graph, see [`FalkorSyncClient::copy_graph`]
+    fn copy_graph(
+        &self,
+        graph_to_clone: &str,
+        new_graph_name: &str,
+    ) -> FalkorResult<Box<dyn GraphOps>>;
+}
+
+impl FalkorClientTrait for FalkorSyncClient {
+    fn list_graphs(&self) -> FalkorResult<Vec<String>> {
+        FalkorSyncClient::list_graphs(self)
+    }
+
+    fn config_get(
+        &self,
+        config_key: &str,
+    ) -> FalkorResult<HashMap<String, ConfigValue>> {
+        FalkorSyncClient::config_get(self, config_key)
+    }
+
+    fn config_set(
+        &self,
+        config_key: &str,
+        value: ConfigValue,
+    ) -> FalkorResult<redis::Value> {
+        FalkorSyncClient::config_set(self, config_key, value)
+    }
+
+    fn select_graph(
+        &self,
+        graph_name: &str,
+    ) -> Box<dyn GraphOps> {
+        Box::new(FalkorSyncClient::select_graph(self, graph_name))
+    }
+
+    fn copy_graph(
+        &self,
+        graph_to_clone: &str,
+        new_graph_name: &str,
+    ) -> FalkorResult<Box<dyn GraphOps>> {
+        FalkorSyncClient::copy_graph(self, graph_to_clone, new_graph_name)
+            .map(|graph| Box::new(graph) as _)
+    }
+}
+
+/// The asynchronous counterpart of [`FalkorClientTrait`], implemented by [`FalkorAsyncClient`].
+///
+/// The trait is object-safe, returning boxed futures, so clients can be passed around as `&dyn AsyncFalkorClientTrait`
+/// or `Arc<dyn AsyncFalkorClientTrait>`, graphs are selected as [`AsyncGraphOps`] trait objects.
+#[cfg(feature = "tokio")]
+pub trait AsyncFalkorClientTrait: Send + Sync {
+    /// Returns the names of the graphs in the database, see [`FalkorAsyncClient::list_graphs`]
+    fn list_graphs(&self) -> TransportFuture<'_, Vec<String>>;
+
+    /// Returns the values of configuration options, see [`FalkorAsyncClient::config_get`]
+    fn config_get<'a>(
+        &'a self,
+        config_key: &'a str,
+    ) -> TransportFuture<'a, HashMap<String, ConfigValue>>;
+
+    /// Sets the value of a configuration option, see [`FalkorAsyncClient::config_set`]
+    fn config_set<'a>(
+        &'a self,
+        config_key: &'a str,
+        value: ConfigValue,
+    ) -> TransportFuture<'a, redis::Value>;
+
+    /// Selects a graph to operate on, see [`FalkorAsyncClient::select_graph`]
+    fn select_graph(
+        &self,
+        graph_name: &str,
+    ) -> Box<dyn AsyncGraphOps>;
+
+    /// Copies a graph, see [`FalkorAsyncClient::copy_graph`]
+    fn copy_graph<'a>(
+        &'a self,
+        graph_to_clone: &'a str,
+        new_graph_name: &'a str,
+    ) -> TransportFuture<'a, Box<dyn AsyncGraphOps>>;
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncFalkorClientTrait for FalkorAsyncClient {
+    fn list_graphs(&self) -> TransportFuture<'_, Vec<String>> {
+        Box::pin(FalkorAsyncClient::list_graphs(self))
+    }
+
+    fn config_get<'a>(
+        &'a self,
+        config_key: &'a str,
+    ) -> TransportFuture<'a, HashMap<String, ConfigValue>> {
+        Box::pin(FalkorAsyncClient::config_get(self, config_key))
+    }
+
+    fn config_set<'a>(
+        &'a self,
+        config_key: &'a str,
+        value: ConfigValue,
+    ) -> TransportFuture<'a, redis::Value> {
+        Box::pin(FalkorAsyncClient::config_set(self, config_key, value))
+    }
+
+    fn select_graph(
+        &self,
+        graph_name: &str,
+    ) -> Box<dyn AsyncGraphOps> {
+        Box::new(FalkorAsyncClient::select_graph(self, graph_name))
+    }
+
+    fn copy_graph<'a>(
+        &'a self,
+        graph_to_clone: &'a str,
+        new_graph_name: &'a str,
+    ) -> TransportFuture<'a, Box<dyn AsyncGraphOps>> {
+        Box::pin(async move {
+            FalkorAsyncClient::copy_graph(self, graph_to_clone, new_graph_name)
+                .await
+                .map(|graph| Box::new(graph) as _)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constraint, FalkorDBError, FalkorIndex, FalkorValue, QueryResult, QueryRows};
+
+    /// Application code, which only depends on the traits
+    fn count_people(client: &dyn FalkorClientTrait) -> FalkorResult<i64> {
+        let graph = client.select_graph("social");
+        let res = graph.ro_query("MATCH (p:Person) RETURN count(p)", None)?;
+        match res.data.first().and_then(|row| row.first()) {
+            Some(FalkorValue::I64(count)) => Ok(*count),
+            _ => Err(FalkorDBError::ParsingError(
+                "Expected a count of people".to_string(),
+            )),
+        }
+    }
+
+    struct MockGraph(String);
+
+    impl GraphOps for MockGraph {
+        fn graph_name(&self) -> &str {
+            &self.0
+        }
+
+        fn query(
+            &self,
+            _query_string: &str,
+            _params: Option<&HashMap<String, String>>,
+        ) -> FalkorResult<QueryRows> {
+            Ok(QueryResult::default())
+        }
+
+        fn ro_query(
+            &self,
+            _query_string: &str,
+            _params: Option<&HashMap<String, String>>,
+        ) -> FalkorResult<QueryRows> {
+            Ok(QueryResult {
+                data: vec![vec![FalkorValue::I64(3)]],
+                ..Default::default()
+            })
+        }
+
+        fn delete(&self) -> FalkorResult<()> {
+            Ok(())
+        }
+
+        fn list_indices(&self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
+            Ok(QueryResult::default())
+        }
+
+        fn list_constraints(&self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
+            Ok(QueryResult::default())
+        }
+
+        fn labels(&self) -> FalkorResult<Vec<String>> {
+            Ok(vec!["Person".to_string()])
+        }
+
+        fn property_keys(&self) -> FalkorResult<Vec<String>> {
+            Ok(vec![])
+        }
+
+        fn relationship_types(&self) -> FalkorResult<Vec<String>> {
+            Ok(vec![])
+        }
+    }
+
+    struct MockClient;
+
+    impl FalkorClientTrait for MockClient {
+        fn list_graphs(&self) -> FalkorResult<Vec<String>> {
+            Ok(vec!["social".to_string()])
+        }
+
+        fn config_get(
+            &self,
+            _config_key: &str,
+        ) -> FalkorResult<HashMap<String, ConfigValue>> {
+            Ok(HashMap::new())
+        }
+
+        fn config_set(
+            &self,
+            _config_key: &str,
+            _value: ConfigValue,
+        ) -> FalkorResult<redis::Value> {
+            Ok(redis::Value::Okay)
+        }
+
+        fn select_graph(
+            &self,
+            graph_name: &str,
+        ) -> Box<dyn GraphOps> {
+            Box::new(MockGraph(graph_name.to_string()))
+        }
+
+        fn copy_graph(
+            &self,
+            _graph_to_clone: &str,
+            new_graph_name: &str,
+        ) -> FalkorResult<Box<dyn GraphOps>> {
+            Ok(self.select_graph(new_graph_name))
+        }
+    }
+
+    #[test]
+    fn test_mocked_client() {
+        assert_eq!(count_people(&MockClient), Ok(3));
+
+        let copy = MockClient
+            .copy_graph("social", "social_copy")
+            .expect("Could not copy the mocked graph");
+        assert_eq!(copy.graph_name(), "social_copy");
+    }
+}
@@ -0,0 +1,312 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{client::circuit::CircuitStats, FalkorDBError};
+use std::{
+    sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The capacity of the channel backing the connection pool, large enough to hold any pool size, so it can be resized at runtime
+pub(crate) const MAX_POOL_CAPACITY: usize = u8::MAX as usize;
+
+/// How often closing a client checks whether connections it is waiting for were closed instead of returned
+pub(crate) const CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Tracks the requested size of a connection pool, against the number of connections it currently holds, idle or borrowed
+pub(crate) struct PoolSize {
+    target: AtomicU8,
+    live: AtomicU8,
+    generation: AtomicU64,
+}
+
+impl PoolSize {
+    /// A pool which already holds `live` connections, and opens the rest on demand, up to its size
+    pub(crate) fn new(
+        size: u8,
+        live: u8,
+    ) -> Self {
+        Self {
+            target: AtomicU8::new(size),
+            live: AtomicU8::new(live),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// The generation of the pool's connections, connections borrowed during an earlier generation are closed once returned
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Starts a new generation, E.g. after a failover, so all existing connections are replaced
+    pub(crate) fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn target(&self) -> u8 {
+        self.target.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn live(&self) -> u8 {
+        self.live.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_target(
+        &self,
+        size: u8,
+    ) {
+        self.target.store(size, Ordering::SeqCst);
+    }
+
+    /// Reserves a slot for a new connection, if the pool holds fewer connections than requested
+    /// The slot must be given back using [`PoolSize::release`] if creating the connection fails
+    pub(crate) fn try_reserve(&self) -> bool {
+        self.live
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |live| {
+                (live < self.target()).then_some(live + 1)
+            })
+            .is_ok()
+    }
+
+    /// Marks a connection as closed, if the pool holds more connections than requested
+    /// Returns whether the caller should drop the connection instead of returning it to the pool
+    pub(crate) fn try_shed(&self) -> bool {
+        self.live
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |live| {
+                (live > self.target()).then(|| live - 1)
+            })
+            .is_ok()
+    }
+
+    /// Marks a connection as closed, regardless of the requested size
+    pub(crate) fn release(&self) {
+        self.live
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |live| {
+                live.checked_sub(1)
+            })
+            .ok();
+    }
+}
+
+/// A snapshot of a connection pool's occupancy and counters, see [`FalkorSyncClient::pool_stats`](crate::FalkorSyncClient::pool_stats)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The requested size of the pool
+    pub size: u8,
+    /// How many connections the pool currently holds, idle or in use, which may differ from its size while it is being resized
+    pub total: u8,
+    /// How many connections are idle, waiting to be borrowed
+    pub idle: u8,
+    /// How many borrowed connections are in use
+    pub in_use: usize,
+    /// How many times a connection was borrowed
+    pub checkouts: u64,
+    /// How many borrows found no idle connection, and had to wait for one to be returned
+    pub waits: u64,
+    /// How many borrows gave up waiting, see [`FalkorDBError::PoolTimeout`] and [`FalkorDBError::PoolExhausted`]
+    pub timeouts: u64,
+    /// The average time it took to borrow a connection, including waiting for one, and opening or validating it
+    pub average_checkout_latency: Duration,
+    /// The state and counters of the connection circuit breaker, or [`None`] if it is disabled,
+    /// see [`FalkorClientBuilder::with_connection_circuit_breaker`](crate::FalkorClientBuilder::with_connection_circuit_breaker)
+    pub connection_circuit: Option<CircuitStats>,
+}
+
+/// Counts the borrows of a connection pool, to report in [`PoolStats`]
+#[derive(Default)]
+pub(crate) struct PoolMetrics {
+    in_use: AtomicUsize,
+    checkouts: AtomicU64,
+    waits: AtomicU64,
+    timeouts: AtomicU64,
+    checkout_nanos: AtomicU64,
+}
+
+impl PoolMetrics {
+    pub(crate) fn record_checkout(
+        &self,
+        started: Instant,
+    ) {
+        self.in_use.fetch_add(1, Ordering::Relaxed);
+        self.checkouts.fetch_add(1, Ordering::Relaxed);
+        self.checkout_nanos.fetch_add(
+            u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Records a borrowed connection being returned, or closed
+    pub(crate) fn record_checkin(&self) {
+        self.in_use
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |in_use| {
+                in_use.checked_sub(1)
+            })
+            .ok();
+    }
+
+    pub(crate) fn record_wait(&self) {
+        self.waits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(
+        &self,
+        pool_size: &PoolSize,
+    ) -> PoolStats {
+        let in_use = self.in_use.load(Ordering::Relaxed);
+        let checkouts = self.checkouts.load(Ordering::Relaxed);
+        let total = pool_size.live();
+        PoolStats {
+            size: pool_size.target(),
+            total,
+            idle: total.saturating_sub(u8::try_from(in_use).unwrap_or(u8::MAX)),
+            in_use,
+            checkouts,
+            waits: self.waits.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            average_checkout_latency: match checkouts {
+                0 => Duration::ZERO,
+                checkouts => {
+                    Duration::from_nanos(self.checkout_nanos.load(Ordering::Relaxed) / checkouts)
+                }
+            },
+            connection_circuit: None,
+        }
+    }
+}
+
+/// The instant to stop waiting for a pooled connection, the earlier of the operation's deadline and the pool's acquisition timeout
+pub(crate) struct AcquisitionDeadline {
+    started: Instant,
+    deadline: Option<Instant>,
+    pool_timeout: Option<Duration>,
+}
+
+impl AcquisitionDeadline {
+    pub(crate) fn new(
+        deadline: Option<Instant>,
+        pool_timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            started: Instant::now(),
+            deadline,
+            pool_timeout,
+        }
+    }
+
+    /// Whether the pool's acquisition timeout passes before the operation's deadline does
+    fn is_pool_timeout(&self) -> bool {
+        match (self.pool_timeout, self.deadline) {
+            (Some(timeout), Some(deadline)) => self.started + timeout <= deadline,
+            (pool_timeout, _) => pool_timeout.is_some(),
+        }
+    }
+
+    pub(crate) fn at(&self) -> Option<Instant> {
+        match self.is_pool_timeout() {
+            true => self.pool_timeout.map(|timeout| self.started + timeout),
+            false => self.deadline,
+        }
+    }
+
+    /// The error to fail with once no connection was returned in time
+    pub(crate) fn error(&self) -> FalkorDBError {
+        match self.pool_timeout.filter(|_| self.is_pool_timeout()) {
+            Some(timeout) => FalkorDBError::PoolTimeout { timeout },
+            None => FalkorDBError::PoolExhausted {
+                waited: self.started.elapsed(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_size() {
+        let pool_size = PoolSize::new(2, 2);
+        assert!(!pool_size.try_reserve());
+        assert!(!pool_size.try_shed());
+
+        pool_size.set_target(3);
+        assert!(pool_size.try_reserve());
+        assert!(!pool_size.try_reserve());
+        assert_eq!(pool_size.live(), 3);
+
+        pool_size.set_target(1);
+        assert!(pool_size.try_shed());
+        assert!(pool_size.try_shed());
+        assert!(!pool_size.try_shed());
+        assert_eq!(pool_size.live(), 1);
+
+        pool_size.release();
+        pool_size.release();
+        assert_eq!(pool_size.live(), 0);
+        assert!(pool_size.try_reserve());
+
+        let generation = pool_size.generation();
+        pool_size.invalidate();
+        assert_eq!(pool_size.generation(), generation + 1);
+    }
+
+    #[test]
+    fn test_pool_metrics() {
+        let pool_size = PoolSize::new(4, 4);
+        let metrics = PoolMetrics::default();
+        assert_eq!(
+            metrics.stats(&pool_size),
+            PoolStats {
+                size: 4,
+                total: 4,
+                idle: 4,
+                ..Default::default()
+            }
+        );
+
+        let started = Instant::now() - Duration::from_millis(10);
+        metrics.record_checkout(started);
+        metrics.record_wait();
+        metrics.record_checkout(started);
+        metrics.record_checkin();
+        metrics.record_timeout();
+
+        let stats = metrics.stats(&pool_size);
+        assert_eq!((stats.idle, stats.in_use), (3, 1));
+        assert_eq!((stats.checkouts, stats.waits, stats.timeouts), (2, 1, 1));
+        assert!(stats.average_checkout_latency >= Duration::from_millis(10));
+
+        metrics.record_checkin();
+        metrics.record_checkin();
+        assert_eq!(metrics.stats(&pool_size).in_use, 0);
+    }
+
+    #[test]
+    fn test_acquisition_deadline() {
+        let unbounded = AcquisitionDeadline::new(None, None);
+        assert_eq!(unbounded.at(), None);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let bounded = AcquisitionDeadline::new(Some(deadline), Some(Duration::from_secs(1)));
+        assert!(bounded.at().is_some_and(|at| at < deadline));
+        assert_eq!(
+            bounded.error(),
+            FalkorDBError::PoolTimeout {
+                timeout: Duration::from_secs(1)
+            }
+        );
+
+        let bounded = AcquisitionDeadline::new(Some(deadline), Some(Duration::from_secs(60)));
+        assert_eq!(bounded.at(), Some(deadline));
+        assert!(matches!(
+            bounded.error(),
+            FalkorDBError::PoolExhausted { .. }
+        ));
+    }
+}
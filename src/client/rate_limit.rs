@@ -0,0 +1,363 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{FalkorDBError, FalkorResult};
+use parking_lot::{Condvar, Mutex};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Limits how many commands the client sends concurrently, and per second, see [`FalkorClientBuilder::with_rate_limit`](crate::FalkorClientBuilder::with_rate_limit)
+/// Protects small FalkorDB instances from thundering herds, E.g. when every instance of a service starts at once.
+///
+/// By default, nothing is limited, and commands exceeding a limit fail with [`FalkorDBError::Throttled`],
+/// unless [`RateLimit::with_waiting`] makes them wait for capacity instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    max_in_flight: Option<NonZeroUsize>,
+    max_per_second: Option<NonZeroU32>,
+    wait: bool,
+}
+
+impl RateLimit {
+    /// Creates a new [`RateLimit`], which does not limit anything until limits are set
+    ///
+    /// # Returns
+    /// The new [`RateLimit`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits how many commands may be in flight at once, which matters most for a multiplexed client,
+    /// as the connection pool already bounds the concurrency of a pooled one
+    ///
+    /// # Arguments
+    /// * `max_in_flight`: The maximum number of concurrent commands
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_max_in_flight(
+        self,
+        max_in_flight: NonZeroUsize,
+    ) -> Self {
+        Self {
+            max_in_flight: Some(max_in_flight),
+            ..self
+        }
+    }
+
+    /// Limits how many commands may be sent per second, allowing bursts of up to a second's worth of commands
+    ///
+    /// # Arguments
+    /// * `max_per_second`: The maximum number of commands per second
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_max_per_second(
+        self,
+        max_per_second: NonZeroU32,
+    ) -> Self {
+        Self {
+            max_per_second: Some(max_per_second),
+            ..self
+        }
+    }
+
+    /// Sets whether commands exceeding a limit wait for capacity, until their deadline or the pool's acquisition timeout passes,
+    /// instead of failing right away with [`FalkorDBError::Throttled`]
+    ///
+    /// # Arguments
+    /// * `wait`: Whether to wait for capacity, the default is false
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_waiting(
+        self,
+        wait: bool,
+    ) -> Self {
+        Self { wait, ..self }
+    }
+}
+
+struct LimiterState {
+    in_flight: usize,
+    /// The commands which may be sent right away, refilled at the rate of the per-second limit
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Enforces a [`RateLimit`], handing out a permit for every command, which is released once it completes
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<LimiterState>,
+    released: Condvar,
+    #[cfg(feature = "tokio")]
+    released_async: tokio::sync::Notify,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                in_flight: 0,
+                tokens: limit.max_per_second.map_or(0.0, |max| f64::from(max.get())),
+                refilled_at: Instant::now(),
+            }),
+            limit,
+            released: Condvar::new(),
+            #[cfg(feature = "tokio")]
+            released_async: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Takes a slot and a token if both are available,
+    /// otherwise returns how long until a token is available, or [`None`] if a slot has to be released first
+    fn try_take(
+        &self,
+        state: &mut LimiterState,
+    ) -> Result<(), Option<Duration>> {
+        if let Some(max_in_flight) = self.limit.max_in_flight {
+            if state.in_flight >= max_in_flight.get() {
+                return Err(None);
+            }
+        }
+        if let Some(max_per_second) = self.limit.max_per_second {
+            let rate = f64::from(max_per_second.get());
+            let now = Instant::now();
+            state.tokens = (state.tokens
+                + now.duration_since(state.refilled_at).as_secs_f64() * rate)
+                .min(rate);
+            state.refilled_at = now;
+            if state.tokens < 1.0 {
+                return Err(Some(Duration::from_secs_f64((1.0 - state.tokens) / rate)));
+            }
+            state.tokens -= 1.0;
+        }
+        state.in_flight += 1;
+        Ok(())
+    }
+
+    fn throttled(&self) -> FalkorDBError {
+        FalkorDBError::Throttled(
+            match (self.limit.max_in_flight, self.limit.max_per_second) {
+                (Some(max_in_flight), Some(max_per_second)) => format!(
+                    "at most {max_in_flight} commands in flight, and {max_per_second} per second"
+                ),
+                (Some(max_in_flight), None) => {
+                    format!("at most {max_in_flight} commands in flight")
+                }
+                (None, Some(max_per_second)) => {
+                    format!("at most {max_per_second} commands per second")
+                }
+                (None, None) => "no limits".to_string(),
+            },
+        )
+    }
+
+    /// Takes a permit, waiting for capacity until the deadline passes if the limit allows waiting
+    pub(crate) fn acquire(
+        self: &Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<RatePermit> {
+        let mut state = self.state.lock();
+        loop {
+            let wait = match self.try_take(&mut state) {
+                Ok(()) => return Ok(RatePermit(Some(self.clone()))),
+                Err(_) if !self.limit.wait => return Err(self.throttled()),
+                Err(wait) => wait,
+            };
+            let wake_at = match (wait.map(|wait| Instant::now() + wait), deadline) {
+                (Some(wake_at), Some(deadline)) => Some(wake_at.min(deadline)),
+                (wake_at, deadline) => wake_at.or(deadline),
+            };
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(self.throttled());
+            }
+            match wake_at {
+                Some(wake_at) => {
+                    self.released.wait_until(&mut state, wake_at);
+                }
+                None => self.released.wait(&mut state),
+            }
+        }
+    }
+
+    /// Takes a permit, waiting for capacity until the deadline passes if the limit allows waiting
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn acquire_async(
+        self: &Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<RatePermit> {
+        loop {
+            // Registered before checking, so a release in between is not missed
+            let released = self.released_async.notified();
+            let wait = match self.try_take(&mut self.state.lock()) {
+                Ok(()) => return Ok(RatePermit(Some(self.clone()))),
+                Err(_) if !self.limit.wait => return Err(self.throttled()),
+                Err(wait) => wait,
+            };
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(self.throttled());
+            }
+
+            let wait_for_capacity = async {
+                match wait {
+                    Some(wait) => {
+                        crate::runtime::timeout(wait, released).await;
+                    }
+                    None => released.await,
+                }
+            };
+            match deadline {
+                Some(deadline) => {
+                    crate::runtime::timeout_at(deadline, wait_for_capacity).await;
+                }
+                None => wait_for_capacity.await,
+            }
+        }
+    }
+
+    fn release(&self) {
+        self.state.lock().in_flight -= 1;
+        self.released.notify_one();
+        #[cfg(feature = "tokio")]
+        self.released_async.notify_waiters();
+    }
+}
+
+/// A command's share of the client's [`RateLimit`], releasing its slot once dropped
+#[derive(Default)]
+pub(crate) struct RatePermit(Option<Arc<RateLimiter>>);
+
+impl RatePermit {
+    /// Takes a permit from the limiter if there is one, see [`RateLimiter::acquire`]
+    pub(crate) fn acquire(
+        limiter: Option<Arc<RateLimiter>>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<Self> {
+        match limiter {
+            Some(limiter) => limiter.acquire(deadline),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Takes a permit from the limiter if there is one, see [`RateLimiter::acquire_async`]
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn acquire_async(
+        limiter: Option<Arc<RateLimiter>>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<Self> {
+        match limiter {
+            Some(limiter) => limiter.acquire_async(deadline).await,
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl Drop for RatePermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = self.0.take() {
+            limiter.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(limit: RateLimit) -> Arc<RateLimiter> {
+        Arc::new(RateLimiter::new(limit))
+    }
+
+    #[test]
+    fn test_max_in_flight() {
+        let limiter = limiter(RateLimit::new().with_max_in_flight(
+            NonZeroUsize::new(2).expect("Could not create a perfectly valid usize"),
+        ));
+        let first = limiter.acquire(None).expect("Could not acquire a permit");
+        let _second = limiter.acquire(None).expect("Could not acquire a permit");
+        assert!(matches!(
+            limiter.acquire(None),
+            Err(FalkorDBError::Throttled(_))
+        ));
+
+        drop(first);
+        assert!(limiter.acquire(None).is_ok());
+    }
+
+    #[test]
+    fn test_max_per_second() {
+        let limiter = limiter(
+            RateLimit::new()
+                .with_max_per_second(
+                    NonZeroU32::new(20).expect("Could not create a perfectly valid u32"),
+                )
+                .with_waiting(true),
+        );
+        for _ in 0..20 {
+            limiter.acquire(None).expect("Could not acquire a permit");
+        }
+
+        // The burst is spent, so the next permit waits for a token to be refilled, unless the deadline passes first
+        assert!(matches!(
+            limiter.acquire(Some(Instant::now() + Duration::from_millis(1))),
+            Err(FalkorDBError::Throttled(_))
+        ));
+        let started = Instant::now();
+        assert!(limiter.acquire(None).is_ok());
+        assert!(started.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_waiting_for_release() {
+        let limiter = limiter(
+            RateLimit::new()
+                .with_max_in_flight(
+                    NonZeroUsize::new(1).expect("Could not create a perfectly valid usize"),
+                )
+                .with_waiting(true),
+        );
+        let permit = limiter.acquire(None).expect("Could not acquire a permit");
+        let waiter = {
+            let limiter = limiter.clone();
+            std::thread::spawn(move || limiter.acquire(None).map(drop))
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        drop(permit);
+        assert_eq!(waiter.join().expect("The waiting thread panicked"), Ok(()));
+        assert!(RatePermit::acquire(None, None).is_ok());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_waiting_for_release_async() {
+        let limiter = limiter(
+            RateLimit::new()
+                .with_max_in_flight(
+                    NonZeroUsize::new(1).expect("Could not create a perfectly valid usize"),
+                )
+                .with_waiting(true),
+        );
+        let permit = limiter
+            .acquire_async(None)
+            .await
+            .expect("Could not acquire a permit");
+        let waiter = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire_async(None).await.map(drop) }
+        });
+        assert!(matches!(
+            limiter
+                .acquire_async(Some(Instant::now() + Duration::from_millis(10)))
+                .await,
+            Err(FalkorDBError::Throttled(_))
+        ));
+        drop(permit);
+        assert_eq!(waiter.await.expect("The waiting task panicked"), Ok(()));
+    }
+}
@@ -0,0 +1,353 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    graph::{has_time_for_retry, with_attempts, RETRY_BACKOFF},
+    FalkorDBError, FalkorResult,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tokio")]
+use crate::TransportFuture;
+
+/// The longest delay between two attempts, by default
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Decides whether an error is worth retrying, see [`RetryPolicy::with_retry_on`]
+pub type RetryClassifier = Arc<dyn Fn(&FalkorDBError) -> bool + Send + Sync>;
+
+/// A policy for retrying idempotent operations inside the client, see [`FalkorClientBuilder::with_retry_policy`](crate::FalkorClientBuilder::with_retry_policy)
+/// It is applied to read-only queries, `EXPLAIN`s, read-only procedure calls and schema refreshes,
+/// which are safe to send again, while writes are never retried automatically, see [`SyncGraph::with_retries`](crate::SyncGraph::with_retries) for those.
+///
+/// By default, 3 retries are made on connection errors, waiting 100ms before the first, and doubling the delay up to 2 seconds.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_on: RetryClassifier,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: RETRY_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            retry_on: Arc::new(FalkorDBError::is_retryable),
+        }
+    }
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`], retrying connection errors
+    ///
+    /// # Arguments
+    /// * `max_retries`: How many times to retry an operation, after its first attempt
+    ///
+    /// # Returns
+    /// The new [`RetryPolicy`]
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the delay before the first retry, which doubles with every following retry, up to a maximum
+    ///
+    /// # Arguments
+    /// * `initial_backoff`: The delay before the first retry, the default is 100ms
+    /// * `max_backoff`: The longest delay between two attempts, the default is 2 seconds
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_backoff(
+        self,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+    ) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff: max_backoff.max(initial_backoff),
+            ..self
+        }
+    }
+
+    /// Sets which errors are retried, by default only connection errors are,
+    /// such as [`FalkorDBError::ConnectionDown`] and [`FalkorDBError::EndpointUnavailable`]
+    ///
+    /// # Arguments
+    /// * `retry_on`: A function returning whether an operation failing with the given error should be retried
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_retry_on<F: Fn(&FalkorDBError) -> bool + Send + Sync + 'static>(
+        self,
+        retry_on: F,
+    ) -> Self {
+        Self {
+            retry_on: Arc::new(retry_on),
+            ..self
+        }
+    }
+
+    /// The delay before the given retry, starting at 0
+    fn backoff(
+        &self,
+        retry: usize,
+    ) -> Duration {
+        let factor = u32::try_from(retry)
+            .ok()
+            .and_then(|retry| 1u32.checked_shl(retry))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Returns the delay to wait before retrying an operation which failed with this error,
+    /// or [`None`] if it should not be retried, because of the error, the number of attempts, or the deadline
+    fn next_backoff(
+        &self,
+        attempts: usize,
+        err: &FalkorDBError,
+        deadline: Option<Instant>,
+    ) -> Option<Duration> {
+        let backoff = self.backoff(attempts - 1);
+        (attempts <= self.max_retries
+            && (self.retry_on)(err)
+            && has_time_for_retry(deadline, backoff))
+        .then_some(backoff)
+    }
+
+    /// Runs an idempotent operation, retrying it according to this policy
+    ///
+    /// # Returns
+    /// The result of the first successful attempt, or the error of the last attempt,
+    /// wrapped in [`FalkorDBError::RetriesFailed`] if there was more than one
+    pub(crate) fn run<T, F: FnMut() -> FalkorResult<T>>(
+        &self,
+        deadline: Option<Instant>,
+        mut operation: F,
+    ) -> FalkorResult<T> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let err = match operation() {
+                Ok(res) => return Ok(res),
+                Err(err) => err,
+            };
+            match self.next_backoff(attempts, &err, deadline) {
+                Some(backoff) => std::thread::sleep(backoff),
+                None => return Err(with_attempts(attempts, err)),
+            }
+        }
+    }
+
+    /// Runs an idempotent asynchronous operation, retrying it according to this policy, see [`RetryPolicy::run`]
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn run_async<'a, T, F: FnMut() -> TransportFuture<'a, T>>(
+        &self,
+        deadline: Option<Instant>,
+        mut operation: F,
+    ) -> FalkorResult<T> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let err = match operation().await {
+                Ok(res) => return Ok(res),
+                Err(err) => err,
+            };
+            match self.next_backoff(attempts, &err, deadline) {
+                Some(backoff) => crate::runtime::sleep(backoff).await,
+                None => return Err(with_attempts(attempts, err)),
+            }
+        }
+    }
+}
+
+/// Runs an idempotent operation, retrying it if a policy is configured
+pub(crate) fn run_with_retries<T, F: FnMut() -> FalkorResult<T>>(
+    policy: Option<&RetryPolicy>,
+    deadline: Option<Instant>,
+    mut operation: F,
+) -> FalkorResult<T> {
+    match policy {
+        Some(policy) => policy.run(deadline, operation),
+        None => operation(),
+    }
+}
+
+/// Runs an idempotent asynchronous operation, retrying it if a policy is configured
+/// Each attempt is boxed, keeping the futures of the callers shallow enough for their auto traits to be inferred
+#[cfg(feature = "tokio")]
+pub(crate) async fn run_with_retries_async<'a, T, F: FnMut() -> TransportFuture<'a, T>>(
+    policy: Option<&RetryPolicy>,
+    deadline: Option<Instant>,
+    mut operation: F,
+) -> FalkorResult<T> {
+    match policy {
+        Some(policy) => policy.run_async(deadline, operation).await,
+        None => operation().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransport, FalkorClientBuilder, FalkorConnectionInfo};
+    use std::{
+        cell::Cell,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[test]
+    fn test_backoff() {
+        let policy =
+            RetryPolicy::new(10).with_backoff(Duration::from_millis(10), Duration::from_millis(50));
+        assert_eq!(policy.backoff(0), Duration::from_millis(10));
+        assert_eq!(policy.backoff(1), Duration::from_millis(20));
+        assert_eq!(policy.backoff(2), Duration::from_millis(40));
+        assert_eq!(policy.backoff(3), Duration::from_millis(50));
+        assert_eq!(policy.backoff(100), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_run() {
+        let policy = RetryPolicy::new(2).with_backoff(Duration::ZERO, Duration::ZERO);
+
+        let attempts = Cell::new(0);
+        let res = policy.run(None, || {
+            attempts.set(attempts.get() + 1);
+            match attempts.get() {
+                3 => Ok(attempts.get()),
+                _ => Err(FalkorDBError::ConnectionDown),
+            }
+        });
+        assert_eq!(res, Ok(3));
+
+        attempts.set(0);
+        let res: FalkorResult<()> = policy.run(None, || {
+            attempts.set(attempts.get() + 1);
+            Err(FalkorDBError::ConnectionDown)
+        });
+        assert_eq!(
+            res,
+            Err(FalkorDBError::RetriesFailed {
+                attempts: 3,
+                last_error: Box::new(FalkorDBError::ConnectionDown)
+            })
+        );
+
+        attempts.set(0);
+        let res: FalkorResult<()> = policy.run(None, || {
+            attempts.set(attempts.get() + 1);
+            Err(FalkorDBError::ParsingError("Not retryable".to_string()))
+        });
+        assert_eq!(attempts.get(), 1);
+        assert_eq!(
+            res,
+            Err(FalkorDBError::ParsingError("Not retryable".to_string()))
+        );
+
+        // A custom classifier, and a deadline which leaves no time for a retry
+        let policy = RetryPolicy::new(5)
+            .with_retry_on(|err| matches!(err, FalkorDBError::ParsingError(_)))
+            .with_backoff(Duration::from_secs(1), Duration::from_secs(1));
+        attempts.set(0);
+        let res: FalkorResult<()> = policy.run(Some(Instant::now()), || {
+            attempts.set(attempts.get() + 1);
+            Err(FalkorDBError::ParsingError("Retryable".to_string()))
+        });
+        assert_eq!(attempts.get(), 1);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_client_retry_policy() {
+        // Only the first query fails
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let provider = MockTransport::new("mock://flaky", {
+            let attempts = attempts.clone();
+            move |args| match args.first().map(String::as_str) {
+                Some("GRAPH.RO_QUERY") | Some("GRAPH.QUERY") => {
+                    match attempts.fetch_add(1, Ordering::Relaxed) {
+                        0 => Err(FalkorDBError::ConnectionDown),
+                        _ => Ok(redis::Value::Array(vec![redis::Value::Array(vec![])])),
+                    }
+                }
+                _ => Ok(redis::Value::Nil),
+            }
+        });
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(FalkorConnectionInfo::Transport(Arc::new(provider)))
+            .with_retry_policy(RetryPolicy::new(2).with_backoff(Duration::ZERO, Duration::ZERO))
+            .build()
+            .expect("Could not create client over transport");
+        let graph = client.select_graph("social");
+
+        // Read-only queries are retried over a fresh connection
+        assert!(graph.ro_query("MATCH (n) RETURN n").execute().is_ok());
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+
+        // Writes are not
+        attempts.store(0, Ordering::Relaxed);
+        assert!(matches!(
+            graph.query("CREATE ()").execute(),
+            Err(FalkorDBError::ConnectionDown)
+        ));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_run_async() {
+        let policy = RetryPolicy::new(1).with_backoff(Duration::ZERO, Duration::ZERO);
+        let attempts = AtomicUsize::new(0);
+        let res = run_with_retries_async(Some(&policy), None, || {
+            Box::pin(async {
+                match attempts.fetch_add(1, Ordering::Relaxed) {
+                    1 => Ok(()),
+                    _ => Err(FalkorDBError::NoConnection),
+                }
+            })
+        })
+        .await;
+        assert_eq!(res, Ok(()));
+
+        attempts.store(0, Ordering::Relaxed);
+        let res: FalkorResult<()> = run_with_retries_async(None, None, || {
+            Box::pin(async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err(FalkorDBError::NoConnection)
+            })
+        })
+        .await;
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+        assert_eq!(res, Err(FalkorDBError::NoConnection));
+    }
+}
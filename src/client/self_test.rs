@@ -0,0 +1,281 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    parser::{redis_value_as_string, redis_value_as_vec},
+    FalkorDBError, FalkorResult, FalkorValue,
+};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The name of the FalkorDB module, as listed by `MODULE LIST`
+const GRAPH_MODULE_NAME: &str = "graph";
+
+/// How long to wait for the scratch index to become operational
+pub(crate) const SELF_TEST_INDEX_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) const SELF_TEST_CREATE_QUERY: &str =
+    "CREATE (:SelfTest {name: 'a'})-[:LINKED {since: 2024}]->(:SelfTest {name: 'b'})";
+
+pub(crate) const SELF_TEST_ROUND_TRIP_QUERY: &str = "MATCH (a:SelfTest {name: 'a'})-[r:LINKED]->(b:SelfTest) \
+    RETURN a, r, b, [1, 2.5, 'three', true, NULL], {key: 'value'}, point({latitude: 32.0, longitude: 34.0})";
+
+/// A single check performed by the client self-test
+#[derive(Copy, Clone, Debug, Eq, PartialEq, strum::Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTestCheck {
+    /// The server responds to a `PING`
+    Ping,
+    /// The FalkorDB module is loaded, reporting its version
+    ModuleVersion,
+    /// A scratch graph can be created
+    CreateGraph,
+    /// Nodes, relationships and scalar values are returned and parsed as expected
+    CompactRoundTrip,
+    /// An index can be created on the scratch graph, and becomes operational
+    IndexCreation,
+    /// The scratch graph can be deleted
+    DropGraph,
+}
+
+/// The outcome of a single [`SelfTestCheck`]
+#[derive(Debug, PartialEq)]
+pub struct SelfTestCheckResult {
+    /// Which check was performed
+    pub check: SelfTestCheck,
+    /// How long the check took
+    pub elapsed: Duration,
+    /// A short description of what was found if the check passed, or the error it failed with
+    pub result: FalkorResult<String>,
+}
+
+/// A structured report of the client self-test, see [`FalkorSyncClient::self_test`](crate::FalkorSyncClient::self_test)
+#[derive(Debug, Default, PartialEq)]
+pub struct SelfTestReport {
+    /// The outcome of every check, in the order they were performed
+    pub checks: Vec<SelfTestCheckResult>,
+}
+
+impl SelfTestReport {
+    /// Returns whether all checks passed
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+
+    /// Returns the checks which failed
+    pub fn failures(&self) -> impl Iterator<Item = &SelfTestCheckResult> {
+        self.checks.iter().filter(|check| check.result.is_err())
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        check: SelfTestCheck,
+        start: Instant,
+        result: FalkorResult<String>,
+    ) -> bool {
+        let passed = result.is_ok();
+        self.checks.push(SelfTestCheckResult {
+            check,
+            elapsed: start.elapsed(),
+            result,
+        });
+        passed
+    }
+}
+
+pub(crate) fn self_test_graph_name() -> String {
+    format!(
+        "falkordb_self_test_{}_{}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+    )
+}
+
+/// Finds the FalkorDB module's version in the reply of `MODULE LIST`, which lists each module as a map, or a flat key-value array in RESP2
+pub(crate) fn parse_module_version(value: redis::Value) -> FalkorResult<String> {
+    for module in redis_value_as_vec(value)? {
+        let fields: Vec<(redis::Value, redis::Value)> = match module {
+            redis::Value::Map(fields) => fields,
+            module => {
+                let mut flat = redis_value_as_vec(module)?.into_iter();
+                std::iter::from_fn(|| flat.next().zip(flat.next())).collect()
+            }
+        };
+
+        let mut name = None;
+        let mut version = None;
+        for (key, val) in fields {
+            match redis_value_as_string(key)?.as_str() {
+                "name" => name = Some(redis_value_as_string(val)?),
+                "ver" => {
+                    version = Some(match val {
+                        redis::Value::Int(version) => version.to_string(),
+                        val => redis_value_as_string(val)?,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        if name.as_deref() == Some(GRAPH_MODULE_NAME) {
+            return version.ok_or(FalkorDBError::ParsingString);
+        }
+    }
+
+    Err(FalkorDBError::ParsingError(
+        "The FalkorDB module is not loaded".to_string(),
+    ))
+}
+
+fn unexpected(
+    what: &str,
+    value: &FalkorValue,
+) -> FalkorDBError {
+    FalkorDBError::ParsingError(format!("Unexpected {what} in self-test: {value:?}"))
+}
+
+/// Validates the row returned by [`SELF_TEST_ROUND_TRIP_QUERY`]
+pub(crate) fn check_round_trip(row: Option<Vec<FalkorValue>>) -> FalkorResult<String> {
+    let row = row.ok_or(FalkorDBError::ParsingError(
+        "The self-test query returned no rows".to_string(),
+    ))?;
+    let [a, r, b, list, map, point]: [FalkorValue; 6] = row.try_into().map_err(|_| {
+        FalkorDBError::ParsingArrayToStructElementCount(
+            "Expected exactly 6 columns in the self-test query",
+        )
+    })?;
+
+    let (FalkorValue::Node(a), FalkorValue::Node(b)) = (&a, &b) else {
+        return Err(unexpected("nodes", &a));
+    };
+    if a.labels != ["SelfTest"] || a.properties.get("name") != Some(&"a".into()) {
+        return Err(unexpected("node", &FalkorValue::Node(a.clone())));
+    }
+
+    match &r {
+        FalkorValue::Edge(edge)
+            if edge.relationship_type == "LINKED"
+                && edge.src_node_id == a.entity_id
+                && edge.dst_node_id == b.entity_id
+                && edge.properties.get("since") == Some(&FalkorValue::I64(2024)) => {}
+        r => return Err(unexpected("relationship", r)),
+    }
+
+    let expected_list = FalkorValue::Array(vec![
+        FalkorValue::I64(1),
+        FalkorValue::F64(2.5),
+        "three".into(),
+        FalkorValue::Bool(true),
+        FalkorValue::None,
+    ]);
+    if list != expected_list {
+        return Err(unexpected("list", &list));
+    }
+    if map.as_map().and_then(|map| map.get("key")) != Some(&"value".into()) {
+        return Err(unexpected("map", &map));
+    }
+    match &point {
+        FalkorValue::Point(point) if point.latitude == 32.0 && point.longitude == 34.0 => {}
+        point => return Err(unexpected("point", point)),
+    }
+
+    Ok("Parsed nodes, relationships, lists, maps and points".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Edge, FalkorMap, Node, Point};
+
+    fn bulk(value: &str) -> redis::Value {
+        redis::Value::BulkString(value.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_module_version() {
+        let resp2 = redis::Value::Array(vec![redis::Value::Array(vec![
+            bulk("name"),
+            bulk("graph"),
+            bulk("ver"),
+            redis::Value::Int(41000),
+        ])]);
+        assert_eq!(parse_module_version(resp2), Ok("41000".to_string()));
+
+        let resp3 = redis::Value::Array(vec![
+            redis::Value::Map(vec![(bulk("name"), bulk("search"))]),
+            redis::Value::Map(vec![
+                (bulk("name"), bulk("graph")),
+                (bulk("ver"), redis::Value::Int(40200)),
+            ]),
+        ]);
+        assert_eq!(parse_module_version(resp3), Ok("40200".to_string()));
+
+        assert!(parse_module_version(redis::Value::Array(vec![])).is_err());
+    }
+
+    #[test]
+    fn test_check_round_trip() {
+        let node = |entity_id, name: &str| {
+            FalkorValue::Node(Node {
+                entity_id,
+                labels: vec!["SelfTest".to_string()],
+                properties: FalkorMap::from_iter([("name".to_string(), name.into())]),
+            })
+        };
+        let mut row = vec![
+            node(0, "a"),
+            FalkorValue::Edge(Edge {
+                entity_id: 0,
+                relationship_type: "LINKED".to_string(),
+                src_node_id: 0,
+                dst_node_id: 1,
+                properties: FalkorMap::from_iter([("since".to_string(), FalkorValue::I64(2024))]),
+            }),
+            node(1, "b"),
+            FalkorValue::Array(vec![
+                FalkorValue::I64(1),
+                FalkorValue::F64(2.5),
+                "three".into(),
+                FalkorValue::Bool(true),
+                FalkorValue::None,
+            ]),
+            FalkorValue::Map(FalkorMap::from_iter([("key".to_string(), "value".into())])),
+            FalkorValue::Point(Point::new(32.0, 34.0)),
+        ];
+        assert!(check_round_trip(Some(row.clone())).is_ok());
+
+        row[3] = FalkorValue::Array(vec![]);
+        assert!(check_round_trip(Some(row.clone())).is_err());
+        row.pop();
+        assert!(check_round_trip(Some(row)).is_err());
+        assert!(check_round_trip(None).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let mut report = SelfTestReport::default();
+        assert!(report.record(SelfTestCheck::Ping, Instant::now(), Ok("PONG".to_string())));
+        assert!(report.passed());
+        assert!(!report.record(
+            SelfTestCheck::ModuleVersion,
+            Instant::now(),
+            Err(FalkorDBError::ParsingString)
+        ));
+        assert!(!report.passed());
+        assert_eq!(
+            report
+                .failures()
+                .map(|failure| failure.check)
+                .collect::<Vec<_>>(),
+            vec![SelfTestCheck::ModuleVersion]
+        );
+        assert_eq!(
+            SelfTestCheck::CompactRoundTrip.to_string(),
+            "COMPACT_ROUND_TRIP"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use std::{sync::Weak, thread, time::Duration};
+
+/// The channel Sentinel announces failovers on, with payloads of the form `<master name> <old ip> <old port> <new ip> <new port>`
+const SWITCH_MASTER_CHANNEL: &str = "+switch-master";
+
+/// How often the watcher checks whether its client was dropped, while no failovers are announced
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait before resubscribing, after the connection to the Sentinel was lost
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A client whose connections should be replaced once the monitored master fails over
+pub(crate) trait FailoverTarget: Send + Sync {
+    /// Closes the connections to the previous master, new connections are opened to the promoted one
+    ///
+    /// # Arguments
+    /// * `promoted`: The address of the promoted master, or [`None`] if a failover may have been missed
+    fn on_failover(
+        &self,
+        promoted: Option<String>,
+    );
+}
+
+/// Returns whether a `+switch-master` announcement concerns the provided master
+fn is_switch_of(
+    payload: &str,
+    master_name: &str,
+) -> bool {
+    payload.split_whitespace().next() == Some(master_name)
+}
+
+/// Returns the address of the master promoted by a `+switch-master` announcement
+fn promoted_master(payload: &str) -> Option<String> {
+    match payload.split_whitespace().collect::<Vec<_>>()[..] {
+        [_, _, _, ip, port] => Some(format!("{ip}:{port}")),
+        _ => None,
+    }
+}
+
+fn watch_failovers(
+    sentinel_info: &redis::ConnectionInfo,
+    master_name: &str,
+    target: &Weak<dyn FailoverTarget>,
+    subscribed: &mut bool,
+) -> redis::RedisResult<()> {
+    let mut conn = redis::Client::open(sentinel_info.clone())?.get_connection()?;
+    let mut pubsub = conn.as_pubsub();
+    pubsub.set_read_timeout(Some(POLL_INTERVAL))?;
+    pubsub.subscribe(SWITCH_MASTER_CHANNEL)?;
+    *subscribed = true;
+
+    while target.strong_count() > 0 {
+        let payload: String = match pubsub.get_message() {
+            Ok(message) => message.get_payload()?,
+            Err(err) if err.is_timeout() => continue,
+            Err(err) => return Err(err),
+        };
+        if !is_switch_of(&payload, master_name) {
+            continue;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            payload,
+            "Sentinel announced a failover, replacing connections"
+        );
+        if let Some(target) = target.upgrade() {
+            target.on_failover(promoted_master(&payload));
+        }
+    }
+    Ok(())
+}
+
+/// Subscribes to the failover announcements of a Sentinel on a background thread,
+/// replacing the target's connections whenever the provided master fails over.
+/// The thread exits once the target is dropped.
+pub(crate) fn spawn_failover_watcher(
+    sentinel_info: redis::ConnectionInfo,
+    master_name: String,
+    target: Weak<dyn FailoverTarget>,
+) {
+    let watcher = move || {
+        while target.strong_count() > 0 {
+            let mut subscribed = false;
+            if let Err(_err) =
+                watch_failovers(&sentinel_info, &master_name, &target, &mut subscribed)
+            {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    error = %_err,
+                    "Lost the Sentinel failover subscription, resubscribing"
+                );
+
+                // A failover may be announced before resubscribing, so the connections are replaced to play it safe
+                if let Some(target) = target.upgrade().filter(|_| subscribed) {
+                    target.on_failover(None);
+                }
+                thread::sleep(RESUBSCRIBE_INTERVAL);
+            }
+        }
+    };
+
+    thread::Builder::new()
+        .name("falkordb-sentinel-watcher".to_string())
+        .spawn(watcher)
+        .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_switch_of() {
+        assert!(is_switch_of(
+            "mymaster 10.0.0.1 6379 10.0.0.2 6379",
+            "mymaster"
+        ));
+        assert!(!is_switch_of(
+            "other 10.0.0.1 6379 10.0.0.2 6379",
+            "mymaster"
+        ));
+        assert!(!is_switch_of(
+            "mymaster2 10.0.0.1 6379 10.0.0.2 6379",
+            "mymaster"
+        ));
+        assert!(!is_switch_of("", "mymaster"));
+    }
+
+    #[test]
+    fn test_promoted_master() {
+        assert_eq!(
+            promoted_master("mymaster 10.0.0.1 6379 10.0.0.2 6380"),
+            Some("10.0.0.2:6380".to_string())
+        );
+        assert_eq!(promoted_master("mymaster 10.0.0.1 6379"), None);
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{redaction::redact_query_literals, FalkorResult};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The literal values of parameters are replaced with, when log redaction is enabled
+const REDACTED_PARAM: &str = "?";
+
+/// A callback invoked with every slow query, see [`SlowQueryLog::with_callback`]
+pub type SlowQueryCallback = Arc<dyn Fn(&SlowQuery) + Send + Sync>;
+
+/// A query which took longer than the threshold of the client's [`SlowQueryLog`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowQuery {
+    /// The graph the query was sent to
+    pub graph: String,
+    /// The query, with its literals masked if log redaction is enabled,
+    /// see [`FalkorClientBuilder::with_log_redaction`](crate::FalkorClientBuilder::with_log_redaction)
+    pub query: String,
+    /// The query's parameters, with their values masked if log redaction is enabled
+    pub params: HashMap<String, String>,
+    /// How long the query took, including borrowing a connection and retries
+    pub duration: Duration,
+    /// Whether the query succeeded
+    pub succeeded: bool,
+}
+
+/// Reports queries which take longer than a threshold, see [`FalkorClientBuilder::with_slow_query_log`](crate::FalkorClientBuilder::with_slow_query_log)
+/// Complements the server's `GRAPH.SLOWLOG`, as the duration includes the network and the client, and the threshold is set per service.
+///
+/// Slow queries are logged as warnings when the `tracing` feature is enabled, and passed to the callback, if one is set.
+#[derive(Clone)]
+pub struct SlowQueryLog {
+    threshold: Duration,
+    callback: Option<SlowQueryCallback>,
+}
+
+impl SlowQueryLog {
+    /// Creates a new [`SlowQueryLog`]
+    ///
+    /// # Arguments
+    /// * `threshold`: The duration above which a query is reported, E.g. 200 milliseconds
+    ///
+    /// # Returns
+    /// The new [`SlowQueryLog`]
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            callback: None,
+        }
+    }
+
+    /// Passes every slow query to a callback, E.g. to report it to the service's own logs or metrics
+    /// The callback is invoked on the path of the query, so it should not block.
+    ///
+    /// # Arguments
+    /// * `callback`: The callback to invoke with every [`SlowQuery`]
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_callback<F: Fn(&SlowQuery) + Send + Sync + 'static>(
+        self,
+        callback: F,
+    ) -> Self {
+        Self {
+            callback: Some(Arc::new(callback)),
+            ..self
+        }
+    }
+
+    fn report(
+        &self,
+        slow_query: &SlowQuery,
+    ) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            graph = %slow_query.graph,
+            query = %slow_query.query,
+            params = ?slow_query.params,
+            duration = ?slow_query.duration,
+            succeeded = slow_query.succeeded,
+            "Slow query"
+        );
+        if let Some(callback) = &self.callback {
+            callback(slow_query);
+        }
+    }
+}
+
+impl Debug for SlowQueryLog {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("SlowQueryLog")
+            .field("threshold", &self.threshold)
+            .field("callback", &self.callback.as_ref().map(|_| "<callback>"))
+            .finish()
+    }
+}
+
+/// Times a query for the client's [`SlowQueryLog`], if it has one
+pub(crate) struct SlowQueryTimer {
+    log: Option<Arc<SlowQueryLog>>,
+    started: Instant,
+}
+
+impl SlowQueryTimer {
+    pub(crate) fn start(log: Option<Arc<SlowQueryLog>>) -> Self {
+        Self {
+            log,
+            started: Instant::now(),
+        }
+    }
+
+    /// Reports the query if it took longer than the threshold, only then rendering its text and parameters
+    pub(crate) fn finish<T, Q: FnOnce() -> (String, Option<HashMap<String, String>>)>(
+        self,
+        graph: &str,
+        res: &FalkorResult<T>,
+        redact: bool,
+        query: Q,
+    ) {
+        let duration = self.started.elapsed();
+        let Some(log) = self.log.filter(|log| duration > log.threshold) else {
+            return;
+        };
+
+        let (query, params) = query();
+        let params = params.unwrap_or_default();
+        log.report(&SlowQuery {
+            graph: graph.to_string(),
+            query: match redact {
+                true => redact_query_literals(&query),
+                false => query,
+            },
+            params: match redact {
+                true => params
+                    .into_keys()
+                    .map(|key| (key, REDACTED_PARAM.to_string()))
+                    .collect(),
+                false => params,
+            },
+            duration,
+            succeeded: res.is_ok(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[test]
+    fn test_slow_query_timer() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::new(SlowQueryLog::new(Duration::from_millis(10)).with_callback({
+            let reported = reported.clone();
+            move |slow_query: &SlowQuery| reported.lock().push(slow_query.clone())
+        }));
+        let query = || {
+            (
+                "MATCH (n {name: 'Alice'}) WHERE n.age > $age RETURN n".to_string(),
+                Some(HashMap::from([("age".to_string(), "30".to_string())])),
+            )
+        };
+
+        SlowQueryTimer::start(Some(log.clone())).finish("social", &Ok(()), false, query);
+        assert!(reported.lock().is_empty());
+
+        let timer = SlowQueryTimer::start(Some(log.clone()));
+        std::thread::sleep(Duration::from_millis(20));
+        timer.finish("social", &Ok(()), true, query);
+        SlowQueryTimer::start(None).finish("social", &Ok(()), false, || unreachable!());
+
+        let reported = reported.lock();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].graph, "social");
+        assert_eq!(
+            reported[0].query,
+            "MATCH (n {name: ?}) WHERE n.age > $age RETURN n"
+        );
+        assert_eq!(reported[0].params["age"], "?");
+        assert!(reported[0].duration >= Duration::from_millis(20));
+        assert!(reported[0].succeeded);
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{FalkorDBError, FalkorResult};
+use std::{
+    fmt::{Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// The outcome of opening and validating a single pooled connection
+#[derive(Debug, PartialEq)]
+pub struct ConnectionWarmUp {
+    /// The position of the connection in the pool, starting at 0
+    pub connection: usize,
+    /// How long it took to borrow the connection, opening it if needed, and validate it
+    pub elapsed: Duration,
+    /// Nothing if the connection answered a `PING`, or the error it failed with
+    pub result: FalkorResult<()>,
+}
+
+/// A structured report of warming up the connection pool, see [`FalkorSyncClient::warm_up`](crate::FalkorSyncClient::warm_up)
+#[derive(Debug, Default, PartialEq)]
+pub struct WarmUpReport {
+    /// The number of connections in the pool
+    pub pool_size: usize,
+    /// The outcome of every connection, in the order they were borrowed
+    pub connections: Vec<ConnectionWarmUp>,
+}
+
+impl WarmUpReport {
+    pub(crate) fn new(pool_size: usize) -> Self {
+        Self {
+            pool_size,
+            connections: Vec::with_capacity(pool_size),
+        }
+    }
+
+    /// Returns whether every connection is open and answered a `PING`
+    pub fn ready(&self) -> bool {
+        self.connections.iter().all(|conn| conn.result.is_ok())
+    }
+
+    /// Returns the connections which could not be opened or validated
+    pub fn failures(&self) -> impl Iterator<Item = &ConnectionWarmUp> {
+        self.connections.iter().filter(|conn| conn.result.is_err())
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        start: Instant,
+        result: FalkorResult<()>,
+    ) {
+        self.connections.push(ConnectionWarmUp {
+            connection: self.connections.len(),
+            elapsed: start.elapsed(),
+            result,
+        });
+    }
+
+    /// Fails with [`FalkorDBError::WarmUpFailed`], carrying this report, unless every connection is ready
+    pub(crate) fn into_result(self) -> FalkorResult<Self> {
+        match self.ready() {
+            true => Ok(self),
+            false => Err(FalkorDBError::WarmUpFailed(self)),
+        }
+    }
+}
+
+impl Display for WarmUpReport {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} connections failed",
+            self.failures().count(),
+            self.pool_size
+        )?;
+        for failure in self.failures() {
+            if let Err(err) = &failure.result {
+                write!(f, ", connection {}: {err}", failure.connection)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warm_up_report() {
+        let mut report = WarmUpReport::new(4);
+        report.record(Instant::now(), Ok(()));
+        assert!(report.ready());
+
+        report.record(Instant::now(), Err(FalkorDBError::NoConnection));
+        report.record(Instant::now(), Err(FalkorDBError::ConnectionDown));
+        assert!(!report.ready());
+        assert_eq!(
+            report
+                .failures()
+                .map(|conn| conn.connection)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "2 of 4 connections failed, connection 1: {}, connection 2: {}",
+                FalkorDBError::NoConnection,
+                FalkorDBError::ConnectionDown
+            )
+        );
+
+        assert!(matches!(
+            report.into_result(),
+            Err(FalkorDBError::WarmUpFailed(report)) if report.connections.len() == 3
+        ));
+        assert!(WarmUpReport::default().into_result().is_ok());
+    }
+}
@@ -0,0 +1,335 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Generates Rust enums and constants for the labels, relationship types and property keys of a graph,
+//! so queries can reference them without typos.
+//!
+//! The generated code can be written to a file once, and committed, or regenerated by a build script:
+//!
+//! ```no_run
+//! // build.rs
+//! use falkordb::{codegen::SchemaCodegen, FalkorClientBuilder};
+//!
+//! let client = FalkorClientBuilder::new().build().expect("Could not create client");
+//! let graph = client.select_graph("social");
+//! let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+//!
+//! SchemaCodegen::from_graph(&graph)
+//!     .expect("Could not introspect graph")
+//!     .write_to(std::path::Path::new(&out_dir).join("social_schema.rs"))
+//!     .expect("Could not write generated code");
+//! ```
+//!
+//! And then included in the application, using `include!(concat!(env!("OUT_DIR"), "/social_schema.rs"));`
+
+use crate::{FalkorResult, SyncGraph};
+use std::{collections::HashSet, fmt::Write as _, path::Path};
+
+#[cfg(feature = "tokio")]
+use crate::AsyncGraph;
+
+/// Splits a name into words, on any character which is not valid in an identifier, and on lower to upper case transitions
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            words.extend((!current.is_empty()).then(|| std::mem::take(&mut current)));
+            prev_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    words.extend((!current.is_empty()).then_some(current));
+    words
+}
+
+fn to_upper_camel_case(name: &str) -> String {
+    split_words(name)
+        .into_iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn to_screaming_snake_case(name: &str) -> String {
+    split_words(name)
+        .into_iter()
+        .map(|word| word.to_ascii_uppercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Makes sure the identifier is valid and unique, E.g. by prefixing names starting with a digit
+fn make_identifier(
+    mut ident: String,
+    used: &mut HashSet<String>,
+) -> String {
+    if ident.is_empty() {
+        ident = "Unnamed".to_string();
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if ident == "Self" {
+        ident.push('_');
+    }
+
+    let mut unique = ident.clone();
+    let mut suffix = 2;
+    while !used.insert(unique.clone()) {
+        unique = format!("{ident}{suffix}");
+        suffix += 1;
+    }
+    unique
+}
+
+/// A snapshot of the names used in a graph, which can be rendered as Rust code
+/// The generated code contains a `Label` and a `RelationshipType` enum, each with an `as_str()` method and a [`Display`](std::fmt::Display) implementation,
+/// and a `property` module with a constant for each property key.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaCodegen {
+    graph_name: String,
+    labels: Vec<String>,
+    relationship_types: Vec<String>,
+    property_keys: Vec<String>,
+}
+
+impl SchemaCodegen {
+    /// Creates a new [`SchemaCodegen`] from explicitly provided names, without introspecting a graph
+    ///
+    /// # Arguments
+    /// * `graph_name`: The name of the graph, only used in the generated documentation
+    /// * `labels`: The node labels
+    /// * `relationship_types`: The relationship types
+    /// * `property_keys`: The property keys
+    ///
+    /// # Returns
+    /// The new [`SchemaCodegen`]
+    pub fn new<S: ToString>(
+        graph_name: S,
+        labels: Vec<String>,
+        relationship_types: Vec<String>,
+        property_keys: Vec<String>,
+    ) -> Self {
+        Self {
+            graph_name: graph_name.to_string(),
+            labels,
+            relationship_types,
+            property_keys,
+        }
+    }
+
+    /// Introspects a graph, collecting all of its labels, relationship types and property keys
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to introspect
+    ///
+    /// # Returns
+    /// The new [`SchemaCodegen`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Introspect Graph For Codegen", skip_all, level = "info")
+    )]
+    pub fn from_graph(graph: &SyncGraph) -> FalkorResult<Self> {
+        Ok(Self::new(
+            graph.graph_name(),
+            graph.labels()?,
+            graph.relationship_types()?,
+            graph.property_keys()?,
+        ))
+    }
+
+    /// Introspects a graph, collecting all of its labels, relationship types and property keys
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to introspect
+    ///
+    /// # Returns
+    /// The new [`SchemaCodegen`]
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Introspect Graph For Codegen", skip_all, level = "info")
+    )]
+    pub async fn from_async_graph(graph: &AsyncGraph) -> FalkorResult<Self> {
+        Ok(Self::new(
+            graph.graph_name(),
+            graph.labels().await?,
+            graph.relationship_types().await?,
+            graph.property_keys().await?,
+        ))
+    }
+
+    fn render_enum(
+        &self,
+        out: &mut String,
+        enum_name: &str,
+        description: &str,
+        names: &[String],
+    ) {
+        let mut used = HashSet::new();
+        let variants: Vec<(String, &String)> = names
+            .iter()
+            .map(|name| (make_identifier(to_upper_camel_case(name), &mut used), name))
+            .collect();
+
+        let _ = writeln!(
+            out,
+            "/// The {description} of the `{}` graph",
+            self.graph_name
+        );
+        let _ = writeln!(out, "#[allow(non_camel_case_types)]");
+        let _ = writeln!(out, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]");
+        let _ = writeln!(out, "pub enum {enum_name} {{");
+        for (variant, name) in &variants {
+            let _ = writeln!(out, "    /// `{}`", name.replace('`', "'"));
+            let _ = writeln!(out, "    {variant},");
+        }
+        let _ = writeln!(out, "}}\n");
+
+        let _ = writeln!(out, "impl {enum_name} {{");
+        let _ = writeln!(
+            out,
+            "    /// All the variants, in the order they were created in the graph"
+        );
+        let _ = writeln!(
+            out,
+            "    pub const ALL: &'static [Self] = &[{}];\n",
+            variants
+                .iter()
+                .map(|(variant, _)| format!("Self::{variant}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let _ = writeln!(out, "    /// The name, as used in queries");
+        let _ = writeln!(out, "    pub const fn as_str(&self) -> &'static str {{");
+        let _ = writeln!(out, "        match *self {{");
+        for (variant, name) in &variants {
+            let _ = writeln!(out, "            Self::{variant} => {name:?},");
+        }
+        let _ = writeln!(out, "        }}\n    }}\n}}\n");
+
+        let _ = writeln!(out, "impl ::std::fmt::Display for {enum_name} {{");
+        let _ = writeln!(
+            out,
+            "    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {{"
+        );
+        let _ = writeln!(out, "        f.write_str(self.as_str())\n    }}\n}}\n");
+    }
+
+    /// Renders the collected names as Rust source code
+    ///
+    /// # Returns
+    /// The generated code, ready to be written to a file or included
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "// Generated by the falkordb codegen from the `{}` graph, do not edit by hand.\n\n",
+            self.graph_name
+        );
+        self.render_enum(&mut out, "Label", "node labels", &self.labels);
+        self.render_enum(
+            &mut out,
+            "RelationshipType",
+            "relationship types",
+            &self.relationship_types,
+        );
+
+        let mut used = HashSet::new();
+        let _ = writeln!(
+            out,
+            "/// The property keys of the `{}` graph",
+            self.graph_name
+        );
+        let _ = writeln!(out, "#[allow(dead_code)]");
+        let _ = writeln!(out, "pub mod property {{");
+        for name in &self.property_keys {
+            let ident = make_identifier(to_screaming_snake_case(name), &mut used);
+            let _ = writeln!(out, "    /// `{}`", name.replace('`', "'"));
+            let _ = writeln!(out, "    pub const {ident}: &str = {name:?};");
+        }
+        let _ = writeln!(out, "}}");
+        out
+    }
+
+    /// Renders the collected names as Rust source code, and writes it to a file
+    ///
+    /// # Arguments
+    /// * `path`: The file to write the generated code to, it is overwritten if it already exists
+    pub fn write_to<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> std::io::Result<()> {
+        std::fs::write(path, self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(to_upper_camel_case("Person"), "Person");
+        assert_eq!(to_upper_camel_case("ACTED_IN"), "ActedIn");
+        assert_eq!(to_upper_camel_case("movieStar"), "MovieStar");
+        assert_eq!(to_upper_camel_case("has-2 parts"), "Has2Parts");
+        assert_eq!(to_screaming_snake_case("firstName"), "FIRST_NAME");
+        assert_eq!(to_screaming_snake_case("created at"), "CREATED_AT");
+        assert_eq!(to_screaming_snake_case("ID"), "ID");
+
+        let mut used = HashSet::new();
+        assert_eq!(make_identifier("Self".to_string(), &mut used), "Self_");
+        assert_eq!(make_identifier("1st".to_string(), &mut used), "_1st");
+        assert_eq!(make_identifier(String::new(), &mut used), "Unnamed");
+        assert_eq!(make_identifier("Person".to_string(), &mut used), "Person");
+        assert_eq!(make_identifier("Person".to_string(), &mut used), "Person2");
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = SchemaCodegen::new(
+            "social",
+            vec!["Person".to_string(), "person".to_string()],
+            vec!["KNOWS".to_string()],
+            vec!["firstName".to_string()],
+        )
+        .render();
+
+        assert!(rendered.contains("pub enum Label {"));
+        assert!(rendered.contains("    Person,\n"));
+        assert!(rendered.contains("    Person2,\n"));
+        assert!(rendered.contains("            Self::Person2 => \"person\",\n"));
+        assert!(rendered.contains("pub const ALL: &'static [Self] = &[Self::Knows];"));
+        assert!(rendered.contains("    pub const FIRST_NAME: &str = \"firstName\";\n"));
+
+        // Empty graphs still produce valid code
+        let empty = SchemaCodegen::new("empty", vec![], vec![], vec![]).render();
+        assert!(empty.contains("pub enum Label {\n}"));
+        assert!(empty.contains("        match *self {\n        }"));
+    }
+
+    #[test]
+    fn test_from_graph() {
+        let client = crate::test_utils::create_test_client();
+        let codegen = SchemaCodegen::from_graph(&client.select_graph("imdb"))
+            .expect("Could not introspect graph");
+        assert!(codegen.labels.contains(&"actor".to_string()));
+        assert!(codegen.relationship_types.contains(&"act".to_string()));
+        assert!(codegen.property_keys.contains(&"name".to_string()));
+    }
+}
@@ -4,14 +4,22 @@
  */
 
 use crate::{
-    client::asynchronous::FalkorAsyncClientInner, connection::map_redis_err,
-    parser::parse_redis_info, FalkorDBError, FalkorResult,
+    client::{
+        asynchronous::FalkorAsyncClientInner, circuit::ConnectionCircuitPass,
+        rate_limit::RatePermit,
+    },
+    connection::{map_redis_err, time_until_deadline},
+    parser::parse_redis_info,
+    runtime,
+    transport::{flatten_command, FalkorAsyncTransport},
+    FalkorDBError, FalkorResult,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Instant};
 use tokio::sync::mpsc;
 
 pub(crate) enum FalkorAsyncConnection {
     Redis(redis::aio::MultiplexedConnection),
+    Transport(Box<dyn FalkorAsyncTransport>),
 }
 
 impl FalkorAsyncConnection {
@@ -41,6 +49,11 @@ impl FalkorAsyncConnection {
                     .await
                     .map_err(map_redis_err)
             }
+            FalkorAsyncConnection::Transport(transport) => {
+                transport
+                    .execute(flatten_command(graph_name, command, subcommand, params))
+                    .await
+            }
         }
     }
 
@@ -72,8 +85,12 @@ impl FalkorAsyncConnection {
 /// This is publicly exposed for user-implementations of [`FalkorParsable`](crate::FalkorParsable)
 pub struct BorrowedAsyncConnection {
     conn: Option<FalkorAsyncConnection>,
-    return_tx: mpsc::Sender<FalkorAsyncConnection>,
+    /// [`None`] for a clone of a multiplexed client's shared connection, which is simply dropped instead of being returned
+    return_tx: Option<mpsc::Sender<FalkorAsyncConnection>>,
     client: Arc<FalkorAsyncClientInner>,
+    generation: u64,
+    circuit_pass: ConnectionCircuitPass,
+    _rate_permit: RatePermit,
 }
 
 impl BorrowedAsyncConnection {
@@ -81,11 +98,33 @@ impl BorrowedAsyncConnection {
         conn: FalkorAsyncConnection,
         return_tx: mpsc::Sender<FalkorAsyncConnection>,
         client: Arc<FalkorAsyncClientInner>,
+        circuit_pass: ConnectionCircuitPass,
+        rate_permit: RatePermit,
+    ) -> Self {
+        Self {
+            conn: Some(conn),
+            return_tx: Some(return_tx),
+            generation: client.connection_generation(),
+            client,
+            circuit_pass,
+            _rate_permit: rate_permit,
+        }
+    }
+
+    /// Wraps a clone of a multiplexed client's shared connection, which many borrowers use concurrently
+    pub(crate) fn shared(
+        conn: FalkorAsyncConnection,
+        client: Arc<FalkorAsyncClientInner>,
+        circuit_pass: ConnectionCircuitPass,
+        rate_permit: RatePermit,
     ) -> Self {
         Self {
             conn: Some(conn),
-            return_tx,
+            return_tx: None,
+            generation: client.connection_generation(),
             client,
+            circuit_pass,
+            _rate_permit: rate_permit,
         }
     }
 
@@ -102,21 +141,59 @@ impl BorrowedAsyncConnection {
         )
     )]
     pub(crate) async fn execute_command(
+        self,
+        graph_name: Option<&str>,
+        command: &str,
+        subcommand: Option<&str>,
+        params: Option<&[&str]>,
+    ) -> FalkorResult<redis::Value> {
+        let Some(interceptors) = self.client.interceptors() else {
+            return self
+                .send_command(graph_name, command, subcommand, params)
+                .await;
+        };
+
+        let mut interception = interceptors.intercept(graph_name, command, subcommand, params);
+        let res = match interception.take_reply() {
+            // Dropping the unused connection returns it to the pool
+            Some(reply) => reply,
+            None => {
+                let command = interception.command();
+                self.send_command(
+                    command.graph.as_deref(),
+                    &command.name,
+                    command.subcommand.as_deref(),
+                    Some(&command.params()),
+                )
+                .await
+            }
+        };
+        interception.finish(res)
+    }
+
+    async fn send_command(
         mut self,
         graph_name: Option<&str>,
         command: &str,
         subcommand: Option<&str>,
         params: Option<&[&str]>,
     ) -> FalkorResult<redis::Value> {
-        let res = match self
+        let res = self
             .as_inner()?
             .execute_command(graph_name, command, subcommand, params)
-            .await
-        {
+            .await;
+        self.circuit_pass.record(&res);
+        let res = match res {
+            // The shared connection is reopened by the next borrower, instead of every failed query reconnecting
+            Err(FalkorDBError::ConnectionDown) if self.return_tx.is_none() => {
+                self.client
+                    .close_shared_connection(&FalkorDBError::ConnectionDown);
+                Err(FalkorDBError::ConnectionDown)
+            }
             Err(FalkorDBError::ConnectionDown) => {
-                if let Ok(new_conn) = self.client.get_async_connection().await {
+                if let Ok(new_conn) = self.client.reconnect(&FalkorDBError::ConnectionDown).await {
                     self.conn = Some(new_conn);
-                    tokio::spawn(async { self.return_to_pool().await });
+                    runtime::spawn(async { self.return_to_pool().await });
                     return Err(FalkorDBError::ConnectionDown);
                 }
                 Err(FalkorDBError::NoConnection)
@@ -124,13 +201,66 @@ impl BorrowedAsyncConnection {
             res => res,
         };
 
-        tokio::spawn(async { self.return_to_pool().await });
+        runtime::spawn(async { self.return_to_pool().await });
         res
     }
 
-    pub(crate) async fn return_to_pool(self) {
-        if let Some(conn) = self.conn {
-            self.return_tx.send(conn).await.ok();
+    /// Executes a command, failing if it does not complete before the deadline
+    pub(crate) async fn execute_command_with_deadline(
+        self,
+        graph_name: Option<&str>,
+        command: &str,
+        subcommand: Option<&str>,
+        params: Option<&[&str]>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<redis::Value> {
+        let Some(deadline) = deadline else {
+            return self
+                .execute_command(graph_name, command, subcommand, params)
+                .await;
+        };
+
+        time_until_deadline(deadline)?;
+        runtime::timeout_at(
+            deadline,
+            self.execute_command(graph_name, command, subcommand, params),
+        )
+        .await
+        .ok_or(FalkorDBError::DeadlineExceeded)?
+    }
+
+    pub(crate) async fn return_to_pool(mut self) {
+        let Some(return_tx) = self.return_tx.clone() else {
+            return;
+        };
+        if let Some(conn) = self.conn.take() {
+            if !self.client.should_close_returned_connection() {
+                return_tx.send(conn).await.ok();
+            }
+        }
+    }
+}
+
+impl Drop for BorrowedAsyncConnection {
+    fn drop(&mut self) {
+        self.client.pool_metrics().record_checkin();
+        let Some(return_tx) = &self.return_tx else {
+            return;
+        };
+        match self.conn.take() {
+            // Connections opened before a failover may still point at the demoted primary
+            Some(_) if self.generation != self.client.connection_generation() => {
+                self.client.discard_connection()
+            }
+            // A multiplexed connection discards replies to cancelled requests, so it can be reused safely
+            Some(conn @ FalkorAsyncConnection::Redis(_))
+                if !self.client.should_close_returned_connection() =>
+            {
+                return_tx.try_send(conn).ok();
+            }
+            // A cancelled request may leave its reply unread on a custom transport, so it is closed and reopened on demand
+            Some(FalkorAsyncConnection::Transport(_)) => self.client.discard_connection(),
+            _ => {}
         }
     }
 }
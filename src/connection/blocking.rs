@@ -4,14 +4,18 @@
  */
 
 use crate::{
-    client::{blocking::FalkorSyncClientInner, ProvidesSyncConnections},
-    connection::map_redis_err,
+    client::{
+        blocking::FalkorSyncClientInner, circuit::ConnectionCircuitPass, rate_limit::RatePermit,
+    },
+    connection::{map_redis_err, time_until_deadline},
     parser::parse_redis_info,
+    transport::{flatten_command, FalkorTransport},
     FalkorDBError, FalkorResult,
 };
 use std::{
     collections::HashMap,
     sync::{mpsc, Arc},
+    time::{Duration, Instant},
 };
 
 pub(crate) enum FalkorSyncConnection {
@@ -19,6 +23,7 @@ pub(crate) enum FalkorSyncConnection {
     None,
 
     Redis(redis::Connection),
+    Transport(Box<dyn FalkorTransport>),
 }
 
 impl FalkorSyncConnection {
@@ -46,11 +51,31 @@ impl FalkorSyncConnection {
                 }
                 redis_conn.req_command(&cmd).map_err(map_redis_err)
             }
+            FalkorSyncConnection::Transport(transport) => {
+                transport.execute(flatten_command(graph_name, command, subcommand, params))
+            }
             #[cfg(test)]
             FalkorSyncConnection::None => Ok(redis::Value::Nil),
         }
     }
 
+    /// Sets the client-side socket timeouts, this is only supported for Redis connections, custom transports are left as-is
+    pub(crate) fn set_timeouts(
+        &mut self,
+        read_timeout: Option<Duration>,
+        write_timeout: Option<Duration>,
+    ) -> FalkorResult<()> {
+        match self {
+            FalkorSyncConnection::Redis(redis_conn) => redis_conn
+                .set_read_timeout(read_timeout)
+                .and_then(|_| redis_conn.set_write_timeout(write_timeout))
+                .map_err(map_redis_err),
+            FalkorSyncConnection::Transport(_) => Ok(()),
+            #[cfg(test)]
+            FalkorSyncConnection::None => Ok(()),
+        }
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Connection Get Redis Info", skip_all, level = "info")
@@ -80,6 +105,9 @@ pub struct BorrowedSyncConnection {
     conn: Option<FalkorSyncConnection>,
     return_tx: mpsc::SyncSender<FalkorSyncConnection>,
     client: Arc<FalkorSyncClientInner>,
+    generation: u64,
+    circuit_pass: ConnectionCircuitPass,
+    _rate_permit: RatePermit,
 }
 
 impl BorrowedSyncConnection {
@@ -87,11 +115,16 @@ impl BorrowedSyncConnection {
         conn: FalkorSyncConnection,
         return_tx: mpsc::SyncSender<FalkorSyncConnection>,
         client: Arc<FalkorSyncClientInner>,
+        circuit_pass: ConnectionCircuitPass,
+        rate_permit: RatePermit,
     ) -> Self {
         Self {
             conn: Some(conn),
             return_tx,
+            generation: client.connection_generation(),
             client,
+            circuit_pass,
+            _rate_permit: rate_permit,
         }
     }
 
@@ -114,26 +147,96 @@ impl BorrowedSyncConnection {
         subcommand: Option<&str>,
         params: Option<&[&str]>,
     ) -> Result<redis::Value, FalkorDBError> {
-        match self
+        let Some(interceptors) = self.client.interceptors() else {
+            return self.send_command(graph_name, command, subcommand, params);
+        };
+
+        let mut interception = interceptors.intercept(graph_name, command, subcommand, params);
+        let res = match interception.take_reply() {
+            Some(reply) => reply,
+            None => {
+                let command = interception.command();
+                self.send_command(
+                    command.graph.as_deref(),
+                    &command.name,
+                    command.subcommand.as_deref(),
+                    Some(&command.params()),
+                )
+            }
+        };
+        interception.finish(res)
+    }
+
+    fn send_command(
+        &mut self,
+        graph_name: Option<&str>,
+        command: &str,
+        subcommand: Option<&str>,
+        params: Option<&[&str]>,
+    ) -> FalkorResult<redis::Value> {
+        let res = self
             .as_inner()?
-            .execute_command(graph_name, command, subcommand, params)
-        {
-            Err(FalkorDBError::ConnectionDown) => {
-                if let Ok(new_conn) = self.client.get_connection() {
+            .execute_command(graph_name, command, subcommand, params);
+        self.circuit_pass.record(&res);
+        match res {
+            // A timed out connection is replaced too, as the late reply would otherwise be read by the next command
+            Err(err @ (FalkorDBError::ConnectionDown | FalkorDBError::SocketTimeout)) => {
+                if let Ok(new_conn) = self.client.reconnect(&err) {
                     self.conn = Some(new_conn);
-                    return Err(FalkorDBError::ConnectionDown);
+                    return Err(err);
                 }
                 Err(FalkorDBError::NoConnection)
             }
             res => res,
         }
     }
+
+    /// Executes a command, using the time left until the deadline as the socket timeout, unless the configured one is shorter
+    /// Timing out replaces the connection, as a late reply would otherwise be read by the next command.
+    pub(crate) fn execute_command_with_deadline(
+        &mut self,
+        graph_name: Option<&str>,
+        command: &str,
+        subcommand: Option<&str>,
+        params: Option<&[&str]>,
+        deadline: Option<Instant>,
+    ) -> FalkorResult<redis::Value> {
+        let Some(deadline) = deadline else {
+            return self.execute_command(graph_name, command, subcommand, params);
+        };
+
+        let timeouts = self.client.socket_timeouts();
+        let remaining = time_until_deadline(deadline)?;
+        let bounded =
+            |timeout: Option<Duration>| Some(timeout.map_or(remaining, |t| t.min(remaining)));
+        self.as_inner()?
+            .set_timeouts(bounded(timeouts.read), bounded(timeouts.write))?;
+        let res = self.execute_command(graph_name, command, subcommand, params);
+        if let Some(conn) = self.conn.as_mut() {
+            conn.set_timeouts(timeouts.read, timeouts.write).ok();
+        }
+
+        match res {
+            Err(
+                FalkorDBError::ConnectionDown
+                | FalkorDBError::NoConnection
+                | FalkorDBError::SocketTimeout,
+            ) if Instant::now() >= deadline => Err(FalkorDBError::DeadlineExceeded),
+            res => res,
+        }
+    }
 }
 
 impl Drop for BorrowedSyncConnection {
     fn drop(&mut self) {
+        self.client.pool_metrics().record_checkin();
         if let Some(conn) = self.conn.take() {
-            self.return_tx.send(conn).ok();
+            // Connections opened before a failover may still point at the demoted primary
+            if self.generation != self.client.connection_generation() {
+                self.client.discard_connection();
+            } else if !self.client.should_close_returned_connection() {
+                self.return_tx.send(conn).ok();
+            }
         }
     }
 }
@@ -3,19 +3,331 @@
  * Licensed under the MIT License.
  */
 
-use crate::FalkorDBError;
+use crate::{connection_info::format_addr, FalkorDBError, FalkorResult};
+use std::time::{Duration, Instant};
 
 pub(crate) mod blocking;
 
 #[cfg(feature = "tokio")]
 pub(crate) mod asynchronous;
 
-fn map_redis_err(error: redis::RedisError) -> FalkorDBError {
+/// The message the server aborts queries with, once they exceed the configured memory limit
+const MEMORY_LIMIT_EXCEEDED_MESSAGE: &str = "mem consumption exceeded capacity";
+
+/// The message the server aborts queries with, once they exceed the configured timeout
+const QUERY_TIMED_OUT_MESSAGE: &str = "query timed out";
+
+/// Part of the messages the server rejects queries violating a unique or mandatory constraint with
+const CONSTRAINT_VIOLATION_MESSAGE: &str = "constraint violation";
+
+/// The prefixes of the messages the server rejects queries it could not parse with
+const SYNTAX_ERROR_MESSAGES: [&str; 3] = ["errmsg:", "invalid input", "syntax error"];
+
+/// Extracts the offset a syntax error points at, from messages such as `line: 1, column: 5, offset: 4` or `Syntax error at offset 4`
+fn parse_error_offset(message: &str) -> Option<usize> {
+    let (_, rest) = message.split_once("offset")?;
+    rest.trim_start_matches([':', ' '])
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Restores the message of an error reply, which the redis crate splits into a code, being its first word, and the rest,
+/// dropping the generic `ERR` code, E.g. `Query timed out` would otherwise be reported as `timed out`
+fn reply_message(
+    code: &str,
+    detail: Option<&str>,
+) -> String {
+    match (code, detail) {
+        ("ERR", Some(detail)) => detail.to_string(),
+        (code, Some(detail)) => format!("{code} {detail}"),
+        (code, None) => code.to_string(),
+    }
+}
+
+/// The codes of the error replies rejecting a command which was sent without, or with wrong credentials
+const AUTHENTICATION_ERROR_CODES: [&str; 2] = ["NOAUTH", "WRONGPASS"];
+
+/// Maps an error reply to the most specific error variant, by its code first, and by its message otherwise
+fn map_error_reply(
+    code: &str,
+    detail: Option<&str>,
+) -> FalkorDBError {
+    let message = reply_message(code, detail);
+    match code {
+        "MOVED" | "ASK" => detail
+            .and_then(|detail| detail.split_once(' '))
+            .and_then(|(slot, endpoint)| {
+                Some(FalkorDBError::Redirected {
+                    slot: slot.parse().ok()?,
+                    endpoint: endpoint.trim().to_string(),
+                    ask: code == "ASK",
+                })
+            })
+            .unwrap_or(FalkorDBError::RedisError(message)),
+        "OOM" => FalkorDBError::ServerOutOfMemory(message),
+        code if AUTHENTICATION_ERROR_CODES.contains(&code) => {
+            FalkorDBError::AuthenticationFailed(message)
+        }
+        _ => map_server_error(message),
+    }
+}
+
+/// Maps an error message returned by the server to the most specific error variant
+pub(crate) fn map_server_error(message: String) -> FalkorDBError {
+    let lowercase = message.to_lowercase();
+    if lowercase.contains(MEMORY_LIMIT_EXCEEDED_MESSAGE) {
+        FalkorDBError::QueryMemoryLimitExceeded(message)
+    } else if lowercase.contains(QUERY_TIMED_OUT_MESSAGE) {
+        FalkorDBError::QueryTimedOut {
+            message,
+            timeout: None,
+        }
+    } else if lowercase.contains(CONSTRAINT_VIOLATION_MESSAGE) {
+        FalkorDBError::ConstraintViolation(message)
+    } else if SYNTAX_ERROR_MESSAGES
+        .iter()
+        .any(|prefix| lowercase.starts_with(prefix))
+    {
+        FalkorDBError::SyntaxError {
+            offset: parse_error_offset(&lowercase),
+            message,
+        }
+    } else {
+        FalkorDBError::RedisError(message)
+    }
+}
+
+/// Fails with the error the server embedded in a reply, such as a query which failed after it started returning results
+pub(crate) fn reject_server_error(value: redis::Value) -> FalkorResult<redis::Value> {
+    match value {
+        redis::Value::ServerError(err) => Err(map_error_reply(err.code(), err.details())),
+        value => Ok(value),
+    }
+}
+
+/// Maps a failure to open a connection, attributing it to the endpoint it was opened to
+/// A connection attempt which timed out maps to [`FalkorDBError::NoConnection`].
+pub(crate) fn map_connect_err(
+    addr: &redis::ConnectionAddr,
+    error: redis::RedisError,
+) -> FalkorDBError {
+    if error.is_timeout() {
+        return FalkorDBError::NoConnection;
+    }
+    let reason = error.to_string();
+    match map_redis_err(error) {
+        // Connecting again would fail the same way, until the credentials are fixed
+        err @ FalkorDBError::AuthenticationFailed(_) => err,
+        _ => FalkorDBError::EndpointUnavailable {
+            endpoint: format_addr(addr),
+            reason,
+        },
+    }
+}
+
+/// Maps an error returned by the redis crate to the most specific error variant, by its kind, and the code of the error reply it carries
+pub(crate) fn map_redis_err(error: redis::RedisError) -> FalkorDBError {
+    if error.is_timeout() {
+        return FalkorDBError::SocketTimeout;
+    }
     match error.kind() {
         redis::ErrorKind::IoError
         | redis::ErrorKind::ClusterConnectionNotFound
         | redis::ErrorKind::ClusterDown
         | redis::ErrorKind::MasterDown => FalkorDBError::ConnectionDown,
-        _ => FalkorDBError::RedisError(error.to_string()),
+        redis::ErrorKind::AuthenticationFailed => {
+            FalkorDBError::AuthenticationFailed(error.to_string())
+        }
+        _ => match error.code() {
+            Some(code) => map_error_reply(code, error.detail()),
+            None => map_server_error(error.to_string()),
+        },
+    }
+}
+
+/// Returns how much time is left until the deadline, or an error if it has already passed
+pub(crate) fn time_until_deadline(deadline: Instant) -> FalkorResult<Duration> {
+    Some(deadline.saturating_duration_since(Instant::now()))
+        .filter(|remaining| !remaining.is_zero())
+        .ok_or(FalkorDBError::DeadlineExceeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_server_error() {
+        assert!(matches!(
+            map_server_error("Query's mem consumption exceeded capacity".to_string()),
+            FalkorDBError::QueryMemoryLimitExceeded(_)
+        ));
+        assert!(matches!(
+            map_server_error("Query timed out".to_string()),
+            FalkorDBError::QueryTimedOut { .. }
+        ));
+        assert!(matches!(
+            map_server_error("unique constraint violation on node of type Person".to_string()),
+            FalkorDBError::ConstraintViolation(_)
+        ));
+        assert!(matches!(
+            map_server_error("Unknown function 'foo'".to_string()),
+            FalkorDBError::RedisError(_)
+        ));
+    }
+
+    #[test]
+    fn test_map_syntax_error() {
+        assert_eq!(
+            map_server_error(
+                "errMsg: Invalid input 'n': expected MATCH line: 1, column: 1, offset: 0 errCtx: not a query errCtxOffset: 0"
+                    .to_string()
+            ),
+            FalkorDBError::SyntaxError {
+                message: "errMsg: Invalid input 'n': expected MATCH line: 1, column: 1, offset: 0 errCtx: not a query errCtxOffset: 0".to_string(),
+                offset: Some(0),
+            }
+        );
+        assert!(matches!(
+            map_server_error("Syntax error at offset 17 near 'RETRUN'".to_string()),
+            FalkorDBError::SyntaxError {
+                offset: Some(17),
+                ..
+            }
+        ));
+        assert!(matches!(
+            map_server_error("Invalid input".to_string()),
+            FalkorDBError::SyntaxError { offset: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_map_error_replies() {
+        let reply = |line: &str| {
+            redis::parse_redis_value(format!("-{line}\r\n").as_bytes())
+                .expect("Could not parse error reply")
+        };
+        assert!(matches!(
+            reject_server_error(reply("Query timed out")),
+            Err(FalkorDBError::QueryTimedOut { message, timeout: None }) if message == "Query timed out"
+        ));
+        assert!(matches!(
+            reject_server_error(reply(
+                "errMsg: Invalid input 'n' line: 1, column: 1, offset: 0"
+            )),
+            Err(FalkorDBError::SyntaxError {
+                offset: Some(0),
+                ..
+            })
+        ));
+        assert!(matches!(
+            reject_server_error(reply("ERR Unknown function 'foo'")),
+            Err(FalkorDBError::RedisError(message)) if message == "Unknown function 'foo'"
+        ));
+        assert!(matches!(
+            reply("Query timed out").extract_error().map_err(map_redis_err),
+            Err(FalkorDBError::QueryTimedOut { message, .. }) if message == "Query timed out"
+        ));
+        assert_eq!(
+            reject_server_error(redis::Value::Okay),
+            Ok(redis::Value::Okay)
+        );
+    }
+
+    #[test]
+    fn test_map_redis_err() {
+        assert_eq!(
+            map_redis_err(std::io::Error::new(std::io::ErrorKind::WouldBlock, "timed out").into()),
+            FalkorDBError::SocketTimeout
+        );
+        assert_eq!(
+            map_redis_err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "broken").into()),
+            FalkorDBError::ConnectionDown
+        );
+    }
+
+    #[test]
+    fn test_map_connect_err() {
+        let addr = redis::ConnectionAddr::Tcp("10.0.0.1".to_string(), 6379);
+        assert_eq!(
+            map_connect_err(
+                &addr,
+                std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out").into()
+            ),
+            FalkorDBError::NoConnection
+        );
+        assert!(matches!(
+            map_connect_err(
+                &addr,
+                std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused").into()
+            ),
+            FalkorDBError::EndpointUnavailable { endpoint, .. } if endpoint == "10.0.0.1:6379"
+        ));
+        assert!(matches!(
+            map_connect_err(
+                &addr,
+                (
+                    redis::ErrorKind::AuthenticationFailed,
+                    "Password authentication failed"
+                )
+                    .into()
+            ),
+            FalkorDBError::AuthenticationFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_map_error_reply_codes() {
+        let error = |line: &str| {
+            redis::parse_redis_value(format!("-{line}\r\n").as_bytes())
+                .expect("Could not parse error reply")
+                .extract_error()
+                .map_err(map_redis_err)
+        };
+        assert_eq!(
+            error("MOVED 3999 127.0.0.1:6381"),
+            Err(FalkorDBError::Redirected {
+                slot: 3999,
+                endpoint: "127.0.0.1:6381".to_string(),
+                ask: false,
+            })
+        );
+        assert!(matches!(
+            error("ASK 3999 127.0.0.1:6381"),
+            Err(FalkorDBError::Redirected { ask: true, .. })
+        ));
+        assert!(matches!(
+            error("OOM command not allowed when used memory > 'maxmemory'."),
+            Err(FalkorDBError::ServerOutOfMemory(message)) if message.starts_with("OOM command")
+        ));
+        assert!(matches!(
+            error("NOAUTH Authentication required."),
+            Err(FalkorDBError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            error("WRONGPASS invalid username-password pair or user is disabled."),
+            Err(FalkorDBError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            reject_server_error(
+                redis::parse_redis_value(b"-OOM command not allowed\r\n")
+                    .expect("Could not parse error reply")
+            ),
+            Err(FalkorDBError::ServerOutOfMemory(_))
+        ));
+    }
+
+    #[test]
+    fn test_time_until_deadline() {
+        let remaining = time_until_deadline(Instant::now() + Duration::from_secs(60))
+            .expect("Deadline should not have passed");
+        assert!(remaining > Duration::from_secs(59));
+
+        assert_eq!(
+            time_until_deadline(Instant::now()),
+            Err(FalkorDBError::DeadlineExceeded)
+        );
     }
 }
@@ -0,0 +1,257 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    connection_info::tls, FalkorConnectionInfo, FalkorDBError, FalkorResult, FalkorTlsConfig,
+};
+use std::fmt::{Debug, Formatter};
+
+/// The port FalkorDB listens on by default
+const DEFAULT_PORT: u16 = 6379;
+
+/// A typed builder for [`FalkorConnectionInfo`], see [`FalkorConnectionInfo::builder`]
+/// Allows configuring the connection programmatically, instead of formatting a connection string, only for it to be parsed again,
+/// which would require escaping credentials, and enclosing IPv6 addresses in brackets.
+#[derive(Clone)]
+pub struct FalkorConnectionInfoBuilder {
+    host: String,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+    database: u32,
+    tls: Option<FalkorTlsConfig>,
+}
+
+impl Default for FalkorConnectionInfoBuilder {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: DEFAULT_PORT,
+            username: None,
+            password: None,
+            database: 0,
+            tls: None,
+        }
+    }
+}
+
+impl Debug for FalkorConnectionInfoBuilder {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("FalkorConnectionInfoBuilder")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("database", &self.database)
+            .field("tls", &self.tls)
+            .finish()
+    }
+}
+
+impl FalkorConnectionInfoBuilder {
+    /// Sets the host to connect to, the default is `127.0.0.1`
+    ///
+    /// # Arguments
+    /// * `host`: A hostname, or an IPv4 or IPv6 address, IPv6 addresses don't need to be enclosed in brackets
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn host<H: ToString>(
+        self,
+        host: H,
+    ) -> Self {
+        let host = host.to_string();
+        Self {
+            host: host
+                .strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .map(ToString::to_string)
+                .unwrap_or(host),
+            ..self
+        }
+    }
+
+    /// Sets the port to connect to, the default is 6379
+    ///
+    /// # Arguments
+    /// * `port`: The port FalkorDB listens on
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn port(
+        self,
+        port: u16,
+    ) -> Self {
+        Self { port, ..self }
+    }
+
+    /// Sets the ACL user to authenticate as, by default the default user is used if a password is set
+    ///
+    /// # Arguments
+    /// * `username`: The name of the ACL user
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn username<U: ToString>(
+        self,
+        username: U,
+    ) -> Self {
+        Self {
+            username: Some(username.to_string()),
+            ..self
+        }
+    }
+
+    /// Sets the password to authenticate with, no special characters need to be escaped
+    ///
+    /// # Arguments
+    /// * `password`: The password of the user
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn password<P: ToString>(
+        self,
+        password: P,
+    ) -> Self {
+        Self {
+            password: Some(password.to_string()),
+            ..self
+        }
+    }
+
+    /// Selects a logical database, as the path of a connection string does, E.g. `falkor://127.0.0.1:6379/2`
+    ///
+    /// # Arguments
+    /// * `database`: The index of the database to `SELECT`, the default is 0
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn database(
+        self,
+        database: u32,
+    ) -> Self {
+        Self { database, ..self }
+    }
+
+    /// Encrypts the connection, as the `falkors://` scheme does
+    /// Only the hostname verification and root certificate options apply to the connection info,
+    /// a client certificate requires [`FalkorClientBuilder::with_tls`](crate::FalkorClientBuilder::with_tls).
+    ///
+    /// # Arguments
+    /// * `tls_config`: The [`FalkorTlsConfig`] to connect with
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn tls(
+        self,
+        tls_config: FalkorTlsConfig,
+    ) -> Self {
+        Self {
+            tls: Some(tls_config),
+            ..self
+        }
+    }
+
+    /// Builds the connection info
+    ///
+    /// # Returns
+    /// The new [`FalkorConnectionInfo`], or an error if the host is empty, or the TLS options are not supported by this build
+    pub fn build(self) -> FalkorResult<FalkorConnectionInfo> {
+        if self.host.is_empty() {
+            return Err(FalkorDBError::InvalidConnectionInfo(
+                "The host can't be empty".to_string(),
+            ));
+        }
+
+        let connection_info = redis::ConnectionInfo {
+            addr: match self.tls {
+                Some(_) => redis::ConnectionAddr::TcpTls {
+                    host: self.host,
+                    port: self.port,
+                    insecure: false,
+                    tls_params: None,
+                },
+                None => redis::ConnectionAddr::Tcp(self.host, self.port),
+            },
+            redis: redis::RedisConnectionInfo {
+                username: self.username,
+                password: self.password,
+                db: self.database.into(),
+                ..Default::default()
+            },
+        };
+
+        Ok(FalkorConnectionInfo::Redis(match self.tls {
+            Some(tls_config) => tls::apply_url_tls_config(connection_info, &tls_config)?,
+            None => connection_info,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_info_builder() {
+        let FalkorConnectionInfo::Redis(redis_info) = FalkorConnectionInfo::builder()
+            .host("[::1]")
+            .port(6380)
+            .username("app")
+            .password("p@ss/word?")
+            .database(2)
+            .build()
+            .expect("Could not build connection info")
+        else {
+            panic!("Expected a Redis connection info");
+        };
+        assert_eq!(
+            redis_info.addr,
+            redis::ConnectionAddr::Tcp("::1".to_string(), 6380)
+        );
+        assert_eq!(redis_info.redis.username.as_deref(), Some("app"));
+        assert_eq!(redis_info.redis.password.as_deref(), Some("p@ss/word?"));
+        assert_eq!(redis_info.redis.db, 2);
+
+        assert_eq!(
+            FalkorConnectionInfo::builder().build().unwrap().address(),
+            "127.0.0.1:6379"
+        );
+        assert!(matches!(
+            FalkorConnectionInfo::builder().host("").build(),
+            Err(FalkorDBError::InvalidConnectionInfo(_))
+        ));
+        assert!(
+            !format!("{:?}", FalkorConnectionInfo::builder().password("secret")).contains("secret")
+        );
+    }
+
+    #[test]
+    fn test_connection_info_builder_tls() {
+        let result = FalkorConnectionInfo::builder()
+            .host("falkor.example.com")
+            .tls(FalkorTlsConfig::new().with_hostname_verification(false))
+            .build();
+        match tls::TlsBackend::compiled() {
+            Some(_) => {
+                let Ok(FalkorConnectionInfo::Redis(redis_info)) = result else {
+                    panic!("Expected a Redis connection info");
+                };
+                assert!(matches!(
+                    redis_info.addr,
+                    redis::ConnectionAddr::TcpTls {
+                        insecure: true,
+                        port: 6379,
+                        ..
+                    }
+                ));
+            }
+            None => assert!(result.is_err()),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::FalkorResult;
+use std::fmt::{Debug, Formatter};
+
+#[cfg(feature = "tokio")]
+use crate::TransportFuture;
+
+/// The credentials a connection authenticates with, using `AUTH`
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    /// The ACL user to authenticate as, or [`None`] for the default user
+    pub username: Option<String>,
+    /// The user's password
+    pub password: String,
+}
+
+impl Credentials {
+    /// Creates new [`Credentials`] for the default user
+    ///
+    /// # Arguments
+    /// * `password`: The password of the default user
+    ///
+    /// # Returns
+    /// The new [`Credentials`]
+    pub fn new<P: ToString>(password: P) -> Self {
+        Self {
+            username: None,
+            password: password.to_string(),
+        }
+    }
+
+    /// Creates new [`Credentials`] for an ACL user
+    ///
+    /// # Arguments
+    /// * `username`: The name of the ACL user
+    /// * `password`: The user's password
+    ///
+    /// # Returns
+    /// The new [`Credentials`]
+    pub fn with_username<U: ToString, P: ToString>(
+        username: U,
+        password: P,
+    ) -> Self {
+        Self {
+            username: Some(username.to_string()),
+            password: password.to_string(),
+        }
+    }
+
+    /// The arguments of the `AUTH` command authenticating with these credentials
+    pub(crate) fn auth_args(&self) -> Vec<&str> {
+        self.username
+            .as_deref()
+            .into_iter()
+            .chain([self.password.as_str()])
+            .collect()
+    }
+}
+
+impl Debug for Credentials {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Supplies the credentials each new connection authenticates with, instead of a static password in the connection info,
+/// so the client keeps connecting after an IAM or Vault integration rotates the password.
+///
+/// The provider is invoked every time a connection is opened, including when the pool reopens a broken connection,
+/// so it should cache the current credentials, rather than fetch them on every call.
+/// Any closure returning [`FalkorResult<Credentials>`] is a provider.
+pub trait CredentialsProvider: Send + Sync {
+    /// Returns the credentials to authenticate a new connection with
+    ///
+    /// # Returns
+    /// The current [`Credentials`], or an error failing the connection attempt
+    fn credentials(&self) -> FalkorResult<Credentials>;
+
+    /// Returns the credentials to authenticate a new connection of the async client with.
+    /// By default, this calls [`CredentialsProvider::credentials`].
+    ///
+    /// # Returns
+    /// A future resolving to the current [`Credentials`]
+    #[cfg(feature = "tokio")]
+    fn credentials_async(&self) -> TransportFuture<'_, Credentials> {
+        Box::pin(async { self.credentials() })
+    }
+}
+
+impl<F: Fn() -> FalkorResult<Credentials> + Send + Sync> CredentialsProvider for F {
+    fn credentials(&self) -> FalkorResult<Credentials> {
+        self()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials() {
+        assert_eq!(Credentials::new("secret").auth_args(), vec!["secret"]);
+        assert_eq!(
+            Credentials::with_username("app", "secret").auth_args(),
+            vec!["app", "secret"]
+        );
+        assert!(!format!("{:?}", Credentials::new("secret")).contains("secret"));
+
+        let provider = || Ok(Credentials::new("rotated"));
+        assert_eq!(
+            provider
+                .credentials()
+                .map(|credentials| credentials.password),
+            Ok("rotated".to_string())
+        );
+    }
+}
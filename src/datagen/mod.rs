@@ -0,0 +1,599 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Reproducible synthetic graphs, for load testing and benchmarking applications built on top of FalkorDB.
+//!
+//! The same [`GraphGenerator`] configuration and seed always produce the same graph,
+//! the random number generator is built-in so results remain stable across dependency upgrades.
+//!
+//! ```no_run
+//! use falkordb::{
+//!     datagen::{GraphGenerator, PropertyGenerator},
+//!     FalkorClientBuilder,
+//! };
+//!
+//! let client = FalkorClientBuilder::new().build().expect("Could not create client");
+//! let graph = client.select_graph("load_test");
+//!
+//! GraphGenerator::new(42)
+//!     .with_node_count(10_000)
+//!     .with_edge_count(50_000)
+//!     .with_label("Person", 9)
+//!     .with_label("Company", 1)
+//!     .with_relationship_type("KNOWS", 1)
+//!     .with_node_property("age", PropertyGenerator::Int { min: 18, max: 90 })
+//!     .populate(&graph)
+//!     .expect("Could not populate graph");
+//! ```
+
+use crate::{
+    value::vec32::Vec32, EntityType, FalkorMap, FalkorResult, FalkorValue, IndexType, SyncGraph,
+};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "tokio")]
+use crate::AsyncGraph;
+
+/// A small, fast and deterministic pseudo-random number generator (SplitMix64)
+#[derive(Clone, Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed float in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a uniformly distributed integer in [0, bound)
+    fn below(
+        &mut self,
+        bound: u64,
+    ) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    fn weighted<'a, T>(
+        &mut self,
+        choices: &'a [(T, u32)],
+    ) -> &'a T {
+        let total: u64 = choices.iter().map(|(_, weight)| *weight as u64).sum();
+        let mut target = self.below(total.max(1));
+        for (choice, weight) in choices {
+            if target < *weight as u64 {
+                return choice;
+            }
+            target -= *weight as u64;
+        }
+        &choices[choices.len() - 1].0
+    }
+}
+
+/// Describes how to generate the values of a single property
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyGenerator {
+    /// The index of the generated entity, unique per entity type
+    Sequence,
+    /// A uniformly distributed integer, both bounds are inclusive
+    Int {
+        /// The minimal value
+        min: i64,
+        /// The maximal value
+        max: i64,
+    },
+    /// A uniformly distributed float, in the range [min, max)
+    Float {
+        /// The minimal value
+        min: f64,
+        /// The upper bound
+        max: f64,
+    },
+    /// A boolean which is true with the specified probability
+    Bool {
+        /// The probability of generating `true`, between 0 and 1
+        probability: f64,
+    },
+    /// One of the provided values, chosen uniformly
+    Choice(Vec<FalkorValue>),
+    /// A random lowercase alphanumeric string of the specified length
+    String {
+        /// The string length
+        length: usize,
+    },
+    /// A random vector of floats in [-1, 1), suitable for vector indices
+    Vector {
+        /// The vector length
+        dimension: usize,
+    },
+}
+
+impl PropertyGenerator {
+    fn generate(
+        &self,
+        index: usize,
+        rng: &mut SplitMix64,
+    ) -> FalkorValue {
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+        match self {
+            PropertyGenerator::Sequence => FalkorValue::I64(index as i64),
+            PropertyGenerator::Int { min, max } => FalkorValue::I64(
+                (*min.min(max))
+                    .wrapping_add(rng.below(max.abs_diff(*min).saturating_add(1)) as i64),
+            ),
+            PropertyGenerator::Float { min, max } => {
+                FalkorValue::F64(min + rng.next_f64() * (max - min))
+            }
+            PropertyGenerator::Bool { probability } => {
+                FalkorValue::Bool(rng.next_f64() < *probability)
+            }
+            PropertyGenerator::Choice(choices) => match choices.is_empty() {
+                true => FalkorValue::None,
+                false => choices[rng.below(choices.len() as u64) as usize].clone(),
+            },
+            PropertyGenerator::String { length } => FalkorValue::String(
+                (0..*length)
+                    .map(|_| ALPHABET[rng.below(ALPHABET.len() as u64) as usize] as char)
+                    .collect(),
+            ),
+            PropertyGenerator::Vector { dimension } => FalkorValue::Vec32(Vec32 {
+                values: (0..*dimension)
+                    .map(|_| (rng.next_f64() * 2.0 - 1.0) as f32)
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// A generated node
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedNode {
+    /// The label of this node
+    pub label: String,
+    /// The properties of this node, including the id property
+    pub properties: FalkorMap,
+}
+
+/// A generated relationship, referencing its endpoints by their index in [`GeneratedGraph::nodes`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedEdge {
+    /// The relationship type
+    pub relationship_type: String,
+    /// The index of the source node
+    pub src: usize,
+    /// The index of the destination node
+    pub dst: usize,
+    /// The properties of this relationship
+    pub properties: FalkorMap,
+}
+
+/// The output of [`GraphGenerator::generate`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeneratedGraph {
+    /// The generated nodes, in creation order
+    pub nodes: Vec<GeneratedNode>,
+    /// The generated relationships, in creation order
+    pub edges: Vec<GeneratedEdge>,
+}
+
+/// A Builder-pattern struct describing a reproducible synthetic graph
+#[derive(Clone, Debug)]
+pub struct GraphGenerator {
+    seed: u64,
+    node_count: usize,
+    edge_count: usize,
+    batch_size: usize,
+    id_property: String,
+    labels: Vec<(String, u32)>,
+    relationship_types: Vec<(String, u32)>,
+    node_properties: Vec<(String, PropertyGenerator)>,
+    edge_properties: Vec<(String, PropertyGenerator)>,
+}
+
+impl GraphGenerator {
+    /// Creates a new generator with the specified seed, producing 100 `Node` nodes, 0 relationships, and no extra properties
+    ///
+    /// # Arguments
+    /// * `seed`: The seed for the random number generator, the same seed always produces the same graph
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            node_count: 100,
+            edge_count: 0,
+            batch_size: 1000,
+            id_property: "id".to_string(),
+            labels: vec![],
+            relationship_types: vec![],
+            node_properties: vec![],
+            edge_properties: vec![],
+        }
+    }
+
+    /// Sets the number of nodes to generate
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_node_count(
+        self,
+        node_count: usize,
+    ) -> Self {
+        Self { node_count, ..self }
+    }
+
+    /// Sets the number of relationships to generate, endpoints are chosen uniformly among the generated nodes
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_edge_count(
+        self,
+        edge_count: usize,
+    ) -> Self {
+        Self { edge_count, ..self }
+    }
+
+    /// Sets how many entities are created per query when populating a graph, defaults to 1000
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_batch_size(
+        self,
+        batch_size: usize,
+    ) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the name of the property holding each node's unique index, defaults to `id`
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_id_property(
+        self,
+        id_property: &str,
+    ) -> Self {
+        Self {
+            id_property: id_property.to_string(),
+            ..self
+        }
+    }
+
+    /// Adds a node label, nodes are assigned labels randomly, proportionally to their weights
+    /// If no labels are added, all nodes will be labeled `Node`
+    ///
+    /// # Arguments
+    /// * `label`: The label name
+    /// * `weight`: The relative weight of this label
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_label(
+        mut self,
+        label: &str,
+        weight: u32,
+    ) -> Self {
+        self.labels.push((label.to_string(), weight));
+        self
+    }
+
+    /// Adds a relationship type, relationships are assigned types randomly, proportionally to their weights
+    /// If no types are added, all relationships will have the type `EDGE`
+    ///
+    /// # Arguments
+    /// * `relationship_type`: The relationship type name
+    /// * `weight`: The relative weight of this type
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_relationship_type(
+        mut self,
+        relationship_type: &str,
+        weight: u32,
+    ) -> Self {
+        self.relationship_types
+            .push((relationship_type.to_string(), weight));
+        self
+    }
+
+    /// Adds a property to every generated node
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_node_property(
+        mut self,
+        name: &str,
+        generator: PropertyGenerator,
+    ) -> Self {
+        self.node_properties.push((name.to_string(), generator));
+        self
+    }
+
+    /// Adds a property to every generated relationship
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_edge_property(
+        mut self,
+        name: &str,
+        generator: PropertyGenerator,
+    ) -> Self {
+        self.edge_properties.push((name.to_string(), generator));
+        self
+    }
+
+    /// Generates the graph in memory, without touching the database
+    ///
+    /// # Returns
+    /// The [`GeneratedGraph`]
+    pub fn generate(&self) -> GeneratedGraph {
+        let mut rng = SplitMix64(self.seed);
+        let labels = match self.labels.is_empty() {
+            true => vec![("Node".to_string(), 1)],
+            false => self.labels.clone(),
+        };
+        let relationship_types = match self.relationship_types.is_empty() {
+            true => vec![("EDGE".to_string(), 1)],
+            false => self.relationship_types.clone(),
+        };
+
+        let nodes = (0..self.node_count)
+            .map(|index| {
+                let label = rng.weighted(&labels).clone();
+                let mut properties = FalkorMap::new();
+                properties.insert(self.id_property.clone(), FalkorValue::I64(index as i64));
+                for (name, generator) in &self.node_properties {
+                    properties.insert(name.clone(), generator.generate(index, &mut rng));
+                }
+                GeneratedNode { label, properties }
+            })
+            .collect();
+
+        let edges = match self.node_count {
+            0 => vec![],
+            node_count => (0..self.edge_count)
+                .map(|index| {
+                    let relationship_type = rng.weighted(&relationship_types).clone();
+                    let src = rng.below(node_count as u64) as usize;
+                    let dst = rng.below(node_count as u64) as usize;
+                    let properties = self
+                        .edge_properties
+                        .iter()
+                        .map(|(name, generator)| {
+                            (name.clone(), generator.generate(index, &mut rng))
+                        })
+                        .collect();
+                    GeneratedEdge {
+                        relationship_type,
+                        src,
+                        dst,
+                        properties,
+                    }
+                })
+                .collect(),
+        };
+
+        GeneratedGraph { nodes, edges }
+    }
+
+    /// Generates the Cypher queries that create this graph, in the order they should be executed
+    ///
+    /// # Returns
+    /// A [`Vec`] of queries, each creating up to `batch_size` entities
+    pub fn queries(&self) -> FalkorResult<Vec<String>> {
+        let graph = self.generate();
+        let id_property = escape_name(&self.id_property);
+        let mut queries = vec![];
+
+        let mut nodes_by_label: BTreeMap<&str, Vec<&GeneratedNode>> = BTreeMap::new();
+        for node in &graph.nodes {
+            nodes_by_label.entry(&node.label).or_default().push(node);
+        }
+        for (label, nodes) in nodes_by_label {
+            for batch in nodes.chunks(self.batch_size) {
+                let rows = batch
+                    .iter()
+                    .map(|node| FalkorValue::Map(node.properties.clone()).to_cypher_literal())
+                    .collect::<FalkorResult<Vec<_>>>()?;
+                queries.push(format!(
+                    "UNWIND [{}] AS row CREATE (n:{}) SET n = row",
+                    rows.join(", "),
+                    escape_name(label)
+                ));
+            }
+        }
+
+        let mut edges_by_pattern: BTreeMap<(&str, &str, &str), Vec<&GeneratedEdge>> =
+            BTreeMap::new();
+        for edge in &graph.edges {
+            edges_by_pattern
+                .entry((
+                    &graph.nodes[edge.src].label,
+                    &edge.relationship_type,
+                    &graph.nodes[edge.dst].label,
+                ))
+                .or_default()
+                .push(edge);
+        }
+        for ((src_label, relationship_type, dst_label), edges) in edges_by_pattern {
+            for batch in edges.chunks(self.batch_size) {
+                let rows = batch
+                    .iter()
+                    .map(|edge| {
+                        FalkorValue::Map(edge.properties.clone())
+                            .to_cypher_literal()
+                            .map(|properties| format!("[{}, {}, {properties}]", edge.src, edge.dst))
+                    })
+                    .collect::<FalkorResult<Vec<_>>>()?;
+                queries.push(format!(
+                    "UNWIND [{}] AS e MATCH (a:{} {{{id_property}: e[0]}}), (b:{} {{{id_property}: e[1]}}) CREATE (a)-[r:{}]->(b) SET r = e[2]",
+                    rows.join(", "),
+                    escape_name(src_label),
+                    escape_name(dst_label),
+                    escape_name(relationship_type)
+                ));
+            }
+        }
+
+        Ok(queries)
+    }
+
+    fn index_labels(&self) -> Vec<String> {
+        match self.labels.is_empty() {
+            true => vec!["Node".to_string()],
+            false => self.labels.iter().map(|(label, _)| label.clone()).collect(),
+        }
+    }
+
+    /// Creates the generated graph in the database.
+    /// A range index is created on the id property of each label, so relationships can be connected efficiently,
+    /// this is expected to be called on an empty graph.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to populate
+    pub fn populate(
+        &self,
+        graph: &SyncGraph,
+    ) -> FalkorResult<()> {
+        let queries = self.queries()?;
+        for label in self.index_labels() {
+            graph.create_index(
+                IndexType::Range,
+                EntityType::Node,
+                &escape_name(&label),
+                &[escape_name(&self.id_property)],
+                None,
+            )?;
+        }
+        for query in queries {
+            graph.query(query).execute()?;
+        }
+        Ok(())
+    }
+
+    /// Creates the generated graph in the database, see [`GraphGenerator::populate`]
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to populate
+    #[cfg(feature = "tokio")]
+    pub async fn populate_async(
+        &self,
+        graph: &AsyncGraph,
+    ) -> FalkorResult<()> {
+        let queries = self.queries()?;
+        for label in self.index_labels() {
+            graph
+                .create_index(
+                    IndexType::Range,
+                    EntityType::Node,
+                    &escape_name(&label),
+                    &[escape_name(&self.id_property)],
+                    None,
+                )
+                .await?;
+        }
+        for query in queries {
+            graph.query(query).execute().await?;
+        }
+        Ok(())
+    }
+}
+
+fn escape_name(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_generator(seed: u64) -> GraphGenerator {
+        GraphGenerator::new(seed)
+            .with_node_count(200)
+            .with_edge_count(300)
+            .with_label("Person", 3)
+            .with_label("Company", 1)
+            .with_relationship_type("KNOWS", 1)
+            .with_node_property("age", PropertyGenerator::Int { min: 18, max: 90 })
+            .with_node_property("name", PropertyGenerator::String { length: 8 })
+            .with_edge_property("since", PropertyGenerator::Sequence)
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        assert_eq!(test_generator(7).generate(), test_generator(7).generate());
+        assert_ne!(test_generator(7).generate(), test_generator(8).generate());
+    }
+
+    #[test]
+    fn test_generate_respects_config() {
+        let graph = test_generator(1).generate();
+        assert_eq!(graph.nodes.len(), 200);
+        assert_eq!(graph.edges.len(), 300);
+
+        let people = graph
+            .nodes
+            .iter()
+            .filter(|node| node.label == "Person")
+            .count();
+        assert!((100..200).contains(&people));
+
+        for (index, node) in graph.nodes.iter().enumerate() {
+            assert_eq!(node.properties["id"], FalkorValue::I64(index as i64));
+            let age = node.properties["age"].to_i64().unwrap();
+            assert!((18..=90).contains(&age));
+            assert_eq!(node.properties["name"].as_string().unwrap().len(), 8);
+        }
+        for edge in &graph.edges {
+            assert!(edge.src < 200 && edge.dst < 200);
+            assert_eq!(edge.relationship_type, "KNOWS");
+        }
+    }
+
+    #[test]
+    fn test_queries() {
+        let queries = GraphGenerator::new(0)
+            .with_node_count(3)
+            .with_edge_count(1)
+            .with_batch_size(2)
+            .queries()
+            .unwrap();
+
+        assert_eq!(queries.len(), 3);
+        assert_eq!(
+            queries[0],
+            "UNWIND [{`id`: 0}, {`id`: 1}] AS row CREATE (n:`Node`) SET n = row"
+        );
+        assert_eq!(
+            queries[1],
+            "UNWIND [{`id`: 2}] AS row CREATE (n:`Node`) SET n = row"
+        );
+        assert!(queries[2].starts_with("UNWIND [["));
+        assert!(queries[2].ends_with(
+            "AS e MATCH (a:`Node` {`id`: e[0]}), (b:`Node` {`id`: e[1]}) CREATE (a)-[r:`EDGE`]->(b) SET r = e[2]"
+        ));
+    }
+
+    #[test]
+    fn test_populate() {
+        let graph = crate::test_utils::open_empty_test_graph("test_datagen_populate");
+        test_generator(3)
+            .populate(&graph.inner)
+            .expect("Could not populate graph");
+
+        let mut res = graph
+            .inner
+            .query("MATCH (n) RETURN count(n)")
+            .execute()
+            .expect("Could not count nodes");
+        assert_eq!(res.data.next(), Some(vec![FalkorValue::I64(200)]));
+    }
+}
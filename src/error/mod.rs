@@ -3,11 +3,15 @@
  * Licensed under the MIT License.
  */
 
-use crate::SchemaType;
+use crate::{redact_query_literals, SchemaType, TypeCoercion, WarmUpReport};
+use std::{convert::Infallible, time::Duration};
+
+/// The longest query carried by [`FalkorDBError::QueryFailed`], longer ones are truncated
+const MAX_CONTEXT_QUERY_LEN: usize = 1024;
 
 /// A verbose error enum used throughout the client, messages are static string slices.
 /// this allows easy error integration using [`thiserror`]
-#[derive(thiserror::Error, Debug, PartialEq)]
+#[derive(thiserror::Error, Debug, PartialEq, strum::IntoStaticStr)]
 pub enum FalkorDBError {
     /// A required ID for parsing was not found in the schema.
     #[error("A required Id for parsing was not found in the schema")]
@@ -32,6 +36,14 @@ pub enum FalkorDBError {
     /// The provided connection info is invalid.
     #[error("Could not parse the provided connection info: {0}")]
     InvalidConnectionInfo(String),
+    /// The provided connection string could not be parsed, E.g. it has an unsupported scheme, or an invalid port.
+    #[error("Could not parse the connection string {url}: {reason}")]
+    InvalidConnectionUrl {
+        /// The connection string, with its password redacted
+        url: String,
+        /// Why it could not be parsed
+        reason: String,
+    },
     /// The connection returned invalid data for this command.
     #[error("The connection returned invalid data for this command")]
     InvalidDataReceived,
@@ -124,6 +136,296 @@ pub enum FalkorDBError {
     /// No runtime detected, you are trying to run an async operation from a sync context
     #[error("No runtime detected, you are trying to run an async operation from a sync context")]
     NoRuntime,
+    /// The provided value can not be used as a query parameter.
+    #[error("The provided value can not be used as a query parameter: {0}")]
+    InvalidParameter(String),
+    /// The requested column does not exist in the result set.
+    #[error("The requested column does not exist in the result set: {0}")]
+    MissingColumn(String),
+    /// The graph lock could not be acquired before the timeout passed.
+    #[error("The graph lock could not be acquired before the timeout passed")]
+    LockTimeout,
+    /// The index did not become operational before the timeout passed.
+    #[error("The index did not become operational before the timeout passed")]
+    IndexWaitTimeout,
+    /// The constraint could not be applied, as not all entities comply with it.
+    #[error("The constraint could not be applied, as not all entities comply with it: {0}")]
+    ConstraintFailed(String),
+    /// The constraint did not become operational before the timeout passed.
+    #[error("The constraint did not become operational before the timeout passed")]
+    ConstraintWaitTimeout,
+    /// The operation's deadline passed before it completed.
+    #[error("The operation's deadline passed before it completed")]
+    DeadlineExceeded,
+    /// The schema snapshot was taken from a different graph.
+    #[error("The schema snapshot was taken from a different graph: {0}")]
+    SchemaSnapshotMismatch(String),
+    /// The columns yielded by a procedure do not match the requested type.
+    #[error("The procedure's YIELD columns do not match the requested type: {0}")]
+    YieldColumnMismatch(String),
+    /// The graph fixtures are inconsistent, and can't be seeded.
+    #[error("The graph fixtures are invalid: {0}")]
+    InvalidFixtures(String),
+    /// The TLS options can't be satisfied by the compiled TLS backend, or the connection.
+    #[error("Invalid TLS configuration: {0}")]
+    InvalidTlsConfig(String),
+    /// The client name can't be set using `CLIENT SETNAME`, as it is empty, or contains spaces, newlines or other special characters.
+    #[error("Invalid client name: {0:?}")]
+    InvalidClientName(String),
+    /// The client owns a single connection, see [`FalkorClientBuilder::with_single_connection`](crate::FalkorClientBuilder::with_single_connection), so its pool can't be resized.
+    #[error("The client owns a single connection, its pool can't be resized")]
+    SingleConnectionMode,
+    /// The client was closed, and no longer hands out connections, see [`FalkorSyncClient::close`](crate::FalkorSyncClient::close).
+    #[error("The client was closed")]
+    ClientClosed,
+    /// A new connection could not authenticate with the credentials supplied by the [`CredentialsProvider`](crate::CredentialsProvider),
+    /// or the server rejected a command, as the connection is not, or no longer authenticated, E.g. after its ACL user was changed.
+    #[error("Could not authenticate a new connection: {0}")]
+    AuthenticationFailed(String),
+    /// The query was aborted by the server, as it exceeded the memory limit set for queries.
+    #[error("The query exceeded the memory limit set for queries: {0}")]
+    QueryMemoryLimitExceeded(String),
+    /// The query was aborted by the server, as it ran longer than its timeout, see [`QueryBuilder::with_timeout`](crate::QueryBuilder::with_timeout).
+    #[error("The query timed out{}: {message}", timeout.map(|timeout| format!(" after {timeout:?}")).unwrap_or_default())]
+    QueryTimedOut {
+        /// The error message returned by the server
+        message: String,
+        /// The timeout the query was sent with, or [`None`] if the server's default timeout applied
+        timeout: Option<Duration>,
+    },
+    /// The server could not parse the query.
+    #[error("Syntax error in query: {message}")]
+    SyntaxError {
+        /// The error message returned by the server
+        message: String,
+        /// The offset in the query at which parsing failed, if the server reported it
+        offset: Option<usize>,
+    },
+    /// The query was rejected by the server, as it would violate a constraint, E.g. a unique or mandatory property.
+    #[error("The query violated a constraint: {0}")]
+    ConstraintViolation(String),
+    /// The server rejected the command, as it reached its `maxmemory` limit, so commands which may use more memory are refused until memory is freed.
+    #[error("The server is out of memory: {0}")]
+    ServerOutOfMemory(String),
+    /// A cluster node redirected the command to the node serving its hash slot, using a `MOVED` or `ASK` reply.
+    #[error("The command was redirected to {endpoint}, which serves slot {slot}")]
+    Redirected {
+        /// The hash slot of the command's key
+        slot: u16,
+        /// The address of the node to send the command to
+        endpoint: String,
+        /// Whether the slot is being migrated, so only this command should be sent to the other node (`ASK`),
+        /// rather than every command for the slot (`MOVED`)
+        ask: bool,
+    },
+    /// The server failed a query, this carries the query alongside the server's error, see [`FalkorDBError::without_context`] to match on the latter.
+    #[error("{source}, in query: {query}")]
+    QueryFailed {
+        /// The failed query, truncated if it is long, and with its literals redacted if log redaction is enabled,
+        /// see [`FalkorClientBuilder::with_log_redaction`](crate::FalkorClientBuilder::with_log_redaction)
+        query: String,
+        /// The offset in the query at which parsing failed, for syntax errors reporting it
+        offset: Option<usize>,
+        /// The error returned by the server
+        source: Box<FalkorDBError>,
+    },
+    /// All pooled connections were in use, and none was returned before the operation's deadline.
+    /// This indicates the pool is too small for the load, rather than a problem with the server.
+    #[error("All pooled connections were in use, and none was returned within {waited:?}")]
+    PoolExhausted {
+        /// How long the operation waited for a connection
+        waited: Duration,
+    },
+    /// The graph's circuit breaker is open after its queries failed repeatedly, so the query was not sent,
+    /// see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker).
+    #[error("The circuit breaker of graph {graph} is open, retry in {retry_in:?}")]
+    GraphCircuitOpen {
+        /// The name of the graph
+        graph: String,
+        /// How long until a trial query is let through
+        retry_in: Duration,
+    },
+    /// The connection circuit breaker is open after connecting to the server failed repeatedly, so the command was not sent,
+    /// see [`FalkorClientBuilder::with_connection_circuit_breaker`](crate::FalkorClientBuilder::with_connection_circuit_breaker).
+    #[error("The connection circuit breaker is open, retry in {retry_in:?}")]
+    ConnectionCircuitOpen {
+        /// How long until a trial command is let through
+        retry_in: Duration,
+    },
+    /// The command exceeded the client's rate limit, so it was not sent,
+    /// see [`FalkorClientBuilder::with_rate_limit`](crate::FalkorClientBuilder::with_rate_limit).
+    #[error("The command was throttled, the client allows {0}")]
+    Throttled(String),
+    /// No query is registered under the provided name, see [`QueryRegistry`](crate::QueryRegistry).
+    #[error("No query is registered under the name {0}")]
+    UnregisteredQuery(String),
+    /// The provided parameters do not match the ones referenced by the registered query.
+    #[error("The parameters of query {name} do not match its template, missing: {missing:?}, unexpected: {unexpected:?}")]
+    QueryParamsMismatch {
+        /// The name of the registered query
+        name: String,
+        /// The parameters the query references, which were not provided
+        missing: Vec<String>,
+        /// The provided parameters which the query does not reference
+        unexpected: Vec<String>,
+    },
+    /// All pooled connections were in use, and none was returned within the pool's acquisition timeout,
+    /// see [`FalkorClientBuilder::with_pool_timeout`](crate::FalkorClientBuilder::with_pool_timeout).
+    #[error("All pooled connections were in use, and none was returned within the acquisition timeout of {timeout:?}")]
+    PoolTimeout {
+        /// The configured acquisition timeout
+        timeout: Duration,
+    },
+    /// Some pooled connections could not be opened or validated while warming up the pool,
+    /// see [`FalkorSyncClient::warm_up`](crate::FalkorSyncClient::warm_up).
+    #[error("Could not warm up the connection pool, {0}")]
+    WarmUpFailed(WarmUpReport),
+    /// A connection to a specific endpoint could not be established.
+    #[error("Could not connect to {endpoint}: {reason}")]
+    EndpointUnavailable {
+        /// The address of the endpoint
+        endpoint: String,
+        /// The reason the connection failed
+        reason: String,
+    },
+    /// The graph lock expired while it was held, so it may have already been acquired by someone else.
+    #[error("The graph lock expired while it was held")]
+    LockLost,
+    /// The provided migrations are invalid.
+    #[error("The provided migrations are invalid: {0}")]
+    InvalidMigrations(String),
+    /// The graph's schema version is newer than the latest known migration, E.g. it was migrated by a newer release.
+    #[error(
+        "The graph is at schema version {found}, while the latest known migration is {expected}"
+    )]
+    SchemaVersionMismatch {
+        /// The version of the latest known migration
+        expected: u64,
+        /// The graph's schema version
+        found: u64,
+    },
+    /// The server did not respond before the socket timeout passed, see [`FalkorClientBuilder::with_read_timeout`](crate::FalkorClientBuilder::with_read_timeout).
+    #[error("The server did not respond before the socket timeout passed")]
+    SocketTimeout,
+    /// A retried operation failed on its last attempt, after its earlier attempts failed on connection errors.
+    #[error("The operation failed after {attempts} attempts: {last_error}")]
+    RetriesFailed {
+        /// How many times the operation was attempted
+        attempts: usize,
+        /// The error of the last attempt
+        last_error: Box<FalkorDBError>,
+    },
+    /// The server stored a property with a different type than the one written, see [`FalkorClientBuilder::with_strict_typing`](crate::FalkorClientBuilder::with_strict_typing).
+    #[error("The server coerced property {property}, {coercion}")]
+    TypeCoerced {
+        /// The name of the property
+        property: String,
+        /// The first coerced value found, see [`find_type_coercions`](crate::find_type_coercions)
+        coercion: TypeCoercion,
+    },
+    /// An environment variable read by [`FalkorClientBuilder::from_env`](crate::FalkorClientBuilder::from_env) holds an invalid value.
+    #[error("Invalid value for the {name} environment variable: {reason}")]
+    InvalidEnvironmentVariable {
+        /// The name of the environment variable
+        name: String,
+        /// Why the value could not be applied
+        reason: String,
+    },
+}
+
+impl FalkorDBError {
+    /// Returns the underlying error, without the context added to it, such as the query of [`FalkorDBError::QueryFailed`]
+    ///
+    /// # Returns
+    /// The underlying error, or self if there is no context
+    pub fn without_context(&self) -> &FalkorDBError {
+        match self {
+            FalkorDBError::QueryFailed { source, .. } => source.without_context(),
+            err => err,
+        }
+    }
+
+    /// Attaches the timeout a query was sent with to [`FalkorDBError::QueryTimedOut`], other errors are left as-is
+    pub(crate) fn with_query_timeout(
+        self,
+        timeout: Option<Duration>,
+    ) -> Self {
+        match self {
+            FalkorDBError::QueryTimedOut {
+                message,
+                timeout: None,
+            } => FalkorDBError::QueryTimedOut { message, timeout },
+            err => err,
+        }
+    }
+
+    /// Attaches the failed query to an error returned by the server, other errors, such as connection errors, are left as-is
+    pub(crate) fn with_query_context(
+        self,
+        query: &str,
+        redact: bool,
+    ) -> Self {
+        let offset = match &self {
+            FalkorDBError::SyntaxError { offset, .. } => *offset,
+            FalkorDBError::RedisError(_)
+            | FalkorDBError::QueryMemoryLimitExceeded(_)
+            | FalkorDBError::QueryTimedOut { .. }
+            | FalkorDBError::ConstraintViolation(_)
+            | FalkorDBError::ServerOutOfMemory(_) => None,
+            _ => return self,
+        };
+
+        let query = match redact {
+            true => redact_query_literals(query),
+            false => query.to_string(),
+        };
+        FalkorDBError::QueryFailed {
+            query: match query.char_indices().nth(MAX_CONTEXT_QUERY_LEN) {
+                Some((end, _)) => format!("{}...", &query[..end]),
+                None => query,
+            },
+            offset,
+            source: Box::new(self),
+        }
+    }
+
+    /// Whether the error is transient, so the operation may succeed if it is retried over a new connection,
+    /// E.g. a connection reset, or a failover in progress, as opposed to a permanent error, such as a syntax error
+    ///
+    /// # Returns
+    /// True if retrying the operation may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.without_context(),
+            FalkorDBError::ConnectionDown
+                | FalkorDBError::NoConnection
+                | FalkorDBError::EndpointUnavailable { .. }
+        )
+    }
+
+    /// Whether the error means an operation ran out of time, be it on the server, on the socket, or waiting on the client
+    ///
+    /// # Returns
+    /// True if the operation timed out
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self.without_context(),
+            FalkorDBError::QueryTimedOut { .. }
+                | FalkorDBError::SocketTimeout
+                | FalkorDBError::DeadlineExceeded
+                | FalkorDBError::PoolExhausted { .. }
+                | FalkorDBError::PoolTimeout { .. }
+                | FalkorDBError::LockTimeout
+                | FalkorDBError::IndexWaitTimeout
+                | FalkorDBError::ConstraintWaitTimeout
+        )
+    }
+}
+
+impl From<Infallible> for FalkorDBError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
 }
 
 impl From<strum::ParseError> for FalkorDBError {
@@ -131,3 +433,77 @@ impl From<strum::ParseError> for FalkorDBError {
         FalkorDBError::InvalidEnumType(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        assert!(FalkorDBError::ConnectionDown.is_retryable());
+        assert!(FalkorDBError::EndpointUnavailable {
+            endpoint: "127.0.0.1:6379".to_string(),
+            reason: "Connection refused".to_string(),
+        }
+        .is_retryable());
+        assert!(!FalkorDBError::SyntaxError {
+            message: "Invalid input".to_string(),
+            offset: None,
+        }
+        .is_retryable());
+
+        assert!(FalkorDBError::QueryTimedOut {
+            message: "Query timed out".to_string(),
+            timeout: None,
+        }
+        .is_timeout());
+        assert!(FalkorDBError::SocketTimeout.is_timeout());
+        assert!(!FalkorDBError::SocketTimeout.is_retryable());
+        assert!(!FalkorDBError::ConnectionDown.is_timeout());
+    }
+
+    #[test]
+    fn test_query_context() {
+        let err = FalkorDBError::SyntaxError {
+            message: "Invalid input".to_string(),
+            offset: Some(14),
+        }
+        .with_query_context("MATCH (n {name: 'Alice'}) RETRUN n", true);
+        assert!(matches!(
+            &err,
+            FalkorDBError::QueryFailed { query, offset: Some(14), .. }
+                if query == "MATCH (n {name: ?}) RETRUN n"
+        ));
+        assert!(matches!(
+            err.without_context(),
+            FalkorDBError::SyntaxError { .. }
+        ));
+
+        let err = FalkorDBError::QueryTimedOut {
+            message: "Query timed out".to_string(),
+            timeout: None,
+        }
+        .with_query_context(&"a".repeat(MAX_CONTEXT_QUERY_LEN + 1), false);
+        assert!(err.is_timeout());
+        assert!(matches!(
+            &err,
+            FalkorDBError::QueryFailed { query, .. } if query.len() == MAX_CONTEXT_QUERY_LEN + 3
+        ));
+
+        assert_eq!(
+            FalkorDBError::QueryTimedOut {
+                message: "Query timed out".to_string(),
+                timeout: None,
+            }
+            .with_query_timeout(Some(Duration::from_millis(500)))
+            .to_string(),
+            "The query timed out after 500ms: Query timed out"
+        );
+
+        // Errors which are not attributable to the query are left as-is
+        assert_eq!(
+            FalkorDBError::ConnectionDown.with_query_context("RETURN 1", false),
+            FalkorDBError::ConnectionDown
+        );
+    }
+}
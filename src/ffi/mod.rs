@@ -0,0 +1,608 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! A C-compatible API, allowing this crate to serve as the backbone for bindings in languages without a native FalkorDB client.
+//!
+//! All objects handed out are opaque pointers, owned by the caller, which must be released using the matching `*_free` function.
+//! Functions that can fail return a null pointer (or a negative value), and the error message can be retrieved using [`falkordb_last_error`].
+//!
+//! To produce a shared or static library, build this crate with the `ffi` feature enabled, E.g.:
+//! `cargo rustc --release --features ffi --crate-type cdylib`
+
+use crate::{FalkorClientBuilder, FalkorConnectionInfo, FalkorSyncClient, FalkorValue};
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, c_int, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error<E: ToString>(err: E) {
+    let message = CString::new(err.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = None);
+}
+
+unsafe fn str_from_ptr<'a>(
+    value: *const c_char,
+    arg_name: &str,
+) -> Option<&'a str> {
+    if value.is_null() {
+        set_last_error(format!("Argument '{arg_name}' must not be null"));
+        return None;
+    }
+
+    match CStr::from_ptr(value).to_str() {
+        Ok(as_str) => Some(as_str),
+        Err(_) => {
+            set_last_error(format!("Argument '{arg_name}' is not valid UTF-8"));
+            None
+        }
+    }
+}
+
+/// Runs the body of an exported function, so a panic never unwinds into the C caller, which would be undefined behavior.
+/// A panic is reported through [`falkordb_last_error`], returning `on_panic` instead.
+fn catch_panic<T, F: FnOnce() -> T>(
+    on_panic: T,
+    body: F,
+) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload: Box<dyn Any + Send>| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string());
+        set_last_error(format!("Internal error: {message}"));
+        on_panic
+    })
+}
+
+/// Converts a count to a C int, or -1 if it does not fit in one
+fn count_as_c_int(count: usize) -> c_int {
+    c_int::try_from(count).unwrap_or(-1)
+}
+
+fn string_into_raw(value: String) -> *mut c_char {
+    CString::new(value.replace('\0', ""))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// An opaque handle to a [`FalkorSyncClient`]
+pub struct FalkorFfiClient {
+    inner: FalkorSyncClient,
+}
+
+/// An opaque handle to a fully retrieved query result, with a cursor pointing at the current row
+pub struct FalkorFfiResult {
+    header: Vec<CString>,
+    rows: Vec<Vec<FalkorValue>>,
+    stats: Vec<String>,
+    cursor: Option<usize>,
+}
+
+impl FalkorFfiResult {
+    fn current_value(
+        &self,
+        column: usize,
+    ) -> Option<&FalkorValue> {
+        self.cursor
+            .and_then(|row| self.rows.get(row))
+            .and_then(|row| row.get(column))
+    }
+}
+
+/// The type of a value in a query result, as exposed to C callers
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FalkorFfiValueType {
+    /// The value is NULL, or the row/column requested does not exist
+    None = 0,
+    /// A string
+    String = 1,
+    /// A signed 64-bit integer
+    I64 = 2,
+    /// A boolean
+    Bool = 3,
+    /// A double precision float
+    F64 = 4,
+    /// An array of other values
+    Array = 5,
+    /// A graph edge
+    Edge = 6,
+    /// A graph node
+    Node = 7,
+    /// A path of nodes and edges
+    Path = 8,
+    /// A map of strings to other values
+    Map = 9,
+    /// A geographical point
+    Point = 10,
+    /// A vector of 32-bit floats
+    Vec32 = 11,
+    /// This value could not be parsed
+    Unparseable = 12,
+}
+
+impl From<&FalkorValue> for FalkorFfiValueType {
+    fn from(value: &FalkorValue) -> Self {
+        match value {
+            FalkorValue::None => Self::None,
+            FalkorValue::String(_) => Self::String,
+            FalkorValue::I64(_) => Self::I64,
+            FalkorValue::Bool(_) => Self::Bool,
+            FalkorValue::F64(_) => Self::F64,
+            FalkorValue::Array(_) => Self::Array,
+            FalkorValue::Edge(_) => Self::Edge,
+            FalkorValue::Node(_) => Self::Node,
+            FalkorValue::Path(_) => Self::Path,
+            FalkorValue::Map(_) => Self::Map,
+            FalkorValue::Point(_) => Self::Point,
+            FalkorValue::Vec32(_) => Self::Vec32,
+            FalkorValue::Unparseable(_) => Self::Unparseable,
+        }
+    }
+}
+
+/// Returns the message of the last error that occurred on this thread, or null if there was none.
+/// The returned pointer is owned by the library, and is valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn falkordb_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map(|err| err.as_ptr())
+            .unwrap_or(ptr::null())
+    })
+}
+
+/// Creates a new client, connected to the provided URL
+///
+/// # Arguments
+/// * `url`: A null-terminated connection string, such as "falkor://127.0.0.1:6379", or null to use the default address.
+///
+/// # Returns
+/// A client handle, to be released with [`falkordb_client_free`], or null on failure.
+///
+/// # Safety
+/// `url` must be either null, or a valid pointer to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_client_new(url: *const c_char) -> *mut FalkorFfiClient {
+    clear_last_error();
+    catch_panic(ptr::null_mut(), || client_new(url))
+}
+
+unsafe fn client_new(url: *const c_char) -> *mut FalkorFfiClient {
+    let mut builder = FalkorClientBuilder::new();
+    if !url.is_null() {
+        let Some(url) = str_from_ptr(url, "url") else {
+            return ptr::null_mut();
+        };
+
+        match FalkorConnectionInfo::try_from(url) {
+            Ok(connection_info) => builder = builder.with_connection_info(connection_info),
+            Err(err) => {
+                set_last_error(err);
+                return ptr::null_mut();
+            }
+        }
+    }
+
+    match builder.build() {
+        Ok(inner) => Box::into_raw(Box::new(FalkorFfiClient { inner })),
+        Err(err) => {
+            set_last_error(err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a client handle, closing all of its connections.
+///
+/// # Safety
+/// `client` must be either null, or a pointer returned from [`falkordb_client_new`] which was not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_client_free(client: *mut FalkorFfiClient) {
+    if !client.is_null() {
+        drop(Box::from_raw(client));
+    }
+}
+
+/// Runs a query on the specified graph, retrieving the entire result set.
+///
+/// # Arguments
+/// * `client`: The client handle to use.
+/// * `graph_name`: A null-terminated graph name.
+/// * `query`: A null-terminated query string.
+///
+/// # Returns
+/// A result handle, to be released with [`falkordb_result_free`], or null on failure.
+///
+/// # Safety
+/// `client` must be a valid client handle, and `graph_name` and `query` must be valid pointers to null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_query(
+    client: *const FalkorFfiClient,
+    graph_name: *const c_char,
+    query: *const c_char,
+) -> *mut FalkorFfiResult {
+    clear_last_error();
+    catch_panic(ptr::null_mut(), || query_graph(client, graph_name, query))
+}
+
+unsafe fn query_graph(
+    client: *const FalkorFfiClient,
+    graph_name: *const c_char,
+    query: *const c_char,
+) -> *mut FalkorFfiResult {
+    let Some(client) = client.as_ref() else {
+        set_last_error("Argument 'client' must not be null");
+        return ptr::null_mut();
+    };
+    let (Some(graph_name), Some(query)) = (
+        str_from_ptr(graph_name, "graph_name"),
+        str_from_ptr(query, "query"),
+    ) else {
+        return ptr::null_mut();
+    };
+
+    let graph = client.inner.select_graph(graph_name);
+    match graph.query(query).execute() {
+        Ok(res) => Box::into_raw(Box::new(FalkorFfiResult {
+            header: res
+                .header
+                .into_iter()
+                .map(|column| CString::new(column.replace('\0', "")).unwrap_or_default())
+                .collect(),
+            rows: res.data.collect(),
+            stats: res.stats,
+            cursor: None,
+        })),
+        Err(err) => {
+            set_last_error(err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a result handle.
+///
+/// # Safety
+/// `result` must be either null, or a pointer returned from [`falkordb_query`] which was not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_free(result: *mut FalkorFfiResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result));
+    }
+}
+
+/// Returns the number of columns in the result, or -1 if `result` is null, or the count does not fit in an int.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_column_count(result: *const FalkorFfiResult) -> c_int {
+    result
+        .as_ref()
+        .map(|result| count_as_c_int(result.header.len()))
+        .unwrap_or(-1)
+}
+
+/// Returns the name of the column at the specified index, or null if it does not exist.
+/// The returned pointer is owned by the result, and is valid until it is freed.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_column_name(
+    result: *const FalkorFfiResult,
+    column: usize,
+) -> *const c_char {
+    result
+        .as_ref()
+        .and_then(|result| result.header.get(column))
+        .map(|column_name| column_name.as_ptr())
+        .unwrap_or(ptr::null())
+}
+
+/// Returns the number of rows in the result, or -1 if `result` is null, or the count does not fit in an int.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_row_count(result: *const FalkorFfiResult) -> c_int {
+    result
+        .as_ref()
+        .map(|result| count_as_c_int(result.rows.len()))
+        .unwrap_or(-1)
+}
+
+/// Advances the result cursor to the next row, this must be called once before reading the first row.
+///
+/// # Returns
+/// 1 if the cursor now points at a valid row, 0 if the rows are depleted, or -1 if `result` is null.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_next_row(result: *mut FalkorFfiResult) -> c_int {
+    let Some(result) = result.as_mut() else {
+        return -1;
+    };
+
+    let next_row = result.cursor.map(|row| row + 1).unwrap_or_default();
+    if next_row >= result.rows.len() {
+        result.cursor = Some(result.rows.len());
+        return 0;
+    }
+
+    result.cursor = Some(next_row);
+    1
+}
+
+/// Returns the type of the value at the specified column of the current row.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_value_type(
+    result: *const FalkorFfiResult,
+    column: usize,
+) -> FalkorFfiValueType {
+    result
+        .as_ref()
+        .and_then(|result| result.current_value(column))
+        .map(FalkorFfiValueType::from)
+        .unwrap_or(FalkorFfiValueType::None)
+}
+
+/// Writes the integer value at the specified column of the current row into `out`.
+///
+/// # Returns
+/// 0 on success, or -1 if the value is not an integer.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle, and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_value_i64(
+    result: *const FalkorFfiResult,
+    column: usize,
+    out: *mut i64,
+) -> c_int {
+    match result
+        .as_ref()
+        .and_then(|result| result.current_value(column))
+        .and_then(FalkorValue::to_i64)
+    {
+        Some(value) if !out.is_null() => {
+            *out = value;
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// Writes the floating point value at the specified column of the current row into `out`.
+///
+/// # Returns
+/// 0 on success, or -1 if the value is not a floating point number.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle, and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_value_f64(
+    result: *const FalkorFfiResult,
+    column: usize,
+    out: *mut f64,
+) -> c_int {
+    match result
+        .as_ref()
+        .and_then(|result| result.current_value(column))
+        .and_then(FalkorValue::to_f64)
+    {
+        Some(value) if !out.is_null() => {
+            *out = value;
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// Writes the boolean value at the specified column of the current row into `out`, as 0 or 1.
+///
+/// # Returns
+/// 0 on success, or -1 if the value is not a boolean.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle, and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_value_bool(
+    result: *const FalkorFfiResult,
+    column: usize,
+    out: *mut c_int,
+) -> c_int {
+    match result
+        .as_ref()
+        .and_then(|result| result.current_value(column))
+        .and_then(FalkorValue::to_bool)
+    {
+        Some(value) if !out.is_null() => {
+            *out = value as c_int;
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// Returns the value at the specified column of the current row as a newly allocated string.
+/// Strings are returned as-is, while any other type is returned in its debug representation.
+///
+/// # Returns
+/// A string to be released with [`falkordb_string_free`], or null if there is no such value.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_value_string(
+    result: *const FalkorFfiResult,
+    column: usize,
+) -> *mut c_char {
+    result
+        .as_ref()
+        .and_then(|result| result.current_value(column))
+        .map(|value| match value {
+            FalkorValue::String(str_val) => str_val.clone(),
+            other => format!("{other:?}"),
+        })
+        .map(string_into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Returns the query statistics, joined by newlines, as a newly allocated string.
+///
+/// # Returns
+/// A string to be released with [`falkordb_string_free`], or null if `result` is null.
+///
+/// # Safety
+/// `result` must be either null, or a valid result handle.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_result_stats(result: *const FalkorFfiResult) -> *mut c_char {
+    result
+        .as_ref()
+        .map(|result| string_into_raw(result.stats.join("\n")))
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Releases a string allocated by this library.
+///
+/// # Safety
+/// `value` must be either null, or a string returned from this library which was not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn falkordb_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result() -> FalkorFfiResult {
+        FalkorFfiResult {
+            header: vec![c"name".to_owned(), c"age".to_owned()],
+            rows: vec![
+                vec![
+                    FalkorValue::String("Alice".to_string()),
+                    FalkorValue::I64(30),
+                ],
+                vec![
+                    FalkorValue::String("Bob".to_string()),
+                    FalkorValue::F64(1.5),
+                ],
+            ],
+            stats: vec!["Nodes created: 2".to_string()],
+            cursor: None,
+        }
+    }
+
+    #[test]
+    fn test_invalid_url_sets_last_error() {
+        let url = c"custom://127.0.0.1:6379";
+        let client = unsafe { falkordb_client_new(url.as_ptr()) };
+        assert!(client.is_null());
+        assert!(!falkordb_last_error().is_null());
+    }
+
+    #[test]
+    fn test_null_arguments() {
+        let res = unsafe { falkordb_query(ptr::null(), ptr::null(), ptr::null()) };
+        assert!(res.is_null());
+
+        let last_error = unsafe { CStr::from_ptr(falkordb_last_error()) };
+        assert_eq!(
+            last_error.to_str().unwrap(),
+            "Argument 'client' must not be null"
+        );
+
+        assert_eq!(unsafe { falkordb_result_column_count(ptr::null()) }, -1);
+        assert_eq!(unsafe { falkordb_result_next_row(ptr::null_mut()) }, -1);
+    }
+
+    #[test]
+    fn test_panics_set_last_error() {
+        let res = catch_panic(ptr::null_mut::<FalkorFfiResult>(), || {
+            panic!("Unexpected reply");
+        });
+        assert!(res.is_null());
+
+        let last_error = unsafe { CStr::from_ptr(falkordb_last_error()) };
+        assert_eq!(
+            last_error.to_str().unwrap(),
+            "Internal error: Unexpected reply"
+        );
+    }
+
+    #[test]
+    fn test_counts_do_not_truncate() {
+        assert_eq!(count_as_c_int(2), 2);
+        assert_eq!(count_as_c_int(c_int::MAX as usize), c_int::MAX);
+        assert_eq!(count_as_c_int(c_int::MAX as usize + 1), -1);
+    }
+
+    #[test]
+    fn test_result_iteration() {
+        let result = Box::into_raw(Box::new(create_test_result()));
+
+        unsafe {
+            assert_eq!(falkordb_result_column_count(result), 2);
+            assert_eq!(
+                CStr::from_ptr(falkordb_result_column_name(result, 1)),
+                c"age"
+            );
+            assert!(falkordb_result_column_name(result, 2).is_null());
+
+            // Nothing is readable before advancing
+            assert_eq!(
+                falkordb_result_value_type(result, 0),
+                FalkorFfiValueType::None
+            );
+
+            assert_eq!(falkordb_result_next_row(result), 1);
+            assert_eq!(
+                falkordb_result_value_type(result, 0),
+                FalkorFfiValueType::String
+            );
+            let name = falkordb_result_value_string(result, 0);
+            assert_eq!(CStr::from_ptr(name), c"Alice");
+            falkordb_string_free(name);
+
+            let mut age = 0;
+            assert_eq!(falkordb_result_value_i64(result, 1, &mut age), 0);
+            assert_eq!(age, 30);
+
+            assert_eq!(falkordb_result_next_row(result), 1);
+            let mut as_f64 = 0.0;
+            assert_eq!(falkordb_result_value_i64(result, 1, &mut age), -1);
+            assert_eq!(falkordb_result_value_f64(result, 1, &mut as_f64), 0);
+            assert_eq!(as_f64, 1.5);
+
+            assert_eq!(falkordb_result_next_row(result), 0);
+            assert_eq!(falkordb_result_next_row(result), 0);
+
+            falkordb_result_free(result);
+        }
+    }
+}
@@ -0,0 +1,461 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    Edge, FalkorDBError, FalkorResult, FalkorValue, LazyResultSet, Node, Path, QueryResult,
+};
+use std::collections::HashMap;
+
+/// The direction in which relationships are traversed
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum TraversalDirection {
+    /// Follow relationships from their source to their destination
+    #[default]
+    Outgoing,
+    /// Follow relationships from their destination to their source
+    Incoming,
+    /// Follow relationships regardless of their direction
+    Both,
+}
+
+/// Options for the shortest path procedures, see [`SyncGraph::shortest_paths`](crate::SyncGraph::shortest_paths)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShortestPathOptions {
+    /// Only traverse relationships of these types, all types are traversed if this is empty
+    pub relationship_types: Vec<String>,
+    /// The direction in which relationships are traversed
+    pub direction: TraversalDirection,
+    /// The relationship property holding each relationship's weight, every relationship weighs 1 if not specified
+    pub weight_property: Option<String>,
+    /// The maximal number of relationships in a path
+    pub max_length: Option<u32>,
+    /// The maximal number of paths to return, uses the server default (a single path) if not specified, 0 returns all shortest paths
+    pub path_count: Option<u32>,
+}
+
+impl ShortestPathOptions {
+    /// Only traverse relationships of this type, can be called several times to allow several types
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_relationship_type(
+        mut self,
+        relationship_type: &str,
+    ) -> Self {
+        self.relationship_types.push(relationship_type.to_string());
+        self
+    }
+
+    /// Sets the direction in which relationships are traversed
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_direction(
+        self,
+        direction: TraversalDirection,
+    ) -> Self {
+        Self { direction, ..self }
+    }
+
+    /// Sets the relationship property holding each relationship's weight
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_weight_property(
+        self,
+        weight_property: &str,
+    ) -> Self {
+        Self {
+            weight_property: Some(weight_property.to_string()),
+            ..self
+        }
+    }
+
+    /// Sets the maximal number of relationships in a path
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_max_length(
+        self,
+        max_length: u32,
+    ) -> Self {
+        Self {
+            max_length: Some(max_length),
+            ..self
+        }
+    }
+
+    /// Sets the maximal number of paths to return, 0 returns all shortest paths
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_path_count(
+        self,
+        path_count: u32,
+    ) -> Self {
+        Self {
+            path_count: Some(path_count),
+            ..self
+        }
+    }
+
+    fn config_entries(&self) -> Vec<String> {
+        let mut entries = vec![];
+        if !self.relationship_types.is_empty() {
+            entries.push(format!(
+                "relTypes: {}",
+                string_list_literal(&self.relationship_types)
+            ));
+        }
+        entries.push(format!("relDirection: '{}'", self.direction));
+        entries.extend(
+            self.weight_property
+                .as_deref()
+                .map(|weight_property| format!("weightProp: {}", string_literal(weight_property))),
+        );
+        entries.extend(
+            self.max_length
+                .map(|max_length| format!("maxLen: {max_length}")),
+        );
+        entries.extend(
+            self.path_count
+                .map(|path_count| format!("pathCount: {path_count}")),
+        );
+        entries
+    }
+}
+
+pub(crate) fn string_literal(value: &str) -> String {
+    // Strings can always be represented as literals
+    FalkorValue::from(value)
+        .to_cypher_literal()
+        .unwrap_or_default()
+}
+
+pub(crate) fn string_list_literal<S: AsRef<str>>(values: &[S]) -> String {
+    format!(
+        "[{}]",
+        values
+            .iter()
+            .map(|value| string_literal(value.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+pub(crate) fn generate_shortest_paths_query(
+    source_id: i64,
+    target_id: Option<i64>,
+    options: &ShortestPathOptions,
+) -> String {
+    let mut config = vec!["sourceNode: source".to_string()];
+    let (target_match, procedure) = match target_id {
+        Some(target_id) => {
+            config.push("targetNode: target".to_string());
+            (
+                format!(" MATCH (target) WHERE ID(target) = {target_id}"),
+                "algo.SPpaths",
+            )
+        }
+        None => (String::new(), "algo.SSpaths"),
+    };
+    config.extend(options.config_entries());
+
+    format!(
+        "MATCH (source) WHERE ID(source) = {source_id}{target_match} CALL {procedure}({{{}}}) YIELD path RETURN path",
+        config.join(", ")
+    )
+}
+
+pub(crate) fn parse_paths(res: QueryResult<LazyResultSet>) -> FalkorResult<Vec<Path>> {
+    res.data
+        .map(|row| match row.into_iter().next() {
+            Some(FalkorValue::Path(path)) => Ok(path),
+            _ => Err(FalkorDBError::ParsingPath),
+        })
+        .collect()
+}
+
+/// A single level of a breadth-first traversal, see [`SyncGraph::bfs`](crate::SyncGraph::bfs)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TraversalLevel {
+    /// The distance, in relationships, of this level from the start node
+    pub depth: u32,
+    /// The nodes first discovered at this depth
+    pub nodes: Vec<Node>,
+    /// The relationships through which the nodes of this level were discovered, in the same order as the nodes
+    pub edges: Vec<Edge>,
+}
+
+pub(crate) fn generate_bfs_query(
+    start_node_id: i64,
+    max_depth: u32,
+    relationship_filter: Option<&str>,
+) -> String {
+    let relationship_filter = relationship_filter
+        .map(string_literal)
+        .unwrap_or_else(|| "NULL".to_string());
+
+    format!(
+        "MATCH (source) WHERE ID(source) = {start_node_id} CALL algo.BFS(source, {max_depth}, {relationship_filter}) YIELD nodes, edges RETURN source, nodes, edges"
+    )
+}
+
+fn into_nodes(value: FalkorValue) -> FalkorResult<Vec<Node>> {
+    value
+        .into_vec()?
+        .into_iter()
+        .map(|node| match node {
+            FalkorValue::Node(node) => Ok(node),
+            _ => Err(FalkorDBError::ParsingFNode),
+        })
+        .collect()
+}
+
+fn into_edges(value: FalkorValue) -> FalkorResult<Vec<Edge>> {
+    value
+        .into_vec()?
+        .into_iter()
+        .map(|edge| match edge {
+            FalkorValue::Edge(edge) => Ok(edge),
+            _ => Err(FalkorDBError::ParsingFEdge),
+        })
+        .collect()
+}
+
+/// Groups the discovered nodes into levels, each node is one level deeper than the node its discovering edge leads from
+pub(crate) fn group_into_levels(
+    source: Node,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+) -> FalkorResult<Vec<TraversalLevel>> {
+    if nodes.len() != edges.len() {
+        return Err(FalkorDBError::ParsingArrayToStructElementCount(
+            "Expected a discovering edge for every traversed node",
+        ));
+    }
+
+    let mut depths = HashMap::from([(source.entity_id, 0)]);
+    let mut levels = vec![TraversalLevel {
+        depth: 0,
+        nodes: vec![source],
+        edges: vec![],
+    }];
+    for (node, edge) in nodes.into_iter().zip(edges) {
+        let parent_id = match edge.src_node_id == node.entity_id {
+            true => edge.dst_node_id,
+            false => edge.src_node_id,
+        };
+        let depth = depths
+            .get(&parent_id)
+            .map(|parent_depth| parent_depth + 1)
+            .ok_or(FalkorDBError::ParsingFEdge)?;
+        depths.insert(node.entity_id, depth);
+
+        if levels.len() <= depth as usize {
+            levels.resize_with(depth as usize + 1, TraversalLevel::default);
+            levels[depth as usize].depth = depth;
+        }
+        levels[depth as usize].nodes.push(node);
+        levels[depth as usize].edges.push(edge);
+    }
+
+    Ok(levels)
+}
+
+pub(crate) fn parse_bfs_levels(
+    res: QueryResult<LazyResultSet>
+) -> FalkorResult<Vec<TraversalLevel>> {
+    let mut data = res.data;
+    let Some(row) = data.next() else {
+        return Ok(vec![]);
+    };
+
+    let [source, nodes, edges]: [FalkorValue; 3] = row.try_into().map_err(|_| {
+        FalkorDBError::ParsingArrayToStructElementCount(
+            "Expected exactly 3 elements in result - source, nodes and edges",
+        )
+    })?;
+    let FalkorValue::Node(source) = source else {
+        return Err(FalkorDBError::ParsingFNode);
+    };
+
+    group_into_levels(source, into_nodes(nodes)?, into_edges(edges)?)
+}
+
+fn optional_string_literal(value: Option<&str>) -> String {
+    value
+        .map(string_literal)
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
+pub(crate) fn generate_page_rank_query(
+    label: Option<&str>,
+    relationship_type: Option<&str>,
+) -> String {
+    format!(
+        "CALL algo.pageRank({}, {}) YIELD node, score RETURN node, score ORDER BY score DESC",
+        optional_string_literal(label),
+        optional_string_literal(relationship_type)
+    )
+}
+
+pub(crate) fn generate_betweenness_query(
+    node_labels: &[&str],
+    relationship_types: &[&str],
+) -> String {
+    let mut config = vec![];
+    if !node_labels.is_empty() {
+        config.push(format!("nodeLabels: {}", string_list_literal(node_labels)));
+    }
+    if !relationship_types.is_empty() {
+        config.push(format!(
+            "relationshipTypes: {}",
+            string_list_literal(relationship_types)
+        ));
+    }
+
+    format!(
+        "CALL algo.betweenness({{{}}}) YIELD node, score RETURN node, score ORDER BY score DESC",
+        config.join(", ")
+    )
+}
+
+pub(crate) fn generate_degree_query(
+    label: Option<&str>,
+    relationship_types: &[&str],
+    direction: TraversalDirection,
+) -> String {
+    let mut config = vec![];
+    config.extend(label.map(|label| format!("srcLabel: {}", string_literal(label))));
+    if !relationship_types.is_empty() {
+        config.push(format!(
+            "relationshipTypes: {}",
+            string_list_literal(relationship_types)
+        ));
+    }
+    config.push(format!("dir: '{direction}'"));
+
+    format!(
+        "CALL algo.degree({{{}}}) YIELD node, degree RETURN node, degree ORDER BY degree DESC",
+        config.join(", ")
+    )
+}
+
+pub(crate) fn parse_node_scores(res: QueryResult<LazyResultSet>) -> FalkorResult<Vec<(Node, f64)>> {
+    res.data
+        .map(|row| {
+            let [node, score]: [FalkorValue; 2] = row.try_into().map_err(|_| {
+                FalkorDBError::ParsingArrayToStructElementCount(
+                    "Expected exactly 2 elements in result - node and score",
+                )
+            })?;
+
+            match (node, score) {
+                (FalkorValue::Node(node), FalkorValue::F64(score)) => Ok((node, score)),
+                (FalkorValue::Node(node), FalkorValue::I64(score)) => Ok((node, score as f64)),
+                (FalkorValue::Node(_), _) => Err(FalkorDBError::ParsingF64),
+                _ => Err(FalkorDBError::ParsingFNode),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(entity_id: i64) -> Node {
+        Node {
+            entity_id,
+            ..Default::default()
+        }
+    }
+
+    fn edge(
+        src_node_id: i64,
+        dst_node_id: i64,
+    ) -> Edge {
+        Edge {
+            src_node_id,
+            dst_node_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_bfs_query() {
+        assert_eq!(
+            generate_bfs_query(3, 2, Some("KNOWS")),
+            "MATCH (source) WHERE ID(source) = 3 CALL algo.BFS(source, 2, \"KNOWS\") YIELD nodes, edges RETURN source, nodes, edges"
+        );
+        assert_eq!(
+            generate_bfs_query(3, 0, None),
+            "MATCH (source) WHERE ID(source) = 3 CALL algo.BFS(source, 0, NULL) YIELD nodes, edges RETURN source, nodes, edges"
+        );
+    }
+
+    #[test]
+    fn test_group_into_levels() {
+        let levels = group_into_levels(
+            node(0),
+            vec![node(1), node(2), node(3)],
+            vec![edge(0, 1), edge(2, 0), edge(1, 3)],
+        )
+        .expect("Could not group levels");
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].nodes, vec![node(0)]);
+        assert!(levels[0].edges.is_empty());
+        assert_eq!(levels[1].depth, 1);
+        assert_eq!(levels[1].nodes, vec![node(1), node(2)]);
+        assert_eq!(levels[1].edges, vec![edge(0, 1), edge(2, 0)]);
+        assert_eq!(levels[2].depth, 2);
+        assert_eq!(levels[2].nodes, vec![node(3)]);
+
+        assert!(group_into_levels(node(0), vec![node(1)], vec![edge(5, 1)]).is_err());
+        assert!(group_into_levels(node(0), vec![node(1)], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_generate_shortest_paths_query() {
+        assert_eq!(
+            generate_shortest_paths_query(1, Some(5), &ShortestPathOptions::default()),
+            "MATCH (source) WHERE ID(source) = 1 MATCH (target) WHERE ID(target) = 5 CALL algo.SPpaths({sourceNode: source, targetNode: target, relDirection: 'outgoing'}) YIELD path RETURN path"
+        );
+
+        let options = ShortestPathOptions::default()
+            .with_relationship_type("Road")
+            .with_direction(TraversalDirection::Both)
+            .with_weight_property("distance")
+            .with_max_length(4)
+            .with_path_count(0);
+        assert_eq!(
+            generate_shortest_paths_query(1, None, &options),
+            "MATCH (source) WHERE ID(source) = 1 CALL algo.SSpaths({sourceNode: source, relTypes: [\"Road\"], relDirection: 'both', weightProp: \"distance\", maxLen: 4, pathCount: 0}) YIELD path RETURN path"
+        );
+    }
+
+    #[test]
+    fn test_generate_centrality_queries() {
+        assert_eq!(
+            generate_page_rank_query(Some("Page"), None),
+            "CALL algo.pageRank(\"Page\", NULL) YIELD node, score RETURN node, score ORDER BY score DESC"
+        );
+        assert_eq!(
+            generate_betweenness_query(&[], &[]),
+            "CALL algo.betweenness({}) YIELD node, score RETURN node, score ORDER BY score DESC"
+        );
+        assert_eq!(
+            generate_betweenness_query(&["Person"], &["KNOWS", "WORKS_WITH"]),
+            "CALL algo.betweenness({nodeLabels: [\"Person\"], relationshipTypes: [\"KNOWS\", \"WORKS_WITH\"]}) YIELD node, score RETURN node, score ORDER BY score DESC"
+        );
+        assert_eq!(
+            generate_degree_query(Some("Person"), &["KNOWS"], TraversalDirection::Incoming),
+            "CALL algo.degree({srcLabel: \"Person\", relationshipTypes: [\"KNOWS\"], dir: 'incoming'}) YIELD node, degree RETURN node, degree ORDER BY degree DESC"
+        );
+    }
+}
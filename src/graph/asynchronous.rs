@@ -4,25 +4,68 @@
  */
 
 use crate::{
-    client::asynchronous::FalkorAsyncClientInner,
+    client::{asynchronous::FalkorAsyncClientInner, circuit::CircuitBreaker},
     graph::HasGraphSchema,
-    graph::{generate_create_index_query, generate_drop_index_query},
+    graph::{
+        algorithms::{
+            generate_betweenness_query, generate_bfs_query, generate_degree_query,
+            generate_page_rank_query, generate_shortest_paths_query, parse_bfs_levels,
+            parse_node_scores, parse_paths,
+        },
+        collect_names,
+        constraint_builder::{
+            check_constraint_status, generate_constraint_violations_query,
+            CONSTRAINT_VIOLATIONS_LIMIT,
+        },
+        find_operational_index, generate_create_index_query, generate_create_vector_index_query,
+        generate_drop_all_indices_queries, generate_drop_index_query, generate_knn_query,
+        generate_radius_query, has_time_for_retry,
+        idempotency::idempotency_key,
+        lock::{
+            parse_lock_script_reply, parse_set_nx_reply, ttl_millis, EXTEND_LOCK_SCRIPT,
+            LOCK_RETRY_INTERVAL, RELEASE_LOCK_SCRIPT,
+        },
+        migration::{
+            parse_schema_version, pending_migrations, schema_version_key, RECORD_VERSION_SCRIPT,
+        },
+        parse_node_distance_results,
+        seed::generate_seed_query,
+        soft_delete::generate_soft_delete_query,
+        strict_typing::{check_property_round_trip, generate_set_property_query},
+        versioning::{
+            generate_property_as_of_query, generate_property_history_query,
+            generate_set_versioned_query, parse_property_as_of, parse_property_history,
+        },
+        with_attempts, LABELS_QUERY, PROPERTY_KEYS_QUERY, RELATIONSHIP_TYPES_QUERY, RETRY_BACKOFF,
+        SCHEMA_POLL_INTERVAL,
+    },
     parser::redis_value_as_vec,
-    Constraint, ConstraintType, EntityType, ExecutionPlan, FalkorIndex, FalkorResult, GraphSchema,
-    IndexType, LazyResultSet, ProcedureQueryBuilder, QueryBuilder, QueryResult, SlowlogEntry,
+    runtime, CircuitStats, Constraint, ConstraintBuilder, ConstraintType, EntityType,
+    ExecutionPlan, FalkorDBError, FalkorIndex, FalkorResult, FalkorValue, GraphFixtures, GraphLock,
+    GraphSchema, IndexBuilder, IndexType, LazyResultSet, Migration, MigrationReport, Node, Path,
+    Point, ProcedureQueryBuilder, PropertyVersion, QueryBuilder, QueryResult, SchemaType,
+    ShortestPathOptions, SlowlogEntry, TraversalDirection, TraversalLevel, VectorIndexOptions,
+};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{collections::HashMap, fmt::Display, sync::Arc};
 
 /// The main graph API, this allows the user to perform graph operations while exposing as little details as possible.
 /// # Thread Safety
-/// This struct is NOT thread safe, and synchronization is up to the user.
+/// All operations take a shared reference, as the graph schema is refreshed behind internal locks,
+/// so a single graph handle can be shared between threads, E.g. behind an [`Arc`](std::sync::Arc).
 /// It does, however, allow the user to perform nonblocking operations
-/// Graph schema is not shared between instances of AsyncGraph, even with the same name, but cloning will maintain the current schema
+/// Graph schema is shared between all instances of AsyncGraph with the same name selected from the same client, including clones
 #[derive(Clone)]
 pub struct AsyncGraph {
     client: Arc<FalkorAsyncClientInner>,
     graph_name: String,
-    graph_schema: GraphSchema,
+    graph_schema: Arc<GraphSchema>,
+    circuit: Option<Arc<CircuitBreaker>>,
 }
 
 impl AsyncGraph {
@@ -30,9 +73,11 @@ impl AsyncGraph {
         client: Arc<FalkorAsyncClientInner>,
         graph_name: T,
     ) -> Self {
+        let graph_name = graph_name.to_string();
         Self {
-            graph_name: graph_name.to_string(),
-            graph_schema: GraphSchema::new(graph_name, client.clone()), // Required for requesting refreshes
+            graph_schema: client.graph_schema(&graph_name),
+            circuit: client.graph_circuit(&graph_name),
+            graph_name,
             client,
         }
     }
@@ -45,10 +90,30 @@ impl AsyncGraph {
         self.graph_name.as_str()
     }
 
+    /// Returns the schema cache of this graph, shared by all handles to it, E.g. to persist or restore it
+    ///
+    /// # Returns
+    /// A reference to the [`GraphSchema`] of this graph
+    pub fn graph_schema(&self) -> &GraphSchema {
+        &self.graph_schema
+    }
+
     pub(crate) fn get_client(&self) -> &Arc<FalkorAsyncClientInner> {
         &self.client
     }
 
+    pub(crate) fn circuit(&self) -> Option<&CircuitBreaker> {
+        self.circuit.as_deref()
+    }
+
+    /// Returns the state and counters of this graph's circuit breaker, see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker)
+    ///
+    /// # Returns
+    /// The [`CircuitStats`] of this graph, or [`None`] if circuit breakers are disabled
+    pub fn circuit_stats(&self) -> Option<CircuitStats> {
+        self.circuit.as_ref().map(|circuit| circuit.stats())
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Graph Execute Command", skip_all, level = "info")
@@ -72,7 +137,7 @@ impl AsyncGraph {
         feature = "tracing",
         tracing::instrument(name = "Delete Graph", skip_all, level = "info")
     )]
-    pub async fn delete(&mut self) -> FalkorResult<()> {
+    pub async fn delete(&self) -> FalkorResult<()> {
         self.execute_command("GRAPH.DELETE", None, None).await?;
         self.graph_schema.clear();
         Ok(())
@@ -105,8 +170,210 @@ impl AsyncGraph {
             .await
     }
 
+    /// Attempts to acquire an exclusive lock on this graph, held on a companion key set with SET NX and a TTL
+    /// The lock is advisory, it only excludes other callers of this function, and not writes in general.
+    ///
+    /// # Arguments
+    /// * `ttl`: How long the lock is held before expiring, unless it is released or extended first
+    ///
+    /// # Returns
+    /// The acquired [`GraphLock`], or [`None`] if the lock is currently held by someone else
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Try Lock", skip_all, level = "info")
+    )]
+    pub async fn try_lock(
+        &self,
+        ttl: Duration,
+    ) -> FalkorResult<Option<GraphLock>> {
+        let lock = GraphLock::new(&self.graph_name);
+        let ttl = ttl_millis(ttl);
+        self.client
+            .borrow_connection(self.client.clone())
+            .await?
+            .execute_command(
+                Some(lock.key()),
+                "SET",
+                None,
+                Some(&[lock.token(), "NX", "PX", ttl.as_str()]),
+            )
+            .await
+            .map(|res| parse_set_nx_reply(res).then_some(lock))
+    }
+
+    /// Acquires an exclusive lock on this graph, retrying until the lock is acquired or the timeout passes, see [`AsyncGraph::try_lock`]
+    ///
+    /// # Arguments
+    /// * `ttl`: How long the lock is held before expiring, unless it is released or extended first
+    /// * `timeout`: How long to keep retrying, returns [`FalkorDBError::LockTimeout`](crate::FalkorDBError::LockTimeout) once it passes
+    ///
+    /// # Returns
+    /// The acquired [`GraphLock`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Lock", skip_all, level = "info")
+    )]
+    pub async fn lock(
+        &self,
+        ttl: Duration,
+        timeout: Duration,
+    ) -> FalkorResult<GraphLock> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(lock) = self.try_lock(ttl).await? {
+                return Ok(lock);
+            }
+            if Instant::now() + LOCK_RETRY_INTERVAL > deadline {
+                return Err(FalkorDBError::LockTimeout);
+            }
+            runtime::sleep(LOCK_RETRY_INTERVAL).await;
+        }
+    }
+
+    /// Resets the TTL of a held lock, allowing long-running jobs to keep holding it
+    ///
+    /// # Arguments
+    /// * `lock`: The held [`GraphLock`]
+    /// * `ttl`: The new TTL, counted from now
+    ///
+    /// # Returns
+    /// Whether the lock was still held, if it wasn't, it may have already been acquired by someone else
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Extend Lock", skip_all, level = "info")
+    )]
+    pub async fn extend_lock(
+        &self,
+        lock: &GraphLock,
+        ttl: Duration,
+    ) -> FalkorResult<bool> {
+        let ttl = ttl_millis(ttl);
+        self.client
+            .borrow_connection(self.client.clone())
+            .await?
+            .execute_command(
+                None,
+                "EVAL",
+                Some(EXTEND_LOCK_SCRIPT),
+                Some(&["1", lock.key(), lock.token(), ttl.as_str()]),
+            )
+            .await
+            .map(parse_lock_script_reply)
+    }
+
+    /// Releases a held lock, only if it is still held by the provided [`GraphLock`]
+    ///
+    /// # Arguments
+    /// * `lock`: The held [`GraphLock`]
+    ///
+    /// # Returns
+    /// Whether the lock was still held, if it wasn't, it had already expired
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Unlock", skip_all, level = "info")
+    )]
+    pub async fn unlock(
+        &self,
+        lock: GraphLock,
+    ) -> FalkorResult<bool> {
+        self.client
+            .borrow_connection(self.client.clone())
+            .await?
+            .execute_command(
+                None,
+                "EVAL",
+                Some(RELEASE_LOCK_SCRIPT),
+                Some(&["1", lock.key(), lock.token()]),
+            )
+            .await
+            .map(parse_lock_script_reply)
+    }
+
+    /// Applies the migrations this graph is missing, while holding the graph's lock, see [`AsyncGraph::lock`]
+    /// Replicas starting simultaneously apply each migration exactly once, the others wait for the lock to be released,
+    /// and then verify the graph reached the latest known version. The version is recorded in a companion key, see [`SCHEMA_VERSION_KEY_PREFIX`](crate::SCHEMA_VERSION_KEY_PREFIX).
+    ///
+    /// # Arguments
+    /// * `migrations`: All known migrations, in ascending version order
+    /// * `lock_ttl`: How long the lock is held, it is extended after each applied migration, so this only has to cover the longest one
+    /// * `wait_timeout`: How long to wait for another replica to finish migrating, returns [`FalkorDBError::LockTimeout`](crate::FalkorDBError::LockTimeout) once it passes
+    ///
+    /// # Returns
+    /// A [`MigrationReport`] of the applied migrations and the resulting version,
+    /// or [`FalkorDBError::SchemaVersionMismatch`](crate::FalkorDBError::SchemaVersionMismatch) if the graph was migrated past the latest known migration
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Run Migrations", skip_all, level = "info")
+    )]
+    pub async fn run_migrations(
+        &self,
+        migrations: &[Migration],
+        lock_ttl: Duration,
+        wait_timeout: Duration,
+    ) -> FalkorResult<MigrationReport> {
+        let lock = self.lock(lock_ttl, wait_timeout).await?;
+        let res = self.apply_migrations(migrations, &lock, lock_ttl).await;
+        let released = self.unlock(lock).await;
+        let report = res?;
+        released?;
+        Ok(report)
+    }
+
+    async fn apply_migrations(
+        &self,
+        migrations: &[Migration],
+        lock: &GraphLock,
+        lock_ttl: Duration,
+    ) -> FalkorResult<MigrationReport> {
+        let version_key = schema_version_key(&self.graph_name);
+        let current_version = self
+            .client
+            .borrow_connection(self.client.clone())
+            .await?
+            .execute_command(Some(version_key.as_str()), "GET", None, None)
+            .await
+            .and_then(parse_schema_version)?;
+
+        let ttl = ttl_millis(lock_ttl);
+        let mut report = MigrationReport {
+            applied: Vec::new(),
+            version: current_version,
+        };
+        for migration in pending_migrations(migrations, current_version)? {
+            for query in &migration.queries {
+                self.query(query).execute().await?;
+            }
+
+            let version = migration.version.to_string();
+            let still_held = self
+                .client
+                .borrow_connection(self.client.clone())
+                .await?
+                .execute_command(
+                    None,
+                    "EVAL",
+                    Some(RECORD_VERSION_SCRIPT),
+                    Some(&[
+                        "2",
+                        lock.key(),
+                        version_key.as_str(),
+                        lock.token(),
+                        version.as_str(),
+                        ttl.as_str(),
+                    ]),
+                )
+                .await
+                .map(parse_lock_script_reply)?;
+            if !still_held {
+                return Err(FalkorDBError::LockLost);
+            }
+            report.applied.push(migration.version);
+            report.version = migration.version;
+        }
+        Ok(report)
+    }
+
     /// Creates a [`QueryBuilder`] for this graph, in an attempt to profile a specific query
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     ///
     /// # Arguments
     /// * `query_string`: The query to profile
@@ -114,14 +381,13 @@ impl AsyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object, which when performed will return an [`ExecutionPlan`]
     pub fn profile<'a>(
-        &'a mut self,
+        &'a self,
         query_string: &'a str,
     ) -> QueryBuilder<'a, ExecutionPlan, &'a str, Self> {
         QueryBuilder::<'a>::new(self, "GRAPH.PROFILE", query_string)
     }
 
     /// Creates a [`QueryBuilder`] for this graph, in an attempt to explain a specific query
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     ///
     /// # Arguments
     /// * `query_string`: The query to explain the process for
@@ -129,14 +395,13 @@ impl AsyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object, which when performed will return an [`ExecutionPlan`]
     pub fn explain<'a>(
-        &'a mut self,
+        &'a self,
         query_string: &'a str,
     ) -> QueryBuilder<'a, ExecutionPlan, &'a str, Self> {
         QueryBuilder::new(self, "GRAPH.EXPLAIN", query_string)
     }
 
     /// Creates a [`QueryBuilder`] for this graph
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     ///
     /// # Arguments
     /// * `query_string`: The query to run
@@ -144,14 +409,13 @@ impl AsyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object, which when performed will return a [`QueryResult<FalkorResultSet>`]
     pub fn query<T: Display>(
-        &mut self,
+        &self,
         query_string: T,
-    ) -> QueryBuilder<QueryResult<LazyResultSet>, T, Self> {
+    ) -> QueryBuilder<'_, QueryResult<LazyResultSet<'_>>, T, Self> {
         QueryBuilder::new(self, "GRAPH.QUERY", query_string)
     }
 
     /// Creates a [`QueryBuilder`] for this graph, for a readonly query
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only queries are more limited with the operations they are allowed to perform.
     ///
     /// # Arguments
@@ -160,39 +424,215 @@ impl AsyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object
     pub fn ro_query<'a>(
-        &'a mut self,
+        &'a self,
         query_string: &'a str,
     ) -> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, &'a str, Self> {
         QueryBuilder::new(self, "GRAPH.RO_QUERY", query_string)
     }
 
+    /// Runs a query registered in the client's [`QueryRegistry`] by name, see [`FalkorClientBuilder::with_query_registry`](crate::FalkorClientBuilder::with_query_registry)
+    /// The parameters are validated against the ones the query references before it is sent, and the query's name and fingerprint are recorded in the tracing span.
+    ///
+    /// # Arguments
+    /// * `name`: The name the query was registered by
+    /// * `params`: The query's parameters, in key-val format
+    ///
+    /// # Returns
+    /// A [`QueryResult`] with a [`LazyResultSet`], or an error if the query is not registered, or the parameters do not match it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Graph Run Registered Query",
+            skip_all,
+            level = "info",
+            fields(query_name = name, fingerprint)
+        )
+    )]
+    pub async fn run<'a>(
+        &'a self,
+        name: &str,
+        params: &'a HashMap<String, String>,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+        let template = self.client.query_registry();
+        let template = template.resolve(name, params)?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("fingerprint", format!("{:016x}", template.fingerprint));
+
+        let command = match template.read_only {
+            true => "GRAPH.RO_QUERY",
+            false => "GRAPH.QUERY",
+        };
+        QueryBuilder::<QueryResult<LazyResultSet>, _, _>::new(self, command, template.query.clone())
+            .with_params(params)
+            .execute()
+            .await
+    }
+
+    /// Runs a write query at most once per idempotency key, so retried requests don't apply the same write twice
+    /// Processed keys are recorded on companion keys prefixed with [`IDEMPOTENCY_KEY_PREFIX`](crate::IDEMPOTENCY_KEY_PREFIX),
+    /// the key is recorded before the query runs, and removed again if the query fails, so failed queries can be retried.
+    ///
+    /// # Arguments
+    /// * `key`: The idempotency key identifying this write, such as a request or message ID
+    /// * `query_string`: The query to run
+    /// * `ttl`: How long to remember the key, retries arriving after it expires will run the query again
+    ///
+    /// # Returns
+    /// The [`QueryResult`] of the query, or [`None`] if this key was already processed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Execute Idempotent", skip_all, level = "info")
+    )]
+    pub async fn execute_idempotent<T: Display>(
+        &self,
+        key: &str,
+        query_string: T,
+        ttl: Duration,
+    ) -> FalkorResult<Option<QueryResult<LazyResultSet<'_>>>> {
+        let client = self.client.clone();
+        let key = idempotency_key(&self.graph_name, key);
+        let ttl = ttl_millis(ttl);
+        let first_attempt = client
+            .borrow_connection(client.clone())
+            .await?
+            .execute_command(
+                Some(&key),
+                "SET",
+                None,
+                Some(&["1", "NX", "PX", ttl.as_str()]),
+            )
+            .await
+            .map(parse_set_nx_reply)?;
+        if !first_attempt {
+            return Ok(None);
+        }
+
+        match self.query(query_string).execute().await {
+            Ok(res) => Ok(Some(res)),
+            Err(err) => {
+                // Forget the key, so the failed write can be retried, the original error is more relevant than a cleanup failure
+                if let Ok(conn) = client.borrow_connection(client.clone()).await {
+                    conn.execute_command(Some(&key), "DEL", None, None)
+                        .await
+                        .ok();
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Runs an async closure, re-running it when it fails on a connection error
+    /// Failed connections are replaced as the errors are returned, so each retry runs over a fresh connection.
+    /// The closure may run several times, so it should be safe to repeat, such as a single query or an idempotent write.
+    ///
+    /// # Arguments
+    /// * `max_retries`: How many times to re-run the closure, after its first attempt
+    /// * `operation`: The closure to run, receiving a clone of this graph on every attempt
+    ///
+    /// # Returns
+    /// The result of the first successful attempt, or the error of the last attempt,
+    /// wrapped in [`FalkorDBError::RetriesFailed`](crate::FalkorDBError::RetriesFailed) if there was more than one
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph With Retries", skip_all, level = "info")
+    )]
+    pub async fn with_retries<T, F, Fut>(
+        &self,
+        max_retries: usize,
+        operation: F,
+    ) -> FalkorResult<T>
+    where
+        F: FnMut(AsyncGraph) -> Fut,
+        Fut: Future<Output = FalkorResult<T>>,
+    {
+        self.retry_until(None, max_retries, operation).await
+    }
+
+    /// Runs an async closure, re-running it when it fails on a connection error, as long as the deadline allows
+    /// All attempts share a single budget, the closure receives the deadline, and should pass it to its queries using
+    /// [`QueryBuilder::with_deadline`], so every attempt only gets the time left, and no retry is started that can't complete in time.
+    ///
+    /// # Arguments
+    /// * `deadline`: The instant by which the operation must complete, including all of its attempts
+    /// * `max_retries`: How many times at most to re-run the closure, after its first attempt
+    /// * `operation`: The closure to run, receiving a clone of this graph and the deadline on every attempt
+    ///
+    /// # Returns
+    /// The result of the first successful attempt, or the error of the last attempt,
+    /// wrapped in [`FalkorDBError::RetriesFailed`](crate::FalkorDBError::RetriesFailed) if there was more than one
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph With Retries Within Deadline", skip_all, level = "info")
+    )]
+    pub async fn with_retries_within<T, F, Fut>(
+        &self,
+        deadline: Instant,
+        max_retries: usize,
+        mut operation: F,
+    ) -> FalkorResult<T>
+    where
+        F: FnMut(AsyncGraph, Instant) -> Fut,
+        Fut: Future<Output = FalkorResult<T>>,
+    {
+        self.retry_until(Some(deadline), max_retries, |graph| {
+            operation(graph, deadline)
+        })
+        .await
+    }
+
+    async fn retry_until<T, F, Fut>(
+        &self,
+        deadline: Option<Instant>,
+        max_retries: usize,
+        mut operation: F,
+    ) -> FalkorResult<T>
+    where
+        F: FnMut(AsyncGraph) -> Fut,
+        Fut: Future<Output = FalkorResult<T>>,
+    {
+        let mut backoff = RETRY_BACKOFF;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let err = match operation(self.clone()).await {
+                Err(err) if err.is_retryable() => err,
+                res => return res.map_err(|err| with_attempts(attempts, err)),
+            };
+            if attempts > max_retries || !has_time_for_retry(deadline, backoff) {
+                return Err(with_attempts(attempts, err));
+            }
+            runtime::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
     /// Creates a [`ProcedureQueryBuilder`] for this graph
-    /// This [`ProcedureQueryBuilder`] has to be dropped or ran using [`ProcedureQueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only queries are more limited with the operations they are allowed to perform.
     ///
     /// # Arguments
     /// * `procedure_name`: The name of the procedure to call
     ///
     /// # Returns
-    /// A [`ProcedureQueryBuilder`] object
+    /// A [`ProcedureQueryBuilder`] object, whose output type `P` determines how results are parsed,
+    /// E.g. `QueryResult<Vec<(String, f64)>>` converts each row of yielded columns into a tuple, see [`FromYieldRow`](crate::FromYieldRow)
     pub fn call_procedure<'a, P>(
-        &'a mut self,
+        &'a self,
         procedure_name: &'a str,
     ) -> ProcedureQueryBuilder<'a, P, Self> {
         ProcedureQueryBuilder::new(self, procedure_name)
     }
 
     /// Creates a [`ProcedureQueryBuilder`] for this graph, for a readonly procedure
-    /// This [`ProcedureQueryBuilder`] has to be dropped or ran using [`ProcedureQueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only procedures are more limited with the operations they are allowed to perform.
     ///
     /// # Arguments
     /// * `procedure_name`: The name of the procedure to call
     ///
     /// # Returns
-    /// A [`ProcedureQueryBuilder`] object
+    /// A [`ProcedureQueryBuilder`] object, whose output type `P` determines how results are parsed,
+    /// E.g. `QueryResult<Vec<(String, f64)>>` converts each row of yielded columns into a tuple, see [`FromYieldRow`](crate::FromYieldRow)
     pub fn call_procedure_ro<'a, P>(
-        &'a mut self,
+        &'a self,
         procedure_name: &'a str,
     ) -> ProcedureQueryBuilder<'a, P, Self> {
         ProcedureQueryBuilder::new_readonly(self, procedure_name)
@@ -206,12 +646,152 @@ impl AsyncGraph {
         feature = "tracing",
         tracing::instrument(name = "List Graph Indices", skip_all, level = "info")
     )]
-    pub async fn list_indices(&mut self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
+    pub async fn list_indices(&self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
         ProcedureQueryBuilder::<QueryResult<Vec<FalkorIndex>>, Self>::new(self, "DB.INDEXES")
             .execute()
             .await
     }
 
+    /// Eagerly populates the cached graph schema with all of the graph's labels, properties and relationship types,
+    /// so the first queries returning them don't pay for refreshing it, E.g. during startup of latency-sensitive services.
+    /// Schema entities created afterwards are still fetched on demand.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Preload Graph Schema", skip_all, level = "info")
+    )]
+    pub async fn preload_schema(&self) -> FalkorResult<()> {
+        let labels = self.labels().await?;
+        let properties = self.property_keys().await?;
+        let relationships = self.relationship_types().await?;
+
+        self.graph_schema.populate(SchemaType::Labels, labels);
+        self.graph_schema
+            .populate(SchemaType::Properties, properties);
+        self.graph_schema
+            .populate(SchemaType::Relationships, relationships);
+        Ok(())
+    }
+
+    /// Calls the DB.LABELS procedure on the graph, returning all the node labels it contains
+    /// This always queries the server, regardless of the graph schema cached for parsing results.
+    ///
+    /// # Returns
+    /// A [`Vec`] of the node labels, in the order they were created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Labels", skip_all, level = "info")
+    )]
+    pub async fn labels(&self) -> FalkorResult<Vec<String>> {
+        self.ro_query(LABELS_QUERY)
+            .execute()
+            .await
+            .map(|res| collect_names(res.data))
+    }
+
+    /// Calls the DB.PROPERTYKEYS procedure on the graph, returning all the property keys it contains
+    /// This always queries the server, regardless of the graph schema cached for parsing results.
+    ///
+    /// # Returns
+    /// A [`Vec`] of the property keys, in the order they were created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Property Keys", skip_all, level = "info")
+    )]
+    pub async fn property_keys(&self) -> FalkorResult<Vec<String>> {
+        self.ro_query(PROPERTY_KEYS_QUERY)
+            .execute()
+            .await
+            .map(|res| collect_names(res.data))
+    }
+
+    /// Calls the DB.RELATIONSHIPTYPES procedure on the graph, returning all the relationship types it contains
+    /// This always queries the server, regardless of the graph schema cached for parsing results.
+    ///
+    /// # Returns
+    /// A [`Vec`] of the relationship types, in the order they were created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Relationship Types", skip_all, level = "info")
+    )]
+    pub async fn relationship_types(&self) -> FalkorResult<Vec<String>> {
+        self.ro_query(RELATIONSHIP_TYPES_QUERY)
+            .execute()
+            .await
+            .map(|res| collect_names(res.data))
+    }
+
+    /// Waits until an index on the provided label and properties is operational, by polling the graph's indices
+    /// Index creation returns while the index is still under construction, so this allows waiting until it can actually be used.
+    ///
+    /// # Arguments
+    /// * `label`: The label or relationship type of the index
+    /// * `properties`: The properties the index should cover
+    /// * `timeout`: How long to keep polling, returns [`FalkorDBError::IndexWaitTimeout`] once it passes
+    ///
+    /// # Returns
+    /// The operational [`FalkorIndex`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Wait For Index", skip_all, level = "info")
+    )]
+    pub async fn wait_for_index<P: AsRef<str>>(
+        &self,
+        label: &str,
+        properties: &[P],
+        timeout: Duration,
+    ) -> FalkorResult<FalkorIndex> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let indices = self.list_indices().await?.data;
+            if let Some(index) = find_operational_index(indices, label, properties) {
+                return Ok(index);
+            }
+            if Instant::now() + SCHEMA_POLL_INTERVAL > deadline {
+                return Err(FalkorDBError::IndexWaitTimeout);
+            }
+            runtime::sleep(SCHEMA_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Drops every index in the graph, optionally only those on the provided label
+    /// The indices are enumerated using [`Self::list_indices`], this is useful for resetting the schema in tests and migrations.
+    /// Indices backing unique constraints cannot be dropped while the constraint exists, so drop the constraints first using [`Self::drop_all_constraints`].
+    ///
+    /// # Arguments
+    /// * `label`: Only drop indices on this label or relationship type, or drop all of them if [`None`]
+    ///
+    /// # Returns
+    /// The number of drop queries performed, one per index type of each label
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Drop All Graph Indices", skip_all, level = "info")
+    )]
+    pub async fn drop_all_indices(
+        &self,
+        label: Option<&str>,
+    ) -> FalkorResult<usize> {
+        let indices = self.list_indices().await?.data;
+        let queries = generate_drop_all_indices_queries(&indices, label);
+        for query_str in &queries {
+            self.query(query_str).execute().await?;
+        }
+        Ok(queries.len())
+    }
+
+    /// Creates an [`IndexBuilder`] for an index on this label, a self-documenting alternative to [`Self::create_index`] and [`Self::drop_index`]
+    ///
+    /// # Arguments
+    /// * `label`: The node label or relationship type to index
+    ///
+    /// # Returns
+    /// An [`IndexBuilder`] object, describing a range index on nodes by default
+    pub fn index<'a>(
+        &'a self,
+        label: &str,
+    ) -> IndexBuilder<'a, Self> {
+        IndexBuilder::new(self, label)
+    }
+
     /// Creates a new index in the graph, for the selected entity type(Node/Edge), selected label, and properties
     ///
     /// # Arguments
@@ -228,13 +808,13 @@ impl AsyncGraph {
         tracing::instrument(name = "Graph Create Index", skip_all, level = "info")
     )]
     pub async fn create_index<P: Display>(
-        &mut self,
+        &self,
         index_field_type: IndexType,
         entity_type: EntityType,
         label: &str,
         properties: &[P],
         options: Option<&HashMap<String, String>>,
-    ) -> FalkorResult<QueryResult<LazyResultSet>> {
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
         // Create index from these properties
         let query_str =
             generate_create_index_query(index_field_type, entity_type, label, properties, options);
@@ -248,6 +828,31 @@ impl AsyncGraph {
         .await
     }
 
+    /// Creates a new vector index in the graph, with typed options for the vector dimension, similarity function and tuning
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether to index nodes or relationships
+    /// * `label`: Entities with this label will be indexed
+    /// * `properties`: The vector properties to index
+    /// * `options`: The [`VectorIndexOptions`] for this index
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing information on the created index
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Create Vector Index", skip_all, level = "info")
+    )]
+    pub async fn create_vector_index<P: Display>(
+        &self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+        options: VectorIndexOptions,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        let query_str = generate_create_vector_index_query(entity_type, label, properties, options);
+        self.query(query_str).execute().await
+    }
+
     /// Drop an existing index, by specifying its type, entity, label and specific properties
     ///
     /// # Arguments
@@ -257,16 +862,394 @@ impl AsyncGraph {
         tracing::instrument(name = "Graph Drop Index", skip_all, level = "info")
     )]
     pub async fn drop_index<P: Display>(
-        &mut self,
+        &self,
         index_field_type: IndexType,
         entity_type: EntityType,
         label: &str,
         properties: &[P],
-    ) -> FalkorResult<QueryResult<LazyResultSet>> {
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
         let query_str = generate_drop_index_query(index_field_type, entity_type, label, properties);
         self.query(query_str).execute().await
     }
 
+    /// Performs a K-nearest-neighbours search over a vector index, using the `db.idx.vector.queryNodes` procedure
+    ///
+    /// # Arguments
+    /// * `label`: The label of the indexed nodes
+    /// * `property`: The indexed vector property
+    /// * `query_vector`: The vector to search around
+    /// * `k`: The maximum number of results to return
+    ///
+    /// # Returns
+    /// A [`Vec`] of the nearest [`Node`]s, along with their distance from the query vector, closest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph KNN Search", skip_all, level = "info")
+    )]
+    pub async fn knn_search(
+        &self,
+        label: &str,
+        property: &str,
+        query_vector: &[f32],
+        k: usize,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_knn_query(label, property, query_vector, k);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_node_distance_results)
+    }
+
+    /// Finds all nodes with a point property within the specified distance of a center point
+    ///
+    /// # Arguments
+    /// * `label`: The label of the nodes to search
+    /// * `property`: The [`Point`] property to measure the distance from
+    /// * `center`: The center of the search radius
+    /// * `radius_meters`: The maximal distance, in meters
+    ///
+    /// # Returns
+    /// A [`Vec`] of the matching [`Node`]s, along with their distance from the center in meters, closest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Nodes Within Radius", skip_all, level = "info")
+    )]
+    pub async fn nodes_within_radius(
+        &self,
+        label: &str,
+        property: &str,
+        center: &Point,
+        radius_meters: f64,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_radius_query(label, property, center, radius_meters)?;
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_node_distance_results)
+    }
+
+    /// Finds the shortest paths between two nodes, using the `algo.SPpaths` procedure
+    ///
+    /// # Arguments
+    /// * `source_id`: The entity ID of the node the paths start at
+    /// * `target_id`: The entity ID of the node the paths end at
+    /// * `options`: The [`ShortestPathOptions`] controlling the traversal
+    ///
+    /// # Returns
+    /// A [`Vec`] of the shortest [`Path`]s found
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Shortest Paths", skip_all, level = "info")
+    )]
+    pub async fn shortest_paths(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        options: &ShortestPathOptions,
+    ) -> FalkorResult<Vec<Path>> {
+        let query_str = generate_shortest_paths_query(source_id, Some(target_id), options);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_paths)
+    }
+
+    /// Finds the shortest paths from a node to every node reachable from it, using the `algo.SSpaths` procedure
+    ///
+    /// # Arguments
+    /// * `source_id`: The entity ID of the node the paths start at
+    /// * `options`: The [`ShortestPathOptions`] controlling the traversal
+    ///
+    /// # Returns
+    /// A [`Vec`] of the shortest [`Path`]s found
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Single Source Shortest Paths", skip_all, level = "info")
+    )]
+    pub async fn single_source_shortest_paths(
+        &self,
+        source_id: i64,
+        options: &ShortestPathOptions,
+    ) -> FalkorResult<Vec<Path>> {
+        let query_str = generate_shortest_paths_query(source_id, None, options);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_paths)
+    }
+
+    /// Traverses the graph breadth-first from a node, using the `algo.BFS` procedure
+    ///
+    /// # Arguments
+    /// * `start_node_id`: The entity ID of the node the traversal starts at
+    /// * `max_depth`: The maximal number of relationships to traverse from the start node, 0 means unlimited
+    /// * `relationship_filter`: Only traverse outgoing relationships of this type, all types are traversed if not specified
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`TraversalLevel`]s, ordered by depth, where the first level holds only the start node
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph BFS", skip_all, level = "info")
+    )]
+    pub async fn bfs(
+        &self,
+        start_node_id: i64,
+        max_depth: u32,
+        relationship_filter: Option<&str>,
+    ) -> FalkorResult<Vec<TraversalLevel>> {
+        let query_str = generate_bfs_query(start_node_id, max_depth, relationship_filter);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_bfs_levels)
+    }
+
+    /// Ranks nodes by their PageRank score, using the `algo.pageRank` procedure
+    ///
+    /// # Arguments
+    /// * `label`: Only rank nodes with this label, all nodes are ranked if not specified
+    /// * `relationship_type`: Only follow relationships of this type, all types are followed if not specified
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`Node`]s and their scores, ordered from the highest score
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph PageRank", skip_all, level = "info")
+    )]
+    pub async fn page_rank(
+        &self,
+        label: Option<&str>,
+        relationship_type: Option<&str>,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_page_rank_query(label, relationship_type);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_node_scores)
+    }
+
+    /// Scores nodes by their betweenness centrality, using the `algo.betweenness` procedure
+    ///
+    /// # Arguments
+    /// * `node_labels`: Only consider nodes with these labels, all nodes are considered if this is empty
+    /// * `relationship_types`: Only follow relationships of these types, all types are followed if this is empty
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`Node`]s and their scores, ordered from the highest score
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Betweenness Centrality", skip_all, level = "info")
+    )]
+    pub async fn betweenness_centrality(
+        &self,
+        node_labels: &[&str],
+        relationship_types: &[&str],
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_betweenness_query(node_labels, relationship_types);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_node_scores)
+    }
+
+    /// Scores nodes by their degree, using the `algo.degree` procedure
+    ///
+    /// # Arguments
+    /// * `label`: Only score nodes with this label, all nodes are scored if not specified
+    /// * `relationship_types`: Only count relationships of these types, all types are counted if this is empty
+    /// * `direction`: Which relationships of each node to count
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`Node`]s and their degrees, ordered from the highest degree
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Degree Centrality", skip_all, level = "info")
+    )]
+    pub async fn degree_centrality(
+        &self,
+        label: Option<&str>,
+        relationship_types: &[&str],
+        direction: TraversalDirection,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_degree_query(label, relationship_types, direction);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_node_scores)
+    }
+
+    /// Marks an entity as soft deleted, by setting its [`SOFT_DELETE_PROPERTY`](crate::SOFT_DELETE_PROPERTY) to the current time
+    /// Soft deleted entities can be excluded from queries using [`QueryBuilder::excluding_soft_deleted`]
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the entity is a node or a relationship
+    /// * `entity_id`: The entity ID of the entity to mark
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Soft Delete", skip_all, level = "info")
+    )]
+    pub async fn soft_delete(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        self.query(generate_soft_delete_query(entity_type, entity_id, false))
+            .execute()
+            .await
+    }
+
+    /// Restores a soft deleted entity, by removing its [`SOFT_DELETE_PROPERTY`](crate::SOFT_DELETE_PROPERTY)
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the entity is a node or a relationship
+    /// * `entity_id`: The entity ID of the entity to restore
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Restore Soft Deleted", skip_all, level = "info")
+    )]
+    pub async fn restore_soft_deleted(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        self.query(generate_soft_delete_query(entity_type, entity_id, true))
+            .execute()
+            .await
+    }
+
+    /// Writes declarative fixtures to the graph, merging all of their nodes, followed by the edges between them,
+    /// in a single query, so seeding either fully succeeds or has no effect
+    ///
+    /// # Arguments
+    /// * `fixtures`: The [`GraphFixtures`] to write
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics, or an error if the fixtures are inconsistent
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Seed", skip_all, level = "info")
+    )]
+    pub async fn seed(
+        &self,
+        fixtures: &GraphFixtures,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        self.query(generate_seed_query(fixtures)?).execute().await
+    }
+
+    /// Sets a property of a node or a relationship, returning the value the server stored
+    /// The stored value is compared with the written one, and any value whose type the server coerced,
+    /// E.g. a float read back as an integer, is logged, or fails the call when strict typing is enabled,
+    /// see [`FalkorClientBuilder::with_strict_typing`](crate::FalkorClientBuilder::with_strict_typing).
+    /// Note that the property is written either way, a failed call only reports the coercion.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the entity is a node or a relationship
+    /// * `entity_id`: The entity ID of the entity to update
+    /// * `property`: The name of the property to set
+    /// * `value`: The new value of the property
+    ///
+    /// # Returns
+    /// The value read back from the server, [`None`] if no entity has the provided ID, or [`FalkorDBError::TypeCoerced`] in strict mode
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Set Property", skip_all, level = "info")
+    )]
+    pub async fn set_property(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+        property: &str,
+        value: &FalkorValue,
+    ) -> FalkorResult<Option<FalkorValue>> {
+        let query_str = generate_set_property_query(entity_type, entity_id, property, value)?;
+        let res = self.query(query_str).execute().await?;
+        check_property_round_trip(property, value, res, self.client.strict_typing())
+    }
+
+    /// Sets a node property, while keeping its previous values as a queryable history
+    /// Every call creates a [`PROPERTY_VERSION_LABEL`](crate::PROPERTY_VERSION_LABEL) node, connected to the node through a
+    /// [`PROPERTY_VERSION_RELATIONSHIP`](crate::PROPERTY_VERSION_RELATIONSHIP) relationship, holding the value and the timestamps between which it is valid.
+    /// The version valid at `valid_from` is closed, and the property on the node itself is only updated if the new version is the latest one.
+    ///
+    /// # Arguments
+    /// * `node_id`: The entity ID of the node to update
+    /// * `property`: The name of the property to set
+    /// * `value`: The new value of the property
+    /// * `valid_from`: The timestamp from which the new value is valid, usually in milliseconds since the epoch
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Set Property Versioned", skip_all, level = "info")
+    )]
+    pub async fn set_property_versioned(
+        &self,
+        node_id: i64,
+        property: &str,
+        value: &FalkorValue,
+        valid_from: i64,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        let query_str = generate_set_versioned_query(node_id, property, value, valid_from)?;
+        self.query(query_str).execute().await
+    }
+
+    /// Returns the value a versioned property held at a given time, see [`AsyncGraph::set_property_versioned`]
+    ///
+    /// # Arguments
+    /// * `node_id`: The entity ID of the node
+    /// * `property`: The name of the versioned property
+    /// * `timestamp`: The time at which to read the property
+    ///
+    /// # Returns
+    /// The value valid at that time, or [`None`] if no version of the property was valid at that time
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Property As Of", skip_all, level = "info")
+    )]
+    pub async fn property_as_of(
+        &self,
+        node_id: i64,
+        property: &str,
+        timestamp: i64,
+    ) -> FalkorResult<Option<FalkorValue>> {
+        let query_str = generate_property_as_of_query(node_id, property, timestamp);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_property_as_of)
+    }
+
+    /// Returns every version of a versioned property, see [`AsyncGraph::set_property_versioned`]
+    ///
+    /// # Arguments
+    /// * `node_id`: The entity ID of the node
+    /// * `property`: The name of the versioned property
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`PropertyVersion`]s, ordered by the time from which they are valid
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Property History", skip_all, level = "info")
+    )]
+    pub async fn property_history(
+        &self,
+        node_id: i64,
+        property: &str,
+    ) -> FalkorResult<Vec<PropertyVersion>> {
+        let query_str = generate_property_history_query(node_id, property);
+        self.ro_query(&query_str)
+            .execute()
+            .await
+            .and_then(parse_property_history)
+    }
+
     /// Calls the DB.CONSTRAINTS procedure on the graph, returning an array of the graph's constraints
     ///
     /// # Returns
@@ -275,12 +1258,132 @@ impl AsyncGraph {
         feature = "tracing",
         tracing::instrument(name = "List Graph Constraints", skip_all, level = "info")
     )]
-    pub async fn list_constraints(&mut self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
+    pub async fn list_constraints(&self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
         ProcedureQueryBuilder::<QueryResult<Vec<Constraint>>, Self>::new(self, "DB.CONSTRAINTS")
             .execute()
             .await
     }
 
+    /// Lists example entities violating a constraint, usually one in [`ConstraintStatus::Failed`](crate::ConstraintStatus::Failed) status,
+    /// so the offending data can be fixed before the constraint is recreated. These are the nodes or relationships missing
+    /// any of the properties of a mandatory constraint, or sharing all the values of a unique one, up to 100 of them.
+    ///
+    /// # Arguments
+    /// * `constraint`: The [`Constraint`] to diagnose, as listed by [`Self::list_constraints`]
+    ///
+    /// # Returns
+    /// The offending entities, each a [`FalkorValue::Node`] or a [`FalkorValue::Edge`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Constraint Violations", skip_all, level = "info")
+    )]
+    pub async fn constraint_violations(
+        &self,
+        constraint: &Constraint,
+    ) -> FalkorResult<Vec<FalkorValue>> {
+        let query = generate_constraint_violations_query(constraint, CONSTRAINT_VIOLATIONS_LIMIT);
+        self.query(query)
+            .execute()
+            .await?
+            .data
+            .map(|row| row.into_iter().next().ok_or(FalkorDBError::ParsingArray))
+            .collect()
+    }
+
+    /// Creates a [`ConstraintBuilder`] for a constraint on this label, a self-documenting alternative to the positional constraint functions
+    ///
+    /// # Arguments
+    /// * `label`: The node label or relationship type to constrain
+    ///
+    /// # Returns
+    /// A [`ConstraintBuilder`] object, describing a mandatory constraint on nodes by default
+    pub fn constraint<'a>(
+        &'a self,
+        label: &str,
+    ) -> ConstraintBuilder<'a, Self> {
+        ConstraintBuilder::new(self, label)
+    }
+
+    /// Waits until a constraint is operational, by polling the graph's constraints
+    /// Constraint creation returns while the constraint is still being verified against the existing entities.
+    ///
+    /// # Arguments
+    /// * `constraint_type`: The type of the constraint
+    /// * `entity_type`: Whether the constraint applies to nodes or relationships
+    /// * `label`: The label or relationship type of the constraint
+    /// * `properties`: The properties of the constraint
+    /// * `timeout`: How long to keep polling, returns [`FalkorDBError::ConstraintWaitTimeout`] once it passes
+    ///
+    /// # Returns
+    /// The operational [`Constraint`], or [`FalkorDBError::ConstraintFailed`] if existing entities violate it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Wait For Constraint", skip_all, level = "info")
+    )]
+    pub async fn wait_for_constraint<P: AsRef<str>>(
+        &self,
+        constraint_type: ConstraintType,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+        timeout: Duration,
+    ) -> FalkorResult<Constraint> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let constraints = self.list_constraints().await?.data;
+            if let Some(constraint) = check_constraint_status(
+                constraints,
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            )? {
+                return Ok(constraint);
+            }
+            if Instant::now() + SCHEMA_POLL_INTERVAL > deadline {
+                return Err(FalkorDBError::ConstraintWaitTimeout);
+            }
+            runtime::sleep(SCHEMA_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Drops every constraint in the graph, optionally only those on the provided label
+    /// The constraints are enumerated using [`Self::list_constraints`], this is useful for resetting the schema in tests and migrations.
+    /// The indices created for unique constraints are not dropped, see [`Self::drop_all_indices`].
+    ///
+    /// # Arguments
+    /// * `label`: Only drop constraints on this label or relationship type, or drop all of them if [`None`]
+    ///
+    /// # Returns
+    /// The number of constraints dropped
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Drop All Graph Constraints", skip_all, level = "info")
+    )]
+    pub async fn drop_all_constraints(
+        &self,
+        label: Option<&str>,
+    ) -> FalkorResult<usize> {
+        let constraints: Vec<Constraint> = self
+            .list_constraints()
+            .await?
+            .data
+            .into_iter()
+            .filter(|constraint| label.is_none_or(|label| constraint.label == label))
+            .collect();
+        for constraint in &constraints {
+            let properties: Vec<&str> = constraint.properties.iter().map(String::as_str).collect();
+            self.drop_constraint(
+                constraint.constraint_type,
+                constraint.entity_type,
+                &constraint.label,
+                &properties,
+            )
+            .await?;
+        }
+        Ok(constraints.len())
+    }
+
     /// Creates a new constraint for this graph, making the provided properties mandatory
     ///
     /// # Arguments
@@ -325,7 +1428,7 @@ impl AsyncGraph {
         tracing::instrument(name = "Create Graph Unique Constraint", skip_all, level = "info")
     )]
     pub async fn create_unique_constraint(
-        &mut self,
+        &self,
         entity_type: EntityType,
         label: String,
         properties: &[&str],
@@ -394,8 +1497,8 @@ impl AsyncGraph {
 }
 
 impl HasGraphSchema for AsyncGraph {
-    fn get_graph_schema_mut(&mut self) -> &mut GraphSchema {
-        &mut self.graph_schema
+    fn get_graph_schema(&self) -> &GraphSchema {
+        &self.graph_schema
     }
 }
 
@@ -409,7 +1512,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_create_drop_index() {
-        let mut graph = open_empty_async_test_graph("test_create_drop_index_async").await;
+        let graph = open_empty_async_test_graph("test_create_drop_index_async").await;
 
         graph
             .inner
@@ -447,7 +1550,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_list_indices() {
-        let mut graph = create_async_test_client().await.select_graph("imdb");
+        let graph = create_async_test_client().await.select_graph("imdb");
         let indices = graph.list_indices().await.expect("Could not list indices");
 
         assert_eq!(indices.data.len(), 1);
@@ -487,7 +1590,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_create_drop_unique_constraint() {
-        let mut graph = open_empty_async_test_graph("test_unique_constraint_async").await;
+        let graph = open_empty_async_test_graph("test_unique_constraint_async").await;
 
         graph
             .inner
@@ -513,7 +1616,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_list_constraints() {
-        let mut graph = open_empty_async_test_graph("test_list_constraint_async").await;
+        let graph = open_empty_async_test_graph("test_list_constraint_async").await;
 
         graph
             .inner
@@ -535,7 +1638,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_slowlog() {
-        let mut graph = open_empty_async_test_graph("test_slowlog_async").await;
+        let graph = open_empty_async_test_graph("test_slowlog_async").await;
 
         graph
             .inner
@@ -581,7 +1684,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_explain() {
-        let mut graph = create_async_test_client().await.select_graph("imdb");
+        let graph = create_async_test_client().await.select_graph("imdb");
 
         let execution_plan = graph.explain("MATCH (a:actor) WITH a MATCH (b:actor) WHERE a.age = b.age AND a <> b RETURN a, collect(b) LIMIT 100").execute().await.expect("Could not create execution plan");
         assert_eq!(execution_plan.plan().len(), 7);
@@ -596,7 +1699,7 @@ mod tests {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_profile() {
-        let mut graph = open_empty_async_test_graph("test_profile_async").await;
+        let graph = open_empty_async_test_graph("test_profile_async").await;
 
         let execution_plan = graph
             .inner
@@ -4,23 +4,65 @@
  */
 
 use crate::{
-    client::blocking::FalkorSyncClientInner,
-    graph::{generate_create_index_query, generate_drop_index_query, HasGraphSchema},
+    client::{blocking::FalkorSyncClientInner, circuit::CircuitBreaker},
+    graph::{
+        algorithms::{
+            generate_betweenness_query, generate_bfs_query, generate_degree_query,
+            generate_page_rank_query, generate_shortest_paths_query, parse_bfs_levels,
+            parse_node_scores, parse_paths,
+        },
+        collect_names,
+        constraint_builder::{
+            check_constraint_status, generate_constraint_violations_query,
+            CONSTRAINT_VIOLATIONS_LIMIT,
+        },
+        find_operational_index, generate_create_index_query, generate_create_vector_index_query,
+        generate_drop_all_indices_queries, generate_drop_index_query, generate_knn_query,
+        generate_radius_query, has_time_for_retry,
+        idempotency::idempotency_key,
+        lock::{
+            parse_lock_script_reply, parse_set_nx_reply, ttl_millis, EXTEND_LOCK_SCRIPT,
+            LOCK_RETRY_INTERVAL, RELEASE_LOCK_SCRIPT,
+        },
+        migration::{
+            parse_schema_version, pending_migrations, schema_version_key, RECORD_VERSION_SCRIPT,
+        },
+        parse_node_distance_results,
+        seed::generate_seed_query,
+        soft_delete::generate_soft_delete_query,
+        strict_typing::{check_property_round_trip, generate_set_property_query},
+        versioning::{
+            generate_property_as_of_query, generate_property_history_query,
+            generate_set_versioned_query, parse_property_as_of, parse_property_history,
+        },
+        with_attempts, HasGraphSchema, LABELS_QUERY, PROPERTY_KEYS_QUERY, RELATIONSHIP_TYPES_QUERY,
+        RETRY_BACKOFF, SCHEMA_POLL_INTERVAL,
+    },
     parser::redis_value_as_vec,
-    Constraint, ConstraintType, EntityType, ExecutionPlan, FalkorIndex, FalkorResult, GraphSchema,
-    IndexType, LazyResultSet, ProcedureQueryBuilder, QueryBuilder, QueryResult, SlowlogEntry,
+    CircuitStats, Constraint, ConstraintBuilder, ConstraintType, EntityType, ExecutionPlan,
+    FalkorDBError, FalkorIndex, FalkorResult, FalkorValue, GraphFixtures, GraphLock, GraphSchema,
+    IndexBuilder, IndexType, LazyResultSet, Migration, MigrationReport, Node, Path, Point,
+    ProcedureQueryBuilder, PropertyVersion, QueryBuilder, QueryResult, SchemaType,
+    ShortestPathOptions, SlowlogEntry, TraversalDirection, TraversalLevel, VectorIndexOptions,
+};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{collections::HashMap, fmt::Display, sync::Arc};
 
 /// The main graph API, this allows the user to perform graph operations while exposing as little details as possible.
 /// # Thread Safety
-/// This struct is NOT thread safe, and synchronization is up to the user.
-/// Graph schema is not shared between instances of SyncGraph, even with the same name, but cloning will maintain the current schema
+/// All operations take a shared reference, as the graph schema is refreshed behind internal locks,
+/// so a single graph handle can be shared between threads, E.g. behind an [`Arc`](std::sync::Arc).
+/// Graph schema is shared between all instances of SyncGraph with the same name selected from the same client, including clones
 #[derive(Clone)]
 pub struct SyncGraph {
     client: Arc<FalkorSyncClientInner>,
     graph_name: String,
-    graph_schema: GraphSchema,
+    graph_schema: Arc<GraphSchema>,
+    circuit: Option<Arc<CircuitBreaker>>,
 }
 
 impl SyncGraph {
@@ -28,9 +70,11 @@ impl SyncGraph {
         client: Arc<FalkorSyncClientInner>,
         graph_name: T,
     ) -> Self {
+        let graph_name = graph_name.to_string();
         Self {
-            graph_name: graph_name.to_string(),
-            graph_schema: GraphSchema::new(graph_name, client.clone()), // Required for requesting refreshes
+            graph_schema: client.graph_schema(&graph_name),
+            circuit: client.graph_circuit(&graph_name),
+            graph_name,
             client,
         }
     }
@@ -43,10 +87,30 @@ impl SyncGraph {
         self.graph_name.as_str()
     }
 
+    /// Returns the schema cache of this graph, shared by all handles to it, E.g. to persist or restore it
+    ///
+    /// # Returns
+    /// A reference to the [`GraphSchema`] of this graph
+    pub fn graph_schema(&self) -> &GraphSchema {
+        &self.graph_schema
+    }
+
     pub(crate) fn get_client(&self) -> &Arc<FalkorSyncClientInner> {
         &self.client
     }
 
+    pub(crate) fn circuit(&self) -> Option<&CircuitBreaker> {
+        self.circuit.as_deref()
+    }
+
+    /// Returns the state and counters of this graph's circuit breaker, see [`FalkorClientBuilder::with_graph_circuit_breaker`](crate::FalkorClientBuilder::with_graph_circuit_breaker)
+    ///
+    /// # Returns
+    /// The [`CircuitStats`] of this graph, or [`None`] if circuit breakers are disabled
+    pub fn circuit_stats(&self) -> Option<CircuitStats> {
+        self.circuit.as_ref().map(|circuit| circuit.stats())
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Graph Execute Command", skip_all, level = "info")
@@ -68,7 +132,7 @@ impl SyncGraph {
         feature = "tracing",
         tracing::instrument(name = "Delete Graph", skip_all, level = "info")
     )]
-    pub fn delete(&mut self) -> FalkorResult<()> {
+    pub fn delete(&self) -> FalkorResult<()> {
         self.execute_command("GRAPH.DELETE", None, None)?;
         self.graph_schema.clear();
         Ok(())
@@ -99,8 +163,200 @@ impl SyncGraph {
         self.execute_command("GRAPH.SLOWLOG", None, Some(&["RESET"]))
     }
 
+    /// Attempts to acquire an exclusive lock on this graph, held on a companion key set with SET NX and a TTL
+    /// The lock is advisory, it only excludes other callers of this function, and not writes in general.
+    ///
+    /// # Arguments
+    /// * `ttl`: How long the lock is held before expiring, unless it is released or extended first
+    ///
+    /// # Returns
+    /// The acquired [`GraphLock`], or [`None`] if the lock is currently held by someone else
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Try Lock", skip_all, level = "info")
+    )]
+    pub fn try_lock(
+        &self,
+        ttl: Duration,
+    ) -> FalkorResult<Option<GraphLock>> {
+        let lock = GraphLock::new(&self.graph_name);
+        let ttl = ttl_millis(ttl);
+        self.client
+            .borrow_connection(self.client.clone())?
+            .execute_command(
+                Some(lock.key()),
+                "SET",
+                None,
+                Some(&[lock.token(), "NX", "PX", ttl.as_str()]),
+            )
+            .map(|res| parse_set_nx_reply(res).then_some(lock))
+    }
+
+    /// Acquires an exclusive lock on this graph, retrying until the lock is acquired or the timeout passes, see [`SyncGraph::try_lock`]
+    ///
+    /// # Arguments
+    /// * `ttl`: How long the lock is held before expiring, unless it is released or extended first
+    /// * `timeout`: How long to keep retrying, returns [`FalkorDBError::LockTimeout`](crate::FalkorDBError::LockTimeout) once it passes
+    ///
+    /// # Returns
+    /// The acquired [`GraphLock`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Lock", skip_all, level = "info")
+    )]
+    pub fn lock(
+        &self,
+        ttl: Duration,
+        timeout: Duration,
+    ) -> FalkorResult<GraphLock> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(lock) = self.try_lock(ttl)? {
+                return Ok(lock);
+            }
+            if Instant::now() + LOCK_RETRY_INTERVAL > deadline {
+                return Err(FalkorDBError::LockTimeout);
+            }
+            std::thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
+
+    /// Resets the TTL of a held lock, allowing long-running jobs to keep holding it
+    ///
+    /// # Arguments
+    /// * `lock`: The held [`GraphLock`]
+    /// * `ttl`: The new TTL, counted from now
+    ///
+    /// # Returns
+    /// Whether the lock was still held, if it wasn't, it may have already been acquired by someone else
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Extend Lock", skip_all, level = "info")
+    )]
+    pub fn extend_lock(
+        &self,
+        lock: &GraphLock,
+        ttl: Duration,
+    ) -> FalkorResult<bool> {
+        let ttl = ttl_millis(ttl);
+        self.client
+            .borrow_connection(self.client.clone())?
+            .execute_command(
+                None,
+                "EVAL",
+                Some(EXTEND_LOCK_SCRIPT),
+                Some(&["1", lock.key(), lock.token(), ttl.as_str()]),
+            )
+            .map(parse_lock_script_reply)
+    }
+
+    /// Releases a held lock, only if it is still held by the provided [`GraphLock`]
+    ///
+    /// # Arguments
+    /// * `lock`: The held [`GraphLock`]
+    ///
+    /// # Returns
+    /// Whether the lock was still held, if it wasn't, it had already expired
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Unlock", skip_all, level = "info")
+    )]
+    pub fn unlock(
+        &self,
+        lock: GraphLock,
+    ) -> FalkorResult<bool> {
+        self.client
+            .borrow_connection(self.client.clone())?
+            .execute_command(
+                None,
+                "EVAL",
+                Some(RELEASE_LOCK_SCRIPT),
+                Some(&["1", lock.key(), lock.token()]),
+            )
+            .map(parse_lock_script_reply)
+    }
+
+    /// Applies the migrations this graph is missing, while holding the graph's lock, see [`SyncGraph::lock`]
+    /// Replicas starting simultaneously apply each migration exactly once, the others wait for the lock to be released,
+    /// and then verify the graph reached the latest known version. The version is recorded in a companion key, see [`SCHEMA_VERSION_KEY_PREFIX`](crate::SCHEMA_VERSION_KEY_PREFIX).
+    ///
+    /// # Arguments
+    /// * `migrations`: All known migrations, in ascending version order
+    /// * `lock_ttl`: How long the lock is held, it is extended after each applied migration, so this only has to cover the longest one
+    /// * `wait_timeout`: How long to wait for another replica to finish migrating, returns [`FalkorDBError::LockTimeout`](crate::FalkorDBError::LockTimeout) once it passes
+    ///
+    /// # Returns
+    /// A [`MigrationReport`] of the applied migrations and the resulting version,
+    /// or [`FalkorDBError::SchemaVersionMismatch`](crate::FalkorDBError::SchemaVersionMismatch) if the graph was migrated past the latest known migration
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Run Migrations", skip_all, level = "info")
+    )]
+    pub fn run_migrations(
+        &self,
+        migrations: &[Migration],
+        lock_ttl: Duration,
+        wait_timeout: Duration,
+    ) -> FalkorResult<MigrationReport> {
+        let lock = self.lock(lock_ttl, wait_timeout)?;
+        let res = self.apply_migrations(migrations, &lock, lock_ttl);
+        let released = self.unlock(lock);
+        let report = res?;
+        released?;
+        Ok(report)
+    }
+
+    fn apply_migrations(
+        &self,
+        migrations: &[Migration],
+        lock: &GraphLock,
+        lock_ttl: Duration,
+    ) -> FalkorResult<MigrationReport> {
+        let version_key = schema_version_key(&self.graph_name);
+        let current_version = self
+            .client
+            .borrow_connection(self.client.clone())?
+            .execute_command(Some(version_key.as_str()), "GET", None, None)
+            .and_then(parse_schema_version)?;
+
+        let ttl = ttl_millis(lock_ttl);
+        let mut report = MigrationReport {
+            applied: Vec::new(),
+            version: current_version,
+        };
+        for migration in pending_migrations(migrations, current_version)? {
+            for query in &migration.queries {
+                self.query(query).execute()?;
+            }
+
+            let version = migration.version.to_string();
+            let still_held = self
+                .client
+                .borrow_connection(self.client.clone())?
+                .execute_command(
+                    None,
+                    "EVAL",
+                    Some(RECORD_VERSION_SCRIPT),
+                    Some(&[
+                        "2",
+                        lock.key(),
+                        version_key.as_str(),
+                        lock.token(),
+                        version.as_str(),
+                        ttl.as_str(),
+                    ]),
+                )
+                .map(parse_lock_script_reply)?;
+            if !still_held {
+                return Err(FalkorDBError::LockLost);
+            }
+            report.applied.push(migration.version);
+            report.version = migration.version;
+        }
+        Ok(report)
+    }
+
     /// Creates a [`QueryBuilder`] for this graph, in an attempt to profile a specific query
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     ///
     /// # Arguments
     /// * `query_string`: The query to profile
@@ -108,14 +364,13 @@ impl SyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object, which when performed will return an [`ExecutionPlan`]
     pub fn profile<'a>(
-        &'a mut self,
+        &'a self,
         query_string: &'a str,
     ) -> QueryBuilder<'a, ExecutionPlan, &'a str, Self> {
         QueryBuilder::<'a>::new(self, "GRAPH.PROFILE", query_string)
     }
 
     /// Creates a [`QueryBuilder`] for this graph, in an attempt to explain a specific query
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     ///
     /// # Arguments
     /// * `query_string`: The query to explain the process for
@@ -123,14 +378,13 @@ impl SyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object, which when performed will return an [`ExecutionPlan`]
     pub fn explain<'a>(
-        &'a mut self,
+        &'a self,
         query_string: &'a str,
     ) -> QueryBuilder<'a, ExecutionPlan, &'a str, Self> {
         QueryBuilder::new(self, "GRAPH.EXPLAIN", query_string)
     }
 
     /// Creates a [`QueryBuilder`] for this graph
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     ///
     /// # Arguments
     /// * `query_string`: The query to run
@@ -138,14 +392,13 @@ impl SyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object, which when performed will return a [`QueryResult<FalkorResultSet>`]
     pub fn query<T: Display>(
-        &mut self,
+        &self,
         query_string: T,
-    ) -> QueryBuilder<QueryResult<LazyResultSet>, T, Self> {
+    ) -> QueryBuilder<'_, QueryResult<LazyResultSet<'_>>, T, Self> {
         QueryBuilder::new(self, "GRAPH.QUERY", query_string)
     }
 
     /// Creates a [`QueryBuilder`] for this graph, for a readonly query
-    /// This [`QueryBuilder`] has to be dropped or ran using [`QueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only queries are more limited with the operations they are allowed to perform.
     ///
     /// # Arguments
@@ -154,39 +407,198 @@ impl SyncGraph {
     /// # Returns
     /// A [`QueryBuilder`] object
     pub fn ro_query<'a>(
-        &'a mut self,
+        &'a self,
         query_string: &'a str,
     ) -> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, &'a str, Self> {
         QueryBuilder::new(self, "GRAPH.RO_QUERY", query_string)
     }
 
+    /// Runs a query registered in the client's [`QueryRegistry`] by name, see [`FalkorClientBuilder::with_query_registry`](crate::FalkorClientBuilder::with_query_registry)
+    /// The parameters are validated against the ones the query references before it is sent, and the query's name and fingerprint are recorded in the tracing span.
+    ///
+    /// # Arguments
+    /// * `name`: The name the query was registered by
+    /// * `params`: The query's parameters, in key-val format
+    ///
+    /// # Returns
+    /// A [`QueryResult`] with a [`LazyResultSet`], or an error if the query is not registered, or the parameters do not match it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Graph Run Registered Query",
+            skip_all,
+            level = "info",
+            fields(query_name = name, fingerprint)
+        )
+    )]
+    pub fn run<'a>(
+        &'a self,
+        name: &str,
+        params: &'a HashMap<String, String>,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+        let template = self.client.query_registry();
+        let template = template.resolve(name, params)?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("fingerprint", format!("{:016x}", template.fingerprint));
+
+        let command = match template.read_only {
+            true => "GRAPH.RO_QUERY",
+            false => "GRAPH.QUERY",
+        };
+        QueryBuilder::<QueryResult<LazyResultSet>, _, _>::new(self, command, template.query.clone())
+            .with_params(params)
+            .execute()
+    }
+
+    /// Runs a write query at most once per idempotency key, so retried requests don't apply the same write twice
+    /// Processed keys are recorded on companion keys prefixed with [`IDEMPOTENCY_KEY_PREFIX`](crate::IDEMPOTENCY_KEY_PREFIX),
+    /// the key is recorded before the query runs, and removed again if the query fails, so failed queries can be retried.
+    ///
+    /// # Arguments
+    /// * `key`: The idempotency key identifying this write, such as a request or message ID
+    /// * `query_string`: The query to run
+    /// * `ttl`: How long to remember the key, retries arriving after it expires will run the query again
+    ///
+    /// # Returns
+    /// The [`QueryResult`] of the query, or [`None`] if this key was already processed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Execute Idempotent", skip_all, level = "info")
+    )]
+    pub fn execute_idempotent<T: Display>(
+        &self,
+        key: &str,
+        query_string: T,
+        ttl: Duration,
+    ) -> FalkorResult<Option<QueryResult<LazyResultSet<'_>>>> {
+        let client = self.client.clone();
+        let key = idempotency_key(&self.graph_name, key);
+        let ttl = ttl_millis(ttl);
+        let first_attempt = client
+            .borrow_connection(client.clone())?
+            .execute_command(
+                Some(&key),
+                "SET",
+                None,
+                Some(&["1", "NX", "PX", ttl.as_str()]),
+            )
+            .map(parse_set_nx_reply)?;
+        if !first_attempt {
+            return Ok(None);
+        }
+
+        match self.query(query_string).execute() {
+            Ok(res) => Ok(Some(res)),
+            Err(err) => {
+                // Forget the key, so the failed write can be retried, the original error is more relevant than a cleanup failure
+                client
+                    .borrow_connection(client.clone())
+                    .and_then(|mut conn| conn.execute_command(Some(&key), "DEL", None, None))
+                    .ok();
+                Err(err)
+            }
+        }
+    }
+
+    /// Runs a closure, re-running it when it fails on a connection error
+    /// Failed connections are replaced as the errors are returned, so each retry runs over a fresh connection.
+    /// The closure may run several times, so it should be safe to repeat, such as a single query or an idempotent write.
+    ///
+    /// # Arguments
+    /// * `max_retries`: How many times to re-run the closure, after its first attempt
+    /// * `operation`: The closure to run, receiving this graph
+    ///
+    /// # Returns
+    /// The result of the first successful attempt, or the error of the last attempt,
+    /// wrapped in [`FalkorDBError::RetriesFailed`] if there was more than one
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph With Retries", skip_all, level = "info")
+    )]
+    pub fn with_retries<T, F: FnMut(&Self) -> FalkorResult<T>>(
+        &self,
+        max_retries: usize,
+        operation: F,
+    ) -> FalkorResult<T> {
+        self.retry_until(None, max_retries, operation)
+    }
+
+    /// Runs a closure, re-running it when it fails on a connection error, as long as the deadline allows
+    /// All attempts share a single budget, the closure receives the deadline, and should pass it to its queries using
+    /// [`QueryBuilder::with_deadline`], so every attempt only gets the time left, and no retry is started that can't complete in time.
+    ///
+    /// # Arguments
+    /// * `deadline`: The instant by which the operation must complete, including all of its attempts
+    /// * `max_retries`: How many times at most to re-run the closure, after its first attempt
+    /// * `operation`: The closure to run, receiving this graph and the deadline
+    ///
+    /// # Returns
+    /// The result of the first successful attempt, or the error of the last attempt,
+    /// wrapped in [`FalkorDBError::RetriesFailed`] if there was more than one
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph With Retries Within Deadline", skip_all, level = "info")
+    )]
+    pub fn with_retries_within<T, F: FnMut(&Self, Instant) -> FalkorResult<T>>(
+        &self,
+        deadline: Instant,
+        max_retries: usize,
+        mut operation: F,
+    ) -> FalkorResult<T> {
+        self.retry_until(Some(deadline), max_retries, |graph| {
+            operation(graph, deadline)
+        })
+    }
+
+    fn retry_until<T, F: FnMut(&Self) -> FalkorResult<T>>(
+        &self,
+        deadline: Option<Instant>,
+        max_retries: usize,
+        mut operation: F,
+    ) -> FalkorResult<T> {
+        let mut backoff = RETRY_BACKOFF;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let err = match operation(self) {
+                Err(err) if err.is_retryable() => err,
+                res => return res.map_err(|err| with_attempts(attempts, err)),
+            };
+            if attempts > max_retries || !has_time_for_retry(deadline, backoff) {
+                return Err(with_attempts(attempts, err));
+            }
+            std::thread::sleep(backoff);
+            backoff *= 2;
+        }
+    }
+
     /// Creates a [`ProcedureQueryBuilder`] for this graph
-    /// This [`ProcedureQueryBuilder`] has to be dropped or ran using [`ProcedureQueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only queries are more limited with the operations they are allowed to perform.
     ///
     /// # Arguments
     /// * `procedure_name`: The name of the procedure to call
     ///
     /// # Returns
-    /// A [`ProcedureQueryBuilder`] object
+    /// A [`ProcedureQueryBuilder`] object, whose output type `P` determines how results are parsed,
+    /// E.g. `QueryResult<Vec<(String, f64)>>` converts each row of yielded columns into a tuple, see [`FromYieldRow`](crate::FromYieldRow)
     pub fn call_procedure<'a, P>(
-        &'a mut self,
+        &'a self,
         procedure_name: &'a str,
     ) -> ProcedureQueryBuilder<'a, P, Self> {
         ProcedureQueryBuilder::new(self, procedure_name)
     }
 
     /// Creates a [`ProcedureQueryBuilder`] for this graph, for a readonly procedure
-    /// This [`ProcedureQueryBuilder`] has to be dropped or ran using [`ProcedureQueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only procedures are more limited with the operations they are allowed to perform.
     ///
     /// # Arguments
     /// * `procedure_name`: The name of the procedure to call
     ///
     /// # Returns
-    /// A [`ProcedureQueryBuilder`] object
+    /// A [`ProcedureQueryBuilder`] object, whose output type `P` determines how results are parsed,
+    /// E.g. `QueryResult<Vec<(String, f64)>>` converts each row of yielded columns into a tuple, see [`FromYieldRow`](crate::FromYieldRow)
     pub fn call_procedure_ro<'a, P>(
-        &'a mut self,
+        &'a self,
         procedure_name: &'a str,
     ) -> ProcedureQueryBuilder<'a, P, Self> {
         ProcedureQueryBuilder::new_readonly(self, procedure_name)
@@ -200,11 +612,148 @@ impl SyncGraph {
         feature = "tracing",
         tracing::instrument(name = "List Graph Indices", skip_all, level = "info")
     )]
-    pub fn list_indices(&mut self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
+    pub fn list_indices(&self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
         ProcedureQueryBuilder::<QueryResult<Vec<FalkorIndex>>, Self>::new(self, "DB.INDEXES")
             .execute()
     }
 
+    /// Eagerly populates the cached graph schema with all of the graph's labels, properties and relationship types,
+    /// so the first queries returning them don't pay for refreshing it, E.g. during startup of latency-sensitive services.
+    /// Schema entities created afterwards are still fetched on demand.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Preload Graph Schema", skip_all, level = "info")
+    )]
+    pub fn preload_schema(&self) -> FalkorResult<()> {
+        let labels = self.labels()?;
+        let properties = self.property_keys()?;
+        let relationships = self.relationship_types()?;
+
+        self.graph_schema.populate(SchemaType::Labels, labels);
+        self.graph_schema
+            .populate(SchemaType::Properties, properties);
+        self.graph_schema
+            .populate(SchemaType::Relationships, relationships);
+        Ok(())
+    }
+
+    /// Calls the DB.LABELS procedure on the graph, returning all the node labels it contains
+    /// This always queries the server, regardless of the graph schema cached for parsing results.
+    ///
+    /// # Returns
+    /// A [`Vec`] of the node labels, in the order they were created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Labels", skip_all, level = "info")
+    )]
+    pub fn labels(&self) -> FalkorResult<Vec<String>> {
+        self.ro_query(LABELS_QUERY)
+            .execute()
+            .map(|res| collect_names(res.data))
+    }
+
+    /// Calls the DB.PROPERTYKEYS procedure on the graph, returning all the property keys it contains
+    /// This always queries the server, regardless of the graph schema cached for parsing results.
+    ///
+    /// # Returns
+    /// A [`Vec`] of the property keys, in the order they were created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Property Keys", skip_all, level = "info")
+    )]
+    pub fn property_keys(&self) -> FalkorResult<Vec<String>> {
+        self.ro_query(PROPERTY_KEYS_QUERY)
+            .execute()
+            .map(|res| collect_names(res.data))
+    }
+
+    /// Calls the DB.RELATIONSHIPTYPES procedure on the graph, returning all the relationship types it contains
+    /// This always queries the server, regardless of the graph schema cached for parsing results.
+    ///
+    /// # Returns
+    /// A [`Vec`] of the relationship types, in the order they were created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Relationship Types", skip_all, level = "info")
+    )]
+    pub fn relationship_types(&self) -> FalkorResult<Vec<String>> {
+        self.ro_query(RELATIONSHIP_TYPES_QUERY)
+            .execute()
+            .map(|res| collect_names(res.data))
+    }
+
+    /// Waits until an index on the provided label and properties is operational, by polling the graph's indices
+    /// Index creation returns while the index is still under construction, so this allows waiting until it can actually be used.
+    ///
+    /// # Arguments
+    /// * `label`: The label or relationship type of the index
+    /// * `properties`: The properties the index should cover
+    /// * `timeout`: How long to keep polling, returns [`FalkorDBError::IndexWaitTimeout`] once it passes
+    ///
+    /// # Returns
+    /// The operational [`FalkorIndex`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Wait For Index", skip_all, level = "info")
+    )]
+    pub fn wait_for_index<P: AsRef<str>>(
+        &self,
+        label: &str,
+        properties: &[P],
+        timeout: Duration,
+    ) -> FalkorResult<FalkorIndex> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let indices = self.list_indices()?.data;
+            if let Some(index) = find_operational_index(indices, label, properties) {
+                return Ok(index);
+            }
+            if Instant::now() + SCHEMA_POLL_INTERVAL > deadline {
+                return Err(FalkorDBError::IndexWaitTimeout);
+            }
+            std::thread::sleep(SCHEMA_POLL_INTERVAL);
+        }
+    }
+
+    /// Drops every index in the graph, optionally only those on the provided label
+    /// The indices are enumerated using [`Self::list_indices`], this is useful for resetting the schema in tests and migrations.
+    /// Indices backing unique constraints cannot be dropped while the constraint exists, so drop the constraints first using [`Self::drop_all_constraints`].
+    ///
+    /// # Arguments
+    /// * `label`: Only drop indices on this label or relationship type, or drop all of them if [`None`]
+    ///
+    /// # Returns
+    /// The number of drop queries performed, one per index type of each label
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Drop All Graph Indices", skip_all, level = "info")
+    )]
+    pub fn drop_all_indices(
+        &self,
+        label: Option<&str>,
+    ) -> FalkorResult<usize> {
+        let indices = self.list_indices()?.data;
+        let queries = generate_drop_all_indices_queries(&indices, label);
+        for query_str in &queries {
+            self.query(query_str).execute()?;
+        }
+        Ok(queries.len())
+    }
+
+    /// Creates an [`IndexBuilder`] for an index on this label, a self-documenting alternative to [`Self::create_index`] and [`Self::drop_index`]
+    ///
+    /// # Arguments
+    /// * `label`: The node label or relationship type to index
+    ///
+    /// # Returns
+    /// An [`IndexBuilder`] object, describing a range index on nodes by default
+    pub fn index<'a>(
+        &'a self,
+        label: &str,
+    ) -> IndexBuilder<'a, Self> {
+        IndexBuilder::new(self, label)
+    }
+
     /// Creates a new index in the graph, for the selected entity type(Node/Edge), selected label, and properties
     ///
     /// # Arguments
@@ -221,13 +770,13 @@ impl SyncGraph {
         tracing::instrument(name = "Graph Create Index", skip_all, level = "info")
     )]
     pub fn create_index<P: Display>(
-        &mut self,
+        &self,
         index_field_type: IndexType,
         entity_type: EntityType,
         label: &str,
         properties: &[P],
         options: Option<&HashMap<String, String>>,
-    ) -> FalkorResult<QueryResult<LazyResultSet>> {
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
         // Create index from these properties
 
         let query_str =
@@ -241,6 +790,31 @@ impl SyncGraph {
         .execute()
     }
 
+    /// Creates a new vector index in the graph, with typed options for the vector dimension, similarity function and tuning
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether to index nodes or relationships
+    /// * `label`: Entities with this label will be indexed
+    /// * `properties`: The vector properties to index
+    /// * `options`: The [`VectorIndexOptions`] for this index
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing information on the created index
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Create Vector Index", skip_all, level = "info")
+    )]
+    pub fn create_vector_index<P: Display>(
+        &self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+        options: VectorIndexOptions,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        let query_str = generate_create_vector_index_query(entity_type, label, properties, options);
+        self.query(query_str).execute()
+    }
+
     /// Drop an existing index, by specifying its type, entity, label and specific properties
     ///
     /// # Arguments
@@ -250,27 +824,506 @@ impl SyncGraph {
         tracing::instrument(name = "Graph Drop Index", skip_all, level = "info")
     )]
     pub fn drop_index<P: Display>(
-        &mut self,
+        &self,
         index_field_type: IndexType,
         entity_type: EntityType,
         label: &str,
         properties: &[P],
-    ) -> FalkorResult<QueryResult<LazyResultSet>> {
-        let query_str = generate_drop_index_query(index_field_type, entity_type, label, properties);
-        self.query(query_str).execute()
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        let query_str = generate_drop_index_query(index_field_type, entity_type, label, properties);
+        self.query(query_str).execute()
+    }
+
+    /// Performs a K-nearest-neighbours search over a vector index, using the `db.idx.vector.queryNodes` procedure
+    ///
+    /// # Arguments
+    /// * `label`: The label of the indexed nodes
+    /// * `property`: The indexed vector property
+    /// * `query_vector`: The vector to search around
+    /// * `k`: The maximum number of results to return
+    ///
+    /// # Returns
+    /// A [`Vec`] of the nearest [`Node`]s, along with their distance from the query vector, closest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph KNN Search", skip_all, level = "info")
+    )]
+    pub fn knn_search(
+        &self,
+        label: &str,
+        property: &str,
+        query_vector: &[f32],
+        k: usize,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_knn_query(label, property, query_vector, k);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_node_distance_results)
+    }
+
+    /// Finds all nodes with a point property within the specified distance of a center point
+    ///
+    /// # Arguments
+    /// * `label`: The label of the nodes to search
+    /// * `property`: The [`Point`] property to measure the distance from
+    /// * `center`: The center of the search radius
+    /// * `radius_meters`: The maximal distance, in meters
+    ///
+    /// # Returns
+    /// A [`Vec`] of the matching [`Node`]s, along with their distance from the center in meters, closest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Nodes Within Radius", skip_all, level = "info")
+    )]
+    pub fn nodes_within_radius(
+        &self,
+        label: &str,
+        property: &str,
+        center: &Point,
+        radius_meters: f64,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_radius_query(label, property, center, radius_meters)?;
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_node_distance_results)
+    }
+
+    /// Finds the shortest paths between two nodes, using the `algo.SPpaths` procedure
+    ///
+    /// # Arguments
+    /// * `source_id`: The entity ID of the node the paths start at
+    /// * `target_id`: The entity ID of the node the paths end at
+    /// * `options`: The [`ShortestPathOptions`] controlling the traversal
+    ///
+    /// # Returns
+    /// A [`Vec`] of the shortest [`Path`]s found
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Shortest Paths", skip_all, level = "info")
+    )]
+    pub fn shortest_paths(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        options: &ShortestPathOptions,
+    ) -> FalkorResult<Vec<Path>> {
+        let query_str = generate_shortest_paths_query(source_id, Some(target_id), options);
+        self.ro_query(&query_str).execute().and_then(parse_paths)
+    }
+
+    /// Finds the shortest paths from a node to every node reachable from it, using the `algo.SSpaths` procedure
+    ///
+    /// # Arguments
+    /// * `source_id`: The entity ID of the node the paths start at
+    /// * `options`: The [`ShortestPathOptions`] controlling the traversal
+    ///
+    /// # Returns
+    /// A [`Vec`] of the shortest [`Path`]s found
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Single Source Shortest Paths", skip_all, level = "info")
+    )]
+    pub fn single_source_shortest_paths(
+        &self,
+        source_id: i64,
+        options: &ShortestPathOptions,
+    ) -> FalkorResult<Vec<Path>> {
+        let query_str = generate_shortest_paths_query(source_id, None, options);
+        self.ro_query(&query_str).execute().and_then(parse_paths)
+    }
+
+    /// Traverses the graph breadth-first from a node, using the `algo.BFS` procedure
+    ///
+    /// # Arguments
+    /// * `start_node_id`: The entity ID of the node the traversal starts at
+    /// * `max_depth`: The maximal number of relationships to traverse from the start node, 0 means unlimited
+    /// * `relationship_filter`: Only traverse outgoing relationships of this type, all types are traversed if not specified
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`TraversalLevel`]s, ordered by depth, where the first level holds only the start node
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph BFS", skip_all, level = "info")
+    )]
+    pub fn bfs(
+        &self,
+        start_node_id: i64,
+        max_depth: u32,
+        relationship_filter: Option<&str>,
+    ) -> FalkorResult<Vec<TraversalLevel>> {
+        let query_str = generate_bfs_query(start_node_id, max_depth, relationship_filter);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_bfs_levels)
+    }
+
+    /// Ranks nodes by their PageRank score, using the `algo.pageRank` procedure
+    ///
+    /// # Arguments
+    /// * `label`: Only rank nodes with this label, all nodes are ranked if not specified
+    /// * `relationship_type`: Only follow relationships of this type, all types are followed if not specified
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`Node`]s and their scores, ordered from the highest score
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph PageRank", skip_all, level = "info")
+    )]
+    pub fn page_rank(
+        &self,
+        label: Option<&str>,
+        relationship_type: Option<&str>,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_page_rank_query(label, relationship_type);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_node_scores)
+    }
+
+    /// Scores nodes by their betweenness centrality, using the `algo.betweenness` procedure
+    ///
+    /// # Arguments
+    /// * `node_labels`: Only consider nodes with these labels, all nodes are considered if this is empty
+    /// * `relationship_types`: Only follow relationships of these types, all types are followed if this is empty
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`Node`]s and their scores, ordered from the highest score
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Betweenness Centrality", skip_all, level = "info")
+    )]
+    pub fn betweenness_centrality(
+        &self,
+        node_labels: &[&str],
+        relationship_types: &[&str],
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_betweenness_query(node_labels, relationship_types);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_node_scores)
+    }
+
+    /// Scores nodes by their degree, using the `algo.degree` procedure
+    ///
+    /// # Arguments
+    /// * `label`: Only score nodes with this label, all nodes are scored if not specified
+    /// * `relationship_types`: Only count relationships of these types, all types are counted if this is empty
+    /// * `direction`: Which relationships of each node to count
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`Node`]s and their degrees, ordered from the highest degree
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Degree Centrality", skip_all, level = "info")
+    )]
+    pub fn degree_centrality(
+        &self,
+        label: Option<&str>,
+        relationship_types: &[&str],
+        direction: TraversalDirection,
+    ) -> FalkorResult<Vec<(Node, f64)>> {
+        let query_str = generate_degree_query(label, relationship_types, direction);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_node_scores)
+    }
+
+    /// Marks an entity as soft deleted, by setting its [`SOFT_DELETE_PROPERTY`](crate::SOFT_DELETE_PROPERTY) to the current time
+    /// Soft deleted entities can be excluded from queries using [`QueryBuilder::excluding_soft_deleted`]
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the entity is a node or a relationship
+    /// * `entity_id`: The entity ID of the entity to mark
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Soft Delete", skip_all, level = "info")
+    )]
+    pub fn soft_delete(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        self.query(generate_soft_delete_query(entity_type, entity_id, false))
+            .execute()
+    }
+
+    /// Restores a soft deleted entity, by removing its [`SOFT_DELETE_PROPERTY`](crate::SOFT_DELETE_PROPERTY)
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the entity is a node or a relationship
+    /// * `entity_id`: The entity ID of the entity to restore
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Restore Soft Deleted", skip_all, level = "info")
+    )]
+    pub fn restore_soft_deleted(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        self.query(generate_soft_delete_query(entity_type, entity_id, true))
+            .execute()
+    }
+
+    /// Writes declarative fixtures to the graph, merging all of their nodes, followed by the edges between them,
+    /// in a single query, so seeding either fully succeeds or has no effect
+    ///
+    /// # Arguments
+    /// * `fixtures`: The [`GraphFixtures`] to write
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics, or an error if the fixtures are inconsistent
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Seed", skip_all, level = "info")
+    )]
+    pub fn seed(
+        &self,
+        fixtures: &GraphFixtures,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        self.query(generate_seed_query(fixtures)?).execute()
+    }
+
+    /// Sets a property of a node or a relationship, returning the value the server stored
+    /// The stored value is compared with the written one, and any value whose type the server coerced,
+    /// E.g. a float read back as an integer, is logged, or fails the call when strict typing is enabled,
+    /// see [`FalkorClientBuilder::with_strict_typing`](crate::FalkorClientBuilder::with_strict_typing).
+    /// Note that the property is written either way, a failed call only reports the coercion.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the entity is a node or a relationship
+    /// * `entity_id`: The entity ID of the entity to update
+    /// * `property`: The name of the property to set
+    /// * `value`: The new value of the property
+    ///
+    /// # Returns
+    /// The value read back from the server, [`None`] if no entity has the provided ID, or [`FalkorDBError::TypeCoerced`] in strict mode
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Set Property", skip_all, level = "info")
+    )]
+    pub fn set_property(
+        &self,
+        entity_type: EntityType,
+        entity_id: i64,
+        property: &str,
+        value: &FalkorValue,
+    ) -> FalkorResult<Option<FalkorValue>> {
+        let query_str = generate_set_property_query(entity_type, entity_id, property, value)?;
+        let res = self.query(query_str).execute()?;
+        check_property_round_trip(property, value, res, self.client.strict_typing())
+    }
+
+    /// Sets a node property, while keeping its previous values as a queryable history
+    /// Every call creates a [`PROPERTY_VERSION_LABEL`](crate::PROPERTY_VERSION_LABEL) node, connected to the node through a
+    /// [`PROPERTY_VERSION_RELATIONSHIP`](crate::PROPERTY_VERSION_RELATIONSHIP) relationship, holding the value and the timestamps between which it is valid.
+    /// The version valid at `valid_from` is closed, and the property on the node itself is only updated if the new version is the latest one.
+    ///
+    /// # Arguments
+    /// * `node_id`: The entity ID of the node to update
+    /// * `property`: The name of the property to set
+    /// * `value`: The new value of the property
+    /// * `valid_from`: The timestamp from which the new value is valid, usually in milliseconds since the epoch
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing the query statistics
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Set Property Versioned", skip_all, level = "info")
+    )]
+    pub fn set_property_versioned(
+        &self,
+        node_id: i64,
+        property: &str,
+        value: &FalkorValue,
+        valid_from: i64,
+    ) -> FalkorResult<QueryResult<LazyResultSet<'_>>> {
+        let query_str = generate_set_versioned_query(node_id, property, value, valid_from)?;
+        self.query(query_str).execute()
+    }
+
+    /// Returns the value a versioned property held at a given time, see [`SyncGraph::set_property_versioned`]
+    ///
+    /// # Arguments
+    /// * `node_id`: The entity ID of the node
+    /// * `property`: The name of the versioned property
+    /// * `timestamp`: The time at which to read the property
+    ///
+    /// # Returns
+    /// The value valid at that time, or [`None`] if no version of the property was valid at that time
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Property As Of", skip_all, level = "info")
+    )]
+    pub fn property_as_of(
+        &self,
+        node_id: i64,
+        property: &str,
+        timestamp: i64,
+    ) -> FalkorResult<Option<FalkorValue>> {
+        let query_str = generate_property_as_of_query(node_id, property, timestamp);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_property_as_of)
+    }
+
+    /// Returns every version of a versioned property, see [`SyncGraph::set_property_versioned`]
+    ///
+    /// # Arguments
+    /// * `node_id`: The entity ID of the node
+    /// * `property`: The name of the versioned property
+    ///
+    /// # Returns
+    /// A [`Vec`] of [`PropertyVersion`]s, ordered by the time from which they are valid
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Property History", skip_all, level = "info")
+    )]
+    pub fn property_history(
+        &self,
+        node_id: i64,
+        property: &str,
+    ) -> FalkorResult<Vec<PropertyVersion>> {
+        let query_str = generate_property_history_query(node_id, property);
+        self.ro_query(&query_str)
+            .execute()
+            .and_then(parse_property_history)
+    }
+
+    /// Calls the DB.CONSTRAINTS procedure on the graph, returning an array of the graph's constraints
+    ///
+    /// # Returns
+    /// A tuple where the first element is a [`Vec`] of [`Constraint`]s, and the second element is a [`Vec`] of stats as [`String`]s
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "List Graph Constraints", skip_all, level = "info")
+    )]
+    pub fn list_constraints(&self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
+        ProcedureQueryBuilder::<QueryResult<Vec<Constraint>>, Self>::new(self, "DB.CONSTRAINTS")
+            .execute()
+    }
+
+    /// Lists example entities violating a constraint, usually one in [`ConstraintStatus::Failed`](crate::ConstraintStatus::Failed) status,
+    /// so the offending data can be fixed before the constraint is recreated. These are the nodes or relationships missing
+    /// any of the properties of a mandatory constraint, or sharing all the values of a unique one, up to 100 of them.
+    ///
+    /// # Arguments
+    /// * `constraint`: The [`Constraint`] to diagnose, as listed by [`Self::list_constraints`]
+    ///
+    /// # Returns
+    /// The offending entities, each a [`FalkorValue::Node`] or a [`FalkorValue::Edge`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Constraint Violations", skip_all, level = "info")
+    )]
+    pub fn constraint_violations(
+        &self,
+        constraint: &Constraint,
+    ) -> FalkorResult<Vec<FalkorValue>> {
+        let query = generate_constraint_violations_query(constraint, CONSTRAINT_VIOLATIONS_LIMIT);
+        self.query(query)
+            .execute()?
+            .data
+            .map(|row| row.into_iter().next().ok_or(FalkorDBError::ParsingArray))
+            .collect()
+    }
+
+    /// Creates a [`ConstraintBuilder`] for a constraint on this label, a self-documenting alternative to the positional constraint functions
+    ///
+    /// # Arguments
+    /// * `label`: The node label or relationship type to constrain
+    ///
+    /// # Returns
+    /// A [`ConstraintBuilder`] object, describing a mandatory constraint on nodes by default
+    pub fn constraint<'a>(
+        &'a self,
+        label: &str,
+    ) -> ConstraintBuilder<'a, Self> {
+        ConstraintBuilder::new(self, label)
+    }
+
+    /// Waits until a constraint is operational, by polling the graph's constraints
+    /// Constraint creation returns while the constraint is still being verified against the existing entities.
+    ///
+    /// # Arguments
+    /// * `constraint_type`: The type of the constraint
+    /// * `entity_type`: Whether the constraint applies to nodes or relationships
+    /// * `label`: The label or relationship type of the constraint
+    /// * `properties`: The properties of the constraint
+    /// * `timeout`: How long to keep polling, returns [`FalkorDBError::ConstraintWaitTimeout`] once it passes
+    ///
+    /// # Returns
+    /// The operational [`Constraint`], or [`FalkorDBError::ConstraintFailed`] if existing entities violate it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Wait For Constraint", skip_all, level = "info")
+    )]
+    pub fn wait_for_constraint<P: AsRef<str>>(
+        &self,
+        constraint_type: ConstraintType,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+        timeout: Duration,
+    ) -> FalkorResult<Constraint> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let constraints = self.list_constraints()?.data;
+            if let Some(constraint) = check_constraint_status(
+                constraints,
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            )? {
+                return Ok(constraint);
+            }
+            if Instant::now() + SCHEMA_POLL_INTERVAL > deadline {
+                return Err(FalkorDBError::ConstraintWaitTimeout);
+            }
+            std::thread::sleep(SCHEMA_POLL_INTERVAL);
+        }
     }
 
-    /// Calls the DB.CONSTRAINTS procedure on the graph, returning an array of the graph's constraints
+    /// Drops every constraint in the graph, optionally only those on the provided label
+    /// The constraints are enumerated using [`Self::list_constraints`], this is useful for resetting the schema in tests and migrations.
+    /// The indices created for unique constraints are not dropped, see [`Self::drop_all_indices`].
+    ///
+    /// # Arguments
+    /// * `label`: Only drop constraints on this label or relationship type, or drop all of them if [`None`]
     ///
     /// # Returns
-    /// A tuple where the first element is a [`Vec`] of [`Constraint`]s, and the second element is a [`Vec`] of stats as [`String`]s
+    /// The number of constraints dropped
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "List Graph Constraints", skip_all, level = "info")
+        tracing::instrument(name = "Drop All Graph Constraints", skip_all, level = "info")
     )]
-    pub fn list_constraints(&mut self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
-        ProcedureQueryBuilder::<QueryResult<Vec<Constraint>>, Self>::new(self, "DB.CONSTRAINTS")
-            .execute()
+    pub fn drop_all_constraints(
+        &self,
+        label: Option<&str>,
+    ) -> FalkorResult<usize> {
+        let constraints: Vec<Constraint> = self
+            .list_constraints()?
+            .data
+            .into_iter()
+            .filter(|constraint| label.is_none_or(|label| constraint.label == label))
+            .collect();
+        for constraint in &constraints {
+            let properties: Vec<&str> = constraint.properties.iter().map(String::as_str).collect();
+            self.drop_constraint(
+                constraint.constraint_type,
+                constraint.entity_type,
+                &constraint.label,
+                &properties,
+            )?;
+        }
+        Ok(constraints.len())
     }
 
     /// Creates a new constraint for this graph, making the provided properties mandatory
@@ -316,7 +1369,7 @@ impl SyncGraph {
         tracing::instrument(name = "Create Graph Unique Constraint", skip_all, level = "info")
     )]
     pub fn create_unique_constraint(
-        &mut self,
+        &self,
         entity_type: EntityType,
         label: String,
         properties: &[&str],
@@ -382,8 +1435,8 @@ impl SyncGraph {
 }
 
 impl HasGraphSchema for SyncGraph {
-    fn get_graph_schema_mut(&mut self) -> &mut GraphSchema {
-        &mut self.graph_schema
+    fn get_graph_schema(&self) -> &GraphSchema {
+        &self.graph_schema
     }
 }
 
@@ -391,13 +1444,13 @@ impl HasGraphSchema for SyncGraph {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{create_test_client, open_empty_test_graph},
-        FalkorDBError, IndexType,
+        test_utils::{create_test_client, open_empty_test_graph, TestSyncGraphHandle},
+        ConstraintStatus, FalkorDBError, FalkorValue, IndexType, SimilarityFunction,
     };
 
     #[test]
     fn test_create_drop_index() {
-        let mut graph = open_empty_test_graph("test_create_drop_index");
+        let graph = open_empty_test_graph("test_create_drop_index");
 
         let indices = graph
             .inner
@@ -427,13 +1480,16 @@ mod tests {
 
     #[test]
     fn test_invalid_cypher_query_syntax_returns_error() {
-        let mut graph = create_test_client().select_graph("imdb");
+        let graph = create_test_client().select_graph("imdb");
         let res = graph.query("not a cypher query").execute();
-        assert!(matches!(res, Err(FalkorDBError::RedisError(_))));
+        assert!(matches!(
+            res.as_ref().map_err(FalkorDBError::without_context),
+            Err(FalkorDBError::SyntaxError { .. })
+        ));
     }
     #[test]
     fn test_list_indices() {
-        let mut graph = create_test_client().select_graph("imdb");
+        let graph = create_test_client().select_graph("imdb");
         let indices = graph.list_indices().expect("Could not list indices");
 
         assert_eq!(indices.data.len(), 1);
@@ -449,6 +1505,700 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_schema_introspection() {
+        let graph = create_test_client().select_graph("imdb");
+
+        let labels = graph.labels().expect("Could not list labels");
+        assert!(labels.contains(&"actor".to_string()));
+
+        let relationship_types = graph
+            .relationship_types()
+            .expect("Could not list relationship types");
+        assert!(relationship_types.contains(&"act".to_string()));
+
+        let property_keys = graph.property_keys().expect("Could not list property keys");
+        assert!(property_keys.contains(&"name".to_string()));
+    }
+
+    #[test]
+    fn test_call_procedure_typed_yields() {
+        let graph = create_test_client().select_graph("imdb");
+
+        let labels = graph
+            .call_procedure_ro::<QueryResult<Vec<(String,)>>>("DB.LABELS")
+            .execute()
+            .expect("Could not call procedure");
+        assert_eq!(labels.header, vec!["label".to_string()]);
+        assert!(labels.data.contains(&("actor".to_string(),)));
+
+        let res = graph
+            .call_procedure_ro::<QueryResult<Vec<(String, f64)>>>("DB.LABELS")
+            .execute();
+        assert!(matches!(res, Err(FalkorDBError::YieldColumnMismatch(_))));
+
+        let res = graph
+            .call_procedure_ro::<QueryResult<Vec<(i64,)>>>("DB.LABELS")
+            .execute();
+        assert!(matches!(res, Err(FalkorDBError::YieldColumnMismatch(_))));
+    }
+
+    #[test]
+    fn test_preload_schema() {
+        let graph = create_test_client().select_graph("imdb");
+        graph.graph_schema().clear();
+
+        graph.preload_schema().expect("Could not preload schema");
+        assert!(graph
+            .graph_schema()
+            .labels()
+            .values()
+            .any(|label| label == "actor"));
+        assert!(graph
+            .graph_schema()
+            .relationships()
+            .values()
+            .any(|relationship| relationship == "act"));
+    }
+
+    #[test]
+    fn test_knn_search() {
+        let graph = open_empty_test_graph("test_knn_search");
+
+        graph
+            .inner
+            .create_vector_index(
+                EntityType::Node,
+                "Product",
+                &["embedding"],
+                VectorIndexOptions::new(2, SimilarityFunction::Euclidean),
+            )
+            .expect("Could not create vector index");
+        graph
+            .inner
+            .query("UNWIND range(0, 4) AS x CREATE (:Product {id: x, embedding: vecf32([x, x])})")
+            .execute()
+            .expect("Could not create products");
+
+        let res = graph
+            .inner
+            .knn_search("Product", "embedding", &[1.0, 1.0], 2)
+            .expect("Could not perform vector search");
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].0.properties.get("id"), Some(&FalkorValue::I64(1)));
+        assert!(res[0].1 <= res[1].1);
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let graph = open_empty_test_graph("test_shortest_paths");
+        graph
+            .inner
+            .query("CREATE (a:City {name: 'A'})-[:Road {distance: 5}]->(b:City {name: 'B'})-[:Road {distance: 5}]->(c:City {name: 'C'}), (a)-[:Road {distance: 20}]->(c)")
+            .execute()
+            .expect("Could not create cities");
+
+        let paths = graph
+            .inner
+            .shortest_paths(
+                0,
+                2,
+                &ShortestPathOptions::default()
+                    .with_relationship_type("Road")
+                    .with_weight_property("distance"),
+            )
+            .expect("Could not find shortest paths");
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let graph = open_empty_test_graph("test_bfs");
+        graph
+            .inner
+            .query("CREATE (a:Person {name: 'A'})-[:KNOWS]->(b:Person {name: 'B'})-[:KNOWS]->(c:Person {name: 'C'}), (a)-[:KNOWS]->(d:Person {name: 'D'}), (a)-[:LIKES]->(c)")
+            .execute()
+            .expect("Could not create people");
+
+        let levels = graph
+            .inner
+            .bfs(0, 0, Some("KNOWS"))
+            .expect("Could not traverse graph");
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].nodes[0].entity_id, 0);
+        assert_eq!(levels[1].nodes.len(), 2);
+        assert_eq!(levels[1].edges.len(), 2);
+        assert_eq!(levels[2].nodes.len(), 1);
+
+        let levels = graph
+            .inner
+            .bfs(0, 1, None)
+            .expect("Could not traverse graph");
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[1].nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_soft_delete() {
+        let graph = open_empty_test_graph("test_soft_delete");
+        graph
+            .inner
+            .query("CREATE (:Person {name: 'A'}), (:Person {name: 'B'})")
+            .execute()
+            .expect("Could not create people");
+
+        graph
+            .inner
+            .soft_delete(EntityType::Node, 0)
+            .expect("Could not soft delete node");
+        let count_people = |graph: &SyncGraph| {
+            graph
+                .query("MATCH (p:Person) RETURN p")
+                .excluding_soft_deleted("p")
+                .execute()
+                .expect("Could not query people")
+                .data
+                .count()
+        };
+        assert_eq!(count_people(&graph.inner), 1);
+
+        graph
+            .inner
+            .restore_soft_deleted(EntityType::Node, 0)
+            .expect("Could not restore node");
+        assert_eq!(count_people(&graph.inner), 2);
+    }
+
+    #[test]
+    fn test_seed() {
+        let graph = open_empty_test_graph("test_seed");
+        let fixtures = GraphFixtures::new()
+            .node("alice", &["Person"], [("name", "Alice")])
+            .node("bob", &["Person"], [("name", "Bob")])
+            .edge("alice", "KNOWS", "bob", [("since", 2020)]);
+
+        let res = graph.inner.seed(&fixtures).expect("Could not seed graph");
+        assert_eq!(res.get_nodes_created(), Some(2));
+        assert_eq!(res.get_relationship_created(), Some(1));
+
+        // Seeding again merges into the existing entities
+        let res = graph.inner.seed(&fixtures).expect("Could not seed graph");
+        assert_eq!(res.get_nodes_created(), None);
+    }
+
+    #[test]
+    fn test_centrality() {
+        let graph = open_empty_test_graph("test_centrality");
+        graph
+            .inner
+            .query("CREATE (hub:Page {name: 'hub'}), (:Page {name: 'a'})-[:LINKS]->(hub), (:Page {name: 'b'})-[:LINKS]->(hub), (hub)-[:LINKS]->(:Page {name: 'c'})")
+            .execute()
+            .expect("Could not create pages");
+
+        let ranks = graph
+            .inner
+            .page_rank(Some("Page"), Some("LINKS"))
+            .expect("Could not compute PageRank");
+        assert_eq!(ranks.len(), 4);
+
+        let degrees = graph
+            .inner
+            .degree_centrality(Some("Page"), &["LINKS"], TraversalDirection::Incoming)
+            .expect("Could not compute degrees");
+        assert_eq!(degrees[0].1, 2.0);
+
+        let betweenness = graph
+            .inner
+            .betweenness_centrality(&["Page"], &["LINKS"])
+            .expect("Could not compute betweenness");
+        assert_eq!(
+            betweenness[0].0.properties.get("name"),
+            Some(&FalkorValue::String("hub".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_versioned_property() {
+        let graph = open_empty_test_graph("test_versioned_property");
+        graph
+            .inner
+            .query("CREATE (:Product {name: 'Widget'})")
+            .execute()
+            .expect("Could not create product");
+
+        for (price, valid_from) in [(10, 1000), (15, 3000), (12, 2000)] {
+            graph
+                .inner
+                .set_property_versioned(0, "price", &FalkorValue::I64(price), valid_from)
+                .expect("Could not set versioned property");
+        }
+
+        assert_eq!(graph.inner.property_as_of(0, "price", 500).unwrap(), None);
+        assert_eq!(
+            graph.inner.property_as_of(0, "price", 2500).unwrap(),
+            Some(FalkorValue::I64(12))
+        );
+        assert_eq!(
+            graph.inner.property_as_of(0, "price", 5000).unwrap(),
+            Some(FalkorValue::I64(15))
+        );
+
+        let history = graph
+            .inner
+            .property_history(0, "price")
+            .expect("Could not read history");
+        assert_eq!(
+            history
+                .iter()
+                .map(|version| (version.valid_from, version.valid_to))
+                .collect::<Vec<_>>(),
+            vec![(1000, Some(2000)), (2000, Some(3000)), (3000, None)]
+        );
+    }
+
+    #[test]
+    fn test_set_property_round_trip() {
+        let client = create_test_client();
+        client.set_strict_typing(true);
+        let graph = TestSyncGraphHandle {
+            inner: client.select_graph("test_set_property_round_trip"),
+        };
+        graph
+            .inner
+            .query("CREATE (:Reading)")
+            .execute()
+            .expect("Could not create reading");
+
+        let values = [
+            FalkorValue::I64(42),
+            FalkorValue::F64(1.0),
+            FalkorValue::F64(-2.5),
+            FalkorValue::String("forty two".to_string()),
+            FalkorValue::Bool(false),
+            FalkorValue::Array(vec![
+                FalkorValue::I64(1),
+                FalkorValue::F64(2.0),
+                FalkorValue::String("three".to_string()),
+            ]),
+            FalkorValue::Point(Point {
+                latitude: 32.5,
+                longitude: 34.25,
+            }),
+            FalkorValue::Vec32(crate::value::vec32::Vec32 {
+                values: vec![0.5, 1.0, -1.5],
+            }),
+        ];
+        for value in values {
+            assert_eq!(
+                graph
+                    .inner
+                    .set_property(EntityType::Node, 0, "value", &value),
+                Ok(Some(value))
+            );
+        }
+
+        assert_eq!(
+            graph
+                .inner
+                .set_property(EntityType::Node, 1000, "value", &FalkorValue::I64(1)),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_run_registered_query() {
+        let client = crate::FalkorClientBuilder::new()
+            .with_query_registry(
+                crate::QueryRegistry::new()
+                    .with_query("add_user", "CREATE (:User {id: $id})")
+                    .with_ro_query("find_user", "MATCH (u:User {id: $id}) RETURN u.id"),
+            )
+            .build()
+            .expect("Could not create client");
+        let graph = TestSyncGraphHandle {
+            inner: client.select_graph("test_run_registered_query"),
+        };
+
+        let params = HashMap::from([("id".to_string(), "7".to_string())]);
+        let res = graph
+            .inner
+            .run("add_user", &params)
+            .expect("Could not add user");
+        assert_eq!(res.get_nodes_created(), Some(1));
+
+        let mut res = graph
+            .inner
+            .run("find_user", &params)
+            .expect("Could not find user");
+        assert_eq!(res.data.next(), Some(vec![FalkorValue::I64(7)]));
+
+        assert!(matches!(
+            graph.inner.run("find_user", &HashMap::new()),
+            Err(FalkorDBError::QueryParamsMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_graph_circuit_breaker() {
+        let client = crate::FalkorClientBuilder::new()
+            .with_graph_circuit_breaker(
+                std::num::NonZeroU32::new(2).expect("Could not create a perfectly valid u32"),
+                Duration::from_secs(60),
+            )
+            .build()
+            .expect("Could not create client");
+        let failing = TestSyncGraphHandle {
+            inner: client.select_graph("test_graph_circuit_breaker_failing"),
+        };
+        let healthy = TestSyncGraphHandle {
+            inner: client.select_graph("test_graph_circuit_breaker_healthy"),
+        };
+
+        // Errors caused by the query itself don't count against the graph
+        assert!(failing
+            .inner
+            .query("RETURN nonexistent(1)")
+            .execute()
+            .is_err());
+        for _ in 0..2 {
+            assert!(failing
+                .inner
+                .query("UNWIND range(1, 100000000) AS x RETURN count(x)")
+                .with_timeout(1)
+                .execute()
+                .is_err());
+        }
+        assert!(matches!(
+            failing.inner.query("RETURN 1").execute(),
+            Err(FalkorDBError::GraphCircuitOpen { .. })
+        ));
+        assert!(healthy.inner.query("RETURN 1").execute().is_ok());
+
+        let stats = client
+            .graph_circuit_stats("test_graph_circuit_breaker_failing")
+            .expect("Circuit breakers should be enabled");
+        assert_eq!(stats.state, crate::CircuitState::Open);
+        assert_eq!((stats.failures, stats.rejected), (2, 1));
+        assert_eq!(
+            healthy.inner.circuit_stats().map(|stats| stats.state),
+            Some(crate::CircuitState::Closed)
+        );
+    }
+
+    #[test]
+    fn test_graph_lock() {
+        let graph = open_empty_test_graph("test_graph_lock");
+
+        let lock = graph
+            .inner
+            .try_lock(Duration::from_secs(10))
+            .expect("Could not try to lock")
+            .expect("Lock should have been free");
+        assert!(graph
+            .inner
+            .try_lock(Duration::from_secs(10))
+            .expect("Could not try to lock")
+            .is_none());
+        assert_eq!(
+            graph
+                .inner
+                .lock(Duration::from_secs(10), Duration::from_millis(100)),
+            Err(FalkorDBError::LockTimeout)
+        );
+
+        assert!(graph
+            .inner
+            .extend_lock(&lock, Duration::from_secs(20))
+            .expect("Could not extend lock"));
+        assert!(graph.inner.unlock(lock.clone()).expect("Could not unlock"));
+        assert!(!graph.inner.unlock(lock).expect("Could not unlock"));
+
+        let lock = graph
+            .inner
+            .lock(Duration::from_secs(10), Duration::from_millis(100))
+            .expect("Lock should have been free");
+        graph.inner.unlock(lock).expect("Could not unlock");
+    }
+
+    #[test]
+    fn test_execute_idempotent() {
+        let graph = open_empty_test_graph("test_execute_idempotent");
+        let key = format!("{:?}", std::time::SystemTime::now());
+
+        for _ in 0..3 {
+            graph
+                .inner
+                .execute_idempotent(
+                    &key,
+                    "CREATE (:Payment {amount: 10})",
+                    Duration::from_secs(60),
+                )
+                .expect("Could not execute idempotent query");
+        }
+        assert!(graph
+            .inner
+            .execute_idempotent(
+                &format!("{key}-retry"),
+                "NOT CYPHER",
+                Duration::from_secs(60)
+            )
+            .is_err());
+        assert!(graph
+            .inner
+            .execute_idempotent(
+                &format!("{key}-retry"),
+                "CREATE (:Payment {amount: 5})",
+                Duration::from_secs(60)
+            )
+            .expect("Could not retry failed query")
+            .is_some());
+
+        let count = graph
+            .inner
+            .query("MATCH (p:Payment) RETURN count(p)")
+            .execute()
+            .expect("Could not count payments")
+            .data
+            .next()
+            .and_then(|row| row[0].to_i64());
+        assert_eq!(count, Some(2));
+    }
+
+    #[test]
+    fn test_wait_for_index() {
+        let graph = open_empty_test_graph("test_wait_for_index");
+        graph
+            .inner
+            .index("actor")
+            .properties(["name"])
+            .create()
+            .expect("Could not create index");
+
+        let index = graph
+            .inner
+            .wait_for_index("actor", &["name"], Duration::from_secs(10))
+            .expect("Index did not become operational");
+        assert_eq!(index.index_label, "actor");
+
+        assert_eq!(
+            graph
+                .inner
+                .wait_for_index("actor", &["age"], Duration::from_millis(200))
+                .unwrap_err(),
+            FalkorDBError::IndexWaitTimeout
+        );
+    }
+
+    #[test]
+    fn test_constraint_builder() {
+        let graph = open_empty_test_graph("test_constraint_builder");
+        graph
+            .inner
+            .query("CREATE (:actor {name: 'A'}), (:actor {name: 'A'})")
+            .execute()
+            .expect("Could not create actors");
+
+        let constraint = graph
+            .inner
+            .constraint("actor")
+            .mandatory()
+            .property("name")
+            .create_and_wait(Duration::from_secs(10))
+            .expect("Could not create mandatory constraint");
+        assert_eq!(constraint.status, ConstraintStatus::Active);
+
+        let res = graph
+            .inner
+            .constraint("actor")
+            .unique()
+            .property("name")
+            .create_and_wait(Duration::from_secs(10));
+        assert!(matches!(res, Err(FalkorDBError::ConstraintFailed(_))));
+
+        let failed = graph
+            .inner
+            .list_constraints()
+            .expect("Could not list constraints")
+            .data
+            .into_iter()
+            .find(|constraint| constraint.status == ConstraintStatus::Failed)
+            .expect("Could not find the failed constraint");
+        let violations = graph
+            .inner
+            .constraint_violations(&failed)
+            .expect("Could not list violations");
+        assert_eq!(violations.len(), 2);
+        assert!(violations
+            .iter()
+            .all(|entity| matches!(entity, FalkorValue::Node(node) if node.labels == ["actor"])));
+
+        graph
+            .inner
+            .constraint("actor")
+            .property("name")
+            .drop()
+            .expect("Could not drop constraint");
+    }
+
+    #[test]
+    fn test_run_migrations_once() {
+        let graph = open_empty_test_graph("test_run_migrations_once");
+        graph.inner.delete().ok();
+        graph
+            .inner
+            .client
+            .borrow_connection(graph.inner.client.clone())
+            .and_then(|mut conn| {
+                conn.execute_command(
+                    Some(&schema_version_key("test_run_migrations_once")),
+                    "DEL",
+                    None,
+                    None,
+                )
+            })
+            .expect("Could not reset schema version");
+
+        let migrations = [
+            Migration::new(1, "seed").with_query("CREATE (:Config {name: 'main'})"),
+            Migration::new(2, "index").with_query("CREATE INDEX FOR (c:Config) ON (c.name)"),
+        ];
+        let reports: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..3)
+                .map(|_| {
+                    scope.spawn(|| {
+                        graph.inner.run_migrations(
+                            &migrations,
+                            Duration::from_secs(10),
+                            Duration::from_secs(10),
+                        )
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Migration thread panicked"))
+                .collect()
+        });
+
+        assert!(reports
+            .iter()
+            .all(|report| report.as_ref().is_ok_and(|report| report.version == 2)));
+        assert_eq!(
+            reports
+                .iter()
+                .flatten()
+                .filter(|report| report.applied == [1, 2])
+                .count(),
+            1
+        );
+
+        let count = graph
+            .inner
+            .query("MATCH (c:Config) RETURN count(c)")
+            .execute()
+            .expect("Could not count nodes")
+            .data
+            .next();
+        assert_eq!(count, Some(vec![FalkorValue::I64(1)]));
+    }
+
+    #[test]
+    fn test_with_retries() {
+        let graph = open_empty_test_graph("test_with_retries");
+        let mut attempts = 0;
+        let res = graph.inner.with_retries(2, |graph| {
+            attempts += 1;
+            if attempts < 3 {
+                return Err(FalkorDBError::ConnectionDown);
+            }
+            graph.query("RETURN 1").execute().map(|res| res.stats.len())
+        });
+        assert!(res.is_ok());
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let res: FalkorResult<()> = graph.inner.with_retries(5, |_| {
+            attempts += 1;
+            Err(FalkorDBError::ParsingError("Not retryable".to_string()))
+        });
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_with_retries_within_deadline() {
+        let graph = SyncGraph::new(
+            crate::client::blocking::create_empty_inner_sync_client(),
+            "test_with_retries_within_deadline",
+        );
+
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(250);
+        let res: FalkorResult<()> = graph.with_retries_within(deadline, 10, |_, budget| {
+            assert_eq!(budget, deadline);
+            Err(FalkorDBError::ConnectionDown)
+        });
+
+        // Backoffs of 100ms and 200ms don't both fit in 250ms, so only a single retry is attempted
+        assert!(start.elapsed() < Duration::from_millis(250));
+        assert_eq!(
+            res,
+            Err(FalkorDBError::RetriesFailed {
+                attempts: 2,
+                last_error: Box::new(FalkorDBError::ConnectionDown),
+            })
+        );
+    }
+
+    #[test]
+    fn test_drop_all_indices_and_constraints() {
+        let graph = open_empty_test_graph("test_drop_all_indices_and_constraints");
+        graph
+            .inner
+            .query("CREATE (:actor {name: 'A', age: 30}), (:movie {title: 'M'})")
+            .execute()
+            .expect("Could not create nodes");
+
+        graph
+            .inner
+            .create_index(IndexType::Range, EntityType::Node, "actor", &["name"], None)
+            .expect("Could not create index");
+        graph
+            .inner
+            .create_index(
+                IndexType::Range,
+                EntityType::Node,
+                "movie",
+                &["title"],
+                None,
+            )
+            .expect("Could not create index");
+        graph
+            .inner
+            .create_mandatory_constraint(EntityType::Node, "actor", &["age"])
+            .expect("Could not create constraint");
+
+        assert_eq!(graph.inner.drop_all_constraints(Some("actor")), Ok(1));
+        assert_eq!(graph.inner.drop_all_indices(Some("actor")), Ok(1));
+        assert_eq!(
+            graph
+                .inner
+                .list_indices()
+                .expect("Could not list indices")
+                .data
+                .len(),
+            1
+        );
+        assert_eq!(graph.inner.drop_all_indices(None), Ok(1));
+        assert!(graph
+            .inner
+            .list_constraints()
+            .expect("Could not list constraints")
+            .data
+            .is_empty());
+    }
+
     #[test]
     fn test_create_drop_mandatory_constraint() {
         let graph = open_empty_test_graph("test_mandatory_constraint");
@@ -471,7 +2221,7 @@ mod tests {
 
     #[test]
     fn test_create_drop_unique_constraint() {
-        let mut graph = open_empty_test_graph("test_unique_constraint");
+        let graph = open_empty_test_graph("test_unique_constraint");
 
         graph
             .inner
@@ -495,7 +2245,7 @@ mod tests {
 
     #[test]
     fn test_list_constraints() {
-        let mut graph = open_empty_test_graph("test_list_constraints");
+        let graph = open_empty_test_graph("test_list_constraints");
 
         graph
             .inner
@@ -515,7 +2265,7 @@ mod tests {
 
     #[test]
     fn test_slowlog() {
-        let mut graph = open_empty_test_graph("test_slowlog");
+        let graph = open_empty_test_graph("test_slowlog");
 
         graph
             .inner
@@ -556,7 +2306,7 @@ mod tests {
 
     #[test]
     fn test_explain() {
-        let mut graph = create_test_client().select_graph("imdb");
+        let graph = create_test_client().select_graph("imdb");
 
         let execution_plan = graph.explain("MATCH (a:actor) WITH a MATCH (b:actor) WHERE a.age = b.age AND a <> b RETURN a, collect(b) LIMIT 100").execute().expect("Could not create execution plan");
         assert_eq!(execution_plan.plan().len(), 7);
@@ -571,7 +2321,7 @@ mod tests {
 
     #[test]
     fn test_profile() {
-        let mut graph = open_empty_test_graph("test_profile");
+        let graph = open_empty_test_graph("test_profile");
 
         let execution_plan = graph
             .inner
@@ -589,5 +2339,9 @@ mod tests {
                 current_rc = current_rc.children[0].clone();
             }
         }
+
+        assert_eq!(execution_plan.profile().len(), 3);
+        assert_eq!(execution_plan.records_produced("Unwind"), Some(1001));
+        assert!(execution_plan.execution_time("Results").is_some());
     }
 }
@@ -0,0 +1,436 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    graph::escape_name, Constraint, ConstraintStatus, ConstraintType, EntityType, FalkorDBError,
+    FalkorResult, SyncGraph,
+};
+use std::time::Duration;
+
+#[cfg(feature = "tokio")]
+use crate::AsyncGraph;
+
+/// A Builder-pattern struct that allows creating and dropping constraints on a graph, without positional arguments
+/// By default, this describes a mandatory constraint on nodes.
+///
+/// ```no_run
+/// # use falkordb::{FalkorClientBuilder, FalkorResult};
+/// # use std::time::Duration;
+/// # fn main() -> FalkorResult<()> {
+/// # let client = FalkorClientBuilder::new().build()?;
+/// # let graph = client.select_graph("imdb");
+/// graph
+///     .constraint("actor")
+///     .on_node()
+///     .unique()
+///     .properties(["name"])
+///     .create_and_wait(Duration::from_secs(10))?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConstraintBuilder<'a, G> {
+    graph: &'a G,
+    label: String,
+    entity_type: EntityType,
+    constraint_type: ConstraintType,
+    properties: Vec<String>,
+}
+
+impl<'a, G> ConstraintBuilder<'a, G> {
+    pub(crate) fn new(
+        graph: &'a G,
+        label: &str,
+    ) -> Self {
+        Self {
+            graph,
+            label: label.to_string(),
+            entity_type: EntityType::Node,
+            constraint_type: ConstraintType::Mandatory,
+            properties: vec![],
+        }
+    }
+
+    /// Apply the constraint to nodes with this label, this is the default
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn on_node(self) -> Self {
+        Self {
+            entity_type: EntityType::Node,
+            ..self
+        }
+    }
+
+    /// Apply the constraint to relationships with this type
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn on_edge(self) -> Self {
+        Self {
+            entity_type: EntityType::Edge,
+            ..self
+        }
+    }
+
+    /// Make the properties mandatory, this is the default
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn mandatory(self) -> Self {
+        Self {
+            constraint_type: ConstraintType::Mandatory,
+            ..self
+        }
+    }
+
+    /// Make the properties unique, this also creates the range index unique constraints require
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn unique(self) -> Self {
+        Self {
+            constraint_type: ConstraintType::Unique,
+            ..self
+        }
+    }
+
+    /// Adds a single property to constrain
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn property(
+        mut self,
+        property: &str,
+    ) -> Self {
+        self.properties.push(property.to_string());
+        self
+    }
+
+    /// Adds several properties to constrain
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn properties<P: ToString, I: IntoIterator<Item = P>>(
+        mut self,
+        properties: I,
+    ) -> Self {
+        self.properties
+            .extend(properties.into_iter().map(|property| property.to_string()));
+        self
+    }
+
+    fn property_refs(&self) -> Vec<&str> {
+        self.properties.iter().map(String::as_str).collect()
+    }
+}
+
+impl ConstraintBuilder<'_, SyncGraph> {
+    fn create_constraint(&self) -> FalkorResult<redis::Value> {
+        let properties: Vec<&str> = self.properties.iter().map(String::as_str).collect();
+        match self.constraint_type {
+            ConstraintType::Mandatory => {
+                self.graph
+                    .create_mandatory_constraint(self.entity_type, &self.label, &properties)
+            }
+            ConstraintType::Unique => self.graph.create_unique_constraint(
+                self.entity_type,
+                self.label.clone(),
+                &properties,
+            ),
+        }
+    }
+
+    /// Creates the described constraint, the constraint may still be under construction when this returns
+    ///
+    /// # Returns
+    /// The raw response of the server
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Constraint Builder Create", skip_all, level = "info")
+    )]
+    pub fn create(self) -> FalkorResult<redis::Value> {
+        self.create_constraint()
+    }
+
+    /// Creates the described constraint, and waits until it is operational, see [`SyncGraph::wait_for_constraint`]
+    ///
+    /// # Arguments
+    /// * `timeout`: How long to wait for the constraint to become operational
+    ///
+    /// # Returns
+    /// The operational [`Constraint`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Constraint Builder Create And Wait", skip_all, level = "info")
+    )]
+    pub fn create_and_wait(
+        self,
+        timeout: Duration,
+    ) -> FalkorResult<Constraint> {
+        self.create_constraint()?;
+        self.graph.wait_for_constraint(
+            self.constraint_type,
+            self.entity_type,
+            &self.label,
+            &self.properties,
+            timeout,
+        )
+    }
+
+    /// Drops the described constraint
+    ///
+    /// # Returns
+    /// The raw response of the server
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Constraint Builder Drop", skip_all, level = "info")
+    )]
+    pub fn drop(self) -> FalkorResult<redis::Value> {
+        self.graph.drop_constraint(
+            self.constraint_type,
+            self.entity_type,
+            &self.label,
+            &self.property_refs(),
+        )
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl ConstraintBuilder<'_, AsyncGraph> {
+    async fn create_constraint(&self) -> FalkorResult<redis::Value> {
+        let properties: Vec<&str> = self.properties.iter().map(String::as_str).collect();
+        match self.constraint_type {
+            ConstraintType::Mandatory => {
+                self.graph
+                    .create_mandatory_constraint(self.entity_type, &self.label, &properties)
+                    .await
+            }
+            ConstraintType::Unique => {
+                self.graph
+                    .create_unique_constraint(self.entity_type, self.label.clone(), &properties)
+                    .await
+            }
+        }
+    }
+
+    /// Creates the described constraint, the constraint may still be under construction when this returns
+    ///
+    /// # Returns
+    /// The raw response of the server
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Constraint Builder Create", skip_all, level = "info")
+    )]
+    pub async fn create(self) -> FalkorResult<redis::Value> {
+        self.create_constraint().await
+    }
+
+    /// Creates the described constraint, and waits until it is operational, see [`AsyncGraph::wait_for_constraint`]
+    ///
+    /// # Arguments
+    /// * `timeout`: How long to wait for the constraint to become operational
+    ///
+    /// # Returns
+    /// The operational [`Constraint`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Constraint Builder Create And Wait", skip_all, level = "info")
+    )]
+    pub async fn create_and_wait(
+        self,
+        timeout: Duration,
+    ) -> FalkorResult<Constraint> {
+        self.create_constraint().await?;
+        self.graph
+            .wait_for_constraint(
+                self.constraint_type,
+                self.entity_type,
+                &self.label,
+                &self.properties,
+                timeout,
+            )
+            .await
+    }
+
+    /// Drops the described constraint
+    ///
+    /// # Returns
+    /// The raw response of the server
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Constraint Builder Drop", skip_all, level = "info")
+    )]
+    pub async fn drop(self) -> FalkorResult<redis::Value> {
+        self.graph
+            .drop_constraint(
+                self.constraint_type,
+                self.entity_type,
+                &self.label,
+                &self.property_refs(),
+            )
+            .await
+    }
+}
+
+/// Checks the status of the described constraint in the graph's constraint list
+///
+/// # Returns
+/// The constraint if it is operational, [`None`] if it is still under construction or not listed yet,
+/// or [`FalkorDBError::ConstraintFailed`] if it could not be applied
+pub(crate) fn check_constraint_status<P: AsRef<str>>(
+    constraints: Vec<Constraint>,
+    constraint_type: ConstraintType,
+    entity_type: EntityType,
+    label: &str,
+    properties: &[P],
+) -> FalkorResult<Option<Constraint>> {
+    let constraint = constraints.into_iter().find(|constraint| {
+        constraint.constraint_type == constraint_type
+            && constraint.entity_type == entity_type
+            && constraint.label == label
+            && constraint.properties.len() == properties.len()
+            && properties
+                .iter()
+                .all(|property| constraint.properties.iter().any(|p| p == property.as_ref()))
+    });
+
+    match constraint {
+        Some(constraint) if constraint.status == ConstraintStatus::Failed => {
+            Err(FalkorDBError::ConstraintFailed(format!(
+                "{} {} constraint on {}({})",
+                constraint.constraint_type,
+                constraint.entity_type,
+                constraint.label,
+                constraint.properties.join(", ")
+            )))
+        }
+        Some(constraint) if constraint.status == ConstraintStatus::Active => Ok(Some(constraint)),
+        _ => Ok(None),
+    }
+}
+
+/// The maximal number of offending entities listed when diagnosing a failed constraint
+pub(crate) const CONSTRAINT_VIOLATIONS_LIMIT: usize = 100;
+
+/// Generates a query returning the entities violating a constraint,
+/// those missing any of the properties of a mandatory constraint, or sharing all the values of a unique one
+pub(crate) fn generate_constraint_violations_query(
+    constraint: &Constraint,
+    limit: usize,
+) -> String {
+    let label = escape_name(&constraint.label);
+    let pattern = match constraint.entity_type {
+        EntityType::Node => format!("(e:{label})"),
+        EntityType::Edge => format!("()-[e:{label}]->()"),
+    };
+    let properties: Vec<String> = constraint
+        .properties
+        .iter()
+        .map(|property| format!("e.{}", escape_name(property)))
+        .collect();
+
+    match constraint.constraint_type {
+        ConstraintType::Mandatory => {
+            let missing: Vec<String> = properties
+                .iter()
+                .map(|property| format!("{property} IS NULL"))
+                .collect();
+            format!(
+                "MATCH {pattern} WHERE {} RETURN e LIMIT {limit}",
+                missing.join(" OR ")
+            )
+        }
+        ConstraintType::Unique => {
+            let present: Vec<String> = properties
+                .iter()
+                .map(|property| format!("{property} IS NOT NULL"))
+                .collect();
+            format!(
+                "MATCH {pattern} WHERE {} WITH [{}] AS values, collect(e) AS entities WHERE size(entities) > 1 UNWIND entities AS e RETURN e LIMIT {limit}",
+                present.join(" AND "),
+                properties.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraint(
+        constraint_type: ConstraintType,
+        properties: &[&str],
+        status: ConstraintStatus,
+    ) -> Constraint {
+        Constraint {
+            constraint_type,
+            label: "actor".to_string(),
+            properties: properties.iter().map(ToString::to_string).collect(),
+            entity_type: EntityType::Node,
+            status,
+        }
+    }
+
+    #[test]
+    fn test_generate_constraint_violations_query() {
+        assert_eq!(
+            generate_constraint_violations_query(
+                &constraint(
+                    ConstraintType::Mandatory,
+                    &["name", "age"],
+                    ConstraintStatus::Failed
+                ),
+                10
+            ),
+            "MATCH (e:`actor`) WHERE e.`name` IS NULL OR e.`age` IS NULL RETURN e LIMIT 10"
+        );
+
+        let unique = Constraint {
+            entity_type: EntityType::Edge,
+            label: "ACTED_IN".to_string(),
+            ..constraint(ConstraintType::Unique, &["role"], ConstraintStatus::Failed)
+        };
+        assert_eq!(
+            generate_constraint_violations_query(&unique, 10),
+            "MATCH ()-[e:`ACTED_IN`]->() WHERE e.`role` IS NOT NULL WITH [e.`role`] AS values, collect(e) AS entities WHERE size(entities) > 1 UNWIND entities AS e RETURN e LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn test_check_constraint_status() {
+        let constraints = vec![
+            constraint(ConstraintType::Unique, &["name"], ConstraintStatus::Pending),
+            constraint(
+                ConstraintType::Mandatory,
+                &["name"],
+                ConstraintStatus::Active,
+            ),
+            constraint(ConstraintType::Unique, &["id"], ConstraintStatus::Failed),
+        ];
+        let check = |constraint_type, properties: &[&str]| {
+            check_constraint_status(
+                constraints.clone(),
+                constraint_type,
+                EntityType::Node,
+                "actor",
+                properties,
+            )
+        };
+
+        assert_eq!(check(ConstraintType::Unique, &["name"]), Ok(None));
+        assert_eq!(
+            check(ConstraintType::Mandatory, &["name"]),
+            Ok(Some(constraints[1].clone()))
+        );
+        assert_eq!(check(ConstraintType::Mandatory, &["name", "age"]), Ok(None));
+        assert_eq!(
+            check(ConstraintType::Unique, &["id"]),
+            Err(FalkorDBError::ConstraintFailed(
+                "UNIQUE NODE constraint on actor(id)".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{FalkorClientBuilder, FalkorResult, FalkorSyncClient, SyncGraph};
+
+#[cfg(feature = "tokio")]
+use crate::{AsyncGraph, FalkorAsyncClient};
+
+/// A lightweight, serializable, reference to a graph, naming the database it resides in and the graph itself
+/// Unlike [`SyncGraph`] and [`AsyncGraph`], it holds no connections, so it can be passed through a job queue,
+/// and rehydrated into a graph handle by the worker picking up the job.
+///
+/// Note that the connection string is passed as is, including any password it contains.
+///
+/// ```no_run
+/// # use falkordb::{FalkorClientBuilder, GraphRef};
+/// let graph_ref = GraphRef::new("falkor://127.0.0.1:6379", "social");
+///
+/// // On the worker, using its existing client
+/// let client = FalkorClientBuilder::new().build().unwrap();
+/// let graph = graph_ref.select(&client);
+/// graph.query("MATCH (n) RETURN count(n)").execute().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphRef {
+    /// The connection string of the database, E.g. `falkor://127.0.0.1:6379`, see [`FalkorConnectionInfo`](crate::FalkorConnectionInfo)
+    pub connection_info: String,
+    /// The name of the graph
+    pub graph_name: String,
+}
+
+impl GraphRef {
+    /// Creates a new [`GraphRef`]
+    ///
+    /// # Arguments
+    /// * `connection_info`: The connection string of the database the graph resides in
+    /// * `graph_name`: The name of the graph
+    ///
+    /// # Returns
+    /// The new [`GraphRef`]
+    pub fn new<C: ToString, G: ToString>(
+        connection_info: C,
+        graph_name: G,
+    ) -> Self {
+        Self {
+            connection_info: connection_info.to_string(),
+            graph_name: graph_name.to_string(),
+        }
+    }
+
+    /// Selects the referenced graph using an existing client, which is expected to be connected to the referenced database
+    /// This is the preferred way of rehydrating a reference, as a worker can share one connection pool between all its jobs.
+    ///
+    /// # Arguments
+    /// * `client`: The [`FalkorSyncClient`] to select the graph from
+    ///
+    /// # Returns
+    /// A [`SyncGraph`] handle to the referenced graph
+    pub fn select(
+        &self,
+        client: &FalkorSyncClient,
+    ) -> SyncGraph {
+        client.select_graph(&self.graph_name)
+    }
+
+    /// Builds a new client connected to the referenced database, and selects the referenced graph
+    /// The client is kept alive by the returned graph, and closed once it is dropped.
+    ///
+    /// # Returns
+    /// A [`SyncGraph`] handle to the referenced graph, or an error if the connection string is invalid or the client could not connect
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Connect Graph Ref", skip_all, level = "info")
+    )]
+    pub fn connect(&self) -> FalkorResult<SyncGraph> {
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(self.connection_info.as_str().try_into()?)
+            .build()?;
+        Ok(self.select(&client))
+    }
+
+    /// Selects the referenced graph using an existing async client, which is expected to be connected to the referenced database
+    ///
+    /// # Arguments
+    /// * `client`: The [`FalkorAsyncClient`] to select the graph from
+    ///
+    /// # Returns
+    /// An [`AsyncGraph`] handle to the referenced graph
+    #[cfg(feature = "tokio")]
+    pub fn select_async(
+        &self,
+        client: &FalkorAsyncClient,
+    ) -> AsyncGraph {
+        client.select_graph(&self.graph_name)
+    }
+
+    /// Builds a new async client connected to the referenced database, and selects the referenced graph
+    /// The client is kept alive by the returned graph, and closed once it is dropped.
+    ///
+    /// # Returns
+    /// An [`AsyncGraph`] handle to the referenced graph, or an error if the connection string is invalid or the client could not connect
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Connect Graph Ref Async", skip_all, level = "info")
+    )]
+    pub async fn connect_async(&self) -> FalkorResult<AsyncGraph> {
+        let client = FalkorClientBuilder::new_async()
+            .with_connection_info(self.connection_info.as_str().try_into()?)
+            .build()
+            .await?;
+        Ok(self.select_async(&client))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_graph_ref_round_trip() {
+        let graph_ref = GraphRef::new("falkor://10.0.0.1:6379", "social");
+        let serialized = serde_json::to_string(&graph_ref).expect("Could not serialize graph ref");
+        assert_eq!(
+            serialized,
+            r#"{"connection_info":"falkor://10.0.0.1:6379","graph_name":"social"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<GraphRef>(&serialized).expect("Could not deserialize graph ref"),
+            graph_ref
+        );
+    }
+
+    #[test]
+    fn test_connect_invalid_connection_info() {
+        assert!(GraphRef::new("redis://:invalid", "social")
+            .connect()
+            .is_err());
+    }
+}
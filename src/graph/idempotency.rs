@@ -0,0 +1,27 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+/// The prefix of the companion keys recording processed idempotency keys, followed by the graph name and the idempotency key
+pub const IDEMPOTENCY_KEY_PREFIX: &str = "falkordb:idempotency:";
+
+pub(crate) fn idempotency_key(
+    graph_name: &str,
+    key: &str,
+) -> String {
+    format!("{IDEMPOTENCY_KEY_PREFIX}{graph_name}:{key}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idempotency_key() {
+        assert_eq!(
+            idempotency_key("social", "import-42"),
+            "falkordb:idempotency:social:import-42"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    graph::{
+        generate_create_index_query, generate_create_vector_index_query, generate_drop_index_query,
+    },
+    EntityType, FalkorResult, IndexType, LazyResultSet, QueryResult, SyncGraph, VectorIndexOptions,
+};
+use std::collections::HashMap;
+
+#[cfg(feature = "tokio")]
+use crate::AsyncGraph;
+
+/// A Builder-pattern struct that allows creating and dropping indices on a graph, without positional arguments
+/// By default, this describes a range index on nodes.
+///
+/// ```no_run
+/// # use falkordb::{FalkorClientBuilder, FalkorResult};
+/// # fn main() -> FalkorResult<()> {
+/// # let client = FalkorClientBuilder::new().build()?;
+/// # let graph = client.select_graph("imdb");
+/// graph
+///     .index("actor")
+///     .on_node()
+///     .fulltext()
+///     .properties(["name"])
+///     .create()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct IndexBuilder<'a, G> {
+    graph: &'a G,
+    label: String,
+    entity_type: EntityType,
+    index_type: IndexType,
+    properties: Vec<String>,
+    options: HashMap<String, String>,
+    vector_options: Option<VectorIndexOptions>,
+}
+
+impl<'a, G> IndexBuilder<'a, G> {
+    pub(crate) fn new(
+        graph: &'a G,
+        label: &str,
+    ) -> Self {
+        Self {
+            graph,
+            label: label.to_string(),
+            entity_type: EntityType::Node,
+            index_type: IndexType::Range,
+            properties: vec![],
+            options: HashMap::new(),
+            vector_options: None,
+        }
+    }
+
+    /// Index nodes with this label, this is the default
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn on_node(self) -> Self {
+        Self {
+            entity_type: EntityType::Node,
+            ..self
+        }
+    }
+
+    /// Index relationships with this type
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn on_edge(self) -> Self {
+        Self {
+            entity_type: EntityType::Edge,
+            ..self
+        }
+    }
+
+    /// Create a range index, this is the default
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn range(self) -> Self {
+        Self {
+            index_type: IndexType::Range,
+            ..self
+        }
+    }
+
+    /// Create a full-text index
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn fulltext(self) -> Self {
+        Self {
+            index_type: IndexType::Fulltext,
+            ..self
+        }
+    }
+
+    /// Create a vector index with the provided options
+    ///
+    /// # Arguments
+    /// * `options`: The [`VectorIndexOptions`] of the index
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn vector(
+        self,
+        options: VectorIndexOptions,
+    ) -> Self {
+        Self {
+            index_type: IndexType::Vector,
+            vector_options: Some(options),
+            ..self
+        }
+    }
+
+    /// Adds a single property to index
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn property(
+        mut self,
+        property: &str,
+    ) -> Self {
+        self.properties.push(property.to_string());
+        self
+    }
+
+    /// Adds several properties to index
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn properties<P: ToString, I: IntoIterator<Item = P>>(
+        mut self,
+        properties: I,
+    ) -> Self {
+        self.properties
+            .extend(properties.into_iter().map(|property| property.to_string()));
+        self
+    }
+
+    /// Adds an option to the OPTIONS clause of the index creation query, such as the language of a full-text index
+    /// Vector indices take their options from [`IndexBuilder::vector`] instead.
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_option(
+        mut self,
+        key: &str,
+        value: &str,
+    ) -> Self {
+        self.options.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub(crate) fn create_query(&self) -> String {
+        match (self.index_type, self.vector_options) {
+            (IndexType::Vector, Some(vector_options)) => generate_create_vector_index_query(
+                self.entity_type,
+                &self.label,
+                &self.properties,
+                vector_options,
+            ),
+            (index_type, _) => generate_create_index_query(
+                index_type,
+                self.entity_type,
+                &self.label,
+                &self.properties,
+                (!self.options.is_empty()).then_some(&self.options),
+            ),
+        }
+    }
+
+    pub(crate) fn drop_query(&self) -> String {
+        generate_drop_index_query(
+            self.index_type,
+            self.entity_type,
+            &self.label,
+            &self.properties,
+        )
+    }
+}
+
+impl<'a> IndexBuilder<'a, SyncGraph> {
+    /// Creates the described index
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing information on the created index
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Index Builder Create", skip_all, level = "info")
+    )]
+    pub fn create(self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+        let query_str = self.create_query();
+        self.graph.query(query_str).execute()
+    }
+
+    /// Drops the described index
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing information on the dropped index
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Index Builder Drop", skip_all, level = "info")
+    )]
+    pub fn drop(self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+        let query_str = self.drop_query();
+        self.graph.query(query_str).execute()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<'a> IndexBuilder<'a, AsyncGraph> {
+    /// Creates the described index
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing information on the created index
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Index Builder Create", skip_all, level = "info")
+    )]
+    pub async fn create(self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+        let query_str = self.create_query();
+        self.graph.query(query_str).execute().await
+    }
+
+    /// Drops the described index
+    ///
+    /// # Returns
+    /// A [`LazyResultSet`] containing information on the dropped index
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Index Builder Drop", skip_all, level = "info")
+    )]
+    pub async fn drop(self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+        let query_str = self.drop_query();
+        self.graph.query(query_str).execute().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimilarityFunction;
+
+    #[test]
+    fn test_index_builder_queries() {
+        let graph = ();
+
+        let builder = IndexBuilder::new(&graph, "actor").property("age");
+        assert_eq!(
+            builder.create_query(),
+            "CREATE INDEX FOR (l:actor) ON (l.age)"
+        );
+        assert_eq!(builder.drop_query(), "DROP  INDEX for (e:actor) ON (e.age)");
+
+        let builder = IndexBuilder::new(&graph, "actor")
+            .on_node()
+            .fulltext()
+            .properties(["name", "bio"])
+            .with_option("language", "english");
+        assert_eq!(
+            builder.create_query(),
+            "CREATE FULLTEXT INDEX FOR (l:actor) ON (l.name, l.bio) OPTIONS { 'language':'english' }"
+        );
+        assert_eq!(
+            builder.drop_query(),
+            "DROP FULLTEXT INDEX for (e:actor) ON (e.name, e.bio)"
+        );
+
+        let builder = IndexBuilder::new(&graph, "similar")
+            .on_edge()
+            .vector(VectorIndexOptions::new(3, SimilarityFunction::Cosine))
+            .property("embedding");
+        assert_eq!(
+            builder.create_query(),
+            "CREATE VECTOR INDEX FOR ()-[l:similar]->() ON (l.embedding) OPTIONS { dimension:3, similarityFunction:'cosine' }"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+/// The similarity function used to compare vectors in a vector index
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum SimilarityFunction {
+    /// Euclidean distance
+    #[default]
+    Euclidean,
+    /// Cosine distance
+    Cosine,
+}
+
+/// Typed options for creating a vector index, serialized into the OPTIONS clause of the index creation query
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VectorIndexOptions {
+    /// The length of the indexed vectors
+    pub dimension: u32,
+    /// The function used to measure the distance between vectors
+    pub similarity_function: SimilarityFunction,
+    /// The maximum number of outgoing edges per node in the HNSW graph, uses the server default if not specified
+    pub m: Option<u32>,
+    /// The number of candidates considered while building the index, uses the server default if not specified
+    pub ef_construction: Option<u32>,
+    /// The number of candidates considered while querying the index, uses the server default if not specified
+    pub ef_runtime: Option<u32>,
+}
+
+impl VectorIndexOptions {
+    /// Creates new vector index options, using the server defaults for the HNSW tuning parameters
+    ///
+    /// # Arguments
+    /// * `dimension`: The length of the indexed vectors
+    /// * `similarity_function`: The function used to measure the distance between vectors
+    ///
+    /// # Returns
+    /// The new [`VectorIndexOptions`]
+    pub fn new(
+        dimension: u32,
+        similarity_function: SimilarityFunction,
+    ) -> Self {
+        Self {
+            dimension,
+            similarity_function,
+            m: None,
+            ef_construction: None,
+            ef_runtime: None,
+        }
+    }
+
+    /// Sets the maximum number of outgoing edges per node in the HNSW graph
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_m(
+        self,
+        m: u32,
+    ) -> Self {
+        Self { m: Some(m), ..self }
+    }
+
+    /// Sets the number of candidates considered while building the index
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_ef_construction(
+        self,
+        ef_construction: u32,
+    ) -> Self {
+        Self {
+            ef_construction: Some(ef_construction),
+            ..self
+        }
+    }
+
+    /// Sets the number of candidates considered while querying the index
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_ef_runtime(
+        self,
+        ef_runtime: u32,
+    ) -> Self {
+        Self {
+            ef_runtime: Some(ef_runtime),
+            ..self
+        }
+    }
+
+    pub(crate) fn to_options_string(self) -> String {
+        let mut options = vec![
+            format!("dimension:{}", self.dimension),
+            format!("similarityFunction:'{}'", self.similarity_function),
+        ];
+        options.extend(self.m.map(|m| format!("M:{m}")));
+        options.extend(
+            self.ef_construction
+                .map(|ef_construction| format!("efConstruction:{ef_construction}")),
+        );
+        options.extend(
+            self.ef_runtime
+                .map(|ef_runtime| format!("efRuntime:{ef_runtime}")),
+        );
+
+        format!("{{ {} }}", options.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_index_options_string() {
+        assert_eq!(
+            VectorIndexOptions::new(768, SimilarityFunction::Cosine).to_options_string(),
+            "{ dimension:768, similarityFunction:'cosine' }"
+        );
+        assert_eq!(
+            VectorIndexOptions::new(3, SimilarityFunction::Euclidean)
+                .with_m(16)
+                .with_ef_construction(200)
+                .with_ef_runtime(10)
+                .to_options_string(),
+            "{ dimension:3, similarityFunction:'euclidean', M:16, efConstruction:200, efRuntime:10 }"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The prefix of the companion key holding a graph's lock, the graph name follows it
+pub const GRAPH_LOCK_KEY_PREFIX: &str = "falkordb:lock:";
+
+/// How long to wait between attempts to acquire a held lock
+pub(crate) const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Deletes the lock key, only if it still holds our token
+pub(crate) const RELEASE_LOCK_SCRIPT: &str =
+    "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) else return 0 end";
+
+/// Resets the lock key's TTL, only if it still holds our token
+pub(crate) const EXTEND_LOCK_SCRIPT: &str =
+    "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('PEXPIRE', KEYS[1], ARGV[2]) else return 0 end";
+
+/// A held lock on a graph, see [`SyncGraph::try_lock`](crate::SyncGraph::try_lock)
+/// The lock is not released when this is dropped, it either has to be released explicitly, or it will expire once its TTL passes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphLock {
+    key: String,
+    token: String,
+}
+
+impl GraphLock {
+    pub(crate) fn new(graph_name: &str) -> Self {
+        Self {
+            key: format!("{GRAPH_LOCK_KEY_PREFIX}{graph_name}"),
+            token: generate_lock_token(),
+        }
+    }
+
+    /// Returns the companion key holding this lock
+    pub fn key(&self) -> &str {
+        self.key.as_str()
+    }
+
+    /// Returns the unique token identifying this holder of the lock
+    pub fn token(&self) -> &str {
+        self.token.as_str()
+    }
+}
+
+fn generate_lock_token() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    hasher.write_u32(std::process::id());
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+
+    format!(
+        "{:016x}{:016x}",
+        hasher.finish(),
+        RandomState::new().build_hasher().finish()
+    )
+}
+
+pub(crate) fn ttl_millis(ttl: Duration) -> String {
+    ttl.as_millis().max(1).to_string()
+}
+
+/// SET NX replies with OK when the key was set, and with nil when it already exists
+pub(crate) fn parse_set_nx_reply(value: redis::Value) -> bool {
+    matches!(value, redis::Value::Okay)
+        || matches!(value, redis::Value::SimpleString(ref reply) if reply == "OK")
+}
+
+/// The lock scripts reply with a non-zero integer if the lock was still held by us
+pub(crate) fn parse_lock_script_reply(value: redis::Value) -> bool {
+    matches!(value, redis::Value::Int(reply) if reply != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_lock() {
+        let (first, second) = (GraphLock::new("social"), GraphLock::new("social"));
+        assert_eq!(first.key(), "falkordb:lock:social");
+        assert_eq!(first.token().len(), 32);
+        assert_ne!(first.token(), second.token());
+    }
+
+    #[test]
+    fn test_parse_lock_replies() {
+        assert!(parse_set_nx_reply(redis::Value::Okay));
+        assert!(!parse_set_nx_reply(redis::Value::Nil));
+        assert!(parse_lock_script_reply(redis::Value::Int(1)));
+        assert!(!parse_lock_script_reply(redis::Value::Int(0)));
+        assert_eq!(ttl_millis(Duration::from_secs(3)), "3000");
+        assert_eq!(ttl_millis(Duration::ZERO), "1");
+    }
+}
@@ -0,0 +1,173 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{FalkorDBError, FalkorResult};
+
+/// The prefix of the companion key recording a graph's schema version, the graph name follows it
+pub const SCHEMA_VERSION_KEY_PREFIX: &str = "falkordb:schema-version:";
+
+/// Records the schema version and resets the lock's TTL, only if the lock is still held by us
+pub(crate) const RECORD_VERSION_SCRIPT: &str = "if redis.call('GET', KEYS[1]) == ARGV[1] then redis.call('SET', KEYS[2], ARGV[2]); return redis.call('PEXPIRE', KEYS[1], ARGV[3]) else return 0 end";
+
+/// A single schema migration, such as creating an index, or backfilling a property
+/// Migrations are applied in ascending version order, each exactly once per graph,
+/// see [`SyncGraph::run_migrations`](crate::SyncGraph::run_migrations).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Migration {
+    /// The schema version this migration brings the graph to, starting from 1
+    pub version: u64,
+    /// A human-readable description of this migration
+    pub name: String,
+    /// The queries to run, in order
+    pub queries: Vec<String>,
+}
+
+impl Migration {
+    /// Creates a new [`Migration`], with no queries
+    ///
+    /// # Arguments
+    /// * `version`: The schema version this migration brings the graph to, starting from 1
+    /// * `name`: A human-readable description of this migration
+    ///
+    /// # Returns
+    /// The new [`Migration`]
+    pub fn new<T: ToString>(
+        version: u64,
+        name: T,
+    ) -> Self {
+        Self {
+            version,
+            name: name.to_string(),
+            queries: Vec::new(),
+        }
+    }
+
+    /// Adds a query to run as part of this migration
+    ///
+    /// # Arguments
+    /// * `query`: The query to run, after the ones already added
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_query<T: ToString>(
+        mut self,
+        query: T,
+    ) -> Self {
+        self.queries.push(query.to_string());
+        self
+    }
+}
+
+/// The outcome of running migrations on a graph
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The versions of the migrations applied by this call, empty if another replica had already applied them
+    pub applied: Vec<u64>,
+    /// The graph's schema version once the call completed
+    pub version: u64,
+}
+
+pub(crate) fn schema_version_key(graph_name: &str) -> String {
+    format!("{SCHEMA_VERSION_KEY_PREFIX}{graph_name}")
+}
+
+/// Parses the recorded schema version, a graph which was never migrated is at version 0
+pub(crate) fn parse_schema_version(value: redis::Value) -> FalkorResult<u64> {
+    match value {
+        redis::Value::Nil => Ok(0),
+        redis::Value::Int(version) => u64::try_from(version)
+            .map_err(|_| FalkorDBError::ParsingError(format!("Invalid schema version: {version}"))),
+        redis::Value::BulkString(bytes) => String::from_utf8_lossy(&bytes)
+            .parse()
+            .map_err(|_| FalkorDBError::ParsingError("Invalid schema version".to_string())),
+        _ => Err(FalkorDBError::ParsingError(
+            "Unexpected schema version reply".to_string(),
+        )),
+    }
+}
+
+/// Returns the migrations yet to be applied on a graph at the given version, in order
+/// Fails if the versions are not strictly ascending, or if the graph was migrated past the latest known migration,
+/// which happens when a replica running a newer release already migrated it.
+pub(crate) fn pending_migrations(
+    migrations: &[Migration],
+    current_version: u64,
+) -> FalkorResult<&[Migration]> {
+    if migrations
+        .windows(2)
+        .any(|pair| pair[0].version >= pair[1].version)
+        || migrations.first().is_some_and(|first| first.version == 0)
+    {
+        return Err(FalkorDBError::InvalidMigrations(
+            "Versions must start from 1 and be strictly ascending".to_string(),
+        ));
+    }
+
+    let latest_version = migrations.last().map_or(0, |last| last.version);
+    if current_version > latest_version {
+        return Err(FalkorDBError::SchemaVersionMismatch {
+            expected: latest_version,
+            found: current_version,
+        });
+    }
+
+    let applied = migrations.partition_point(|migration| migration.version <= current_version);
+    Ok(&migrations[applied..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrations() -> Vec<Migration> {
+        vec![
+            Migration::new(1, "person index").with_query("CREATE INDEX FOR (p:Person) ON (p.name)"),
+            Migration::new(2, "backfill")
+                .with_query("MATCH (p:Person) WHERE p.active IS NULL SET p.active = true"),
+        ]
+    }
+
+    #[test]
+    fn test_pending_migrations() {
+        let migrations = migrations();
+        assert_eq!(pending_migrations(&migrations, 0).map(<[_]>::len), Ok(2));
+        assert_eq!(
+            pending_migrations(&migrations, 1).map(|pending| pending[0].version),
+            Ok(2)
+        );
+        assert_eq!(pending_migrations(&migrations, 2), Ok(&[][..]));
+        assert_eq!(
+            pending_migrations(&migrations, 3),
+            Err(FalkorDBError::SchemaVersionMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let unordered = vec![Migration::new(2, "b"), Migration::new(1, "a")];
+        assert!(matches!(
+            pending_migrations(&unordered, 0),
+            Err(FalkorDBError::InvalidMigrations(_))
+        ));
+        assert!(matches!(
+            pending_migrations(&[Migration::new(0, "zero")], 0),
+            Err(FalkorDBError::InvalidMigrations(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_schema_version() {
+        assert_eq!(
+            schema_version_key("social"),
+            "falkordb:schema-version:social"
+        );
+        assert_eq!(parse_schema_version(redis::Value::Nil), Ok(0));
+        assert_eq!(
+            parse_schema_version(redis::Value::BulkString(b"7".to_vec())),
+            Ok(7)
+        );
+        assert!(parse_schema_version(redis::Value::BulkString(b"seven".to_vec())).is_err());
+    }
+}
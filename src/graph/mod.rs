@@ -3,17 +3,38 @@
  * Licensed under the MIT License.
  */
 
-use crate::{EntityType, GraphSchema, IndexType};
-use std::{collections::HashMap, fmt::Display};
+use crate::{
+    EntityType, FalkorDBError, FalkorIndex, FalkorResult, FalkorValue, GraphSchema, IndexStatus,
+    IndexType, LazyResultSet, Node, Point, QueryResult, VectorIndexOptions,
+};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    time::{Duration, Instant},
+};
 
+pub(crate) mod algorithms;
 pub(crate) mod blocking;
+pub(crate) mod constraint_builder;
+pub(crate) mod graph_ref;
+pub(crate) mod idempotency;
+pub(crate) mod index_builder;
+pub(crate) mod index_options;
+pub(crate) mod lock;
+pub(crate) mod migration;
+pub(crate) mod ops;
 pub(crate) mod query_builder;
+pub(crate) mod query_registry;
+pub(crate) mod seed;
+pub(crate) mod soft_delete;
+pub(crate) mod strict_typing;
+pub(crate) mod versioning;
 
 #[cfg(feature = "tokio")]
 pub(crate) mod asynchronous;
 
 pub trait HasGraphSchema {
-    fn get_graph_schema_mut(&mut self) -> &mut GraphSchema;
+    fn get_graph_schema(&self) -> &GraphSchema;
 }
 
 pub(crate) fn generate_create_index_query<P: Display>(
@@ -22,6 +43,48 @@ pub(crate) fn generate_create_index_query<P: Display>(
     label: &str,
     properties: &[P],
     options: Option<&HashMap<String, String>>,
+) -> String {
+    let options_string = options.map(|hashmap| {
+        format!(
+            "{{ {} }}",
+            hashmap
+                .iter()
+                .map(|(key, val)| format!("'{key}':'{val}'"))
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    });
+
+    generate_create_index_query_with_options(
+        index_field_type,
+        entity_type,
+        label,
+        properties,
+        options_string,
+    )
+}
+
+pub(crate) fn generate_create_vector_index_query<P: Display>(
+    entity_type: EntityType,
+    label: &str,
+    properties: &[P],
+    options: VectorIndexOptions,
+) -> String {
+    generate_create_index_query_with_options(
+        IndexType::Vector,
+        entity_type,
+        label,
+        properties,
+        Some(options.to_options_string()),
+    )
+}
+
+fn generate_create_index_query_with_options<P: Display>(
+    index_field_type: IndexType,
+    entity_type: EntityType,
+    label: &str,
+    properties: &[P],
+    options_string: Option<String>,
 ) -> String {
     let properties_string = properties
         .iter()
@@ -40,15 +103,8 @@ pub(crate) fn generate_create_index_query<P: Display>(
         IndexType::Fulltext => "FULLTEXT ",
     };
 
-    let options_string = options
-        .map(|hashmap| {
-            hashmap
-                .iter()
-                .map(|(key, val)| format!("'{key}':'{val}'"))
-                .collect::<Vec<_>>()
-                .join(",")
-        })
-        .map(|options_string| format!(" OPTIONS {{ {} }}", options_string))
+    let options_string = options_string
+        .map(|options_string| format!(" OPTIONS {options_string}"))
         .unwrap_or_default();
 
     format!(
@@ -86,3 +142,277 @@ pub(crate) fn generate_drop_index_query<P: Display>(
         properties_string
     )
 }
+
+/// Generates the queries dropping every index in the list, optionally only those on the provided label
+/// Each index type of a label is dropped separately, covering all the fields indexed with that type.
+pub(crate) fn generate_drop_all_indices_queries(
+    indices: &[FalkorIndex],
+    label: Option<&str>,
+) -> Vec<String> {
+    indices
+        .iter()
+        .filter(|index| label.is_none_or(|label| index.index_label == label))
+        .flat_map(|index| {
+            [IndexType::Range, IndexType::Fulltext, IndexType::Vector]
+                .into_iter()
+                .filter_map(move |index_type| {
+                    let fields: Vec<&str> = index
+                        .fields
+                        .iter()
+                        .filter(|field| {
+                            index
+                                .field_types
+                                .get(field.as_str())
+                                .is_some_and(|types| types.contains(&index_type))
+                        })
+                        .map(String::as_str)
+                        .collect();
+                    (!fields.is_empty()).then(|| {
+                        generate_drop_index_query(
+                            index_type,
+                            index.entity_type,
+                            &index.index_label,
+                            &fields,
+                        )
+                    })
+                })
+        })
+        .collect()
+}
+
+/// The delay before the first retry of a retried operation, doubling with every following retry
+pub(crate) const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Whether another attempt fits in the budget, after waiting for the backoff
+pub(crate) fn has_time_for_retry(
+    deadline: Option<Instant>,
+    backoff: Duration,
+) -> bool {
+    deadline.is_none_or(|deadline| Instant::now() + backoff < deadline)
+}
+
+/// Attaches the number of attempts to the error of the last one, if the operation was retried at all
+pub(crate) fn with_attempts(
+    attempts: usize,
+    err: FalkorDBError,
+) -> FalkorDBError {
+    match attempts {
+        0 | 1 => err,
+        attempts => FalkorDBError::RetriesFailed {
+            attempts,
+            last_error: Box::new(err),
+        },
+    }
+}
+
+pub(crate) const LABELS_QUERY: &str = "CALL db.labels() YIELD label RETURN label";
+pub(crate) const PROPERTY_KEYS_QUERY: &str =
+    "CALL db.propertyKeys() YIELD propertyKey RETURN propertyKey";
+pub(crate) const RELATIONSHIP_TYPES_QUERY: &str =
+    "CALL db.relationshipTypes() YIELD relationshipType RETURN relationshipType";
+
+/// Collects the names returned by one of the schema introspection procedures, one per row
+pub(crate) fn collect_names(result_set: LazyResultSet) -> Vec<String> {
+    result_set
+        .flat_map(|row| row.into_iter().next())
+        .flat_map(FalkorValue::into_string)
+        .collect()
+}
+
+/// How long to wait between polls of the graph's indices or constraints, while waiting for them to become operational
+pub(crate) const SCHEMA_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Finds the operational index on this label covering all the provided properties
+pub(crate) fn find_operational_index<P: AsRef<str>>(
+    indices: Vec<FalkorIndex>,
+    label: &str,
+    properties: &[P],
+) -> Option<FalkorIndex> {
+    indices.into_iter().find(|index| {
+        index.status == IndexStatus::Active
+            && index.index_label == label
+            && properties
+                .iter()
+                .all(|property| index.fields.iter().any(|field| field == property.as_ref()))
+    })
+}
+
+pub(crate) fn generate_knn_query(
+    label: &str,
+    property: &str,
+    query_vector: &[f32],
+    k: usize,
+) -> String {
+    let vector_string = query_vector
+        .iter()
+        .map(|element| format!("{element:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "CALL db.idx.vector.queryNodes('{}', '{}', {k}, vecf32([{vector_string}])) YIELD node, score RETURN node, score",
+        label.replace('\'', "\\'"),
+        property.replace('\'', "\\'")
+    )
+}
+
+pub(crate) fn generate_radius_query(
+    label: &str,
+    property: &str,
+    center: &Point,
+    radius_meters: f64,
+) -> FalkorResult<String> {
+    let center = FalkorValue::Point(center.clone()).to_cypher_literal()?;
+    let radius = FalkorValue::F64(radius_meters).to_cypher_literal()?;
+    let (label, property) = (escape_name(label), escape_name(property));
+
+    Ok(format!(
+        "MATCH (n:{label}) WITH n, distance(n.{property}, {center}) AS distance WHERE distance <= {radius} RETURN n, distance ORDER BY distance"
+    ))
+}
+
+pub(crate) fn escape_name(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+pub(crate) fn parse_node_distance_results(
+    res: QueryResult<LazyResultSet>
+) -> FalkorResult<Vec<(Node, f64)>> {
+    res.data
+        .map(|row| {
+            let [node, score]: [FalkorValue; 2] = row.try_into().map_err(|_| {
+                FalkorDBError::ParsingArrayToStructElementCount(
+                    "Expected exactly 2 elements in result - node and distance",
+                )
+            })?;
+
+            match (node, score) {
+                (FalkorValue::Node(node), FalkorValue::F64(score)) => Ok((node, score)),
+                (FalkorValue::Node(_), _) => Err(FalkorDBError::ParsingF64),
+                _ => Err(FalkorDBError::ParsingFNode),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_create_index_query() {
+        assert_eq!(
+            generate_create_index_query(
+                IndexType::Fulltext,
+                EntityType::Node,
+                "actor",
+                &["name"],
+                Some(&HashMap::from([(
+                    "language".to_string(),
+                    "english".to_string()
+                )]))
+            ),
+            "CREATE FULLTEXT INDEX FOR (l:actor) ON (l.name) OPTIONS { 'language':'english' }"
+        );
+        assert_eq!(
+            generate_create_vector_index_query(
+                EntityType::Edge,
+                "similar",
+                &["embedding"],
+                VectorIndexOptions::new(4, crate::SimilarityFunction::Cosine)
+            ),
+            "CREATE VECTOR INDEX FOR ()-[l:similar]->() ON (l.embedding) OPTIONS { dimension:4, similarityFunction:'cosine' }"
+        );
+    }
+
+    #[test]
+    fn test_find_operational_index() {
+        let index = |label: &str, fields: &[&str], status| FalkorIndex {
+            entity_type: EntityType::Node,
+            status,
+            index_label: label.to_string(),
+            fields: fields.iter().map(ToString::to_string).collect(),
+            field_types: Default::default(),
+            language: "english".to_string(),
+            stopwords: vec![],
+            info: Default::default(),
+            options: Default::default(),
+        };
+        let indices = vec![
+            index("actor", &["name"], IndexStatus::Pending),
+            index("actor", &["age", "name"], IndexStatus::Active),
+            index("movie", &["title"], IndexStatus::Active),
+        ];
+
+        assert_eq!(
+            find_operational_index(indices.clone(), "actor", &["name"]).map(|index| index.fields),
+            Some(vec!["age".to_string(), "name".to_string()])
+        );
+        assert!(find_operational_index(indices.clone(), "actor", &["title"]).is_none());
+        assert!(find_operational_index(indices, "director", &["name"]).is_none());
+    }
+
+    #[test]
+    fn test_generate_drop_all_indices_queries() {
+        let index = |label: &str, entity_type, fields: &[(&str, &[IndexType])]| FalkorIndex {
+            entity_type,
+            status: IndexStatus::Active,
+            index_label: label.to_string(),
+            fields: fields.iter().map(|(field, _)| field.to_string()).collect(),
+            field_types: fields
+                .iter()
+                .map(|(field, types)| (field.to_string(), types.to_vec()))
+                .collect(),
+            language: "english".to_string(),
+            stopwords: vec![],
+            info: Default::default(),
+            options: Default::default(),
+        };
+        let indices = vec![
+            index(
+                "actor",
+                EntityType::Node,
+                &[
+                    ("name", &[IndexType::Range, IndexType::Fulltext]),
+                    ("age", &[IndexType::Range]),
+                ],
+            ),
+            index("knows", EntityType::Edge, &[("since", &[IndexType::Range])]),
+        ];
+
+        assert_eq!(
+            generate_drop_all_indices_queries(&indices, None),
+            vec![
+                "DROP  INDEX for (e:actor) ON (e.name, e.age)",
+                "DROP FULLTEXT INDEX for (e:actor) ON (e.name)",
+                "DROP  INDEX for ()-[e:knows]->() ON (e.since)",
+            ]
+        );
+        assert_eq!(
+            generate_drop_all_indices_queries(&indices, Some("knows")),
+            vec!["DROP  INDEX for ()-[e:knows]->() ON (e.since)"]
+        );
+        assert!(generate_drop_all_indices_queries(&indices, Some("movie")).is_empty());
+    }
+
+    #[test]
+    fn test_generate_radius_query() {
+        assert_eq!(
+            generate_radius_query("City", "location", &Point::new(32.0, 34.75), 1500.0).unwrap(),
+            "MATCH (n:`City`) WITH n, distance(n.`location`, point({latitude: 32.0, longitude: 34.75})) AS distance WHERE distance <= 1500.0 RETURN n, distance ORDER BY distance"
+        );
+        assert!(generate_radius_query("City", "location", &Point::default(), f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_generate_knn_query() {
+        assert_eq!(
+            generate_knn_query("Product", "embedding", &[1.0, 0.5], 3),
+            "CALL db.idx.vector.queryNodes('Product', 'embedding', 3, vecf32([1.0, 0.5])) YIELD node, score RETURN node, score"
+        );
+        assert_eq!(
+            generate_knn_query("It's", "p", &[], 1),
+            "CALL db.idx.vector.queryNodes('It\\'s', 'p', 1, vecf32([])) YIELD node, score RETURN node, score"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    Constraint, FalkorIndex, FalkorResult, FalkorValue, LazyResultSet, QueryResult, SyncGraph,
+};
+use std::collections::HashMap;
+
+#[cfg(feature = "tokio")]
+use crate::{AsyncGraph, TransportFuture};
+
+/// The rows of a query, collected so they don't borrow the graph they were queried from
+pub type QueryRows = QueryResult<Vec<Vec<FalkorValue>>>;
+
+fn collect_rows(res: QueryResult<LazyResultSet>) -> QueryRows {
+    QueryResult {
+        header: res.header,
+        data: res.data.collect(),
+        stats: res.stats,
+    }
+}
+
+/// The common operations of a graph, implemented by [`SyncGraph`], and mockable like [`FalkorClientTrait`](crate::FalkorClientTrait).
+///
+/// The trait is object-safe, so graphs can be passed around as `&dyn GraphOps` or `Box<dyn GraphOps>`,
+/// see [`FalkorClientTrait`](crate::FalkorClientTrait) for the client's side.
+pub trait GraphOps: Send + Sync {
+    /// Returns the name of the graph
+    fn graph_name(&self) -> &str;
+
+    /// Runs a query, with optional key-val parameters, and collects its rows, see [`SyncGraph::query`]
+    fn query(
+        &self,
+        query_string: &str,
+        params: Option<&HashMap<String, String>>,
+    ) -> FalkorResult<QueryRows>;
+
+    /// Runs a read-only query, with optional key-val parameters, and collects its rows, see [`SyncGraph::ro_query`]
+    fn ro_query(
+        &self,
+        query_string: &str,
+        params: Option<&HashMap<String, String>>,
+    ) -> FalkorResult<QueryRows>;
+
+    /// Deletes the graph, see [`SyncGraph::delete`]
+    fn delete(&self) -> FalkorResult<()>;
+
+    /// Returns the graph's indices, see [`SyncGraph::list_indices`]
+    fn list_indices(&self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>>;
+
+    /// Returns the graph's constraints, see [`SyncGraph::list_constraints`]
+    fn list_constraints(&self) -> FalkorResult<QueryResult<Vec<Constraint>>>;
+
+    /// Returns the graph's node labels, see [`SyncGraph::labels`]
+    fn labels(&self) -> FalkorResult<Vec<String>>;
+
+    /// Returns the graph's property keys, see [`SyncGraph::property_keys`]
+    fn property_keys(&self) -> FalkorResult<Vec<String>>;
+
+    /// Returns the graph's relationship types, see [`SyncGraph::relationship_types`]
+    fn relationship_types(&self) -> FalkorResult<Vec<String>>;
+}
+
+impl GraphOps for SyncGraph {
+    fn graph_name(&self) -> &str {
+        SyncGraph::graph_name(self)
+    }
+
+    fn query(
+        &self,
+        query_string: &str,
+        params: Option<&HashMap<String, String>>,
+    ) -> FalkorResult<QueryRows> {
+        let builder = SyncGraph::query(self, query_string);
+        match params {
+            Some(params) => builder.with_params(params),
+            None => builder,
+        }
+        .execute()
+        .map(collect_rows)
+    }
+
+    fn ro_query(
+        &self,
+        query_string: &str,
+        params: Option<&HashMap<String, String>>,
+    ) -> FalkorResult<QueryRows> {
+        let builder = SyncGraph::ro_query(self, query_string);
+        match params {
+            Some(params) => builder.with_params(params),
+            None => builder,
+        }
+        .execute()
+        .map(collect_rows)
+    }
+
+    fn delete(&self) -> FalkorResult<()> {
+        SyncGraph::delete(self)
+    }
+
+    fn list_indices(&self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
+        SyncGraph::list_indices(self)
+    }
+
+    fn list_constraints(&self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
+        SyncGraph::list_constraints(self)
+    }
+
+    fn labels(&self) -> FalkorResult<Vec<String>> {
+        SyncGraph::labels(self)
+    }
+
+    fn property_keys(&self) -> FalkorResult<Vec<String>> {
+        SyncGraph::property_keys(self)
+    }
+
+    fn relationship_types(&self) -> FalkorResult<Vec<String>> {
+        SyncGraph::relationship_types(self)
+    }
+}
+
+/// The asynchronous counterpart of [`GraphOps`], implemented by [`AsyncGraph`].
+///
+/// The trait is object-safe, returning boxed futures, so graphs can be passed around as `&dyn AsyncGraphOps` or `Box<dyn AsyncGraphOps>`,
+/// see [`AsyncFalkorClientTrait`](crate::AsyncFalkorClientTrait) for the client's side.
+#[cfg(feature = "tokio")]
+pub trait AsyncGraphOps: Send + Sync {
+    /// Returns the name of the graph
+    fn graph_name(&self) -> &str;
+
+    /// Runs a query, with optional key-val parameters, and collects its rows, see [`AsyncGraph::query`]
+    fn query<'a>(
+        &'a self,
+        query_string: &'a str,
+        params: Option<&'a HashMap<String, String>>,
+    ) -> TransportFuture<'a, QueryRows>;
+
+    /// Runs a read-only query, with optional key-val parameters, and collects its rows, see [`AsyncGraph::ro_query`]
+    fn ro_query<'a>(
+        &'a self,
+        query_string: &'a str,
+        params: Option<&'a HashMap<String, String>>,
+    ) -> TransportFuture<'a, QueryRows>;
+
+    /// Deletes the graph, see [`AsyncGraph::delete`]
+    fn delete(&self) -> TransportFuture<'_, ()>;
+
+    /// Returns the graph's indices, see [`AsyncGraph::list_indices`]
+    fn list_indices(&self) -> TransportFuture<'_, QueryResult<Vec<FalkorIndex>>>;
+
+    /// Returns the graph's constraints, see [`AsyncGraph::list_constraints`]
+    fn list_constraints(&self) -> TransportFuture<'_, QueryResult<Vec<Constraint>>>;
+
+    /// Returns the graph's node labels, see [`AsyncGraph::labels`]
+    fn labels(&self) -> TransportFuture<'_, Vec<String>>;
+
+    /// Returns the graph's property keys, see [`AsyncGraph::property_keys`]
+    fn property_keys(&self) -> TransportFuture<'_, Vec<String>>;
+
+    /// Returns the graph's relationship types, see [`AsyncGraph::relationship_types`]
+    fn relationship_types(&self) -> TransportFuture<'_, Vec<String>>;
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncGraphOps for AsyncGraph {
+    fn graph_name(&self) -> &str {
+        AsyncGraph::graph_name(self)
+    }
+
+    fn query<'a>(
+        &'a self,
+        query_string: &'a str,
+        params: Option<&'a HashMap<String, String>>,
+    ) -> TransportFuture<'a, QueryRows> {
+        Box::pin(async move {
+            let builder = AsyncGraph::query(self, query_string);
+            match params {
+                Some(params) => builder.with_params(params),
+                None => builder,
+            }
+            .execute()
+            .await
+            .map(collect_rows)
+        })
+    }
+
+    fn ro_query<'a>(
+        &'a self,
+        query_string: &'a str,
+        params: Option<&'a HashMap<String, String>>,
+    ) -> TransportFuture<'a, QueryRows> {
+        Box::pin(async move {
+            let builder = AsyncGraph::ro_query(self, query_string);
+            match params {
+                Some(params) => builder.with_params(params),
+                None => builder,
+            }
+            .execute()
+            .await
+            .map(collect_rows)
+        })
+    }
+
+    fn delete(&self) -> TransportFuture<'_, ()> {
+        Box::pin(AsyncGraph::delete(self))
+    }
+
+    fn list_indices(&self) -> TransportFuture<'_, QueryResult<Vec<FalkorIndex>>> {
+        Box::pin(AsyncGraph::list_indices(self))
+    }
+
+    fn list_constraints(&self) -> TransportFuture<'_, QueryResult<Vec<Constraint>>> {
+        Box::pin(AsyncGraph::list_constraints(self))
+    }
+
+    fn labels(&self) -> TransportFuture<'_, Vec<String>> {
+        Box::pin(AsyncGraph::labels(self))
+    }
+
+    fn property_keys(&self) -> TransportFuture<'_, Vec<String>> {
+        Box::pin(AsyncGraph::property_keys(self))
+    }
+
+    fn relationship_types(&self) -> TransportFuture<'_, Vec<String>> {
+        Box::pin(AsyncGraph::relationship_types(self))
+    }
+}
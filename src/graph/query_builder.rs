@@ -4,15 +4,29 @@
  */
 
 use crate::{
-    graph::HasGraphSchema,
-    parser::{redis_value_as_vec, SchemaParsable},
-    Constraint, ExecutionPlan, FalkorDBError, FalkorIndex, FalkorResult, LazyResultSet,
-    QueryResult, SyncGraph,
+    client::{
+        circuit::{acquire_graph_circuit, record_graph_outcome},
+        metrics::QueryMetrics,
+        retry::run_with_retries,
+        slow_query::SlowQueryTimer,
+    },
+    connection::{reject_server_error, time_until_deadline},
+    graph::{soft_delete::apply_soft_delete_filter, HasGraphSchema},
+    parser::{parse_type, redis_value_as_vec, ParserTypeMarker, SchemaParsable},
+    value::conversion::check_yield_header,
+    Constraint, ExecutionPlan, FalkorDBError, FalkorIndex, FalkorResult, FalkorValue, FromYieldRow,
+    LazyResultSet, QueryResult, SyncGraph,
+};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    marker::PhantomData,
+    ops::Not,
+    time::{Duration, Instant},
 };
-use std::{collections::HashMap, fmt::Display, marker::PhantomData, ops::Not};
 
 #[cfg(feature = "tokio")]
-use crate::AsyncGraph;
+use crate::{client::retry::run_with_retries_async, AsyncGraph};
 
 #[cfg_attr(
     feature = "tracing",
@@ -42,16 +56,18 @@ pub(crate) fn construct_query<Q: Display, T: Display, Z: Display>(
 /// A Builder-pattern struct that allows creating and executing queries on a graph
 pub struct QueryBuilder<'a, Output, T: Display, G: HasGraphSchema> {
     _unused: PhantomData<Output>,
-    graph: &'a mut G,
+    graph: &'a G,
     command: &'a str,
     query_string: T,
     params: Option<&'a HashMap<String, String>>,
     timeout: Option<i64>,
+    deadline: Option<Instant>,
+    soft_delete_aliases: Vec<String>,
 }
 
 impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
     pub(crate) fn new(
-        graph: &'a mut G,
+        graph: &'a G,
         command: &'a str,
         query_string: T,
     ) -> Self {
@@ -62,6 +78,8 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
             query_string,
             params: None,
             timeout: None,
+            deadline: None,
+            soft_delete_aliases: vec![],
         }
     }
 
@@ -83,7 +101,7 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
     ///
     /// # Arguments
     /// * `timeout`: the timeout after which the server is allowed to abort or throw this request,
-    ///    in milliseconds, when that happens the server will return a timeout error
+    ///   in milliseconds, when that happens the server will return a timeout error
     pub fn with_timeout(
         self,
         timeout: i64,
@@ -94,16 +112,71 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
         }
     }
 
+    /// Specify an absolute deadline for the query, E.g. derived from the budget of the request being served
+    /// The time left until the deadline is sent to the server as the query timeout, if it is shorter than the one specified using [`QueryBuilder::with_timeout`],
+    /// and is also used as the client-side timeout, so the query fails with [`FalkorDBError::DeadlineExceeded`] even if the server does not respond.
+    /// Client-side timeouts are not enforced by custom sync transports, which only rely on the server timeout.
+    /// Waiting for a pooled connection is bounded by the deadline as well, failing with [`FalkorDBError::PoolExhausted`] once it passes.
+    ///
+    /// # Arguments
+    /// * `deadline`: The instant by which the query must complete
+    pub fn with_deadline(
+        self,
+        deadline: Instant,
+    ) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Exclude soft deleted entities bound to this alias, see [`SOFT_DELETE_PROPERTY`](crate::SOFT_DELETE_PROPERTY)
+    /// The exclusion predicate is added to the WHERE clause of the last MATCH clause in the query,
+    /// so the alias must be bound by the time that clause is evaluated
+    ///
+    /// # Arguments
+    /// * `alias`: The variable name the entity is bound to in the query
+    pub fn excluding_soft_deleted(
+        mut self,
+        alias: &str,
+    ) -> Self {
+        self.soft_delete_aliases.push(alias.to_string());
+        self
+    }
+
+    /// Whether the query can be sent again after failing, so it is retried according to the client's [`RetryPolicy`](crate::RetryPolicy)
+    fn is_idempotent(&self) -> bool {
+        matches!(self.command, "GRAPH.RO_QUERY" | "GRAPH.EXPLAIN")
+    }
+
+    fn render_query(&self) -> String {
+        construct_query(self.filtered_query(), self.params)
+    }
+
+    /// The query without its parameters, as reported to the slow query log
+    fn filtered_query(&self) -> String {
+        apply_soft_delete_filter(&self.query_string.to_string(), &self.soft_delete_aliases)
+    }
+
+    /// The server timeout, in milliseconds, taking into account both the explicit timeout and the deadline
+    fn effective_timeout(&self) -> FalkorResult<Option<i64>> {
+        let Some(deadline) = self.deadline else {
+            return Ok(self.timeout);
+        };
+
+        // Round up, so a sub-millisecond remainder doesn't become an unlimited timeout of 0
+        let remaining = time_until_deadline(deadline)?.as_micros().div_ceil(1000);
+        let remaining = i64::try_from(remaining).unwrap_or(i64::MAX);
+        Ok(Some(
+            self.timeout
+                .map_or(remaining, |timeout| timeout.min(remaining)),
+        ))
+    }
+
     fn generate_query_result_set(
         self,
         value: redis::Value,
     ) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
-        if let redis::Value::ServerError(e) = value {
-            return Err(FalkorDBError::RedisError(
-                e.details().unwrap_or("Unknown error").to_string(),
-            ));
-        }
-
         let res = redis_value_as_vec(value)?;
 
         match res.len() {
@@ -116,7 +189,7 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
 
                 QueryResult::from_response(
                     None,
-                    LazyResultSet::new(Default::default(), self.graph.get_graph_schema_mut()),
+                    LazyResultSet::new(Default::default(), self.graph.get_graph_schema()),
                     stats,
                 )
             }
@@ -129,7 +202,7 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
 
                 QueryResult::from_response(
                     Some(header),
-                    LazyResultSet::new(Default::default(), self.graph.get_graph_schema_mut()),
+                    LazyResultSet::new(Default::default(), self.graph.get_graph_schema()),
                     stats,
                 )
             }
@@ -142,10 +215,7 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
 
                 QueryResult::from_response(
                     Some(header),
-                    LazyResultSet::new(
-                        redis_value_as_vec(data)?,
-                        self.graph.get_graph_schema_mut(),
-                    ),
+                    LazyResultSet::new(redis_value_as_vec(data)?, self.graph.get_graph_schema()),
                     stats,
                 )
             }
@@ -156,29 +226,85 @@ impl<'a, Output, T: Display, G: HasGraphSchema> QueryBuilder<'a, Output, T, G> {
     }
 }
 
+/// The timeout a query was sent with, as reported by [`FalkorDBError::QueryTimedOut`]
+fn sent_timeout(timeout_ms: Option<i64>) -> Option<Duration> {
+    timeout_ms
+        .and_then(|timeout| u64::try_from(timeout).ok())
+        .map(Duration::from_millis)
+}
+
 impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "Common Query Execution Steps", skip_all, level = "trace")
+        tracing::instrument(
+            name = "Common Query Execution Steps",
+            skip_all,
+            level = "trace",
+            fields(query)
+        )
     )]
-    fn common_execute_steps(&mut self) -> FalkorResult<redis::Value> {
-        let query = construct_query(&self.query_string, self.params);
-
-        let timeout = self.timeout.map(|timeout| format!("timeout {timeout}"));
+    fn common_execute_steps(&self) -> FalkorResult<redis::Value> {
+        let query = self.render_query();
+        #[cfg(feature = "tracing")]
+        crate::redaction::record_query(&query, self.graph.get_client().log_redaction());
+        #[cfg(feature = "otel")]
+        let span = crate::otel::query_span(
+            self.graph.get_client().address(),
+            self.graph.graph_name(),
+            self.command,
+            &query,
+            self.graph.get_client().log_redaction(),
+        );
+        #[cfg(feature = "otel")]
+        let _entered = span.enter();
+
+        let timeout_ms = self.effective_timeout()?;
+        let timeout = timeout_ms.map(|timeout| format!("timeout {timeout}"));
         let mut params = vec![query.as_str(), "--compact"];
         params.extend(timeout.as_deref());
 
-        self.graph
-            .get_client()
-            .borrow_connection(self.graph.get_client().clone())
-            .and_then(|mut conn| {
-                conn.execute_command(
-                    Some(self.graph.graph_name()),
-                    self.command,
-                    None,
-                    Some(params.as_slice()),
-                )
-            })
+        let retry_policy = self
+            .is_idempotent()
+            .then(|| self.graph.get_client().retry_policy())
+            .flatten();
+        let circuit = self.graph.circuit();
+        acquire_graph_circuit(circuit, self.graph.graph_name())?;
+        let metrics = QueryMetrics::start(
+            self.graph.get_client().metrics_recorder(),
+            self.graph.graph_name(),
+            self.command,
+        );
+        let slow_query = SlowQueryTimer::start(self.graph.get_client().slow_query_log());
+        let res = run_with_retries(retry_policy.as_ref(), self.deadline, || {
+            self.graph
+                .get_client()
+                .borrow_connection_with_deadline(self.graph.get_client().clone(), self.deadline)
+                .and_then(|mut conn| {
+                    conn.execute_command_with_deadline(
+                        Some(self.graph.graph_name()),
+                        self.command,
+                        None,
+                        Some(params.as_slice()),
+                        self.deadline,
+                    )
+                })
+        })
+        .and_then(reject_server_error)
+        .map_err(|err| {
+            err.with_query_timeout(sent_timeout(timeout_ms))
+                .with_query_context(&query, self.graph.get_client().log_redaction())
+        });
+        metrics.finish(&res);
+        slow_query.finish(
+            self.graph.graph_name(),
+            &res,
+            self.graph.get_client().log_redaction(),
+            || (self.filtered_query(), self.params.cloned()),
+        );
+        record_graph_outcome(circuit, &res);
+        #[cfg(feature = "otel")]
+        crate::otel::record_outcome(&span, &res);
+        res
     }
 }
 
@@ -186,26 +312,78 @@ impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
 impl<'a, Out, T: Display> QueryBuilder<'a, Out, T, AsyncGraph> {
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "Common Query Execution Steps", skip_all, level = "trace")
+        tracing::instrument(
+            name = "Common Query Execution Steps",
+            skip_all,
+            level = "trace",
+            fields(query)
+        )
     )]
-    async fn common_execute_steps(&mut self) -> FalkorResult<redis::Value> {
-        let query = construct_query(&self.query_string, self.params);
+    async fn common_execute_steps(&self) -> FalkorResult<redis::Value> {
+        let query = self.render_query();
+        #[cfg(feature = "tracing")]
+        crate::redaction::record_query(&query, self.graph.get_client().log_redaction());
 
-        let timeout = self.timeout.map(|timeout| format!("timeout {timeout}"));
+        let timeout_ms = self.effective_timeout()?;
+        let timeout = timeout_ms.map(|timeout| format!("timeout {timeout}"));
         let mut params = vec![query.as_str(), "--compact"];
         params.extend(timeout.as_deref());
 
-        self.graph
-            .get_client()
-            .borrow_connection(self.graph.get_client().clone())
-            .await?
-            .execute_command(
-                Some(self.graph.graph_name()),
-                self.command,
-                None,
-                Some(params.as_slice()),
-            )
-            .await
+        let retry_policy = self
+            .is_idempotent()
+            .then(|| self.graph.get_client().retry_policy())
+            .flatten();
+        let (graph, command, deadline, params) =
+            (self.graph, self.command, self.deadline, params.as_slice());
+        #[cfg(feature = "otel")]
+        let span = crate::otel::query_span(
+            graph.get_client().address(),
+            graph.graph_name(),
+            command,
+            &query,
+            graph.get_client().log_redaction(),
+        );
+        let circuit = graph.circuit();
+        acquire_graph_circuit(circuit, graph.graph_name())?;
+        let metrics = QueryMetrics::start(
+            graph.get_client().metrics_recorder(),
+            graph.graph_name(),
+            command,
+        );
+        let slow_query = SlowQueryTimer::start(graph.get_client().slow_query_log());
+        let res = run_with_retries_async(retry_policy.as_ref(), deadline, || {
+            Box::pin(async move {
+                graph
+                    .get_client()
+                    .borrow_connection_with_deadline(graph.get_client().clone(), deadline)
+                    .await?
+                    .execute_command_with_deadline(
+                        Some(graph.graph_name()),
+                        command,
+                        None,
+                        Some(params),
+                        deadline,
+                    )
+                    .await
+            })
+        });
+        #[cfg(feature = "otel")]
+        let res = tracing::Instrument::instrument(res, span.clone());
+        let res = res.await.and_then(reject_server_error).map_err(|err| {
+            err.with_query_timeout(sent_timeout(timeout_ms))
+                .with_query_context(&query, graph.get_client().log_redaction())
+        });
+        metrics.finish(&res);
+        slow_query.finish(
+            graph.graph_name(),
+            &res,
+            graph.get_client().log_redaction(),
+            || (self.filtered_query(), self.params.cloned()),
+        );
+        record_graph_outcome(circuit, &res);
+        #[cfg(feature = "otel")]
+        crate::otel::record_outcome(&span, &res);
+        res
     }
 }
 
@@ -215,7 +393,7 @@ impl<'a, T: Display> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, T, SyncGra
         feature = "tracing",
         tracing::instrument(name = "Execute Lazy Result Set Query", skip_all, level = "info")
     )]
-    pub fn execute(mut self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+    pub fn execute(self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
         self.common_execute_steps()
             .and_then(|res| self.generate_query_result_set(res))
     }
@@ -228,7 +406,7 @@ impl<'a, T: Display> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, T, AsyncGr
         feature = "tracing",
         tracing::instrument(name = "Execute Lazy Result Set Query", skip_all, level = "info")
     )]
-    pub async fn execute(mut self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
+    pub async fn execute(self) -> FalkorResult<QueryResult<LazyResultSet<'a>>> {
         self.common_execute_steps()
             .await
             .and_then(|res| self.generate_query_result_set(res))
@@ -237,7 +415,7 @@ impl<'a, T: Display> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, T, AsyncGr
 
 impl<T: Display> QueryBuilder<'_, ExecutionPlan, T, SyncGraph> {
     /// Executes the query, returning an [`ExecutionPlan`] from the data returned
-    pub fn execute(mut self) -> FalkorResult<ExecutionPlan> {
+    pub fn execute(self) -> FalkorResult<ExecutionPlan> {
         self.common_execute_steps().and_then(ExecutionPlan::parse)
     }
 }
@@ -245,7 +423,7 @@ impl<T: Display> QueryBuilder<'_, ExecutionPlan, T, SyncGraph> {
 #[cfg(feature = "tokio")]
 impl<'a, T: Display> QueryBuilder<'a, ExecutionPlan, T, AsyncGraph> {
     /// Executes the query, returning an [`ExecutionPlan`] from the data returned
-    pub async fn execute(mut self) -> FalkorResult<ExecutionPlan> {
+    pub async fn execute(self) -> FalkorResult<ExecutionPlan> {
         self.common_execute_steps()
             .await
             .and_then(ExecutionPlan::parse)
@@ -296,7 +474,7 @@ pub(crate) fn generate_procedure_call<P: Display, T: Display, Z: Display>(
 /// A Builder-pattern struct that allows creating and executing procedure call on a graph
 pub struct ProcedureQueryBuilder<'a, Output, G: HasGraphSchema> {
     _unused: PhantomData<Output>,
-    graph: &'a mut G,
+    graph: &'a G,
     readonly: bool,
     procedure_name: &'a str,
     args: Option<&'a [&'a str]>,
@@ -305,7 +483,7 @@ pub struct ProcedureQueryBuilder<'a, Output, G: HasGraphSchema> {
 
 impl<'a, Out, G: HasGraphSchema> ProcedureQueryBuilder<'a, Out, G> {
     pub(crate) fn new(
-        graph: &'a mut G,
+        graph: &'a G,
         procedure_name: &'a str,
     ) -> Self {
         Self {
@@ -320,7 +498,7 @@ impl<'a, Out, G: HasGraphSchema> ProcedureQueryBuilder<'a, Out, G> {
     }
 
     pub(crate) fn new_readonly(
-        graph: &'a mut G,
+        graph: &'a G,
         procedure_name: &'a str,
     ) -> Self {
         Self {
@@ -362,7 +540,7 @@ impl<'a, Out, G: HasGraphSchema> ProcedureQueryBuilder<'a, Out, G> {
     }
 
     fn parse_query_result_of_type<T: SchemaParsable>(
-        &mut self,
+        &self,
         res: redis::Value,
     ) -> FalkorResult<QueryResult<Vec<T>>> {
         let [header, indices, stats]: [redis::Value; 3] =
@@ -379,12 +557,48 @@ impl<'a, Out, G: HasGraphSchema> ProcedureQueryBuilder<'a, Out, G> {
             redis_value_as_vec(indices).map(|indices| {
                 indices
                     .into_iter()
-                    .flat_map(|res| T::parse(res, self.graph.get_graph_schema_mut()))
+                    .flat_map(|res| T::parse(res, self.graph.get_graph_schema()))
                     .collect()
             })?,
             stats,
         )
     }
+
+    fn parse_query_result_of_yields<T: FromYieldRow>(
+        &self,
+        res: redis::Value,
+    ) -> FalkorResult<QueryResult<Vec<T>>> {
+        let [header, rows, stats]: [redis::Value; 3] =
+            redis_value_as_vec(res).and_then(|res_vec| {
+                res_vec.try_into().map_err(|_| {
+                    FalkorDBError::ParsingArrayToStructElementCount(
+                        "Expected exactly 3 elements in query response",
+                    )
+                })
+            })?;
+
+        let QueryResult {
+            header,
+            data: rows,
+            stats,
+        } = QueryResult::from_response(Some(header), redis_value_as_vec(rows)?, stats)?;
+        check_yield_header::<T>(&header)?;
+
+        let data = rows
+            .into_iter()
+            .map(|row| {
+                parse_type(ParserTypeMarker::Array, row, self.graph.get_graph_schema())
+                    .and_then(FalkorValue::into_vec)
+                    .and_then(|row| T::from_yield_row(&header, row))
+            })
+            .collect::<FalkorResult<Vec<T>>>()?;
+
+        Ok(QueryResult {
+            header,
+            data,
+            stats,
+        })
+    }
 }
 
 impl<Out> ProcedureQueryBuilder<'_, Out, SyncGraph> {
@@ -393,10 +607,11 @@ impl<Out> ProcedureQueryBuilder<'_, Out, SyncGraph> {
         tracing::instrument(
             name = "Common Procedure Call Execution Steps",
             skip_all,
-            level = "trace"
+            level = "trace",
+            fields(query)
         )
     )]
-    fn common_execute_steps(&mut self) -> FalkorResult<redis::Value> {
+    fn common_execute_steps(&self) -> FalkorResult<redis::Value> {
         let command = match self.readonly {
             true => "GRAPH.RO_QUERY",
             false => "GRAPH.QUERY",
@@ -404,19 +619,54 @@ impl<Out> ProcedureQueryBuilder<'_, Out, SyncGraph> {
 
         let (query_string, params) =
             generate_procedure_call(self.procedure_name, self.args, self.yields);
-        let query = construct_query(query_string, params.as_ref());
-
-        self.graph
-            .get_client()
-            .borrow_connection(self.graph.get_client().clone())
-            .and_then(|mut conn| {
-                conn.execute_command(
-                    Some(self.graph.graph_name()),
-                    command,
-                    None,
-                    Some(&[query.as_str(), "--compact"]),
-                )
-            })
+        let query = construct_query(&query_string, params.as_ref());
+        #[cfg(feature = "tracing")]
+        crate::redaction::record_query(&query, self.graph.get_client().log_redaction());
+        #[cfg(feature = "otel")]
+        let span = crate::otel::query_span(
+            self.graph.get_client().address(),
+            self.graph.graph_name(),
+            command,
+            &query,
+            self.graph.get_client().log_redaction(),
+        );
+        #[cfg(feature = "otel")]
+        let _entered = span.enter();
+
+        let retry_policy = self
+            .readonly
+            .then(|| self.graph.get_client().retry_policy())
+            .flatten();
+        let metrics = QueryMetrics::start(
+            self.graph.get_client().metrics_recorder(),
+            self.graph.graph_name(),
+            command,
+        );
+        let slow_query = SlowQueryTimer::start(self.graph.get_client().slow_query_log());
+        let res = run_with_retries(retry_policy.as_ref(), None, || {
+            self.graph
+                .get_client()
+                .borrow_connection(self.graph.get_client().clone())
+                .and_then(|mut conn| {
+                    conn.execute_command(
+                        Some(self.graph.graph_name()),
+                        command,
+                        None,
+                        Some(&[query.as_str(), "--compact"]),
+                    )
+                })
+        })
+        .map_err(|err| err.with_query_context(&query, self.graph.get_client().log_redaction()));
+        metrics.finish(&res);
+        slow_query.finish(
+            self.graph.graph_name(),
+            &res,
+            self.graph.get_client().log_redaction(),
+            || (query_string, params),
+        );
+        #[cfg(feature = "otel")]
+        crate::otel::record_outcome(&span, &res);
+        res
     }
 }
 
@@ -427,10 +677,11 @@ impl<'a, Out> ProcedureQueryBuilder<'a, Out, AsyncGraph> {
         tracing::instrument(
             name = "Common Procedure Call Execution Steps",
             skip_all,
-            level = "trace"
+            level = "trace",
+            fields(query)
         )
     )]
-    async fn common_execute_steps(&mut self) -> FalkorResult<redis::Value> {
+    async fn common_execute_steps(&self) -> FalkorResult<redis::Value> {
         let command = match self.readonly {
             true => "GRAPH.RO_QUERY",
             false => "GRAPH.QUERY",
@@ -438,19 +689,59 @@ impl<'a, Out> ProcedureQueryBuilder<'a, Out, AsyncGraph> {
 
         let (query_string, params) =
             generate_procedure_call(self.procedure_name, self.args, self.yields);
-        let query = construct_query(query_string, params.as_ref());
-
-        self.graph
-            .get_client()
-            .borrow_connection(self.graph.get_client().clone())
-            .await?
-            .execute_command(
-                Some(self.graph.graph_name()),
-                command,
-                None,
-                Some(&[query.as_str(), "--compact"]),
-            )
+        let query = construct_query(&query_string, params.as_ref());
+        #[cfg(feature = "tracing")]
+        crate::redaction::record_query(&query, self.graph.get_client().log_redaction());
+
+        let retry_policy = self
+            .readonly
+            .then(|| self.graph.get_client().retry_policy())
+            .flatten();
+        let (graph, query) = (self.graph, query.as_str());
+        #[cfg(feature = "otel")]
+        let span = crate::otel::query_span(
+            graph.get_client().address(),
+            graph.graph_name(),
+            command,
+            query,
+            graph.get_client().log_redaction(),
+        );
+        let metrics = QueryMetrics::start(
+            graph.get_client().metrics_recorder(),
+            graph.graph_name(),
+            command,
+        );
+        let slow_query = SlowQueryTimer::start(graph.get_client().slow_query_log());
+        let res = run_with_retries_async(retry_policy.as_ref(), None, || {
+            Box::pin(async move {
+                graph
+                    .get_client()
+                    .borrow_connection(graph.get_client().clone())
+                    .await?
+                    .execute_command(
+                        Some(graph.graph_name()),
+                        command,
+                        None,
+                        Some(&[query, "--compact"]),
+                    )
+                    .await
+            })
+        });
+        #[cfg(feature = "otel")]
+        let res = tracing::Instrument::instrument(res, span.clone());
+        let res = res
             .await
+            .map_err(|err| err.with_query_context(query, graph.get_client().log_redaction()));
+        metrics.finish(&res);
+        slow_query.finish(
+            graph.graph_name(),
+            &res,
+            graph.get_client().log_redaction(),
+            || (query_string, params),
+        );
+        #[cfg(feature = "otel")]
+        crate::otel::record_outcome(&span, &res);
+        res
     }
 }
 
@@ -461,7 +752,7 @@ impl ProcedureQueryBuilder<'_, QueryResult<Vec<FalkorIndex>>, SyncGraph> {
         feature = "tracing",
         tracing::instrument(name = "Execute FalkorIndex Query", skip_all, level = "info")
     )]
-    pub fn execute(mut self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
+    pub fn execute(self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
         self.common_execute_steps()
             .and_then(|res| self.parse_query_result_of_type(res))
     }
@@ -475,7 +766,7 @@ impl<'a> ProcedureQueryBuilder<'a, QueryResult<Vec<FalkorIndex>>, AsyncGraph> {
         feature = "tracing",
         tracing::instrument(name = "Execute FalkorIndex Query", skip_all, level = "info")
     )]
-    pub async fn execute(mut self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
+    pub async fn execute(self) -> FalkorResult<QueryResult<Vec<FalkorIndex>>> {
         self.common_execute_steps()
             .await
             .and_then(|res| self.parse_query_result_of_type(res))
@@ -489,7 +780,7 @@ impl ProcedureQueryBuilder<'_, QueryResult<Vec<Constraint>>, SyncGraph> {
         feature = "tracing",
         tracing::instrument(name = "Execute Constraint Procedure Call", skip_all, level = "info")
     )]
-    pub fn execute(mut self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
+    pub fn execute(self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
         self.common_execute_steps()
             .and_then(|res| self.parse_query_result_of_type(res))
     }
@@ -503,16 +794,85 @@ impl<'a> ProcedureQueryBuilder<'a, QueryResult<Vec<Constraint>>, AsyncGraph> {
         feature = "tracing",
         tracing::instrument(name = "Execute Constraint Procedure Call", skip_all, level = "info")
     )]
-    pub async fn execute(mut self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
+    pub async fn execute(self) -> FalkorResult<QueryResult<Vec<Constraint>>> {
         self.common_execute_steps()
             .await
             .and_then(|res| self.parse_query_result_of_type(res))
     }
 }
 
+impl<T: FromYieldRow> ProcedureQueryBuilder<'_, QueryResult<Vec<T>>, SyncGraph> {
+    /// Executes the procedure call and return a [`QueryResult`] type containing a result set of `T`s,
+    /// each converted from the yielded columns of a single row, in order.
+    /// Fails if the procedure yields a different number of columns, or if any column holds a different type.
+    /// This functions consumes self
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Execute Typed Procedure Call", skip_all, level = "info")
+    )]
+    pub fn execute(self) -> FalkorResult<QueryResult<Vec<T>>> {
+        self.common_execute_steps()
+            .and_then(|res| self.parse_query_result_of_yields(res))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, T: FromYieldRow> ProcedureQueryBuilder<'a, QueryResult<Vec<T>>, AsyncGraph> {
+    /// Executes the procedure call and return a [`QueryResult`] type containing a result set of `T`s,
+    /// each converted from the yielded columns of a single row, in order.
+    /// Fails if the procedure yields a different number of columns, or if any column holds a different type.
+    /// This functions consumes self
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Execute Typed Procedure Call", skip_all, level = "info")
+    )]
+    pub async fn execute(self) -> FalkorResult<QueryResult<Vec<T>>> {
+        self.common_execute_steps()
+            .await
+            .and_then(|res| self.parse_query_result_of_yields(res))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::blocking::create_empty_inner_sync_client;
+
+    #[test]
+    fn test_effective_timeout() {
+        let graph = SyncGraph::new(create_empty_inner_sync_client(), "test_effective_timeout");
+        let query = |timeout: Option<i64>, deadline: Option<Instant>| {
+            let builder = QueryBuilder::<QueryResult<LazyResultSet>, _, _>::new(
+                &graph,
+                "GRAPH.QUERY",
+                "RETURN 1",
+            );
+            let builder = match timeout {
+                Some(timeout) => builder.with_timeout(timeout),
+                None => builder,
+            };
+            match deadline {
+                Some(deadline) => builder.with_deadline(deadline),
+                None => builder,
+            }
+            .effective_timeout()
+        };
+
+        assert_eq!(query(None, None), Ok(None));
+        assert_eq!(query(Some(500), None), Ok(Some(500)));
+
+        let in_a_minute = Instant::now() + Duration::from_secs(60);
+        assert_eq!(query(Some(500), Some(in_a_minute)), Ok(Some(500)));
+        let remaining = query(None, Some(in_a_minute))
+            .expect("Deadline should not have passed")
+            .expect("Deadline should set a timeout");
+        assert!((59_000..=60_000).contains(&remaining));
+
+        assert_eq!(
+            query(Some(500), Some(Instant::now())),
+            Err(FalkorDBError::DeadlineExceeded)
+        );
+    }
 
     #[test]
     fn test_generate_procedure_call_no_args_no_yields() {
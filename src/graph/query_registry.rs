@@ -0,0 +1,306 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{FalkorDBError, FalkorResult};
+use std::collections::{BTreeSet, HashMap};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Returns the names of all parameters referenced in a query, such as `id` for `$id`, skipping string literals,
+/// escaped names and comments
+fn collect_param_names(query: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                let mut escaped = false;
+                for inner in chars.by_ref() {
+                    match inner {
+                        _ if escaped => escaped = false,
+                        '\\' if c != '`' => escaped = true,
+                        _ if inner == c => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|inner| *inner == '\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                let mut last = None;
+                for inner in chars.by_ref() {
+                    if last == Some('*') && inner == '/' {
+                        break;
+                    }
+                    last = Some(inner);
+                }
+            }
+            '$' => {
+                let mut name = String::new();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    name.push(next);
+                    chars.next();
+                }
+                if !name.is_empty() {
+                    names.insert(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// A stable 64-bit FNV-1a hash of the query, ignoring differences in whitespace
+fn fingerprint(query: &str) -> u64 {
+    let normalized = query.split_whitespace().collect::<Vec<_>>().join(" ");
+    normalized.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// A named query registered in a [`QueryRegistry`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryTemplate {
+    /// The name the query is executed by
+    pub name: String,
+    /// The query string, referencing its parameters as `$name`
+    pub query: String,
+    /// Whether the query is executed as a read-only query
+    pub read_only: bool,
+    /// The names of the parameters the query references, all of which must be provided when executing it
+    pub params: BTreeSet<String>,
+    /// A hash of the query string, which is the same across processes, platforms and releases,
+    /// so it can be used to correlate metrics and slow query logs between deployments
+    pub fingerprint: u64,
+}
+
+impl QueryTemplate {
+    fn new(
+        name: String,
+        query: String,
+        read_only: bool,
+    ) -> Self {
+        Self {
+            params: collect_param_names(&query),
+            fingerprint: fingerprint(&query),
+            name,
+            query,
+            read_only,
+        }
+    }
+
+    /// Checks that the provided parameters are exactly the ones the query references
+    ///
+    /// # Arguments
+    /// * `params`: The parameters to execute the query with
+    ///
+    /// # Returns
+    /// Nothing, or [`FalkorDBError::QueryParamsMismatch`] listing the missing and unexpected parameters
+    pub fn validate_params(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> FalkorResult<()> {
+        let missing: Vec<_> = self
+            .params
+            .iter()
+            .filter(|name| !params.contains_key(*name))
+            .cloned()
+            .collect();
+        let mut unexpected: Vec<_> = params
+            .keys()
+            .filter(|name| !self.params.contains(*name))
+            .cloned()
+            .collect();
+        unexpected.sort();
+
+        match missing.is_empty() && unexpected.is_empty() {
+            true => Ok(()),
+            false => Err(FalkorDBError::QueryParamsMismatch {
+                name: self.name.clone(),
+                missing,
+                unexpected,
+            }),
+        }
+    }
+}
+
+/// A registry of named query templates, usually built once at startup, and attached to a client using
+/// [`FalkorClientBuilder::with_query_registry`](crate::FalkorClientBuilder::with_query_registry)
+/// Graphs selected from that client execute them by name, see [`SyncGraph::run`](crate::SyncGraph::run),
+/// keeping every query the application runs in one place, where it can be reviewed, and traced by name.
+///
+/// ```
+/// # use falkordb::QueryRegistry;
+/// let registry = QueryRegistry::new()
+///     .with_ro_query("find_user", "MATCH (u:User {id: $id}) RETURN u")
+///     .with_query("rename_user", "MATCH (u:User {id: $id}) SET u.name = $name");
+/// assert_eq!(registry.get("rename_user").map(|template| template.params.len()), Some(2));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryRegistry {
+    templates: HashMap<String, QueryTemplate>,
+}
+
+impl QueryRegistry {
+    /// Creates a new, empty, [`QueryRegistry`]
+    ///
+    /// # Returns
+    /// The new [`QueryRegistry`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a query, replacing any query previously registered under the same name
+    ///
+    /// # Arguments
+    /// * `name`: The name to execute the query by
+    /// * `query`: The query string, referencing its parameters as `$name`
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_query<N: ToString, Q: ToString>(
+        self,
+        name: N,
+        query: Q,
+    ) -> Self {
+        self.with_template(QueryTemplate::new(
+            name.to_string(),
+            query.to_string(),
+            false,
+        ))
+    }
+
+    /// Registers a read-only query, replacing any query previously registered under the same name
+    ///
+    /// # Arguments
+    /// * `name`: The name to execute the query by
+    /// * `query`: The query string, referencing its parameters as `$name`
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_ro_query<N: ToString, Q: ToString>(
+        self,
+        name: N,
+        query: Q,
+    ) -> Self {
+        self.with_template(QueryTemplate::new(
+            name.to_string(),
+            query.to_string(),
+            true,
+        ))
+    }
+
+    fn with_template(
+        mut self,
+        template: QueryTemplate,
+    ) -> Self {
+        self.templates.insert(template.name.clone(), template);
+        self
+    }
+
+    /// Returns the query registered under the provided name
+    ///
+    /// # Arguments
+    /// * `name`: The name the query was registered by
+    ///
+    /// # Returns
+    /// The [`QueryTemplate`], if one was registered under this name
+    pub fn get(
+        &self,
+        name: &str,
+    ) -> Option<&QueryTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Returns the registered query, after checking the provided parameters match it
+    pub(crate) fn resolve(
+        &self,
+        name: &str,
+        params: &HashMap<String, String>,
+    ) -> FalkorResult<&QueryTemplate> {
+        let template = self
+            .get(name)
+            .ok_or_else(|| FalkorDBError::UnregisteredQuery(name.to_string()))?;
+        template.validate_params(params)?;
+        Ok(template)
+    }
+
+    /// Returns an iterator over all registered queries, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &QueryTemplate> {
+        self.templates.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_param_names() {
+        assert_eq!(
+            collect_param_names(
+                "MATCH (u:User {id: $id}) WHERE u.name <> '$literal' AND u.`$escaped` = $name_2 // $comment\nRETURN u /* $block */"
+            ),
+            BTreeSet::from(["id".to_string(), "name_2".to_string()])
+        );
+        assert!(collect_param_names("RETURN '$' + \"\\\"$quoted\"").is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(""), FNV_OFFSET_BASIS);
+        assert_eq!(fingerprint("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            fingerprint("MATCH (n)\n    RETURN n"),
+            fingerprint("MATCH (n) RETURN n")
+        );
+        assert_ne!(
+            fingerprint("MATCH (n) RETURN n"),
+            fingerprint("MATCH (m) RETURN m")
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let registry = QueryRegistry::new()
+            .with_ro_query("find_user", "MATCH (u:User {id: $id}) RETURN u")
+            .with_query("touch", "MATCH (u:User) SET u.seen = timestamp()");
+
+        let params = HashMap::from([("id".to_string(), "7".to_string())]);
+        let template = registry
+            .resolve("find_user", &params)
+            .expect("Could not resolve query");
+        assert!(template.read_only);
+        assert!(registry.resolve("touch", &HashMap::new()).is_ok());
+
+        assert_eq!(
+            registry.resolve("find_users", &params).err(),
+            Some(FalkorDBError::UnregisteredQuery("find_users".to_string()))
+        );
+        assert_eq!(
+            registry.resolve("touch", &params).err(),
+            Some(FalkorDBError::QueryParamsMismatch {
+                name: "touch".to_string(),
+                missing: vec![],
+                unexpected: vec!["id".to_string()]
+            })
+        );
+        assert_eq!(
+            registry.resolve("find_user", &HashMap::new()).err(),
+            Some(FalkorDBError::QueryParamsMismatch {
+                name: "find_user".to_string(),
+                missing: vec!["id".to_string()],
+                unexpected: vec![]
+            })
+        );
+    }
+}
@@ -0,0 +1,225 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{graph::escape_name, FalkorDBError, FalkorMap, FalkorResult, FalkorValue};
+use std::collections::HashMap;
+
+/// A node to seed, referenced by edges using its key
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeFixture {
+    /// The key edges reference this node by, only used within the fixtures
+    pub key: String,
+    /// The labels of the node
+    pub labels: Vec<String>,
+    /// The properties of the node, which together with the labels identify it when merging
+    pub properties: FalkorMap,
+}
+
+/// An edge to seed, connecting two nodes by their keys
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EdgeFixture {
+    /// The key of the source node
+    pub from: String,
+    /// The relationship type of the edge
+    pub relationship: String,
+    /// The key of the destination node
+    pub to: String,
+    /// The properties of the edge
+    pub properties: FalkorMap,
+}
+
+/// A declarative description of nodes and the edges between them, written to a graph using `seed`,
+/// allowing concise test setups:
+///
+/// ```no_run
+/// # use falkordb::{FalkorClientBuilder, GraphFixtures};
+/// # let client = FalkorClientBuilder::new().build().unwrap();
+/// let graph = client.select_graph("social");
+/// let fixtures = GraphFixtures::new()
+///     .node("alice", &["Person"], [("name", "Alice")])
+///     .node("bob", &["Person"], [("name", "Bob")])
+///     .edge("alice", "KNOWS", "bob", [("since", 2020)]);
+/// graph.seed(&fixtures).expect("Could not seed graph");
+/// ```
+///
+/// Every entity is written using MERGE, so seeding the same fixtures again does not create duplicates.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphFixtures {
+    /// The nodes to seed
+    pub nodes: Vec<NodeFixture>,
+    /// The edges to seed, which may only reference keys of nodes in these fixtures
+    pub edges: Vec<EdgeFixture>,
+}
+
+fn into_properties<K: ToString, V: Into<FalkorValue>, P: IntoIterator<Item = (K, V)>>(
+    properties: P
+) -> FalkorMap {
+    properties
+        .into_iter()
+        .map(|(key, val)| (key.to_string(), val.into()))
+        .collect()
+}
+
+impl GraphFixtures {
+    /// Creates empty fixtures
+    ///
+    /// # Returns
+    /// The new [`GraphFixtures`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node to the fixtures
+    ///
+    /// # Arguments
+    /// * `key`: The key edges reference this node by
+    /// * `labels`: The labels of the node
+    /// * `properties`: The properties of the node
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn node<K: ToString, V: Into<FalkorValue>, P: IntoIterator<Item = (K, V)>>(
+        mut self,
+        key: &str,
+        labels: &[&str],
+        properties: P,
+    ) -> Self {
+        self.nodes.push(NodeFixture {
+            key: key.to_string(),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            properties: into_properties(properties),
+        });
+        self
+    }
+
+    /// Adds an edge to the fixtures
+    ///
+    /// # Arguments
+    /// * `from`: The key of the source node
+    /// * `relationship`: The relationship type of the edge
+    /// * `to`: The key of the destination node
+    /// * `properties`: The properties of the edge
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn edge<K: ToString, V: Into<FalkorValue>, P: IntoIterator<Item = (K, V)>>(
+        mut self,
+        from: &str,
+        relationship: &str,
+        to: &str,
+        properties: P,
+    ) -> Self {
+        self.edges.push(EdgeFixture {
+            from: from.to_string(),
+            relationship: relationship.to_string(),
+            to: to.to_string(),
+            properties: into_properties(properties),
+        });
+        self
+    }
+}
+
+fn properties_literal(properties: &FalkorMap) -> FalkorResult<String> {
+    match properties.is_empty() {
+        true => Ok(String::new()),
+        false => FalkorValue::Map(properties.clone())
+            .to_cypher_literal()
+            .map(|literal| format!(" {literal}")),
+    }
+}
+
+/// Generates a single query merging all nodes, followed by all edges between them,
+/// so the fixtures are written atomically, with every edge's nodes already bound
+pub(crate) fn generate_seed_query(fixtures: &GraphFixtures) -> FalkorResult<String> {
+    let mut aliases = HashMap::with_capacity(fixtures.nodes.len());
+    let mut clauses = Vec::with_capacity(fixtures.nodes.len() + fixtures.edges.len());
+
+    for (idx, node) in fixtures.nodes.iter().enumerate() {
+        if node.labels.is_empty() && node.properties.is_empty() {
+            return Err(FalkorDBError::InvalidFixtures(format!(
+                "node {} has neither labels nor properties, and would match any node",
+                node.key
+            )));
+        }
+        let alias = format!("n{idx}");
+        if aliases.insert(node.key.as_str(), alias.clone()).is_some() {
+            return Err(FalkorDBError::InvalidFixtures(format!(
+                "node key {} is used more than once",
+                node.key
+            )));
+        }
+
+        let labels: String = node
+            .labels
+            .iter()
+            .map(|label| format!(":{}", escape_name(label)))
+            .collect();
+        clauses.push(format!(
+            "MERGE ({alias}{labels}{})",
+            properties_literal(&node.properties)?
+        ));
+    }
+
+    for edge in &fixtures.edges {
+        let alias_of = |key: &str| {
+            aliases.get(key).ok_or_else(|| {
+                FalkorDBError::InvalidFixtures(format!("edge references unknown node key {key}"))
+            })
+        };
+        clauses.push(format!(
+            "MERGE ({})-[:{}{}]->({})",
+            alias_of(&edge.from)?,
+            escape_name(&edge.relationship),
+            properties_literal(&edge.properties)?,
+            alias_of(&edge.to)?
+        ));
+    }
+
+    Ok(clauses.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_seed_query() {
+        let fixtures = GraphFixtures::new()
+            .node("alice", &["Person", "Admin"], [("name", "Alice")])
+            .node("acme", &["Company"], [("name", "Acme")])
+            .edge("alice", "WORKS_AT", "acme", [("since", 2020)])
+            .edge("acme", "EMPLOYS", "alice", Vec::<(&str, i64)>::new());
+
+        assert_eq!(
+            generate_seed_query(&fixtures),
+            Ok(concat!(
+                "MERGE (n0:`Person`:`Admin` {`name`: \"Alice\"}) ",
+                "MERGE (n1:`Company` {`name`: \"Acme\"}) ",
+                "MERGE (n0)-[:`WORKS_AT` {`since`: 2020}]->(n1) ",
+                "MERGE (n1)-[:`EMPLOYS`]->(n0)"
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn test_generate_seed_query_invalid_fixtures() {
+        let no_props = Vec::<(&str, i64)>::new();
+        let unknown_key = GraphFixtures::new()
+            .node("alice", &["Person"], no_props.clone())
+            .edge("alice", "KNOWS", "bob", no_props.clone());
+        let duplicate_key = GraphFixtures::new()
+            .node("alice", &["Person"], no_props.clone())
+            .node("alice", &["Admin"], no_props.clone());
+        let matches_any = GraphFixtures::new().node("alice", &[], no_props);
+
+        for fixtures in [unknown_key, duplicate_key, matches_any] {
+            assert!(matches!(
+                generate_seed_query(&fixtures),
+                Err(FalkorDBError::InvalidFixtures(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::EntityType;
+
+/// The property marking an entity as soft deleted, holding the time of deletion in milliseconds since the epoch
+pub const SOFT_DELETE_PROPERTY: &str = "deleted_at";
+
+/// Keywords starting a new clause, ending the WHERE clause of the preceding MATCH
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "CALL", "CREATE", "DELETE", "DETACH", "FOREACH", "LIMIT", "MATCH", "MERGE", "OPTIONAL",
+    "ORDER", "REMOVE", "RETURN", "SET", "SKIP", "UNION", "UNWIND", "WITH",
+];
+
+/// Returns the predicate excluding soft deleted entities bound to the provided alias
+///
+/// # Arguments
+/// * `alias`: The variable name the entity is bound to in the query
+///
+/// # Returns
+/// A predicate which can be used in a WHERE clause, such as `p.deleted_at IS NULL`
+pub fn not_soft_deleted(alias: &str) -> String {
+    format!("{alias}.{SOFT_DELETE_PROPERTY} IS NULL")
+}
+
+pub(crate) fn generate_soft_delete_query(
+    entity_type: EntityType,
+    entity_id: i64,
+    restore: bool,
+) -> String {
+    let pattern = match entity_type {
+        EntityType::Node => "(e)",
+        EntityType::Edge => "()-[e]->()",
+    };
+    let update = match restore {
+        true => format!("REMOVE e.{SOFT_DELETE_PROPERTY}"),
+        false => format!("SET e.{SOFT_DELETE_PROPERTY} = timestamp()"),
+    };
+
+    format!("MATCH {pattern} WHERE ID(e) = {entity_id} {update}")
+}
+
+/// Returns the byte ranges of all words outside of literals, escaped names and nested expressions
+fn top_level_words(query: &str) -> Vec<(usize, usize)> {
+    let mut words = vec![];
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut word_start = None;
+    let mut prev = None;
+
+    for (idx, c) in query.char_indices() {
+        if let Some(q) = quote {
+            match (escaped, c) {
+                (true, _) => escaped = false,
+                (false, '\\') => escaped = true,
+                (false, c) if c == q => quote = None,
+                _ => {}
+            }
+            prev = Some(c);
+            continue;
+        }
+
+        let is_word_char = c.is_ascii_alphanumeric() || c == '_';
+        match (word_start, is_word_char) {
+            (None, true) if depth == 0 && !matches!(prev, Some('.' | '$')) => {
+                word_start = Some(idx)
+            }
+            (Some(start), false) => {
+                words.push((start, idx));
+                word_start = None;
+            }
+            _ => {}
+        }
+
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    words.extend(word_start.map(|start| (start, query.len())));
+
+    words
+}
+
+/// Adds the soft deletion predicates for the provided aliases to the WHERE clause of the last top-level MATCH clause,
+/// creating the WHERE clause if it doesn't exist, queries without a MATCH clause are returned unchanged
+pub(crate) fn apply_soft_delete_filter(
+    query: &str,
+    aliases: &[String],
+) -> String {
+    if aliases.is_empty() {
+        return query.to_string();
+    }
+
+    let words = top_level_words(query);
+    let is_keyword = |(start, end): &(usize, usize), keyword: &str| {
+        query[*start..*end].eq_ignore_ascii_case(keyword)
+    };
+    let Some(match_idx) = words.iter().rposition(|word| is_keyword(word, "MATCH")) else {
+        return query.to_string();
+    };
+
+    let mut where_end = None;
+    let mut clause_end = query.len();
+    for word in &words[match_idx + 1..] {
+        if CLAUSE_KEYWORDS
+            .iter()
+            .any(|keyword| is_keyword(word, keyword))
+        {
+            clause_end = word.0;
+            break;
+        }
+        if where_end.is_none() && is_keyword(word, "WHERE") {
+            where_end = Some(word.1);
+        }
+    }
+
+    let predicates = aliases
+        .iter()
+        .map(|alias| not_soft_deleted(alias))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let filtered = match where_end {
+        Some(where_end) => format!(
+            "{} ({}) AND {predicates}",
+            &query[..where_end],
+            query[where_end..clause_end].trim()
+        ),
+        None => format!("{} WHERE {predicates}", query[..clause_end].trim_end()),
+    };
+
+    match query[clause_end..].trim_start() {
+        "" => filtered,
+        rest => format!("{filtered} {rest}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(
+        query: &str,
+        aliases: &[&str],
+    ) -> String {
+        apply_soft_delete_filter(
+            query,
+            &aliases.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_generate_soft_delete_query() {
+        assert_eq!(
+            generate_soft_delete_query(EntityType::Node, 4, false),
+            "MATCH (e) WHERE ID(e) = 4 SET e.deleted_at = timestamp()"
+        );
+        assert_eq!(
+            generate_soft_delete_query(EntityType::Edge, 2, true),
+            "MATCH ()-[e]->() WHERE ID(e) = 2 REMOVE e.deleted_at"
+        );
+    }
+
+    #[test]
+    fn test_apply_soft_delete_filter() {
+        assert_eq!(
+            filter("MATCH (p:Person) RETURN p", &["p"]),
+            "MATCH (p:Person) WHERE p.deleted_at IS NULL RETURN p"
+        );
+        assert_eq!(
+            filter(
+                "MATCH (p:Person)-[:KNOWS]->(f) where p.age > 30 OR f.age > 30 return p, f",
+                &["p", "f"]
+            ),
+            "MATCH (p:Person)-[:KNOWS]->(f) where (p.age > 30 OR f.age > 30) AND p.deleted_at IS NULL AND f.deleted_at IS NULL return p, f"
+        );
+        assert_eq!(
+            filter("MATCH (a) MATCH (b {name: 'RETURN'})", &["a"]),
+            "MATCH (a) MATCH (b {name: 'RETURN'}) WHERE a.deleted_at IS NULL"
+        );
+        assert_eq!(
+            filter(
+                "MATCH (p) WHERE p.name = \"WITH \\\" MATCH\" AND p.with = 1 WITH p RETURN p",
+                &["p"]
+            ),
+            "MATCH (p) WHERE (p.name = \"WITH \\\" MATCH\" AND p.with = 1) AND p.deleted_at IS NULL WITH p RETURN p"
+        );
+        assert_eq!(filter("RETURN 1", &["p"]), "RETURN 1");
+        assert_eq!(filter("MATCH (p) RETURN p", &[]), "MATCH (p) RETURN p");
+    }
+}
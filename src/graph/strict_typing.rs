@@ -0,0 +1,80 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    graph::escape_name, value::coercion::find_type_coercions, EntityType, FalkorDBError,
+    FalkorResult, FalkorValue, LazyResultSet, QueryResult,
+};
+
+pub(crate) fn generate_set_property_query(
+    entity_type: EntityType,
+    entity_id: i64,
+    property: &str,
+    value: &FalkorValue,
+) -> FalkorResult<String> {
+    let pattern = match entity_type {
+        EntityType::Node => "(e)",
+        EntityType::Edge => "()-[e]->()",
+    };
+    let property = escape_name(property);
+
+    Ok(format!(
+        "MATCH {pattern} WHERE ID(e) = {entity_id} SET e.{property} = {} RETURN e.{property}",
+        value.to_cypher_literal()?
+    ))
+}
+
+/// Compares the written value with the one the server returned after storing it
+/// In strict mode a coerced value fails the operation, otherwise it is only logged.
+pub(crate) fn check_property_round_trip(
+    property: &str,
+    written: &FalkorValue,
+    mut res: QueryResult<LazyResultSet>,
+    strict: bool,
+) -> FalkorResult<Option<FalkorValue>> {
+    let Some(read) = res.data.next().and_then(|row| row.into_iter().next()) else {
+        return Ok(None);
+    };
+
+    if let Some(coercion) = find_type_coercions(written, &read).into_iter().next() {
+        if strict {
+            return Err(FalkorDBError::TypeCoerced {
+                property: property.to_string(),
+                coercion,
+            });
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(property, %coercion, "The server coerced a written property");
+    }
+
+    Ok(Some(read))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_set_property_query() {
+        assert_eq!(
+            generate_set_property_query(EntityType::Node, 7, "score", &FalkorValue::F64(1.0))
+                .unwrap(),
+            "MATCH (e) WHERE ID(e) = 7 SET e.`score` = 1.0 RETURN e.`score`"
+        );
+        assert_eq!(
+            generate_set_property_query(EntityType::Edge, 3, "since", &FalkorValue::I64(2020))
+                .unwrap(),
+            "MATCH ()-[e]->() WHERE ID(e) = 3 SET e.`since` = 2020 RETURN e.`since`"
+        );
+        assert!(generate_set_property_query(
+            EntityType::Node,
+            7,
+            "score",
+            &FalkorValue::F64(f64::NAN)
+        )
+        .is_err());
+    }
+}
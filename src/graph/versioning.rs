@@ -0,0 +1,141 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    graph::{algorithms::string_literal, escape_name},
+    FalkorDBError, FalkorResult, FalkorValue, LazyResultSet, QueryResult,
+};
+
+/// The label of the nodes holding property versions
+pub const PROPERTY_VERSION_LABEL: &str = "PropertyVersion";
+
+/// The type of the relationships connecting a node to its property versions
+pub const PROPERTY_VERSION_RELATIONSHIP: &str = "HAS_VERSION";
+
+/// A single historical value of a versioned property
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyVersion {
+    /// The value of the property during this version
+    pub value: FalkorValue,
+    /// The timestamp from which this version is valid, inclusive
+    pub valid_from: i64,
+    /// The timestamp until which this version is valid, exclusive, [`None`] if this version is still valid
+    pub valid_to: Option<i64>,
+}
+
+fn version_pattern(property: &str) -> String {
+    format!(
+        "(n)-[:{PROPERTY_VERSION_RELATIONSHIP}]->(v:{PROPERTY_VERSION_LABEL} {{property: {}}})",
+        string_literal(property)
+    )
+}
+
+pub(crate) fn generate_set_versioned_query(
+    node_id: i64,
+    property: &str,
+    value: &FalkorValue,
+    valid_from: i64,
+) -> FalkorResult<String> {
+    let value = value.to_cypher_literal()?;
+    let property_literal = string_literal(property);
+
+    Ok(format!(
+        "MATCH (n) WHERE ID(n) = {node_id} \
+        OPTIONAL MATCH {versions} WHERE v.valid_from > {valid_from} \
+        WITH n, min(v.valid_from) AS valid_to \
+        OPTIONAL MATCH {versions} WHERE v.valid_from <= {valid_from} AND (v.valid_to IS NULL OR v.valid_to > {valid_from}) \
+        SET v.valid_to = {valid_from} \
+        CREATE (n)-[:{PROPERTY_VERSION_RELATIONSHIP}]->(:{PROPERTY_VERSION_LABEL} {{property: {property_literal}, value: {value}, valid_from: {valid_from}, valid_to: valid_to}}) \
+        WITH n, valid_to WHERE valid_to IS NULL \
+        SET n.{} = {value}",
+        escape_name(property),
+        versions = version_pattern(property),
+    ))
+}
+
+pub(crate) fn generate_property_as_of_query(
+    node_id: i64,
+    property: &str,
+    timestamp: i64,
+) -> String {
+    format!(
+        "MATCH {} WHERE ID(n) = {node_id} AND v.valid_from <= {timestamp} AND (v.valid_to IS NULL OR v.valid_to > {timestamp}) \
+        RETURN v.value ORDER BY v.valid_from DESC LIMIT 1",
+        version_pattern(property)
+    )
+}
+
+pub(crate) fn generate_property_history_query(
+    node_id: i64,
+    property: &str,
+) -> String {
+    format!(
+        "MATCH {} WHERE ID(n) = {node_id} RETURN v.value, v.valid_from, v.valid_to ORDER BY v.valid_from",
+        version_pattern(property)
+    )
+}
+
+pub(crate) fn parse_property_as_of(
+    mut res: QueryResult<LazyResultSet>
+) -> FalkorResult<Option<FalkorValue>> {
+    Ok(res.data.next().and_then(|row| row.into_iter().next()))
+}
+
+pub(crate) fn parse_property_history(
+    res: QueryResult<LazyResultSet>
+) -> FalkorResult<Vec<PropertyVersion>> {
+    res.data
+        .map(|row| {
+            let [value, valid_from, valid_to]: [FalkorValue; 3] = row.try_into().map_err(|_| {
+                FalkorDBError::ParsingArrayToStructElementCount(
+                    "Expected exactly 3 elements in result - value, valid_from and valid_to",
+                )
+            })?;
+
+            Ok(PropertyVersion {
+                value,
+                valid_from: valid_from.to_i64().ok_or(FalkorDBError::ParsingI64)?,
+                valid_to: match valid_to {
+                    FalkorValue::None => None,
+                    valid_to => Some(valid_to.to_i64().ok_or(FalkorDBError::ParsingI64)?),
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_set_versioned_query() {
+        assert_eq!(
+            generate_set_versioned_query(7, "price", &FalkorValue::I64(10), 1000).unwrap(),
+            "MATCH (n) WHERE ID(n) = 7 \
+            OPTIONAL MATCH (n)-[:HAS_VERSION]->(v:PropertyVersion {property: \"price\"}) WHERE v.valid_from > 1000 \
+            WITH n, min(v.valid_from) AS valid_to \
+            OPTIONAL MATCH (n)-[:HAS_VERSION]->(v:PropertyVersion {property: \"price\"}) WHERE v.valid_from <= 1000 AND (v.valid_to IS NULL OR v.valid_to > 1000) \
+            SET v.valid_to = 1000 \
+            CREATE (n)-[:HAS_VERSION]->(:PropertyVersion {property: \"price\", value: 10, valid_from: 1000, valid_to: valid_to}) \
+            WITH n, valid_to WHERE valid_to IS NULL \
+            SET n.`price` = 10"
+        );
+        assert!(generate_set_versioned_query(7, "price", &FalkorValue::F64(f64::NAN), 0).is_err());
+    }
+
+    #[test]
+    fn test_generate_read_queries() {
+        assert_eq!(
+            generate_property_as_of_query(7, "price", 1500),
+            "MATCH (n)-[:HAS_VERSION]->(v:PropertyVersion {property: \"price\"}) WHERE ID(n) = 7 AND v.valid_from <= 1500 AND (v.valid_to IS NULL OR v.valid_to > 1500) \
+            RETURN v.value ORDER BY v.valid_from DESC LIMIT 1"
+        );
+        assert_eq!(
+            generate_property_history_query(7, "price"),
+            "MATCH (n)-[:HAS_VERSION]->(v:PropertyVersion {property: \"price\"}) WHERE ID(n) = 7 RETURN v.value, v.valid_from, v.valid_to ORDER BY v.valid_from"
+        );
+    }
+}
@@ -4,13 +4,21 @@
  */
 
 use crate::{
-    client::ProvidesSyncConnections,
+    client::{retry::run_with_retries, ProvidesSyncConnections},
     parser::{
         parse_type, redis_value_as_int, redis_value_as_string, redis_value_as_vec, ParserTypeMarker,
     },
-    FalkorDBError, FalkorResult, FalkorValue,
+    FalkorDBError, FalkorMap, FalkorResult,
+};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
 };
-use std::{collections::HashMap, sync::Arc};
 
 pub(crate) fn get_refresh_command(schema_type: SchemaType) -> &'static str {
     match schema_type {
@@ -20,6 +28,15 @@ pub(crate) fn get_refresh_command(schema_type: SchemaType) -> &'static str {
     }
 }
 
+/// The column yielded by the refresh procedure of a schema type
+fn get_refresh_column(schema_type: SchemaType) -> &'static str {
+    match schema_type {
+        SchemaType::Labels => "label",
+        SchemaType::Properties => "propertyKey",
+        SchemaType::Relationships => "relationshipType",
+    }
+}
+
 // Intermediate type for map parsing
 #[derive(Debug)]
 pub(crate) struct FKeyTypeVal {
@@ -72,15 +89,110 @@ pub enum SchemaType {
 
 pub(crate) type IdMap = HashMap<i64, String>;
 
+/// A callback invoked after a schema refresh discovered new ids, see [`GraphSchema::on_refresh`]
+pub type SchemaRefreshHook = Arc<dyn Fn(SchemaType, &[i64]) + Send + Sync>;
+
+/// A plain copy of a [`GraphSchema`]'s maps, which can be persisted and later restored using [`GraphSchema::restore`],
+/// allowing short-lived processes to skip refreshing the schema on their first queries.
+/// With the `serde` feature enabled this can be serialized to and deserialized from any format serde supports.
+///
+/// Ids are never reused within a graph, so a snapshot remains valid as the graph grows,
+/// but it should be discarded if the graph is deleted and recreated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphSchemaSnapshot {
+    /// The name of the graph this schema belongs to
+    pub graph_name: String,
+    /// The schema version
+    pub version: i64,
+    /// The label ids, and their respective string representations
+    pub labels: HashMap<i64, String>,
+    /// The property ids, and their respective string representations
+    pub properties: HashMap<i64, String>,
+    /// The relationship type ids, and their respective string representations
+    pub relationships: HashMap<i64, String>,
+}
+
 /// A struct containing the various schema maps, allowing conversions between ids and their string representations.
-#[derive(Clone)]
+/// The maps are kept behind read-write locks, so the schema can be refreshed through a shared reference,
+/// which allows querying a graph without exclusive access to it.
+///
+/// Each map may be bounded, see [`FalkorClientBuilder::with_schema_cache_max_ids`](crate::FalkorClientBuilder::with_schema_cache_max_ids),
+/// in which case the least recently used ids are evicted, and fetched again, one at a time, once encountered.
 pub struct GraphSchema {
     client: Arc<dyn ProvidesSyncConnections>,
     graph_name: String,
-    version: i64,
-    labels: IdMap,
-    properties: IdMap,
-    relationships: IdMap,
+    version: AtomicI64,
+    labels: RwLock<IdMap>,
+    properties: RwLock<IdMap>,
+    relationships: RwLock<IdMap>,
+    refreshes: [SchemaRefresh; 3],
+    usage: [Mutex<IdUsage>; 3],
+    max_ids: Option<NonZeroUsize>,
+    hooks: RwLock<Vec<SchemaRefreshHook>>,
+}
+
+/// Tracks how many ids of a schema type were discovered, and when each cached id was last used
+#[derive(Clone, Default)]
+struct IdUsage {
+    discovered: i64,
+    clock: u64,
+    last_used: HashMap<i64, u64>,
+}
+
+impl IdUsage {
+    fn touch(
+        &mut self,
+        id: i64,
+    ) {
+        self.clock += 1;
+        self.last_used.insert(id, self.clock);
+    }
+
+    /// Evicts the least recently used ids, ids which were never used first, until the map fits within the bound
+    fn evict(
+        &mut self,
+        id_map: &mut IdMap,
+        max_ids: NonZeroUsize,
+    ) {
+        if id_map.len() > max_ids.get() {
+            let mut by_last_use: Vec<(u64, i64)> = id_map
+                .keys()
+                .map(|id| (self.last_used.get(id).copied().unwrap_or_default(), *id))
+                .collect();
+            by_last_use.sort_unstable();
+
+            let excess = id_map.len() - max_ids.get();
+            for (_, id) in by_last_use.into_iter().take(excess) {
+                id_map.remove(&id);
+            }
+        }
+        self.last_used.retain(|id, _| id_map.contains_key(id));
+    }
+}
+
+/// Coalesces concurrent refreshes of a single schema type, so only one is in flight at a time
+#[derive(Default)]
+struct SchemaRefresh {
+    in_flight: Mutex<()>,
+    completed: AtomicU64,
+}
+
+impl Clone for GraphSchema {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            graph_name: self.graph_name.clone(),
+            version: AtomicI64::new(self.version.load(Ordering::Relaxed)),
+            labels: RwLock::new(self.labels.read().clone()),
+            properties: RwLock::new(self.properties.read().clone()),
+            relationships: RwLock::new(self.relationships.read().clone()),
+            refreshes: Default::default(),
+            usage: [0, 1, 2].map(|idx| Mutex::new(self.usage[idx].lock().clone())),
+            max_ids: self.max_ids,
+            hooks: RwLock::new(self.hooks.read().clone()),
+        }
+    }
 }
 
 impl GraphSchema {
@@ -91,44 +203,122 @@ impl GraphSchema {
         Self {
             client,
             graph_name: graph_name.to_string(),
-            version: 0,
-            labels: IdMap::new(),
-            properties: IdMap::new(),
-            relationships: IdMap::new(),
+            version: AtomicI64::new(0),
+            labels: RwLock::new(IdMap::new()),
+            properties: RwLock::new(IdMap::new()),
+            relationships: RwLock::new(IdMap::new()),
+            refreshes: Default::default(),
+            usage: Default::default(),
+            max_ids: None,
+            hooks: RwLock::new(Vec::new()),
         }
     }
 
+    /// Bounds the number of ids cached per schema type
+    pub(crate) fn with_max_ids(
+        self,
+        max_ids: Option<NonZeroUsize>,
+    ) -> Self {
+        Self { max_ids, ..self }
+    }
+
+    /// Registers a callback, invoked whenever refreshing the schema discovers new labels, properties or relationship types,
+    /// E.g. to invalidate application caches, or emit metrics.
+    /// The callback receives the refreshed [`SchemaType`] and the newly added ids, in ascending order,
+    /// and is invoked on the thread performing the refresh, so it should return quickly.
+    ///
+    /// Since graph schemas are shared by all handles to the same graph, the callback observes refreshes made through any of them.
+    ///
+    /// # Arguments
+    /// * `hook`: The callback to invoke
+    pub fn on_refresh<F: Fn(SchemaType, &[i64]) + Send + Sync + 'static>(
+        &self,
+        hook: F,
+    ) {
+        self.hooks.write().push(Arc::new(hook));
+    }
+
     /// Clears all cached schemas, this will cause a refresh when next attempting to parse a compact query.
-    pub fn clear(&mut self) {
-        self.version = 0;
-        self.labels.clear();
-        self.properties.clear();
-        self.relationships.clear();
+    pub fn clear(&self) {
+        self.version.store(0, Ordering::Relaxed);
+        self.labels.write().clear();
+        self.properties.write().clear();
+        self.relationships.write().clear();
+        for usage in &self.usage {
+            *usage.lock() = IdUsage::default();
+        }
+    }
+
+    /// Copies the currently cached maps, so they can be persisted, E.g. to disk or an external cache
+    ///
+    /// # Returns
+    /// A [`GraphSchemaSnapshot`] of the cached maps
+    pub fn snapshot(&self) -> GraphSchemaSnapshot {
+        GraphSchemaSnapshot {
+            graph_name: self.graph_name.clone(),
+            version: self.version.load(Ordering::Relaxed),
+            labels: self.labels.read().clone(),
+            properties: self.properties.read().clone(),
+            relationships: self.relationships.read().clone(),
+        }
+    }
+
+    /// Replaces the cached maps with a previously persisted [`GraphSchemaSnapshot`]
+    /// Ids missing from the snapshot will still cause a refresh once encountered.
+    ///
+    /// # Arguments
+    /// * `snapshot`: The snapshot to restore, which must have been taken from a graph with the same name
+    pub fn restore(
+        &self,
+        snapshot: GraphSchemaSnapshot,
+    ) -> FalkorResult<()> {
+        if snapshot.graph_name != self.graph_name {
+            return Err(FalkorDBError::SchemaSnapshotMismatch(snapshot.graph_name));
+        }
+
+        self.version.store(snapshot.version, Ordering::Relaxed);
+        for (schema_type, ids) in [
+            (SchemaType::Labels, snapshot.labels),
+            (SchemaType::Properties, snapshot.properties),
+            (SchemaType::Relationships, snapshot.relationships),
+        ] {
+            let mut id_map = self.get_id_map_by_schema_type(schema_type).write();
+            let mut usage = self.get_usage_by_schema_type(schema_type).lock();
+            *id_map = ids;
+            usage.discovered = usage
+                .discovered
+                .max(id_map.keys().max().map_or(0, |id| id + 1));
+            usage.last_used.clear();
+            if let Some(max_ids) = self.max_ids {
+                usage.evict(&mut id_map, max_ids);
+            }
+        }
+        Ok(())
     }
 
     /// Returns a read-write-locked map, of the relationship ids to their respective string representations.
     /// Minimize locking these to avoid starvation.
-    pub fn relationships(&self) -> &IdMap {
-        &self.relationships
+    pub fn relationships(&self) -> RwLockReadGuard<'_, IdMap> {
+        self.relationships.read()
     }
 
     /// Returns a read-write-locked map, of the label ids to their respective string representations.
     /// Minimize locking these to avoid starvation.
-    pub fn labels(&self) -> &IdMap {
-        &self.labels
+    pub fn labels(&self) -> RwLockReadGuard<'_, IdMap> {
+        self.labels.read()
     }
 
     /// Returns a read-write-locked map, of the property ids to their respective string representations.
     /// Minimize locking these to avoid starvation.
-    pub fn properties(&self) -> &IdMap {
-        &self.properties
+    pub fn properties(&self) -> RwLockReadGuard<'_, IdMap> {
+        self.properties.read()
     }
 
     #[inline]
     fn get_id_map_by_schema_type(
         &self,
         schema_type: SchemaType,
-    ) -> &IdMap {
+    ) -> &RwLock<IdMap> {
         match schema_type {
             SchemaType::Labels => &self.labels,
             SchemaType::Properties => &self.properties,
@@ -136,86 +326,236 @@ impl GraphSchema {
         }
     }
 
+    #[inline]
+    fn get_refresh_by_schema_type(
+        &self,
+        schema_type: SchemaType,
+    ) -> &SchemaRefresh {
+        &self.refreshes[match schema_type {
+            SchemaType::Labels => 0,
+            SchemaType::Properties => 1,
+            SchemaType::Relationships => 2,
+        }]
+    }
+
+    #[inline]
+    fn get_usage_by_schema_type(
+        &self,
+        schema_type: SchemaType,
+    ) -> &Mutex<IdUsage> {
+        &self.usage[match schema_type {
+            SchemaType::Labels => 0,
+            SchemaType::Properties => 1,
+            SchemaType::Relationships => 2,
+        }]
+    }
+
+    /// Looks up an id, without refreshing the schema
+    fn get_cached_id(
+        &self,
+        raw_id: i64,
+        schema_type: SchemaType,
+    ) -> Option<String> {
+        let found = self
+            .get_id_map_by_schema_type(schema_type)
+            .read()
+            .get(&raw_id)
+            .cloned();
+        if found.is_some() && self.max_ids.is_some() {
+            self.get_usage_by_schema_type(schema_type)
+                .lock()
+                .touch(raw_id);
+        }
+        found
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Refresh Schema Type", skip_all, level = "info")
     )]
     fn refresh(
-        &mut self,
+        &self,
         schema_type: SchemaType,
     ) -> FalkorResult<()> {
-        let id_map = match schema_type {
-            SchemaType::Labels => &mut self.labels,
-            SchemaType::Properties => &mut self.properties,
-            SchemaType::Relationships => &mut self.relationships,
-        };
+        let keys =
+            self.query_schema(format!("CALL {}()", get_refresh_command(schema_type)).as_str())?;
+
+        let new_keys = keys
+            .into_iter()
+            .enumerate()
+            .flat_map(|(idx, item)| {
+                FalkorResult::<(i64, String)>::Ok((idx as i64, parse_schema_row(item)?))
+            })
+            .collect::<HashMap<i64, String>>();
+
+        self.replace_ids(schema_type, new_keys);
+        Ok(())
+    }
+
+    /// Fetches the name of a single id which was evicted from a bounded map, adding it back and evicting another instead,
+    /// so a working set larger than the bound costs a small round trip per miss, rather than refetching the whole schema type
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Fetch Evicted Schema Id", skip_all, level = "debug")
+    )]
+    fn fetch_evicted_id(
+        &self,
+        raw_id: i64,
+        schema_type: SchemaType,
+        max_ids: NonZeroUsize,
+    ) -> FalkorResult<String> {
+        let name = self
+            .query_schema(
+                format!(
+                    "CALL {}() YIELD {column} RETURN {column} SKIP {raw_id} LIMIT 1",
+                    get_refresh_command(schema_type),
+                    column = get_refresh_column(schema_type)
+                )
+                .as_str(),
+            )?
+            .into_iter()
+            .next()
+            .ok_or(FalkorDBError::MissingSchemaId(schema_type))
+            .and_then(parse_schema_row)?;
+
+        let mut id_map = self.get_id_map_by_schema_type(schema_type).write();
+        let mut usage = self.get_usage_by_schema_type(schema_type).lock();
+        id_map.insert(raw_id, name.clone());
+        usage.touch(raw_id);
+        usage.evict(&mut id_map, max_ids);
+        Ok(name)
+    }
 
+    /// Runs a schema procedure, returning its rows
+    fn query_schema(
+        &self,
+        query: &str,
+    ) -> FalkorResult<Vec<redis::Value>> {
         // This is essentially the call_procedure(), but can be done here without access to the graph(which would cause ownership issues)
-        let keys = self
-            .client
-            .get_connection()
-            .and_then(|mut conn| {
+        let retry_policy = self.client.retry_policy();
+        run_with_retries(retry_policy.as_ref(), None, || {
+            self.client.get_connection().and_then(|mut conn| {
                 conn.execute_command(
                     Some(self.graph_name.as_str()),
                     "GRAPH.QUERY",
                     None,
-                    Some(&[format!("CALL {}()", get_refresh_command(schema_type)).as_str()]),
+                    Some(&[query]),
                 )
             })
-            .and_then(|res| {
-                redis_value_as_vec(res).and_then(|as_vec| {
-                    as_vec.into_iter().nth(1).ok_or(FalkorDBError::ParsingArrayToStructElementCount(
+        })
+        .and_then(|res| {
+            redis_value_as_vec(res).and_then(|as_vec| {
+                as_vec.into_iter().nth(1).ok_or(FalkorDBError::ParsingArrayToStructElementCount(
             "Expected exactly 3 types for header-resultset-stats response from refresh query"
         ))
-                })
             })
-            .and_then(redis_value_as_vec)?;
+        })
+        .and_then(redis_value_as_vec)
+    }
 
-        let new_keys = keys
-            .into_iter()
-            .enumerate()
-            .flat_map(|(idx, item)| {
-                FalkorResult::<(i64, String)>::Ok((
-                    idx as i64,
-                    redis_value_as_vec(item)
-                        .and_then(|item_seq| {
-                            item_seq.into_iter().next().ok_or_else(|| {
-                                FalkorDBError::ParsingError(
-                            "Expected new label/property to be the first element in an array"
-                                .to_string(),
-                        )
-                            })
-                        })
-                        .and_then(redis_value_as_string)?,
-                ))
-            })
-            .collect::<HashMap<i64, String>>();
+    /// Replaces the map of a schema type with freshly fetched names, each name's id being its position,
+    /// counting as a refresh so concurrent parsers waiting on one don't issue another
+    pub(crate) fn populate(
+        &self,
+        schema_type: SchemaType,
+        names: Vec<String>,
+    ) {
+        let refresh = self.get_refresh_by_schema_type(schema_type);
+        let _in_flight = refresh.in_flight.lock();
+        self.replace_ids(
+            schema_type,
+            names
+                .into_iter()
+                .enumerate()
+                .map(|(idx, name)| (idx as i64, name))
+                .collect(),
+        );
+        refresh.completed.fetch_add(1, Ordering::Release);
+    }
+
+    /// Replaces the map of a schema type, notifying the registered hooks of any ids not discovered before,
+    /// and evicting the least recently used ids if the map is bounded
+    fn replace_ids(
+        &self,
+        schema_type: SchemaType,
+        new_keys: IdMap,
+    ) {
+        let added_ids = {
+            let mut id_map = self.get_id_map_by_schema_type(schema_type).write();
+            let mut usage = self.get_usage_by_schema_type(schema_type).lock();
+            let mut added_ids: Vec<i64> = new_keys
+                .keys()
+                .filter(|id| **id >= usage.discovered)
+                .copied()
+                .collect();
+            added_ids.sort_unstable();
+            usage.discovered = added_ids.last().map_or(usage.discovered, |id| id + 1);
+
+            *id_map = new_keys;
+            if let Some(max_ids) = self.max_ids {
+                usage.evict(&mut id_map, max_ids);
+            }
+            added_ids
+        };
+
+        if added_ids.is_empty() {
+            return;
+        }
+
+        // Clone the hooks so they run without holding any lock, allowing them to access the schema or register other hooks
+        let hooks = self.hooks.read().clone();
+        for hook in hooks {
+            hook(schema_type, &added_ids);
+        }
+    }
 
-        *id_map = new_keys;
+    /// Refreshes the schema type, unless another refresh completed since `observed_refreshes` was read,
+    /// in which case this waits for it instead of issuing another round trip
+    fn refresh_coalesced(
+        &self,
+        schema_type: SchemaType,
+        observed_refreshes: u64,
+    ) -> FalkorResult<()> {
+        let refresh = self.get_refresh_by_schema_type(schema_type);
+        let _in_flight = refresh.in_flight.lock();
+        if refresh.completed.load(Ordering::Acquire) != observed_refreshes {
+            return Ok(());
+        }
+
+        self.refresh(schema_type)?;
+        refresh.completed.fetch_add(1, Ordering::Release);
         Ok(())
     }
 
     pub(crate) fn parse_single_id(
-        &mut self,
+        &self,
         raw_id: i64,
         schema_type: SchemaType,
     ) -> FalkorResult<String> {
-        Ok(
-            match self
-                .get_id_map_by_schema_type(schema_type)
-                .get(&raw_id)
-                .cloned()
-            {
-                None => {
-                    self.refresh(schema_type)?;
-                    self.get_id_map_by_schema_type(schema_type)
-                        .get(&raw_id)
-                        .cloned()
-                        .ok_or(FalkorDBError::MissingSchemaId(schema_type))?
+        // Read before the lookup, so a refresh completing in between is not missed
+        let observed_refreshes = self
+            .get_refresh_by_schema_type(schema_type)
+            .completed
+            .load(Ordering::Acquire);
+
+        Ok(match self.get_cached_id(raw_id, schema_type) {
+            None => {
+                if let Some(max_ids) = self.max_ids {
+                    let mut usage = self.get_usage_by_schema_type(schema_type).lock();
+                    if raw_id >= 0 && raw_id < usage.discovered {
+                        // The id is known to exist, and was only evicted, so there is nothing new to refresh
+                        drop(usage);
+                        return self.fetch_evicted_id(raw_id, schema_type, max_ids);
+                    }
+                    // Mark the id as the most recently used, so a bounded map doesn't evict it right after fetching it
+                    usage.touch(raw_id);
                 }
-                Some(exists) => exists,
-            },
-        )
+                self.refresh_coalesced(schema_type, observed_refreshes)?;
+                self.get_cached_id(raw_id, schema_type)
+                    .ok_or(FalkorDBError::MissingSchemaId(schema_type))?
+            }
+            Some(exists) => exists,
+        })
     }
 
     #[cfg_attr(
@@ -223,7 +563,7 @@ impl GraphSchema {
         tracing::instrument(name = "Parse ID Vec To String Vec", skip_all, level = "debug")
     )]
     pub(crate) fn parse_id_vec(
-        &mut self,
+        &self,
         raw_ids: Vec<redis::Value>,
         schema_type: SchemaType,
     ) -> FalkorResult<Vec<String>> {
@@ -244,26 +584,17 @@ impl GraphSchema {
         tracing::instrument(name = "Parse Properties Map", skip_all, level = "debug")
     )]
     pub(crate) fn parse_properties_map(
-        &mut self,
+        &self,
         value: redis::Value,
-    ) -> FalkorResult<HashMap<String, FalkorValue>> {
+    ) -> FalkorResult<FalkorMap> {
         let raw_properties_vec = redis_value_as_vec(value)?;
 
         let raw_properties_len = raw_properties_vec.len();
         raw_properties_vec.into_iter().try_fold(
-            HashMap::with_capacity(raw_properties_len),
+            FalkorMap::with_capacity(raw_properties_len),
             |mut out_map, item| {
                 let ktv = FKeyTypeVal::try_from(item)?;
-                let key = if let Some(key) = self.properties.get(&ktv.key).cloned() {
-                    key
-                } else {
-                    // Refresh the schema and attempt to retrieve the key again
-                    self.refresh(SchemaType::Properties)?;
-                    self.properties
-                        .get(&ktv.key)
-                        .cloned()
-                        .ok_or(FalkorDBError::MissingSchemaId(SchemaType::Properties))?
-                };
+                let key = self.parse_single_id(ktv.key, SchemaType::Properties)?;
 
                 out_map.insert(key, parse_type(ktv.type_marker, ktv.val, self)?);
                 Ok(out_map)
@@ -272,40 +603,366 @@ impl GraphSchema {
     }
 }
 
+/// Parses the name in a row returned by a schema procedure
+fn parse_schema_row(item: redis::Value) -> FalkorResult<String> {
+    redis_value_as_vec(item)
+        .and_then(|item_seq| {
+            item_seq.into_iter().next().ok_or_else(|| {
+                FalkorDBError::ParsingError(
+                    "Expected new label/property to be the first element in an array".to_string(),
+                )
+            })
+        })
+        .and_then(redis_value_as_string)
+}
+
+/// Holds the schema of every graph selected from a client, keyed by graph name,
+/// so all handles to the same graph share a single cache, and pay for its refreshes once.
+/// Only weak references are kept, so a schema is dropped together with the last handle using it.
+#[derive(Default)]
+pub(crate) struct SchemaCache {
+    schemas: Mutex<HashMap<String, Weak<GraphSchema>>>,
+    max_ids: AtomicUsize,
+}
+
+impl SchemaCache {
+    /// Bounds the number of ids cached per schema type, for schemas created from now on
+    pub(crate) fn set_max_ids(
+        &self,
+        max_ids: Option<NonZeroUsize>,
+    ) {
+        self.max_ids
+            .store(max_ids.map_or(0, NonZeroUsize::get), Ordering::Relaxed);
+    }
+
+    pub(crate) fn get_or_create(
+        &self,
+        graph_name: &str,
+        client: Arc<dyn ProvidesSyncConnections>,
+    ) -> Arc<GraphSchema> {
+        let mut schemas = self.schemas.lock();
+        if let Some(schema) = schemas.get(graph_name).and_then(Weak::upgrade) {
+            return schema;
+        }
+
+        schemas.retain(|_, schema| schema.strong_count() > 0);
+        let schema = Arc::new(
+            GraphSchema::new(graph_name, client)
+                .with_max_ids(NonZeroUsize::new(self.max_ids.load(Ordering::Relaxed))),
+        );
+        schemas.insert(graph_name.to_string(), Arc::downgrade(&schema));
+        schema
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
     use crate::{
-        client::blocking::create_empty_inner_sync_client, graph::HasGraphSchema,
-        test_utils::create_test_client, SyncGraph,
+        client::blocking::create_empty_inner_sync_client,
+        graph::HasGraphSchema,
+        test_utils::{create_test_client, MockTransport},
+        FalkorValue, SyncGraph,
     };
     use std::collections::HashMap;
 
     pub(crate) fn open_readonly_graph_with_modified_schema() -> SyncGraph {
         let client = create_test_client();
-        let mut graph = client.select_graph("imdb");
+        let graph = client.select_graph("imdb");
 
         {
-            let schema = graph.get_graph_schema_mut();
-            schema.properties = HashMap::from([
+            let schema = graph.get_graph_schema();
+            *schema.properties.write() = HashMap::from([
                 (0, "age".to_string()),
                 (1, "is_boring".to_string()),
                 (2, "something_else".to_string()),
                 (3, "secs_since_login".to_string()),
             ]);
 
-            schema.labels = HashMap::from([(0, "much".to_string()), (1, "actor".to_string())]);
+            *schema.labels.write() =
+                HashMap::from([(0, "much".to_string()), (1, "actor".to_string())]);
 
-            schema.relationships = HashMap::from([(0, "very".to_string()), (1, "wow".to_string())]);
+            *schema.relationships.write() =
+                HashMap::from([(0, "very".to_string()), (1, "wow".to_string())]);
         }
 
         graph
     }
 
+    #[derive(Default)]
+    struct CountingConnections {
+        requested: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ProvidesSyncConnections for CountingConnections {
+        fn get_connection(
+            &self
+        ) -> FalkorResult<crate::connection::blocking::FalkorSyncConnection> {
+            self.requested.fetch_add(1, Ordering::SeqCst);
+            Err(FalkorDBError::NoConnection)
+        }
+    }
+
+    #[test]
+    fn test_refresh_coalesced() {
+        let connections = Arc::new(CountingConnections::default());
+        let schema = GraphSchema::new("graph_name", connections.clone());
+
+        // Another refresh completed since this caller looked, so no round trip is made
+        schema
+            .get_refresh_by_schema_type(SchemaType::Labels)
+            .completed
+            .store(1, Ordering::SeqCst);
+        assert!(schema.refresh_coalesced(SchemaType::Labels, 0).is_ok());
+        assert_eq!(connections.requested.load(Ordering::SeqCst), 0);
+
+        assert!(schema.refresh_coalesced(SchemaType::Labels, 1).is_err());
+        assert_eq!(connections.requested.load(Ordering::SeqCst), 1);
+
+        // Schema types are refreshed independently
+        assert!(schema.refresh_coalesced(SchemaType::Properties, 0).is_err());
+        assert_eq!(connections.requested.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_on_refresh() {
+        let schema = GraphSchema::new("graph_name", Arc::new(CountingConnections::default()));
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        schema.on_refresh({
+            let observed = observed.clone();
+            move |schema_type, added_ids| observed.lock().push((schema_type, added_ids.to_vec()))
+        });
+
+        schema.replace_ids(
+            SchemaType::Labels,
+            HashMap::from([(1, "actor".to_string()), (0, "movie".to_string())]),
+        );
+        // Nothing new, so the hooks are not invoked
+        schema.replace_ids(
+            SchemaType::Labels,
+            HashMap::from([(0, "movie".to_string()), (1, "actor".to_string())]),
+        );
+        schema.replace_ids(
+            SchemaType::Properties,
+            HashMap::from([(0, "name".to_string())]),
+        );
+        schema.replace_ids(
+            SchemaType::Labels,
+            HashMap::from([
+                (0, "movie".to_string()),
+                (1, "actor".to_string()),
+                (2, "director".to_string()),
+            ]),
+        );
+
+        assert_eq!(
+            *observed.lock(),
+            vec![
+                (SchemaType::Labels, vec![0, 1]),
+                (SchemaType::Properties, vec![0]),
+                (SchemaType::Labels, vec![2])
+            ]
+        );
+        assert_eq!(schema.labels().len(), 3);
+    }
+
+    #[test]
+    fn test_bounded_schema_evicts_least_recently_used() {
+        let schema = GraphSchema::new("graph_name", Arc::new(CountingConnections::default()))
+            .with_max_ids(NonZeroUsize::new(2));
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        schema.on_refresh({
+            let observed = observed.clone();
+            move |_, added_ids| observed.lock().push(added_ids.to_vec())
+        });
+        let properties = |count: i64| {
+            (0..count)
+                .map(|id| (id, format!("property_{id}")))
+                .collect::<IdMap>()
+        };
+
+        schema.replace_ids(SchemaType::Properties, properties(2));
+        assert_eq!(
+            schema.parse_single_id(0, SchemaType::Properties),
+            Ok("property_0".to_string())
+        );
+
+        // Id 1 was never used since it was fetched, so it is evicted first
+        schema.replace_ids(SchemaType::Properties, properties(3));
+        let mut cached: Vec<i64> = schema.properties().keys().copied().collect();
+        cached.sort_unstable();
+        assert_eq!(cached, vec![0, 2]);
+
+        // Refetching an evicted id doesn't report it as new
+        schema
+            .get_usage_by_schema_type(SchemaType::Properties)
+            .lock()
+            .touch(1);
+        schema.replace_ids(SchemaType::Properties, properties(3));
+        let mut cached: Vec<i64> = schema.properties().keys().copied().collect();
+        cached.sort_unstable();
+        assert_eq!(cached, vec![0, 1]);
+        assert_eq!(*observed.lock(), vec![vec![0, 1], vec![2]]);
+    }
+
+    /// Serves the schema procedures, counting full refreshes and fetches of single ids
+    fn schema_server(
+        full_refreshes: &Arc<AtomicUsize>,
+        single_fetches: &Arc<AtomicUsize>,
+    ) -> MockTransport {
+        let (full_refreshes, single_fetches) = (full_refreshes.clone(), single_fetches.clone());
+        MockTransport::new("mock://schema", move |args| {
+            let row = |id: usize| {
+                redis::Value::Array(vec![redis::Value::BulkString(
+                    format!("property_{id}").into_bytes(),
+                )])
+            };
+            let query = args.last().cloned().unwrap_or_default();
+            let rows = match query.split_once(" SKIP ") {
+                Some((_, skip)) => {
+                    single_fetches.fetch_add(1, Ordering::SeqCst);
+                    let id = skip
+                        .split_whitespace()
+                        .next()
+                        .and_then(|id| id.parse().ok())
+                        .expect("Expected the id to skip to");
+                    vec![row(id)]
+                }
+                None => {
+                    full_refreshes.fetch_add(1, Ordering::SeqCst);
+                    (0..5).map(row).collect()
+                }
+            };
+            Ok(redis::Value::Array(vec![
+                redis::Value::Array(vec![]),
+                redis::Value::Array(rows),
+                redis::Value::Array(vec![]),
+            ]))
+        })
+    }
+
+    #[test]
+    fn test_bounded_schema_fetches_evicted_ids_alone() {
+        let (full_refreshes, single_fetches) = (Arc::default(), Arc::default());
+        let schema = GraphSchema::new(
+            "graph_name",
+            Arc::new(schema_server(&full_refreshes, &single_fetches)),
+        )
+        .with_max_ids(NonZeroUsize::new(2));
+
+        // Cycling through more ids than the bound evicts each of them before it is used again
+        for _ in 0..3 {
+            for id in 0..5 {
+                assert_eq!(
+                    schema.parse_single_id(id, SchemaType::Properties),
+                    Ok(format!("property_{id}"))
+                );
+                assert!(schema.properties().len() <= 2);
+            }
+        }
+
+        // Only the first miss refreshes the whole schema type, every later miss fetches just the evicted id
+        assert_eq!(full_refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(single_fetches.load(Ordering::SeqCst), 14);
+
+        // Ids beyond those discovered still refresh the whole schema type
+        assert!(schema.parse_single_id(5, SchemaType::Properties).is_err());
+        assert_eq!(full_refreshes.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_populate() {
+        let schema = GraphSchema::new("graph_name", Arc::new(CountingConnections::default()));
+        schema.populate(
+            SchemaType::Relationships,
+            vec!["act".to_string(), "directed".to_string()],
+        );
+
+        assert_eq!(
+            schema.parse_single_id(1, SchemaType::Relationships),
+            Ok("directed".to_string())
+        );
+        assert_eq!(
+            schema
+                .get_refresh_by_schema_type(SchemaType::Relationships)
+                .completed
+                .load(Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let client = create_empty_inner_sync_client();
+        let schema = GraphSchema::new("graph_name", client.clone());
+        *schema.labels.write() = HashMap::from([(0, "actor".to_string())]);
+        *schema.properties.write() = HashMap::from([(0, "name".to_string())]);
+        *schema.relationships.write() = HashMap::from([(0, "act".to_string())]);
+
+        let snapshot = schema.snapshot();
+        let restored = GraphSchema::new("graph_name", client.clone());
+        restored
+            .restore(snapshot.clone())
+            .expect("Could not restore snapshot");
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(
+            restored.parse_single_id(0, SchemaType::Relationships),
+            Ok("act".to_string())
+        );
+
+        assert_eq!(
+            GraphSchema::new("other_graph", client).restore(snapshot),
+            Err(FalkorDBError::SchemaSnapshotMismatch(
+                "graph_name".to_string()
+            ))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_serde_round_trip() {
+        let snapshot = GraphSchemaSnapshot {
+            graph_name: "graph_name".to_string(),
+            version: 3,
+            labels: HashMap::from([(0, "actor".to_string()), (1, "movie".to_string())]),
+            properties: HashMap::from([(0, "name".to_string())]),
+            relationships: HashMap::new(),
+        };
+        let serialized = serde_json::to_string(&snapshot).expect("Could not serialize snapshot");
+        assert_eq!(
+            serde_json::from_str::<GraphSchemaSnapshot>(&serialized)
+                .expect("Could not deserialize snapshot"),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn test_schema_cache_is_shared() {
+        let client = create_empty_inner_sync_client();
+        let schema_cache = SchemaCache::default();
+
+        let schema = schema_cache.get_or_create("graph_name", client.clone());
+        schema.labels.write().insert(0, "shared_label".to_string());
+        assert!(Arc::ptr_eq(
+            &schema,
+            &schema_cache.get_or_create("graph_name", client.clone())
+        ));
+        assert!(!Arc::ptr_eq(
+            &schema,
+            &schema_cache.get_or_create("other_graph", client.clone())
+        ));
+
+        // Once the last handle is dropped, the cache starts over
+        drop(schema);
+        assert!(schema_cache
+            .get_or_create("graph_name", client)
+            .labels()
+            .is_empty());
+    }
+
     #[test]
     fn test_label_not_exists() {
-        let mut parser =
-            GraphSchema::new("graph_name".to_string(), create_empty_inner_sync_client());
+        let parser = GraphSchema::new("graph_name".to_string(), create_empty_inner_sync_client());
         let input_value = redis::Value::Array(vec![redis::Value::Array(vec![
             redis::Value::Int(1),
             redis::Value::Int(2),
@@ -318,9 +975,8 @@ pub(crate) mod tests {
 
     #[test]
     fn test_parse_properties_map() {
-        let mut parser =
-            GraphSchema::new("graph_name".to_string(), create_empty_inner_sync_client());
-        parser.properties = HashMap::from([
+        let parser = GraphSchema::new("graph_name".to_string(), create_empty_inner_sync_client());
+        *parser.properties.write() = HashMap::from([
             (1, "property1".to_string()),
             (2, "property2".to_string()),
             (3, "property3".to_string()),
@@ -347,7 +1003,7 @@ pub(crate) mod tests {
 
         let result = parser.parse_properties_map(input_value);
 
-        let expected_map = HashMap::from([
+        let expected_map = FalkorMap::from([
             (
                 "property1".to_string(),
                 FalkorValue::String("test".to_string()),
@@ -360,10 +1016,9 @@ pub(crate) mod tests {
 
     #[test]
     fn test_parse_id_vec() {
-        let mut parser =
-            GraphSchema::new("graph_name".to_string(), create_empty_inner_sync_client());
+        let parser = GraphSchema::new("graph_name".to_string(), create_empty_inner_sync_client());
 
-        parser.labels = HashMap::from([
+        *parser.labels.write() = HashMap::from([
             (1, "property1".to_string()),
             (2, "property2".to_string()),
             (3, "property3".to_string()),
@@ -396,7 +1051,7 @@ pub(crate) mod tests {
 
         parser.clear();
 
-        parser.relationships = HashMap::from([
+        *parser.relationships.write() = HashMap::from([
             (1, "property4".to_string()),
             (2, "property5".to_string()),
             (3, "property6".to_string()),
@@ -0,0 +1,728 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! An adapter applying a stream of entity events, such as those consumed from Kafka or NATS, to a graph.
+//!
+//! Events are applied in the order they are received. Consecutive events of the same kind, on the same labels,
+//! are batched into a single `UNWIND` query, and batches failing with a connection error are retried.
+//! Long-running ingestions can be stopped cleanly using a [`CancellationToken`], and resumed later from the last checkpoint.
+//!
+//! ```no_run
+//! use falkordb::{
+//!     ingest::{EntityEvent, StreamIngestor},
+//!     FalkorClientBuilder, FalkorValue,
+//! };
+//!
+//! # async fn run() -> falkordb::FalkorResult<()> {
+//! let client = FalkorClientBuilder::new_async().build().await?;
+//! let (tx, mut rx) = tokio::sync::mpsc::channel(1024);
+//!
+//! // Forward events from the consumer of your choice
+//! tokio::spawn(async move {
+//!     tx.send(EntityEvent::UpsertNode {
+//!         label: "Person".to_string(),
+//!         id: FalkorValue::I64(1),
+//!         properties: Default::default(),
+//!     })
+//!     .await
+//! });
+//!
+//! let report = StreamIngestor::new(client.select_graph("social"))
+//!     .with_batch_size(500)
+//!     .run(&mut rx)
+//!     .await?;
+//! println!("Applied {} events in {} batches", report.events, report.batches);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{graph::escape_name, runtime, AsyncGraph, FalkorMap, FalkorResult, FalkorValue};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::Notify;
+
+/// Identifies a node by its label and the value of its ID property
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeRef {
+    /// The label of the node
+    pub label: String,
+    /// The value of the node's ID property
+    pub id: FalkorValue,
+}
+
+/// A change to a single entity in the graph
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntityEvent {
+    /// Creates the node if it doesn't exist, and sets the provided properties on it
+    UpsertNode {
+        /// The label of the node
+        label: String,
+        /// The value of the node's ID property
+        id: FalkorValue,
+        /// The properties to set, other existing properties are kept
+        properties: FalkorMap,
+    },
+    /// Deletes the node, along with its relationships
+    DeleteNode {
+        /// The label of the node
+        label: String,
+        /// The value of the node's ID property
+        id: FalkorValue,
+    },
+    /// Creates the relationship between two existing nodes if it doesn't exist, and sets the provided properties on it
+    UpsertEdge {
+        /// The type of the relationship
+        relationship_type: String,
+        /// The node the relationship starts at
+        src: NodeRef,
+        /// The node the relationship ends at
+        dst: NodeRef,
+        /// The properties to set, other existing properties are kept
+        properties: FalkorMap,
+    },
+    /// Deletes the relationships of this type between two nodes
+    DeleteEdge {
+        /// The type of the relationship
+        relationship_type: String,
+        /// The node the relationship starts at
+        src: NodeRef,
+        /// The node the relationship ends at
+        dst: NodeRef,
+    },
+}
+
+/// Events which can share a single batch query
+#[derive(PartialEq)]
+enum BatchKey<'a> {
+    UpsertNode(&'a str),
+    DeleteNode(&'a str),
+    UpsertEdge(&'a str, &'a str, &'a str),
+    DeleteEdge(&'a str, &'a str, &'a str),
+}
+
+impl EntityEvent {
+    fn batch_key(&self) -> BatchKey<'_> {
+        match self {
+            EntityEvent::UpsertNode { label, .. } => BatchKey::UpsertNode(label),
+            EntityEvent::DeleteNode { label, .. } => BatchKey::DeleteNode(label),
+            EntityEvent::UpsertEdge {
+                relationship_type,
+                src,
+                dst,
+                ..
+            } => BatchKey::UpsertEdge(&src.label, relationship_type, &dst.label),
+            EntityEvent::DeleteEdge {
+                relationship_type,
+                src,
+                dst,
+            } => BatchKey::DeleteEdge(&src.label, relationship_type, &dst.label),
+        }
+    }
+
+    fn batch_row(&self) -> FalkorResult<String> {
+        let row = match self {
+            EntityEvent::UpsertNode { id, properties, .. } => {
+                vec![id.clone(), FalkorValue::Map(properties.clone())]
+            }
+            EntityEvent::DeleteNode { id, .. } => vec![id.clone()],
+            EntityEvent::UpsertEdge {
+                src,
+                dst,
+                properties,
+                ..
+            } => vec![
+                src.id.clone(),
+                dst.id.clone(),
+                FalkorValue::Map(properties.clone()),
+            ],
+            EntityEvent::DeleteEdge { src, dst, .. } => vec![src.id.clone(), dst.id.clone()],
+        };
+        FalkorValue::Array(row).to_cypher_literal()
+    }
+}
+
+/// Builds a single query applying a batch of events, all events must share the same [`BatchKey`]
+fn generate_batch_query(
+    events: &[EntityEvent],
+    id_property: &str,
+) -> FalkorResult<Option<String>> {
+    let Some(first) = events.first() else {
+        return Ok(None);
+    };
+    let rows = events
+        .iter()
+        .map(EntityEvent::batch_row)
+        .collect::<FalkorResult<Vec<_>>>()?
+        .join(", ");
+    let id = escape_name(id_property);
+
+    Ok(Some(match first.batch_key() {
+        BatchKey::UpsertNode(label) => format!(
+            "UNWIND [{rows}] AS e MERGE (n:{} {{{id}: e[0]}}) SET n += e[1]",
+            escape_name(label)
+        ),
+        BatchKey::DeleteNode(label) => format!(
+            "UNWIND [{rows}] AS e MATCH (n:{} {{{id}: e[0]}}) DETACH DELETE n",
+            escape_name(label)
+        ),
+        BatchKey::UpsertEdge(src_label, relationship_type, dst_label) => format!(
+            "UNWIND [{rows}] AS e MATCH (a:{} {{{id}: e[0]}}), (b:{} {{{id}: e[1]}}) MERGE (a)-[r:{}]->(b) SET r += e[2]",
+            escape_name(src_label),
+            escape_name(dst_label),
+            escape_name(relationship_type)
+        ),
+        BatchKey::DeleteEdge(src_label, relationship_type, dst_label) => format!(
+            "UNWIND [{rows}] AS e MATCH (a:{} {{{id}: e[0]}})-[r:{}]->(b:{} {{{id}: e[1]}}) DELETE r",
+            escape_name(src_label),
+            escape_name(relationship_type),
+            escape_name(dst_label)
+        ),
+    }))
+}
+
+/// The future returned by [`EventSource::next_event`]
+pub type EventFuture<'a> = Pin<Box<dyn Future<Output = Option<EntityEvent>> + Send + 'a>>;
+
+/// A source of [`EntityEvent`]s, such as a wrapper around a Kafka or NATS consumer
+/// The future returned by [`EventSource::next_event`] may be dropped before completion when a batch is flushed,
+/// so it must not lose events when cancelled.
+pub trait EventSource: Send {
+    /// Waits for the next event
+    ///
+    /// # Returns
+    /// The next event, or [`None`] once the source is exhausted
+    fn next_event(&mut self) -> EventFuture<'_>;
+}
+
+impl EventSource for tokio::sync::mpsc::Receiver<EntityEvent> {
+    fn next_event(&mut self) -> EventFuture<'_> {
+        Box::pin(self.recv())
+    }
+}
+
+impl EventSource for tokio::sync::mpsc::UnboundedReceiver<EntityEvent> {
+    fn next_event(&mut self) -> EventFuture<'_> {
+        Box::pin(self.recv())
+    }
+}
+
+/// The results of a [`StreamIngestor::run`], also reported at every checkpoint
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    /// The number of events applied
+    pub events: usize,
+    /// The number of batch queries sent
+    pub batches: usize,
+    /// The number of batch queries retried after a connection error
+    pub retries: usize,
+    /// Whether the ingestion was stopped by its [`CancellationToken`] before the source was exhausted
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Stops a running [`StreamIngestor::run`], E.g. from a signal handler or an operator command
+/// Clones share the same state, so cancelling any of them stops every ingestion using this token.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    state: Arc<CancellationState>,
+}
+
+impl CancellationToken {
+    /// Creates a new, not yet cancelled, token
+    ///
+    /// # Returns
+    /// The new [`CancellationToken`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation, ingestions using this token stop once their in-flight batch completes
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+        self.state.notify.notify_waiters();
+    }
+
+    /// Returns whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Waits until cancellation is requested
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking, so a cancellation in between is not missed
+            let notified = self.state.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Called with the progress so far after every applied batch, E.g. to commit the offsets of a Kafka consumer,
+/// so a stopped ingestion can be resumed from the source's next event
+pub type IngestCheckpoint = Box<dyn FnMut(&IngestReport) + Send>;
+
+/// Applies the events of an [`EventSource`] to a graph, in batches
+pub struct StreamIngestor {
+    graph: AsyncGraph,
+    id_property: String,
+    batch_size: usize,
+    flush_interval: Duration,
+    max_retries: usize,
+    retry_backoff: Duration,
+    cancellation: Option<CancellationToken>,
+    checkpoint: Option<IngestCheckpoint>,
+    progress: IngestReport,
+}
+
+impl StreamIngestor {
+    /// Creates a new ingestor for the provided graph, identifying nodes by their `id` property,
+    /// with batches of up to 1000 events, flushed after 100ms without new events, and up to 3 retries per batch
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to apply the events to
+    pub fn new(graph: AsyncGraph) -> Self {
+        Self {
+            graph,
+            id_property: "id".to_string(),
+            batch_size: 1000,
+            flush_interval: Duration::from_millis(100),
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(100),
+            cancellation: None,
+            checkpoint: None,
+            progress: IngestReport::default(),
+        }
+    }
+
+    /// Sets the property identifying nodes, this property should be indexed for every ingested label
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_id_property(
+        self,
+        id_property: &str,
+    ) -> Self {
+        Self {
+            id_property: id_property.to_string(),
+            ..self
+        }
+    }
+
+    /// Sets the maximal number of events sent in a single batch query
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_batch_size(
+        self,
+        batch_size: usize,
+    ) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Sets how long to wait for more events before sending an incomplete batch
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_flush_interval(
+        self,
+        flush_interval: Duration,
+    ) -> Self {
+        Self {
+            flush_interval,
+            ..self
+        }
+    }
+
+    /// Sets how many times a batch is retried after a connection error, and the delay before the first retry,
+    /// which doubles with every following retry
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_retries(
+        self,
+        max_retries: usize,
+        retry_backoff: Duration,
+    ) -> Self {
+        Self {
+            max_retries,
+            retry_backoff,
+            ..self
+        }
+    }
+
+    /// Stops the ingestion once the token is cancelled, events already received are applied before stopping,
+    /// so the last checkpoint reflects everything consumed from the source
+    ///
+    /// # Arguments
+    /// * `cancellation`: The [`CancellationToken`] to observe
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_cancellation(
+        self,
+        cancellation: CancellationToken,
+    ) -> Self {
+        Self {
+            cancellation: Some(cancellation),
+            ..self
+        }
+    }
+
+    /// Sets a callback invoked with the progress so far after every applied batch
+    ///
+    /// # Arguments
+    /// * `checkpoint`: The callback, see [`IngestCheckpoint`]
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_checkpoint<F: FnMut(&IngestReport) + Send + 'static>(
+        self,
+        checkpoint: F,
+    ) -> Self {
+        Self {
+            checkpoint: Some(Box::new(checkpoint)),
+            ..self
+        }
+    }
+
+    /// Continues counting from the progress of a previous, stopped, ingestion,
+    /// so the reports of a resumed ingestion cover the whole job
+    ///
+    /// # Arguments
+    /// * `progress`: The last [`IngestReport`] of the previous ingestion
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_progress(
+        self,
+        progress: IngestReport,
+    ) -> Self {
+        Self {
+            progress: IngestReport {
+                cancelled: false,
+                ..progress
+            },
+            ..self
+        }
+    }
+
+    async fn next_event<S: EventSource>(
+        &self,
+        source: &mut S,
+    ) -> Option<EntityEvent> {
+        match &self.cancellation {
+            Some(cancellation) => tokio::select! {
+                biased;
+                _ = cancellation.cancelled() => None,
+                event = source.next_event() => event,
+            },
+            None => source.next_event().await,
+        }
+    }
+
+    /// Applies events from the source until it is exhausted, or the ingestion is cancelled
+    /// Any batch failing with a non-connection error, or failing after all retries, stops the ingestion and returns its error,
+    /// events received after the failing batch are not applied.
+    ///
+    /// # Arguments
+    /// * `source`: The [`EventSource`] to consume
+    ///
+    /// # Returns
+    /// An [`IngestReport`] describing the applied events
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Stream Ingest", skip_all, level = "info")
+    )]
+    pub async fn run<S: EventSource>(
+        &mut self,
+        source: &mut S,
+    ) -> FalkorResult<IngestReport> {
+        let mut report = self.progress.clone();
+        let mut pending: Vec<EntityEvent> = vec![];
+
+        loop {
+            let event = match pending.is_empty() {
+                true => self.next_event(source).await,
+                false => {
+                    match runtime::timeout(self.flush_interval, self.next_event(source)).await {
+                        Some(event) => event,
+                        None => {
+                            self.flush(&mut pending, &mut report).await?;
+                            continue;
+                        }
+                    }
+                }
+            };
+            let Some(event) = event else {
+                break;
+            };
+
+            let starts_new_batch = pending.len() >= self.batch_size
+                || pending
+                    .last()
+                    .is_some_and(|last| last.batch_key() != event.batch_key());
+            if starts_new_batch {
+                self.flush(&mut pending, &mut report).await?;
+            }
+            pending.push(event);
+        }
+
+        self.flush(&mut pending, &mut report).await?;
+        report.cancelled = self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        self.progress = IngestReport {
+            cancelled: false,
+            ..report.clone()
+        };
+        Ok(report)
+    }
+
+    async fn flush(
+        &mut self,
+        pending: &mut Vec<EntityEvent>,
+        report: &mut IngestReport,
+    ) -> FalkorResult<()> {
+        let Some(query) = generate_batch_query(pending, &self.id_property)? else {
+            return Ok(());
+        };
+
+        let mut backoff = self.retry_backoff;
+        for attempt in 0..=self.max_retries {
+            match self.graph.query(query.as_str()).execute().await {
+                Ok(_) => break,
+                Err(err) if attempt < self.max_retries && err.is_retryable() => {
+                    report.retries += 1;
+                    runtime::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        report.events += pending.len();
+        report.batches += 1;
+        pending.clear();
+        if let Some(checkpoint) = self.checkpoint.as_mut() {
+            checkpoint(report);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_async_test_client;
+
+    fn node(
+        label: &str,
+        id: i64,
+    ) -> NodeRef {
+        NodeRef {
+            label: label.to_string(),
+            id: FalkorValue::I64(id),
+        }
+    }
+
+    fn properties(
+        key: &str,
+        value: FalkorValue,
+    ) -> FalkorMap {
+        FalkorMap::from_iter([(key.to_string(), value)])
+    }
+
+    #[test]
+    fn test_generate_batch_query() {
+        assert_eq!(generate_batch_query(&[], "id").unwrap(), None);
+
+        let upserts = [
+            EntityEvent::UpsertNode {
+                label: "Person".to_string(),
+                id: FalkorValue::I64(1),
+                properties: properties("name", FalkorValue::String("A".to_string())),
+            },
+            EntityEvent::UpsertNode {
+                label: "Person".to_string(),
+                id: FalkorValue::I64(2),
+                properties: FalkorMap::default(),
+            },
+        ];
+        assert_eq!(
+            generate_batch_query(&upserts, "id").unwrap().unwrap(),
+            "UNWIND [[1, {`name`: \"A\"}], [2, {}]] AS e MERGE (n:`Person` {`id`: e[0]}) SET n += e[1]"
+        );
+
+        let delete_edge = [EntityEvent::DeleteEdge {
+            relationship_type: "KNOWS".to_string(),
+            src: node("Person", 1),
+            dst: node("Person", 2),
+        }];
+        assert_eq!(
+            generate_batch_query(&delete_edge, "uid").unwrap().unwrap(),
+            "UNWIND [[1, 2]] AS e MATCH (a:`Person` {`uid`: e[0]})-[r:`KNOWS`]->(b:`Person` {`uid`: e[1]}) DELETE r"
+        );
+    }
+
+    #[test]
+    fn test_batch_key() {
+        let upsert = |label: &str| EntityEvent::UpsertNode {
+            label: label.to_string(),
+            id: FalkorValue::I64(1),
+            properties: FalkorMap::default(),
+        };
+        let delete = EntityEvent::DeleteNode {
+            label: "Person".to_string(),
+            id: FalkorValue::I64(1),
+        };
+
+        assert!(upsert("Person").batch_key() == upsert("Person").batch_key());
+        assert!(upsert("Person").batch_key() != upsert("Company").batch_key());
+        assert!(upsert("Person").batch_key() != delete.batch_key());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("Waiter was not notified")
+            .expect("Waiter panicked");
+        assert!(token.is_cancelled());
+
+        // Already cancelled tokens complete immediately
+        token.cancelled().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stream_ingestor_cancellation() {
+        let client = create_async_test_client().await;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for id in 0..3 {
+            tx.send(EntityEvent::UpsertNode {
+                label: "Person".to_string(),
+                id: FalkorValue::I64(id),
+                properties: FalkorMap::default(),
+            })
+            .unwrap();
+        }
+
+        let token = CancellationToken::new();
+        let checkpoints = Arc::new(parking_lot::Mutex::new(vec![]));
+        let mut ingestor =
+            StreamIngestor::new(client.select_graph("test_stream_ingestor_cancellation"))
+                .with_batch_size(2)
+                .with_cancellation(token.clone())
+                .with_checkpoint({
+                    let checkpoints = checkpoints.clone();
+                    move |report| checkpoints.lock().push(report.events)
+                });
+
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            token.cancel();
+        });
+        let report = ingestor
+            .run(&mut rx)
+            .await
+            .expect("Could not ingest events");
+        canceller.await.ok();
+        assert!(report.cancelled);
+        assert_eq!(report.events, 3);
+        assert_eq!(*checkpoints.lock(), vec![2, 3]);
+
+        // The channel is still open, so the ingestion can be resumed
+        tx.send(EntityEvent::DeleteNode {
+            label: "Person".to_string(),
+            id: FalkorValue::I64(0),
+        })
+        .unwrap();
+        drop(tx);
+        let report = ingestor.run(&mut rx).await.expect("Could not resume");
+        assert!(!report.cancelled);
+        assert_eq!(report.events, 4);
+
+        client
+            .select_graph("test_stream_ingestor_cancellation")
+            .delete()
+            .await
+            .ok();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stream_ingestor() {
+        let client = create_async_test_client().await;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for id in 0..10 {
+            tx.send(EntityEvent::UpsertNode {
+                label: "Person".to_string(),
+                id: FalkorValue::I64(id),
+                properties: properties("age", FalkorValue::I64(id * 10)),
+            })
+            .unwrap();
+        }
+        for id in 1..10 {
+            tx.send(EntityEvent::UpsertEdge {
+                relationship_type: "KNOWS".to_string(),
+                src: node("Person", id - 1),
+                dst: node("Person", id),
+                properties: FalkorMap::default(),
+            })
+            .unwrap();
+        }
+        tx.send(EntityEvent::DeleteNode {
+            label: "Person".to_string(),
+            id: FalkorValue::I64(0),
+        })
+        .unwrap();
+        drop(tx);
+
+        let report = StreamIngestor::new(client.select_graph("test_stream_ingestor"))
+            .with_batch_size(4)
+            .run(&mut rx)
+            .await
+            .expect("Could not ingest events");
+        assert_eq!(report.events, 20);
+        assert_eq!(report.batches, 7);
+
+        let graph = client.select_graph("test_stream_ingestor");
+        let count = graph
+            .query("MATCH (:Person)-[r:KNOWS]->(:Person) RETURN count(r)")
+            .execute()
+            .await
+            .expect("Could not count relationships")
+            .data
+            .next()
+            .and_then(|row| row[0].to_i64());
+        graph.delete().await.ok();
+        assert_eq!(count, Some(8));
+    }
+}
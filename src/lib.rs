@@ -3,6 +3,7 @@
  * Licensed under the MIT License.
  */
 
+#![recursion_limit = "256"]
 #![allow(private_interfaces)]
 #![allow(private_bounds)]
 #![deny(missing_docs)]
@@ -15,45 +16,242 @@ mod connection_info;
 mod error;
 mod graph;
 mod graph_schema;
+#[cfg(feature = "otel")]
+mod otel;
 mod parser;
+mod redaction;
 mod response;
+#[cfg(feature = "tokio")]
+mod runtime;
+mod transport;
 mod value;
 
+#[cfg(feature = "bench")]
+pub mod bench;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+#[cfg(feature = "datagen")]
+pub mod datagen;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "stream-ingest")]
+pub mod ingest;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "neo4rs-compat")]
+pub mod neo4rs_compat;
+
 /// A [`Result`] which only returns [`FalkorDBError`] as its E type
 pub type FalkorResult<T> = Result<T, FalkorDBError>;
 
-pub use client::{blocking::FalkorSyncClient, builder::FalkorClientBuilder};
-pub use connection_info::FalkorConnectionInfo;
+#[cfg(feature = "prometheus")]
+pub use client::metrics::prometheus::PrometheusRecorder;
+pub use client::{
+    blocking::FalkorSyncClient,
+    builder::FalkorClientBuilder,
+    capabilities::{Capabilities, RespProtocol},
+    circuit::{CircuitState, CircuitStats},
+    config::FalkorClientConfig,
+    events::{ConnectionEvent, ConnectionEventListener},
+    interceptor::{CommandInterceptor, InterceptedCommand},
+    metrics::MetricsRecorder,
+    ops::FalkorClientTrait,
+    pool::PoolStats,
+    rate_limit::RateLimit,
+    retry::{RetryClassifier, RetryPolicy},
+    self_test::{SelfTestCheck, SelfTestCheckResult, SelfTestReport},
+    slow_query::{SlowQuery, SlowQueryCallback, SlowQueryLog},
+    warm_up::{ConnectionWarmUp, WarmUpReport},
+};
+pub use connection_info::{
+    builder::FalkorConnectionInfoBuilder,
+    credentials::{Credentials, CredentialsProvider},
+    tls::{FalkorTlsConfig, TlsBackend},
+    FalkorConnectionInfo,
+};
 pub use error::FalkorDBError;
 pub use graph::{
+    algorithms::{ShortestPathOptions, TraversalDirection, TraversalLevel},
     blocking::SyncGraph,
+    constraint_builder::ConstraintBuilder,
+    graph_ref::GraphRef,
+    idempotency::IDEMPOTENCY_KEY_PREFIX,
+    index_builder::IndexBuilder,
+    index_options::{SimilarityFunction, VectorIndexOptions},
+    lock::{GraphLock, GRAPH_LOCK_KEY_PREFIX},
+    migration::{Migration, MigrationReport, SCHEMA_VERSION_KEY_PREFIX},
+    ops::{GraphOps, QueryRows},
     query_builder::{ProcedureQueryBuilder, QueryBuilder},
+    query_registry::{QueryRegistry, QueryTemplate},
+    seed::{EdgeFixture, GraphFixtures, NodeFixture},
+    soft_delete::{not_soft_deleted, SOFT_DELETE_PROPERTY},
+    versioning::{PropertyVersion, PROPERTY_VERSION_LABEL, PROPERTY_VERSION_RELATIONSHIP},
 };
-pub use graph_schema::{GraphSchema, SchemaType};
+pub use graph_schema::{GraphSchema, GraphSchemaSnapshot, SchemaRefreshHook, SchemaType};
+pub use redaction::{redact_query_literals, REDACTED_LITERAL};
 pub use response::{
     constraint::{Constraint, ConstraintStatus, ConstraintType},
-    execution_plan::ExecutionPlan,
+    execution_plan::{ExecutionPlan, Operation, OperationProfile, PlanChange},
     index::{FalkorIndex, IndexStatus, IndexType},
     lazy_result_set::LazyResultSet,
     slowlog_entry::SlowlogEntry,
+    statistics::{QueryStatistics, StatsAccumulator, StatsSnapshot},
     QueryResult,
 };
+pub use transport::{
+    decode_resp_reply, encode_resp_command,
+    failover::{FailoverEvent, FailoverHook, FailoverProvider},
+    routing::{EndpointHealth, HealthAwareProvider, HedgingPolicy},
+    FalkorTransport, FalkorTransportProvider,
+};
 pub use value::{
+    coercion::{find_type_coercions, TypeCoercion},
     config::ConfigValue,
+    conversion::{FromFalkorValue, FromYieldRow},
     graph_entities::{Edge, EntityType, Node},
     path::Path,
     point::Point,
-    FalkorValue,
+    FalkorMap, FalkorValue,
 };
 
+#[cfg(feature = "http-sidecar")]
+pub use transport::http::HttpSidecarProvider;
+#[cfg(feature = "socks5")]
+pub use transport::socks5::Socks5Proxy;
+
 #[cfg(feature = "tokio")]
 pub use client::asynchronous::FalkorAsyncClient;
+#[cfg(any(feature = "bb8", feature = "deadpool"))]
+pub use client::managers::{FalkorConnectionManager, FalkorManagedConnection};
+#[cfg(feature = "tokio")]
+pub use client::ops::AsyncFalkorClientTrait;
 #[cfg(feature = "tokio")]
 pub use graph::asynchronous::AsyncGraph;
+#[cfg(feature = "tokio")]
+pub use graph::ops::AsyncGraphOps;
+#[cfg(feature = "tokio")]
+pub use transport::{FalkorAsyncTransport, TransportFuture};
 
 #[cfg(test)]
 pub(crate) mod test_utils {
     use super::*;
+    use crate::{client::ProvidesSyncConnections, connection::blocking::FalkorSyncConnection};
+    use std::{
+        fmt,
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    type Script = Arc<dyn Fn(&[String]) -> FalkorResult<redis::Value> + Send + Sync>;
+
+    /// A scripted transport, replying to every command with whatever its script returns for the command's arguments.
+    ///
+    /// It is its own provider, and every connection shares its state,
+    /// so taking it down refuses new connections and breaks the open ones.
+    #[derive(Clone)]
+    pub(crate) struct MockTransport {
+        address: String,
+        script: Script,
+        down: Arc<AtomicBool>,
+        /// The number of connections opened so far
+        pub(crate) connections: Arc<AtomicUsize>,
+    }
+
+    impl MockTransport {
+        pub(crate) fn new(
+            address: &str,
+            script: impl Fn(&[String]) -> FalkorResult<redis::Value> + Send + Sync + 'static,
+        ) -> Self {
+            Self {
+                address: address.to_string(),
+                script: Arc::new(script),
+                down: Arc::default(),
+                connections: Arc::default(),
+            }
+        }
+
+        pub(crate) fn set_down(
+            &self,
+            down: bool,
+        ) {
+            self.down.store(down, Ordering::Relaxed);
+        }
+
+        fn reply(
+            &self,
+            args: &[String],
+        ) -> FalkorResult<redis::Value> {
+            match self.down.load(Ordering::Relaxed) {
+                true => Err(FalkorDBError::ConnectionDown),
+                false => (self.script)(args),
+            }
+        }
+
+        fn open(&self) -> FalkorResult<Self> {
+            if self.down.load(Ordering::Relaxed) {
+                return Err(FalkorDBError::EndpointUnavailable {
+                    endpoint: self.address.clone(),
+                    reason: "Connection refused".to_string(),
+                });
+            }
+            self.connections.fetch_add(1, Ordering::Relaxed);
+            Ok(self.clone())
+        }
+    }
+
+    impl fmt::Debug for MockTransport {
+        fn fmt(
+            &self,
+            f: &mut fmt::Formatter<'_>,
+        ) -> fmt::Result {
+            f.debug_struct("MockTransport")
+                .field("address", &self.address)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl FalkorTransport for MockTransport {
+        fn execute(
+            &mut self,
+            args: Vec<String>,
+        ) -> FalkorResult<redis::Value> {
+            self.reply(&args)
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl FalkorAsyncTransport for MockTransport {
+        fn execute(
+            &mut self,
+            args: Vec<String>,
+        ) -> TransportFuture<'_, redis::Value> {
+            Box::pin(async move { self.reply(&args) })
+        }
+    }
+
+    impl FalkorTransportProvider for MockTransport {
+        fn address(&self) -> String {
+            self.address.clone()
+        }
+
+        fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+            Ok(Box::new(self.open()?))
+        }
+
+        #[cfg(feature = "tokio")]
+        fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+            Box::pin(async move { Ok(Box::new(self.open()?) as Box<dyn FalkorAsyncTransport>) })
+        }
+    }
+
+    impl ProvidesSyncConnections for MockTransport {
+        fn get_connection(&self) -> FalkorResult<FalkorSyncConnection> {
+            Ok(FalkorSyncConnection::Transport(Box::new(self.open()?)))
+        }
+    }
 
     pub(crate) struct TestSyncGraphHandle {
         pub(crate) inner: SyncGraph,
@@ -75,7 +273,7 @@ pub(crate) mod test_utils {
         fn drop(&mut self) {
             tokio::task::block_in_place(|| {
                 // Avoid copying the schema each time
-                let mut graph_handle =
+                let graph_handle =
                     AsyncGraph::new(self.inner.get_client().clone(), self.inner.graph_name());
                 tokio::runtime::Handle::current().block_on(async move {
                     graph_handle.delete().await.ok();
@@ -0,0 +1,95 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Spans following the OpenTelemetry semantic conventions for database clients,
+//! so exporters such as `tracing-opentelemetry` report queries as client calls to a FalkorDB server,
+//! the same way SQL calls are reported, E.g. in Jaeger or Tempo.
+
+use crate::{redaction::redact_query_literals, FalkorResult};
+use tracing::field::Empty;
+
+/// The `db.system` of FalkorDB, which has no well-known value of its own
+const DB_SYSTEM: &str = "falkordb";
+
+/// Splits an address into its host and port, E.g. `[::1]:6379` into `::1` and 6379,
+/// addresses without a port, such as those of custom transports, are reported as the host
+fn split_address(address: &str) -> (&str, Option<u16>) {
+    match address
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse().ok()?)))
+    {
+        Some((host, port)) => (
+            host.trim_start_matches('[').trim_end_matches(']'),
+            Some(port),
+        ),
+        None => (address, None),
+    }
+}
+
+/// Creates the span of a query sent to a graph, to be entered while it is executed, including its retries
+///
+/// # Arguments
+/// * `address`: The address of the server
+/// * `graph_name`: The graph the query is sent to, reported as `db.name`
+/// * `command`: The command the query is sent with, E.g. `GRAPH.QUERY`, reported as `db.operation`
+/// * `query`: The query, reported as `db.statement`
+/// * `redact`: Whether to mask the literals of the query, see [`FalkorClientBuilder::with_log_redaction`](crate::FalkorClientBuilder::with_log_redaction)
+pub(crate) fn query_span(
+    address: &str,
+    graph_name: &str,
+    command: &str,
+    query: &str,
+    redact: bool,
+) -> tracing::Span {
+    let (host, port) = split_address(address);
+    let statement = match redact {
+        true => redact_query_literals(query),
+        false => query.to_string(),
+    };
+    tracing::info_span!(
+        "FalkorDB Query",
+        otel.name = format!("{command} {graph_name}"),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        otel.status_description = Empty,
+        db.system = DB_SYSTEM,
+        db.name = graph_name,
+        db.operation = command,
+        db.statement = statement,
+        net.peer.name = host,
+        net.peer.port = port,
+    )
+}
+
+/// Marks the span of a failed query as an error, with the error as its description
+pub(crate) fn record_outcome<T>(
+    span: &tracing::Span,
+    res: &FalkorResult<T>,
+) {
+    if let Err(err) = res {
+        span.record("otel.status_code", "ERROR");
+        span.record("otel.status_description", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_address() {
+        assert_eq!(split_address("127.0.0.1:6379"), ("127.0.0.1", Some(6379)));
+        assert_eq!(split_address("[::1]:6380"), ("::1", Some(6380)));
+        assert_eq!(
+            split_address("db.internal:6379"),
+            ("db.internal", Some(6379))
+        );
+        assert_eq!(
+            split_address("http://sidecar:8080/graph"),
+            ("http://sidecar:8080/graph", None)
+        );
+        assert_eq!(split_address("mock://gateway"), ("mock://gateway", None));
+    }
+}
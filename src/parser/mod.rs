@@ -4,8 +4,8 @@
  */
 
 use crate::{
-    value::vec32::Vec32, ConfigValue, Edge, FalkorDBError, FalkorResult, FalkorValue, GraphSchema,
-    Node, Path, Point,
+    value::vec32::Vec32, ConfigValue, Edge, FalkorDBError, FalkorMap, FalkorResult, FalkorValue,
+    GraphSchema, Node, Path, Point,
 };
 use std::collections::HashMap;
 
@@ -260,7 +260,7 @@ pub(crate) fn parse_header(header: redis::Value) -> FalkorResult<Vec<String>> {
 )]
 pub(crate) fn parse_raw_redis_value(
     value: redis::Value,
-    graph_schema: &mut GraphSchema,
+    graph_schema: &GraphSchema,
 ) -> FalkorResult<FalkorValue> {
     type_val_from_value(value)
         .and_then(|(type_marker, val)| parse_type(type_marker, val, graph_schema))
@@ -295,12 +295,12 @@ pub(crate) fn type_val_from_value(
 )]
 fn parse_regular_falkor_map(
     value: redis::Value,
-    graph_schema: &mut GraphSchema,
-) -> FalkorResult<HashMap<String, FalkorValue>> {
+    graph_schema: &GraphSchema,
+) -> FalkorResult<FalkorMap> {
     value
         .into_map_iter()
         .map_err(|_| FalkorDBError::ParsingMap)?
-        .try_fold(HashMap::new(), |mut out_map, (key, val)| {
+        .try_fold(FalkorMap::new(), |mut out_map, (key, val)| {
             out_map.insert(
                 redis_value_as_string(key)?,
                 parse_raw_redis_value(val, graph_schema)?,
@@ -316,7 +316,7 @@ fn parse_regular_falkor_map(
 pub(crate) fn parse_type(
     type_marker: ParserTypeMarker,
     val: redis::Value,
-    graph_schema: &mut GraphSchema,
+    graph_schema: &GraphSchema,
 ) -> Result<FalkorValue, FalkorDBError> {
     let res = match type_marker {
         ParserTypeMarker::None => FalkorValue::None,
@@ -349,7 +349,7 @@ pub(crate) fn parse_type(
 pub(crate) trait SchemaParsable: Sized {
     fn parse(
         value: redis::Value,
-        graph_schema: &mut GraphSchema,
+        graph_schema: &GraphSchema,
     ) -> FalkorResult<Self>;
 }
 
@@ -422,7 +422,7 @@ mod tests {
 
     #[test]
     fn test_parse_edge() {
-        let mut graph = open_readonly_graph_with_modified_schema();
+        let graph = open_readonly_graph_with_modified_schema();
 
         let res = parse_type(
             ParserTypeMarker::Edge,
@@ -444,7 +444,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-            graph.get_graph_schema_mut(),
+            graph.get_graph_schema(),
         );
         assert!(res.is_ok());
 
@@ -468,7 +468,7 @@ mod tests {
 
     #[test]
     fn test_parse_node() {
-        let mut graph = open_readonly_graph_with_modified_schema();
+        let graph = open_readonly_graph_with_modified_schema();
 
         let res = parse_type(
             ParserTypeMarker::Node,
@@ -493,7 +493,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-            graph.get_graph_schema_mut(),
+            graph.get_graph_schema(),
         );
         assert!(res.is_ok());
 
@@ -518,7 +518,7 @@ mod tests {
 
     #[test]
     fn test_parse_path() {
-        let mut graph = open_readonly_graph_with_modified_schema();
+        let graph = open_readonly_graph_with_modified_schema();
 
         let res = parse_type(
             ParserTypeMarker::Path,
@@ -557,7 +557,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-            graph.get_graph_schema_mut(),
+            graph.get_graph_schema(),
         );
         assert!(res.is_ok());
 
@@ -584,7 +584,7 @@ mod tests {
 
     #[test]
     fn test_parse_map() {
-        let mut graph = open_readonly_graph_with_modified_schema();
+        let graph = open_readonly_graph_with_modified_schema();
 
         let res = parse_type(
             ParserTypeMarker::Map,
@@ -602,7 +602,7 @@ mod tests {
                     redis::Value::SimpleString("true".to_string()),
                 ]),
             ]),
-            graph.get_graph_schema_mut(),
+            graph.get_graph_schema(),
         );
         assert!(res.is_ok());
 
@@ -622,7 +622,7 @@ mod tests {
 
     #[test]
     fn test_parse_point() {
-        let mut graph = open_readonly_graph_with_modified_schema();
+        let graph = open_readonly_graph_with_modified_schema();
 
         let res = parse_type(
             ParserTypeMarker::Point,
@@ -630,7 +630,7 @@ mod tests {
                 redis::Value::SimpleString("102.0".to_string()),
                 redis::Value::SimpleString("15.2".to_string()),
             ]),
-            graph.get_graph_schema_mut(),
+            graph.get_graph_schema(),
         );
         assert!(res.is_ok());
 
@@ -644,11 +644,11 @@ mod tests {
 
     #[test]
     fn test_map_not_a_vec() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::SimpleString("Hello".to_string()),
-            &mut graph_schema,
+            &graph_schema,
         );
 
         assert!(res.is_err())
@@ -656,11 +656,11 @@ mod tests {
 
     #[test]
     fn test_map_vec_odd_element_count() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::Array(vec![redis::Value::Nil; 7]),
-            &mut graph_schema,
+            &graph_schema,
         );
 
         assert!(res.is_err())
@@ -668,14 +668,14 @@ mod tests {
 
     #[test]
     fn test_map_val_element_is_not_array() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::Array(vec![
                 redis::Value::SimpleString("Key".to_string()),
                 redis::Value::SimpleString("false".to_string()),
             ]),
-            &mut graph_schema,
+            &graph_schema,
         );
 
         assert!(res.is_err())
@@ -683,14 +683,14 @@ mod tests {
 
     #[test]
     fn test_map_val_element_has_only_1_element() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::Array(vec![
                 redis::Value::SimpleString("Key".to_string()),
                 redis::Value::Array(vec![redis::Value::Int(7)]),
             ]),
-            &mut graph_schema,
+            &graph_schema,
         );
 
         assert!(res.is_err())
@@ -698,14 +698,14 @@ mod tests {
 
     #[test]
     fn test_map_val_element_has_ge_2_elements() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::Array(vec![
                 redis::Value::SimpleString("Key".to_string()),
                 redis::Value::Array(vec![redis::Value::Int(3); 3]),
             ]),
-            &mut graph_schema,
+            &graph_schema,
         );
 
         assert!(res.is_err())
@@ -713,7 +713,7 @@ mod tests {
 
     #[test]
     fn test_map_val_element_mismatch_type_marker() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::Array(vec![
@@ -723,7 +723,7 @@ mod tests {
                     redis::Value::SimpleString("true".to_string()),
                 ]),
             ]),
-            &mut graph_schema,
+            &graph_schema,
         );
 
         assert!(res.is_err())
@@ -731,7 +731,7 @@ mod tests {
 
     #[test]
     fn test_map_ok_values() {
-        let mut graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
+        let graph_schema = GraphSchema::new("test_graph", create_empty_inner_sync_client());
 
         let res = parse_regular_falkor_map(
             redis::Value::Array(vec![
@@ -743,7 +743,7 @@ mod tests {
                     redis::Value::SimpleString("true".to_string()),
                 ]),
             ]),
-            &mut graph_schema,
+            &graph_schema,
         )
         .expect("Could not parse map");
 
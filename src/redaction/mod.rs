@@ -0,0 +1,145 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+/// The placeholder every redacted literal is replaced with
+pub const REDACTED_LITERAL: &str = "?";
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Masks all string and number literals in a query, keeping its shape intact
+/// Identifiers, including backtick-quoted ones, parameter names, and comments are kept as-is,
+/// so `MATCH (n {name: 'Alice', age: 30}) RETURN n` becomes `MATCH (n {name: ?, age: ?}) RETURN n`.
+///
+/// This is what the client uses when log redaction is enabled, see [`FalkorClientBuilder::with_log_redaction`](crate::FalkorClientBuilder::with_log_redaction),
+/// but it is exposed to allow applying the same masking in custom logging.
+///
+/// # Arguments
+/// * `query`: The query string to redact
+///
+/// # Returns
+/// The redacted query string
+pub fn redact_query_literals(query: &str) -> String {
+    let mut redacted = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    let mut prev: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                let mut escaped = false;
+                for inner in chars.by_ref() {
+                    match inner {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if inner == c => break,
+                        _ => {}
+                    }
+                }
+                redacted.push_str(REDACTED_LITERAL);
+            }
+            '`' => {
+                redacted.push(c);
+                for inner in chars.by_ref() {
+                    redacted.push(inner);
+                    if inner == '`' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                redacted.push(c);
+                for inner in chars.by_ref() {
+                    redacted.push(inner);
+                    if inner == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                redacted.push(c);
+                let mut last = None;
+                for inner in chars.by_ref() {
+                    redacted.push(inner);
+                    if last == Some('*') && inner == '/' {
+                        break;
+                    }
+                    last = Some(inner);
+                }
+            }
+            '0'..='9' if !prev.is_some_and(is_identifier_char) => {
+                let mut last = c;
+                while let Some(&next) = chars.peek() {
+                    let part_of_number = next.is_alphanumeric()
+                        || next == '_'
+                        || (next == '.' && !matches!(last, '.'))
+                        || (matches!(next, '+' | '-') && matches!(last, 'e' | 'E'));
+                    if !part_of_number {
+                        break;
+                    }
+
+                    // A range such as `[1..10]`, the dots are not a decimal point
+                    if next == '.' {
+                        let mut lookahead = chars.clone();
+                        lookahead.next();
+                        if !lookahead.peek().is_some_and(char::is_ascii_digit) {
+                            break;
+                        }
+                    }
+                    last = next;
+                    chars.next();
+                }
+                redacted.push_str(REDACTED_LITERAL);
+            }
+            _ => redacted.push(c),
+        }
+        prev = Some(c);
+    }
+
+    redacted
+}
+
+/// Records the query being executed in the current span, masking its literals if requested
+#[cfg(feature = "tracing")]
+pub(crate) fn record_query(
+    query: &str,
+    redact: bool,
+) {
+    let span = tracing::Span::current();
+    match redact {
+        true => span.record("query", redact_query_literals(query)),
+        false => span.record("query", query),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_query_literals() {
+        assert_eq!(
+            redact_query_literals("MATCH (n {name: 'Alice', age: 30}) RETURN n"),
+            "MATCH (n {name: ?, age: ?}) RETURN n"
+        );
+        assert_eq!(
+            redact_query_literals(
+                r#"CYPHER email="a\"b@c.com" MATCH (n:User2 {email: $email}) RETURN n.score * 1.5e-3"#
+            ),
+            "CYPHER email=? MATCH (n:User2 {email: $email}) RETURN n.score * ?"
+        );
+        assert_eq!(
+            redact_query_literals(
+                "UNWIND range(0, 100) AS i RETURN [1..10], `weird 'name`, 0x1F // 'kept'"
+            ),
+            "UNWIND range(?, ?) AS i RETURN [?..?], `weird 'name`, ? // 'kept'"
+        );
+        assert_eq!(
+            redact_query_literals("RETURN 'it\\'s', -2.5, n.x1 /* 42 */"),
+            "RETURN ?, -?, n.x1 /* 42 */"
+        );
+    }
+}
@@ -57,7 +57,7 @@ impl SchemaParsable for Constraint {
     )]
     fn parse(
         value: redis::Value,
-        _: &mut GraphSchema,
+        _: &GraphSchema,
     ) -> FalkorResult<Self> {
         let [constraint_type_raw, label_raw, properties_raw, entity_type_raw, status_raw]: [redis::Value; 5] = redis_value_as_vec(value)
             .and_then(|res| res.try_into()
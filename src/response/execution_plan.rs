@@ -14,6 +14,7 @@ use std::{
     collections::{HashMap, VecDeque},
     ops::Not,
     rc::Rc,
+    time::Duration,
 };
 
 #[derive(Debug)]
@@ -50,7 +51,7 @@ impl IntermediateOperation {
                     .captures(last_arg.trim())
                     .and_then(|cap| cap.get(1))
                     .and_then(|m| m.as_str().parse().ok()),
-                Regex::new(r"Execution time: (\d+\.\d+) ms")
+                Regex::new(r"Execution time: (\d+(?:\.\d+)?) ms")
                     .map_err(|err| {
                         FalkorDBError::ParsingError(format!("Error constructing regex: {err}"))
                     })?
@@ -95,6 +96,113 @@ pub struct Operation {
     depth: usize,
 }
 
+impl Operation {
+    /// Returns the time it took to execute this operation as a [`Duration`], if available
+    pub fn execution_duration(&self) -> Option<Duration> {
+        self.execution_time
+            .map(|execution_time| Duration::from_secs_f64(execution_time / 1000.0))
+    }
+
+    /// Returns the typed profiling metrics of this operation, if this plan was created using `GRAPH.PROFILE`
+    pub fn profile(&self) -> Option<OperationProfile> {
+        Some(OperationProfile {
+            name: self.name.clone(),
+            depth: self.depth,
+            records_produced: self.records_produced?,
+            execution_time: self.execution_duration()?,
+        })
+    }
+}
+
+/// The profiling metrics of a single operation, as reported by `GRAPH.PROFILE`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationProfile {
+    /// The operation name
+    pub name: String,
+    /// How deep in the operation tree this operation is, the root operation is at depth 0
+    pub depth: usize,
+    /// The amount of records produced by this operation
+    pub records_produced: i64,
+    /// The time it took to execute this operation
+    pub execution_time: Duration,
+}
+
+/// A structural difference between two execution plans, see [`ExecutionPlan::diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanChange {
+    /// An operation which only exists in the other plan
+    Added {
+        /// How deep in the operation tree this operation is
+        depth: usize,
+        /// The operation and its arguments, E.g. `Node By Label Scan | (a:actor)`
+        operation: String,
+    },
+    /// An operation which only exists in this plan
+    Removed {
+        /// How deep in the operation tree this operation is
+        depth: usize,
+        /// The operation and its arguments, E.g. `Node By Label Scan | (a:actor)`
+        operation: String,
+    },
+    /// An operation which was replaced, or whose arguments changed, in the same position in the other plan
+    Changed {
+        /// How deep in the operation tree this operation is
+        depth: usize,
+        /// The operation and its arguments in this plan
+        from: String,
+        /// The operation and its arguments in the other plan
+        to: String,
+    },
+}
+
+impl Operation {
+    fn description(&self) -> String {
+        std::iter::once(self.name.as_str())
+            .chain(self.args.iter().flatten().map(|arg| arg.trim()))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    fn subtree_changes(
+        &self,
+        changes: &mut Vec<PlanChange>,
+        added: bool,
+    ) {
+        let (depth, operation) = (self.depth, self.description());
+        changes.push(match added {
+            true => PlanChange::Added { depth, operation },
+            false => PlanChange::Removed { depth, operation },
+        });
+        for child in &self.children {
+            child.subtree_changes(changes, added);
+        }
+    }
+
+    fn diff_into(
+        &self,
+        other: &Operation,
+        changes: &mut Vec<PlanChange>,
+    ) {
+        let (from, to) = (self.description(), other.description());
+        if from != to {
+            changes.push(PlanChange::Changed {
+                depth: self.depth,
+                from,
+                to,
+            });
+        }
+
+        for idx in 0..self.children.len().max(other.children.len()) {
+            match (self.children.get(idx), other.children.get(idx)) {
+                (Some(ours), Some(theirs)) => ours.diff_into(theirs, changes),
+                (Some(ours), None) => ours.subtree_changes(changes, false),
+                (None, Some(theirs)) => theirs.subtree_changes(changes, true),
+                (None, None) => {}
+            }
+        }
+    }
+}
+
 /// An execution plan, allowing access both to the human-readable text representation, access to a per-operation map, or traversable operation tree
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExecutionPlan {
@@ -125,6 +233,72 @@ impl ExecutionPlan {
         self.string_representation.as_str()
     }
 
+    /// Compares the structure of this plan to another plan, such as the plan of the same query before an index was added
+    /// Operations are matched by their position in the operation tree, profiling metrics are not compared.
+    ///
+    /// # Arguments
+    /// * `other`: The plan to compare against
+    ///
+    /// # Returns
+    /// The operations added, removed or changed in the other plan, in the order they appear in the plans, empty if the plans are structurally identical
+    pub fn diff(
+        &self,
+        other: &ExecutionPlan,
+    ) -> Vec<PlanChange> {
+        let mut changes = vec![];
+        self.operation_tree
+            .diff_into(&other.operation_tree, &mut changes);
+        changes
+    }
+
+    /// Returns the profiling metrics of every operation, in the order they appear in the plan
+    /// This is empty unless the plan was created using `GRAPH.PROFILE`.
+    pub fn profile(&self) -> Vec<OperationProfile> {
+        let mut profiles = vec![];
+        let mut stack = vec![&self.operation_tree];
+        while let Some(operation) = stack.pop() {
+            profiles.extend(operation.profile());
+            stack.extend(operation.children.iter().rev());
+        }
+        profiles
+    }
+
+    /// Returns the total amount of records produced by all operations with this name, useful for asserting on plan performance
+    ///
+    /// # Arguments
+    /// * `operation_name`: The name of the operation, E.g. `Node By Label Scan`
+    ///
+    /// # Returns
+    /// The sum of records produced, or [`None`] if no such operation was profiled
+    pub fn records_produced(
+        &self,
+        operation_name: &str,
+    ) -> Option<i64> {
+        self.operations
+            .get(operation_name)?
+            .iter()
+            .map(|operation| operation.records_produced)
+            .sum()
+    }
+
+    /// Returns the total execution time of all operations with this name, useful for asserting on plan performance
+    ///
+    /// # Arguments
+    /// * `operation_name`: The name of the operation, E.g. `Node By Label Scan`
+    ///
+    /// # Returns
+    /// The sum of execution times, or [`None`] if no such operation was profiled
+    pub fn execution_time(
+        &self,
+        operation_name: &str,
+    ) -> Option<Duration> {
+        self.operations
+            .get(operation_name)?
+            .iter()
+            .map(|operation| operation.execution_duration())
+            .sum()
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Create Node", skip_all, level = "debug")
@@ -268,3 +442,104 @@ impl ExecutionPlan {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan_value(lines: &[&str]) -> redis::Value {
+        redis::Value::Array(
+            lines
+                .iter()
+                .map(|line| redis::Value::BulkString(line.as_bytes().to_vec()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_parse_profile_metrics() {
+        let plan = ExecutionPlan::parse(plan_value(&[
+            "Results | Records produced: 1001, Execution time: 0.153 ms",
+            "    Project | Records produced: 1001, Execution time: 0.2 ms",
+            "        Unwind | Records produced: 1001, Execution time: 1 ms",
+        ]))
+        .expect("Could not parse profile");
+
+        let profile = plan.profile();
+        assert_eq!(
+            profile
+                .iter()
+                .map(|operation| (operation.name.as_str(), operation.depth))
+                .collect::<Vec<_>>(),
+            vec![("Results", 0), ("Project", 1), ("Unwind", 2)]
+        );
+        assert!(profile
+            .iter()
+            .all(|operation| operation.records_produced == 1001));
+        assert_eq!(profile[2].execution_time, Duration::from_millis(1));
+
+        assert_eq!(plan.records_produced("Unwind"), Some(1001));
+        assert_eq!(
+            plan.execution_time("Project"),
+            Some(Duration::from_secs_f64(0.0002))
+        );
+        assert_eq!(plan.records_produced("Filter"), None);
+    }
+
+    #[test]
+    fn test_plan_diff() {
+        let before = ExecutionPlan::parse(plan_value(&[
+            "Results",
+            "    Project",
+            "        Filter",
+            "            Node By Label Scan | (a:actor)",
+        ]))
+        .expect("Could not parse plan");
+        let after = ExecutionPlan::parse(plan_value(&[
+            "Results | Records produced: 1, Execution time: 0.1 ms",
+            "    Project | Records produced: 1, Execution time: 0.1 ms",
+            "        Node By Index Scan | (a:actor) | Records produced: 1, Execution time: 0.1 ms",
+        ]))
+        .expect("Could not parse plan");
+
+        assert!(before.diff(&before).is_empty());
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                PlanChange::Changed {
+                    depth: 2,
+                    from: "Filter".to_string(),
+                    to: "Node By Index Scan | (a:actor)".to_string(),
+                },
+                PlanChange::Removed {
+                    depth: 3,
+                    operation: "Node By Label Scan | (a:actor)".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            after.diff(&before)[1],
+            PlanChange::Added {
+                depth: 3,
+                operation: "Node By Label Scan | (a:actor)".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_explain_has_no_profile() {
+        let plan = ExecutionPlan::parse(plan_value(&[
+            "Results",
+            "    Project",
+            "        Node By Label Scan | (a:actor)",
+        ]))
+        .expect("Could not parse plan");
+
+        assert!(plan.profile().is_empty());
+        assert_eq!(plan.records_produced("Project"), None);
+        assert_eq!(
+            plan.operations()["Node By Label Scan"][0].args,
+            Some(vec![" (a:actor)".to_string()])
+        );
+    }
+}
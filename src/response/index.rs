@@ -9,7 +9,7 @@ use crate::{
         parse_falkor_enum, parse_raw_redis_value, redis_value_as_string,
         redis_value_as_typed_string, redis_value_as_vec, type_val_from_value, SchemaParsable,
     },
-    EntityType, FalkorDBError, FalkorValue, GraphSchema,
+    EntityType, FalkorDBError, FalkorMap, FalkorValue, GraphSchema,
 };
 use std::collections::HashMap;
 
@@ -40,7 +40,7 @@ pub enum IndexType {
 // parse array of strings, both array and strings represent as redis values
 fn parse_string_array(
     value: redis::Value,
-    graph_schema: &mut GraphSchema,
+    graph_schema: &GraphSchema,
 ) -> Result<Vec<String>, FalkorDBError> {
     type_val_from_value(value).and_then(|(type_marker, val)| {
         if type_marker != ParserTypeMarker::Array {
@@ -104,9 +104,9 @@ pub struct FalkorIndex {
     /// Words to avoid indexing as they are very common and will just be a waste of resources
     pub stopwords: Vec<String>,
     /// Various other information for querying by the user
-    pub info: HashMap<String, FalkorValue>,
+    pub info: FalkorMap,
     /// Various other options relevant for this index
-    pub options: HashMap<String, FalkorValue>,
+    pub options: FalkorMap,
 }
 
 impl SchemaParsable for FalkorIndex {
@@ -116,7 +116,7 @@ impl SchemaParsable for FalkorIndex {
     )]
     fn parse(
         value: redis::Value,
-        graph_schema: &mut GraphSchema,
+        graph_schema: &GraphSchema,
     ) -> Result<Self, FalkorDBError> {
         let [label, fields, field_types, options, language, stopwords, entity_type, status, info] =
             redis_value_as_vec(value).and_then(|as_vec| {
@@ -11,13 +11,13 @@ use std::collections::VecDeque;
 /// This implements Iterator, so can simply be collect()'ed into any desired container
 pub struct LazyResultSet<'a> {
     data: VecDeque<redis::Value>,
-    graph_schema: &'a mut GraphSchema,
+    graph_schema: &'a GraphSchema,
 }
 
 impl<'a> LazyResultSet<'a> {
     pub(crate) fn new(
         data: Vec<redis::Value>,
-        graph_schema: &'a mut GraphSchema,
+        graph_schema: &'a GraphSchema,
     ) -> Self {
         Self {
             data: data.into(),
@@ -56,13 +56,12 @@ impl Iterator for LazyResultSet<'_> {
 mod tests {
     use crate::graph::HasGraphSchema;
     use crate::test_utils::create_test_client;
-    use crate::{Edge, FalkorValue, LazyResultSet, Node};
-    use std::collections::HashMap;
+    use crate::{Edge, FalkorMap, FalkorValue, LazyResultSet, Node};
 
     #[test]
     fn test_lazy_result_set() {
         let client = create_test_client();
-        let mut graph = client.select_graph("imdb");
+        let graph = client.select_graph("imdb");
 
         let mut result_set = LazyResultSet::new(
             vec![
@@ -105,7 +104,7 @@ mod tests {
                     ]),
                 ])]),
             ],
-            graph.get_graph_schema_mut(),
+            graph.get_graph_schema(),
         );
 
         assert_eq!(
@@ -113,7 +112,7 @@ mod tests {
             Some(vec![FalkorValue::Node(Node {
                 entity_id: 203,
                 labels: vec!["actor".to_string()],
-                properties: HashMap::from([(
+                properties: FalkorMap::from([(
                     "name".to_string(),
                     FalkorValue::String("FirstNode".to_string())
                 )]),
@@ -126,7 +125,7 @@ mod tests {
                 vec![FalkorValue::Node(Node {
                     entity_id: 203,
                     labels: vec!["actor".to_string()],
-                    properties: HashMap::from([(
+                    properties: FalkorMap::from([(
                         "name".to_string(),
                         FalkorValue::String("FirstNode".to_string())
                     )]),
@@ -136,7 +135,7 @@ mod tests {
                     relationship_type: "act".to_string(),
                     src_node_id: 203,
                     dst_node_id: 204,
-                    properties: HashMap::from([(
+                    properties: FalkorMap::from([(
                         "name".to_string(),
                         FalkorValue::String("Edge".to_string())
                     )]),
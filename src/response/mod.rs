@@ -5,15 +5,16 @@
 
 use crate::{
     parser::{parse_header, redis_value_as_untyped_string_vec},
-    FalkorResult,
+    FalkorResult, FalkorValue,
 };
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 pub(crate) mod constraint;
 pub(crate) mod execution_plan;
 pub(crate) mod index;
 pub(crate) mod lazy_result_set;
 pub(crate) mod slowlog_entry;
+pub(crate) mod statistics;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, strum::IntoStaticStr)]
 enum StatisticType {
@@ -161,15 +162,68 @@ impl<T> QueryResult<T> {
     pub fn get_internal_execution_time(&self) -> Option<f64> {
         self.get_statistics(StatisticType::InternalExecutionTime)
     }
+
+    /// Returns all statistics of this query as typed values, where statistics the server did not report are zero
+    ///
+    /// # Returns
+    /// The [`QueryStatistics`](statistics::QueryStatistics) of this query
+    pub fn statistics(&self) -> statistics::QueryStatistics {
+        statistics::QueryStatistics::from(self)
+    }
+}
+
+impl<T: IntoIterator<Item = Vec<FalkorValue>>> QueryResult<T> {
+    /// Consumes the result set, pivoting it from rows into columns, which is convenient for plotting or statistics code
+    /// If several columns share the same name, only the last of them is kept
+    ///
+    /// # Returns
+    /// A [`HashMap`] from each column name in the header, to the values of that column in row order
+    pub fn by_column(self) -> HashMap<String, Vec<FalkorValue>> {
+        let mut columns: Vec<Vec<FalkorValue>> = vec![Vec::new(); self.header.len()];
+        for row in self.data {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        self.header.into_iter().zip(columns).collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::test_utils::open_empty_test_graph;
 
+    #[test]
+    fn test_by_column() {
+        let result = QueryResult {
+            header: vec!["name".to_string(), "age".to_string()],
+            data: vec![
+                vec![FalkorValue::from("Alice"), FalkorValue::I64(30)],
+                vec![FalkorValue::from("Bob"), FalkorValue::I64(25)],
+            ],
+            stats: vec![],
+        };
+
+        assert_eq!(
+            result.by_column(),
+            HashMap::from([
+                (
+                    "name".to_string(),
+                    vec![FalkorValue::from("Alice"), FalkorValue::from("Bob")]
+                ),
+                (
+                    "age".to_string(),
+                    vec![FalkorValue::I64(30), FalkorValue::I64(25)]
+                ),
+            ])
+        );
+    }
+
     #[test]
     fn test_get_statistics() {
-        let mut graph = open_empty_test_graph("imdb_stats_test");
+        let graph = open_empty_test_graph("imdb_stats_test");
         {
             let query_result = graph
                 .inner
@@ -0,0 +1,373 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::QueryResult;
+use parking_lot::Mutex;
+use std::{
+    ops::AddAssign,
+    time::{Duration, Instant},
+};
+
+/// The statistics of a query, as typed values, where statistics the server did not report are zero
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct QueryStatistics {
+    /// The number of labels added
+    pub labels_added: i64,
+    /// The number of labels removed
+    pub labels_removed: i64,
+    /// The number of nodes created
+    pub nodes_created: i64,
+    /// The number of nodes deleted
+    pub nodes_deleted: i64,
+    /// The number of properties set
+    pub properties_set: i64,
+    /// The number of properties removed
+    pub properties_removed: i64,
+    /// The number of indices created
+    pub indices_created: i64,
+    /// The number of indices deleted
+    pub indices_deleted: i64,
+    /// The number of relationships created
+    pub relationships_created: i64,
+    /// The number of relationships deleted
+    pub relationships_deleted: i64,
+    /// Whether the query used a cached execution plan
+    pub cached_execution: bool,
+    /// The time the server spent executing the query
+    pub internal_execution_time: Duration,
+}
+
+impl<T> From<&QueryResult<T>> for QueryStatistics {
+    fn from(result: &QueryResult<T>) -> Self {
+        Self {
+            labels_added: result.get_labels_added().unwrap_or_default(),
+            labels_removed: result.get_labels_removed().unwrap_or_default(),
+            nodes_created: result.get_nodes_created().unwrap_or_default(),
+            nodes_deleted: result.get_nodes_deleted().unwrap_or_default(),
+            properties_set: result.get_properties_set().unwrap_or_default(),
+            properties_removed: result.get_properties_removed().unwrap_or_default(),
+            indices_created: result.get_indices_created().unwrap_or_default(),
+            indices_deleted: result.get_indices_deleted().unwrap_or_default(),
+            relationships_created: result.get_relationship_created().unwrap_or_default(),
+            relationships_deleted: result.get_relationship_deleted().unwrap_or_default(),
+            cached_execution: result.get_cached_execution().unwrap_or_default(),
+            internal_execution_time: result
+                .get_internal_execution_time()
+                .filter(|millis| millis.is_finite() && *millis >= 0.0)
+                .map(|millis| Duration::from_secs_f64(millis / 1000.0))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn format_count(count: i64) -> String {
+    let digits = count.unsigned_abs().to_string();
+    let groups: Vec<&str> = digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .filter_map(|group| std::str::from_utf8(group).ok())
+        .collect();
+    match count < 0 {
+        true => format!("-{}", groups.join(",")),
+        false => groups.join(","),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    match secs {
+        _ if secs >= 60.0 => format!("{}m{:.0}s", duration.as_secs() / 60, secs % 60.0),
+        _ if secs >= 1.0 => format!("{secs:.1}s"),
+        _ => format!("{:.1}ms", secs * 1000.0),
+    }
+}
+
+impl QueryStatistics {
+    /// Returns a one-line human-readable summary of these statistics, for CLIs and job logs,
+    /// such as "12,430 nodes created, 3.2s, cached"
+    /// Only changes that actually happened are listed, followed by the execution time.
+    ///
+    /// # Returns
+    /// The summary of these statistics
+    pub fn summary(&self) -> String {
+        let changes = [
+            (self.nodes_created, "nodes created"),
+            (self.nodes_deleted, "nodes deleted"),
+            (self.relationships_created, "relationships created"),
+            (self.relationships_deleted, "relationships deleted"),
+            (self.properties_set, "properties set"),
+            (self.properties_removed, "properties removed"),
+            (self.labels_added, "labels added"),
+            (self.labels_removed, "labels removed"),
+            (self.indices_created, "indices created"),
+            (self.indices_deleted, "indices deleted"),
+        ];
+
+        let mut parts: Vec<String> = changes
+            .into_iter()
+            .filter(|(count, _)| *count != 0)
+            .map(|(count, name)| format!("{} {name}", format_count(count)))
+            .collect();
+        if parts.is_empty() {
+            parts.push("no changes".to_string());
+        }
+        parts.push(format_duration(self.internal_execution_time));
+        if self.cached_execution {
+            parts.push("cached".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+impl AddAssign for QueryStatistics {
+    /// Sums the counts and execution times, the result is only considered cached if both sides are
+    fn add_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        self.labels_added += rhs.labels_added;
+        self.labels_removed += rhs.labels_removed;
+        self.nodes_created += rhs.nodes_created;
+        self.nodes_deleted += rhs.nodes_deleted;
+        self.properties_set += rhs.properties_set;
+        self.properties_removed += rhs.properties_removed;
+        self.indices_created += rhs.indices_created;
+        self.indices_deleted += rhs.indices_deleted;
+        self.relationships_created += rhs.relationships_created;
+        self.relationships_deleted += rhs.relationships_deleted;
+        self.cached_execution &= rhs.cached_execution;
+        self.internal_execution_time += rhs.internal_execution_time;
+    }
+}
+
+/// The statistics summed by a [`StatsAccumulator`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StatsSnapshot {
+    /// The number of queries recorded
+    pub queries: u64,
+    /// The number of recorded queries which used a cached execution plan
+    pub cached_queries: u64,
+    /// The sums of the recorded statistics, these are only considered cached if all queries were
+    pub totals: QueryStatistics,
+    /// The wall clock time since the accumulator was created or last reset
+    pub elapsed: Duration,
+}
+
+struct Accumulated {
+    queries: u64,
+    cached_queries: u64,
+    totals: QueryStatistics,
+    started: Instant,
+}
+
+impl Accumulated {
+    fn new() -> Self {
+        Self {
+            queries: 0,
+            cached_queries: 0,
+            totals: QueryStatistics {
+                cached_execution: true,
+                ..Default::default()
+            },
+            started: Instant::now(),
+        }
+    }
+
+    fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            queries: self.queries,
+            cached_queries: self.cached_queries,
+            totals: QueryStatistics {
+                cached_execution: self.queries > 0 && self.totals.cached_execution,
+                ..self.totals
+            },
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
+
+/// Sums the statistics of many queries, such as the nodes created by a batch job, and the time it took
+/// This is internally synchronized, so a single accumulator can be shared between threads or tasks.
+///
+/// ```no_run
+/// # use falkordb::{FalkorClientBuilder, StatsAccumulator};
+/// # let client = FalkorClientBuilder::new().build().unwrap();
+/// # let graph = client.select_graph("import");
+/// let stats = StatsAccumulator::new();
+/// for batch in ["CREATE (:Person)", "CREATE (:Person)-[:KNOWS]->(:Person)"] {
+///     stats.record(&graph.query(batch).execute().expect("Could not import batch"));
+/// }
+///
+/// let snapshot = stats.snapshot();
+/// println!(
+///     "imported {} nodes, {} edges in {:?}",
+///     snapshot.totals.nodes_created, snapshot.totals.relationships_created, snapshot.elapsed
+/// );
+/// ```
+pub struct StatsAccumulator {
+    accumulated: Mutex<Accumulated>,
+}
+
+impl Default for StatsAccumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatsAccumulator {
+    /// Creates a new, empty, [`StatsAccumulator`], measuring elapsed time from now
+    ///
+    /// # Returns
+    /// The new [`StatsAccumulator`]
+    pub fn new() -> Self {
+        Self {
+            accumulated: Mutex::new(Accumulated::new()),
+        }
+    }
+
+    /// Adds the statistics of a query result
+    ///
+    /// # Arguments
+    /// * `result`: The [`QueryResult`] whose statistics to add
+    pub fn record<T>(
+        &self,
+        result: &QueryResult<T>,
+    ) {
+        self.record_statistics(QueryStatistics::from(result));
+    }
+
+    /// Adds already parsed statistics
+    ///
+    /// # Arguments
+    /// * `statistics`: The [`QueryStatistics`] to add
+    pub fn record_statistics(
+        &self,
+        statistics: QueryStatistics,
+    ) {
+        let mut accumulated = self.accumulated.lock();
+        accumulated.queries += 1;
+        if statistics.cached_execution {
+            accumulated.cached_queries += 1;
+        }
+        accumulated.totals += statistics;
+    }
+
+    /// Returns the statistics summed so far
+    ///
+    /// # Returns
+    /// A [`StatsSnapshot`] of the sums
+    pub fn snapshot(&self) -> StatsSnapshot {
+        self.accumulated.lock().snapshot()
+    }
+
+    /// Clears the sums, and restarts measuring elapsed time
+    ///
+    /// # Returns
+    /// A [`StatsSnapshot`] of the sums before they were cleared
+    pub fn reset(&self) -> StatsSnapshot {
+        std::mem::replace(&mut *self.accumulated.lock(), Accumulated::new()).snapshot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result_with_stats(stats: &[&str]) -> QueryResult<()> {
+        QueryResult {
+            header: vec![],
+            data: (),
+            stats: stats.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_query_statistics() {
+        let statistics = QueryStatistics::from(&result_with_stats(&[
+            "Nodes created: 2",
+            "Relationships created: 1",
+            "Cached execution: 1",
+            "Query internal execution time: 1.500000 milliseconds",
+        ]));
+
+        assert_eq!(
+            statistics,
+            QueryStatistics {
+                nodes_created: 2,
+                relationships_created: 1,
+                cached_execution: true,
+                internal_execution_time: Duration::from_micros(1500),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(12430), "12,430");
+        assert_eq!(format_count(-1234567), "-1,234,567");
+
+        let statistics = QueryStatistics {
+            nodes_created: 12430,
+            relationships_created: 7,
+            cached_execution: true,
+            internal_execution_time: Duration::from_millis(3200),
+            ..Default::default()
+        };
+        assert_eq!(
+            statistics.summary(),
+            "12,430 nodes created, 7 relationships created, 3.2s, cached"
+        );
+
+        let statistics = QueryStatistics {
+            internal_execution_time: Duration::from_micros(450),
+            ..Default::default()
+        };
+        assert_eq!(statistics.summary(), "no changes, 0.5ms");
+
+        let statistics = QueryStatistics {
+            properties_set: 1,
+            internal_execution_time: Duration::from_secs(125),
+            ..Default::default()
+        };
+        assert_eq!(statistics.summary(), "1 properties set, 2m5s");
+    }
+
+    #[test]
+    fn test_stats_accumulator() {
+        let accumulator = StatsAccumulator::new();
+        assert_eq!(accumulator.snapshot().queries, 0);
+        assert!(!accumulator.snapshot().totals.cached_execution);
+
+        accumulator.record(&result_with_stats(&[
+            "Nodes created: 2",
+            "Cached execution: 1",
+            "Query internal execution time: 1.000000 milliseconds",
+        ]));
+        accumulator.record(&result_with_stats(&[
+            "Nodes created: 3",
+            "Relationships created: 4",
+            "Cached execution: 0",
+            "Query internal execution time: 2.000000 milliseconds",
+        ]));
+
+        let snapshot = accumulator.reset();
+        assert_eq!(snapshot.queries, 2);
+        assert_eq!(snapshot.cached_queries, 1);
+        assert_eq!(snapshot.totals.nodes_created, 5);
+        assert_eq!(snapshot.totals.relationships_created, 4);
+        assert!(!snapshot.totals.cached_execution);
+        assert_eq!(
+            snapshot.totals.internal_execution_time,
+            Duration::from_millis(3)
+        );
+
+        assert_eq!(accumulator.snapshot().queries, 0);
+        assert_eq!(accumulator.snapshot().totals.nodes_created, 0);
+    }
+}
@@ -0,0 +1,259 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! The timers, sockets and background tasks of the asynchronous client, dispatched to the runtime it is used from.
+//! The client itself only relies on runtime-agnostic primitives, such as the locks and channels of `tokio::sync`,
+//! so with the `async-std` feature enabled, it can be used from `async-std` or `smol` applications too.
+
+use crate::{FalkorDBError, FalkorResult};
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// Whether the current thread runs within a tokio runtime, otherwise the `async-std` runtime is used
+#[cfg(feature = "async-std")]
+fn on_tokio() -> bool {
+    tokio::runtime::Handle::try_current().is_ok()
+}
+
+/// Awaits a future, returning [`None`] if it does not complete within the duration
+pub(crate) async fn timeout<F: Future>(
+    duration: Duration,
+    fut: F,
+) -> Option<F::Output> {
+    #[cfg(feature = "async-std")]
+    if !on_tokio() {
+        return async_std::future::timeout(duration, fut).await.ok();
+    }
+    tokio::time::timeout(duration, fut).await.ok()
+}
+
+/// Awaits a future, returning [`None`] if it does not complete before the deadline
+pub(crate) async fn timeout_at<F: Future>(
+    deadline: Instant,
+    fut: F,
+) -> Option<F::Output> {
+    timeout(deadline.saturating_duration_since(Instant::now()), fut).await
+}
+
+/// Waits until the duration has elapsed
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(feature = "async-std")]
+    if !on_tokio() {
+        return async_std::task::sleep(duration).await;
+    }
+    tokio::time::sleep(duration).await
+}
+
+/// Runs a future in the background, without waiting for it to complete
+pub(crate) fn spawn<F: Future<Output = ()> + Send + 'static>(fut: F) {
+    #[cfg(feature = "async-std")]
+    if !on_tokio() {
+        async_std::task::spawn(fut);
+        return;
+    }
+    tokio::spawn(fut);
+}
+
+/// A TCP connection opened by [`connect_tcp`], on the runtime it was opened from,
+/// exposed through the `tokio::io` traits, so it can be handed to the redis crate either way
+#[cfg(any(feature = "http-sidecar", feature = "socks5"))]
+pub(crate) enum TcpStream {
+    Tokio(tokio::net::TcpStream),
+    #[cfg(feature = "async-std")]
+    AsyncStd(async_std::net::TcpStream),
+}
+
+/// Opens a TCP connection
+#[cfg(any(feature = "http-sidecar", feature = "socks5"))]
+pub(crate) async fn connect_tcp(
+    host: &str,
+    port: u16,
+) -> std::io::Result<TcpStream> {
+    #[cfg(feature = "async-std")]
+    if !on_tokio() {
+        return async_std::net::TcpStream::connect((host, port))
+            .await
+            .map(TcpStream::AsyncStd);
+    }
+    tokio::net::TcpStream::connect((host, port))
+        .await
+        .map(TcpStream::Tokio)
+}
+
+#[cfg(feature = "socks5")]
+impl TcpStream {
+    /// Borrows the underlying socket, to set its options
+    pub(crate) fn socket(&self) -> redis::io::tcp::socket2::SockRef<'_> {
+        match self {
+            TcpStream::Tokio(stream) => stream.into(),
+            #[cfg(feature = "async-std")]
+            TcpStream::AsyncStd(stream) => stream.into(),
+        }
+    }
+}
+
+#[cfg(any(feature = "http-sidecar", feature = "socks5"))]
+impl tokio::io::AsyncRead for TcpStream {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            TcpStream::Tokio(stream) => std::pin::Pin::new(stream).poll_read(cx, buf),
+            #[cfg(feature = "async-std")]
+            TcpStream::AsyncStd(stream) => {
+                let read = std::task::ready!(async_std::io::Read::poll_read(
+                    std::pin::Pin::new(stream),
+                    cx,
+                    buf.initialize_unfilled()
+                ))?;
+                buf.advance(read);
+                std::task::Poll::Ready(Ok(()))
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "http-sidecar", feature = "socks5"))]
+impl tokio::io::AsyncWrite for TcpStream {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            TcpStream::Tokio(stream) => std::pin::Pin::new(stream).poll_write(cx, buf),
+            #[cfg(feature = "async-std")]
+            TcpStream::AsyncStd(stream) => {
+                async_std::io::Write::poll_write(std::pin::Pin::new(stream), cx, buf)
+            }
+        }
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            TcpStream::Tokio(stream) => std::pin::Pin::new(stream).poll_flush(cx),
+            #[cfg(feature = "async-std")]
+            TcpStream::AsyncStd(stream) => {
+                async_std::io::Write::poll_flush(std::pin::Pin::new(stream), cx)
+            }
+        }
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            TcpStream::Tokio(stream) => std::pin::Pin::new(stream).poll_shutdown(cx),
+            #[cfg(feature = "async-std")]
+            TcpStream::AsyncStd(stream) => {
+                async_std::io::Write::poll_close(std::pin::Pin::new(stream), cx)
+            }
+        }
+    }
+}
+
+/// Blocks the current thread on a future, from within an asynchronous context,
+/// used where a synchronous API has to wait on the asynchronous client, such as refreshing a graph schema
+pub(crate) fn block_on<F: Future>(fut: F) -> FalkorResult<F::Output> {
+    #[cfg(feature = "async-std")]
+    if !on_tokio() {
+        return Ok(async_std::task::block_on(fut));
+    }
+
+    use tokio::runtime::{Handle, RuntimeFlavor};
+    let handle = Handle::try_current().map_err(|_| FalkorDBError::NoRuntime)?;
+    match handle.runtime_flavor() {
+        RuntimeFlavor::CurrentThread => Err(FalkorDBError::SingleThreadedRuntime),
+        _ => Ok(tokio::task::block_in_place(|| handle.block_on(fut))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tokio_runtime() {
+        #[cfg(feature = "async-std")]
+        assert!(on_tokio());
+        assert_eq!(timeout(Duration::from_secs(1), async { 1 }).await, Some(1));
+        assert_eq!(
+            timeout(Duration::from_millis(1), sleep(Duration::from_secs(1))).await,
+            None
+        );
+        assert_eq!(block_on(async { 2 }), Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_single_threaded_tokio_runtime() {
+        assert_eq!(
+            block_on(async { 2 }),
+            Err(FalkorDBError::SingleThreadedRuntime)
+        );
+    }
+
+    #[cfg(feature = "async-std")]
+    #[test]
+    fn test_async_std_runtime() {
+        async_std::task::block_on(async {
+            assert!(!on_tokio());
+            assert_eq!(timeout(Duration::from_secs(1), async { 1 }).await, Some(1));
+            assert_eq!(
+                timeout_at(Instant::now(), sleep(Duration::from_secs(1))).await,
+                None
+            );
+
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            spawn(async move {
+                tx.send(3).ok();
+            });
+            assert_eq!(rx.await, Ok(3));
+            assert_eq!(block_on(async { 4 }), Ok(4));
+        });
+    }
+
+    #[cfg(all(
+        feature = "async-std",
+        any(feature = "http-sidecar", feature = "socks5")
+    ))]
+    #[test]
+    fn test_async_std_tcp_stream() {
+        use std::io::{Read, Write};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("Could not bind a listener");
+        let port = listener
+            .local_addr()
+            .expect("Could not get the listener's address")
+            .port();
+        let echo = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().expect("Could not accept a connection");
+            let mut buf = [0; 4];
+            conn.read_exact(&mut buf).expect("Could not read");
+            conn.write_all(&buf).expect("Could not write");
+        });
+
+        async_std::task::block_on(async {
+            let mut stream = connect_tcp("127.0.0.1", port)
+                .await
+                .expect("Could not connect");
+            assert!(matches!(stream, TcpStream::AsyncStd(_)));
+            stream.write_all(b"PING").await.expect("Could not write");
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).await.expect("Could not read");
+            assert_eq!(&buf, b"PING");
+        });
+        echo.join().expect("The echo server panicked");
+    }
+}
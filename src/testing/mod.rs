@@ -0,0 +1,680 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Assertions for integration tests of applications built on top of FalkorDB.
+//!
+//! Each assertion panics when it fails, explaining what the graph contains instead,
+//! such as a property diff against the closest matching node.
+//!
+//! ```no_run
+//! use falkordb::{testing::*, ConstraintType, EntityType, FalkorClientBuilder};
+//!
+//! let client = FalkorClientBuilder::new().build().expect("Could not create client");
+//! let graph = client.select_graph("social");
+//!
+//! assert_node_exists(&graph, "Person", [("name", "Alice")]);
+//! assert_edge_count(&graph, "KNOWS", 1);
+//! assert_constraint_operational(&graph, ConstraintType::Unique, EntityType::Node, "Person", &["name"]);
+//! ```
+//!
+//! Suites running in parallel against a shared server can isolate each test in its own graph, using [`TestGraph`],
+//! which is deleted once dropped, or [`unique_graph_name`] along with [`delete_registered_graphs`].
+
+use crate::{
+    graph::escape_name, Constraint, ConstraintStatus, ConstraintType, EntityType, FalkorMap,
+    FalkorSyncClient, FalkorValue, SyncGraph,
+};
+use parking_lot::{const_mutex, Mutex};
+use std::{
+    collections::{hash_map::RandomState, BTreeMap},
+    fmt::Write as _,
+    hash::{BuildHasher, Hasher},
+    ops::Deref,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "tokio")]
+use crate::{AsyncGraph, FalkorAsyncClient};
+
+/// The names generated by [`unique_graph_name`], whose graphs were not deleted yet
+static REGISTERED_GRAPHS: Mutex<Vec<String>> = const_mutex(Vec::new());
+static GRAPH_NAME_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// The maximal number of nodes compared against the expected properties, when looking for the closest match
+const MAX_CANDIDATES: usize = 100;
+
+fn into_properties<K: ToString, V: Into<FalkorValue>, P: IntoIterator<Item = (K, V)>>(
+    properties: P
+) -> BTreeMap<String, FalkorValue> {
+    properties
+        .into_iter()
+        .map(|(key, val)| (key.to_string(), val.into()))
+        .collect()
+}
+
+fn display_value(value: &FalkorValue) -> String {
+    value
+        .to_cypher_literal()
+        .unwrap_or_else(|_| format!("{value:?}"))
+}
+
+fn display_properties(properties: &BTreeMap<String, FalkorValue>) -> String {
+    let properties = properties
+        .iter()
+        .map(|(key, val)| format!("{key}: {}", display_value(val)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{{properties}}}")
+}
+
+fn generate_node_exists_query(
+    label: &str,
+    properties: &BTreeMap<String, FalkorValue>,
+) -> String {
+    let predicate = properties
+        .iter()
+        .map(|(key, val)| format!("n.{} = {}", escape_name(key), display_value(val)))
+        .collect::<Vec<_>>();
+    let predicate = match predicate.is_empty() {
+        true => String::new(),
+        false => format!(" WHERE {}", predicate.join(" AND ")),
+    };
+    format!(
+        "MATCH (n:{}){predicate} RETURN count(n)",
+        escape_name(label)
+    )
+}
+
+fn generate_candidates_query(label: &str) -> String {
+    format!(
+        "MATCH (n:{}) RETURN properties(n) LIMIT {MAX_CANDIDATES}",
+        escape_name(label)
+    )
+}
+
+fn generate_edge_count_query(relationship: &str) -> String {
+    format!(
+        "MATCH (src)-[e:{}]->(dst) RETURN labels(src), labels(dst), count(e)",
+        escape_name(relationship)
+    )
+}
+
+/// Describes why none of the candidates match the expected properties, diffing the closest one against them
+fn node_mismatch_report(
+    label: &str,
+    expected: &BTreeMap<String, FalkorValue>,
+    candidates: Vec<FalkorMap>,
+) -> String {
+    let mut report = format!(
+        "Expected a :{label} node with properties {}",
+        display_properties(expected)
+    );
+
+    let closest = candidates
+        .into_iter()
+        .map(|candidate| candidate.into_iter().collect::<BTreeMap<_, _>>())
+        .max_by_key(|candidate| {
+            expected
+                .iter()
+                .filter(|(key, val)| candidate.get(*key) == Some(val))
+                .count()
+        });
+    let Some(closest) = closest else {
+        let _ = write!(report, ", but there are no :{label} nodes");
+        return report;
+    };
+
+    let _ = writeln!(
+        report,
+        ", the closest one differs by (- expected, + actual):"
+    );
+    for (key, val) in expected {
+        match closest.get(key) {
+            Some(actual) if actual == val => {
+                let _ = writeln!(report, "  {key}: {}", display_value(val));
+            }
+            Some(actual) => {
+                let _ = writeln!(report, "- {key}: {}", display_value(val));
+                let _ = writeln!(report, "+ {key}: {}", display_value(actual));
+            }
+            None => {
+                let _ = writeln!(report, "- {key}: {}", display_value(val));
+            }
+        }
+    }
+    for (key, val) in closest
+        .iter()
+        .filter(|(key, _)| !expected.contains_key(*key))
+    {
+        let _ = writeln!(report, "  {key}: {} (not asserted)", display_value(val));
+    }
+    report
+}
+
+/// Sums the edge counts per endpoint labels, describing them if the total differs from the expected count
+fn edge_count_report(
+    relationship: &str,
+    expected: usize,
+    rows: Vec<Vec<FalkorValue>>,
+) -> Option<String> {
+    let display_labels = |labels: Option<FalkorValue>| {
+        labels
+            .and_then(|labels| labels.into_vec().ok())
+            .map(|labels| {
+                labels
+                    .into_iter()
+                    .flat_map(FalkorValue::into_string)
+                    .map(|label| format!(":{label}"))
+                    .collect::<String>()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut total = 0;
+    let mut breakdown = String::new();
+    for row in rows {
+        let mut row = row.into_iter();
+        let (src, dst) = (display_labels(row.next()), display_labels(row.next()));
+        let count = row.next().and_then(|count| count.to_i64()).unwrap_or(0);
+        total += count as usize;
+        let _ = writeln!(breakdown, "  ({src})-[:{relationship}]->({dst}): {count}");
+    }
+
+    (total != expected)
+        .then(|| format!("Expected {expected} :{relationship} edges, found {total}:\n{breakdown}"))
+}
+
+/// Describes why no constraint matching the requested one is operational
+fn constraint_report(
+    constraint_type: ConstraintType,
+    entity_type: EntityType,
+    label: &str,
+    properties: &[&str],
+    constraints: Vec<Constraint>,
+) -> Option<String> {
+    let describe = |constraint_type: ConstraintType,
+                    entity_type: EntityType,
+                    label: &str,
+                    properties: &[String]| {
+        format!(
+            "{constraint_type} constraint on {entity_type:?} :{label}({})",
+            properties.join(", ")
+        )
+    };
+    let requested = describe(
+        constraint_type,
+        entity_type,
+        label,
+        &properties
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+    );
+
+    let found = constraints.iter().find(|constraint| {
+        constraint.constraint_type == constraint_type
+            && constraint.entity_type == entity_type
+            && constraint.label == label
+            && constraint.properties == properties
+    });
+    match found {
+        Some(constraint) if constraint.status == ConstraintStatus::Active => None,
+        Some(constraint) => Some(format!(
+            "Expected the {requested} to be operational, but its status is {}",
+            constraint.status
+        )),
+        None => {
+            let mut report = format!("Expected a {requested}, the graph has ");
+            match constraints.is_empty() {
+                true => report.push_str("no constraints"),
+                false => {
+                    report.push_str("only these constraints:");
+                    for constraint in &constraints {
+                        let _ = write!(
+                            report,
+                            "\n  {} ({})",
+                            describe(
+                                constraint.constraint_type,
+                                constraint.entity_type,
+                                &constraint.label,
+                                &constraint.properties
+                            ),
+                            constraint.status
+                        );
+                    }
+                }
+            }
+            Some(report)
+        }
+    }
+}
+
+/// Asserts a node with the label and at least the provided properties exists in the graph
+/// On failure, panics with a property diff against the closest node with that label.
+///
+/// # Arguments
+/// * `graph`: The graph to check
+/// * `label`: The label the node should have
+/// * `properties`: The properties the node should have, other properties are ignored
+#[track_caller]
+pub fn assert_node_exists<K: ToString, V: Into<FalkorValue>, P: IntoIterator<Item = (K, V)>>(
+    graph: &SyncGraph,
+    label: &str,
+    properties: P,
+) {
+    let expected = into_properties(properties);
+    let count = graph
+        .ro_query(&generate_node_exists_query(label, &expected))
+        .execute()
+        .expect("Could not query nodes")
+        .data
+        .flat_map(|row| row.into_iter().next())
+        .find_map(|count| count.to_i64())
+        .unwrap_or_default();
+    if count > 0 {
+        return;
+    }
+
+    let candidates = graph
+        .ro_query(&generate_candidates_query(label))
+        .execute()
+        .expect("Could not query candidate nodes")
+        .data
+        .flat_map(|row| row.into_iter().next())
+        .flat_map(FalkorValue::into_map)
+        .collect();
+    panic!("{}", node_mismatch_report(label, &expected, candidates));
+}
+
+/// Asserts the graph contains exactly the expected number of edges of a relationship type
+/// On failure, panics with the actual counts, broken down by the labels of their endpoints.
+///
+/// # Arguments
+/// * `graph`: The graph to check
+/// * `relationship`: The relationship type to count
+/// * `expected`: The expected number of edges
+#[track_caller]
+pub fn assert_edge_count(
+    graph: &SyncGraph,
+    relationship: &str,
+    expected: usize,
+) {
+    let rows = graph
+        .ro_query(&generate_edge_count_query(relationship))
+        .execute()
+        .expect("Could not count edges")
+        .data
+        .collect();
+    if let Some(report) = edge_count_report(relationship, expected, rows) {
+        panic!("{report}");
+    }
+}
+
+/// Asserts a constraint exists on exactly the provided label and properties, and is operational
+/// On failure, panics with the constraint's status, or the constraints the graph has instead.
+///
+/// # Arguments
+/// * `graph`: The graph to check
+/// * `constraint_type`: The type of the constraint
+/// * `entity_type`: Whether the constraint applies to nodes or relationships
+/// * `label`: The label or relationship type the constraint applies to
+/// * `properties`: The properties the constraint applies to, in order
+#[track_caller]
+pub fn assert_constraint_operational(
+    graph: &SyncGraph,
+    constraint_type: ConstraintType,
+    entity_type: EntityType,
+    label: &str,
+    properties: &[&str],
+) {
+    let constraints = graph
+        .list_constraints()
+        .expect("Could not list constraints")
+        .data;
+    if let Some(report) =
+        constraint_report(constraint_type, entity_type, label, properties, constraints)
+    {
+        panic!("{report}");
+    }
+}
+
+/// Asserts a node with the label and at least the provided properties exists in the graph
+/// On failure, panics with a property diff against the closest node with that label.
+///
+/// # Arguments
+/// * `graph`: The graph to check
+/// * `label`: The label the node should have
+/// * `properties`: The properties the node should have, other properties are ignored
+#[cfg(feature = "tokio")]
+pub async fn assert_node_exists_async<
+    K: ToString,
+    V: Into<FalkorValue>,
+    P: IntoIterator<Item = (K, V)>,
+>(
+    graph: &AsyncGraph,
+    label: &str,
+    properties: P,
+) {
+    let expected = into_properties(properties);
+    let count = graph
+        .ro_query(&generate_node_exists_query(label, &expected))
+        .execute()
+        .await
+        .expect("Could not query nodes")
+        .data
+        .flat_map(|row| row.into_iter().next())
+        .find_map(|count| count.to_i64())
+        .unwrap_or_default();
+    if count > 0 {
+        return;
+    }
+
+    let candidates = graph
+        .ro_query(&generate_candidates_query(label))
+        .execute()
+        .await
+        .expect("Could not query candidate nodes")
+        .data
+        .flat_map(|row| row.into_iter().next())
+        .flat_map(FalkorValue::into_map)
+        .collect();
+    panic!("{}", node_mismatch_report(label, &expected, candidates));
+}
+
+/// Asserts the graph contains exactly the expected number of edges of a relationship type
+/// On failure, panics with the actual counts, broken down by the labels of their endpoints.
+///
+/// # Arguments
+/// * `graph`: The graph to check
+/// * `relationship`: The relationship type to count
+/// * `expected`: The expected number of edges
+#[cfg(feature = "tokio")]
+pub async fn assert_edge_count_async(
+    graph: &AsyncGraph,
+    relationship: &str,
+    expected: usize,
+) {
+    let rows = graph
+        .ro_query(&generate_edge_count_query(relationship))
+        .execute()
+        .await
+        .expect("Could not count edges")
+        .data
+        .collect();
+    if let Some(report) = edge_count_report(relationship, expected, rows) {
+        panic!("{report}");
+    }
+}
+
+/// Asserts a constraint exists on exactly the provided label and properties, and is operational
+/// On failure, panics with the constraint's status, or the constraints the graph has instead.
+///
+/// # Arguments
+/// * `graph`: The graph to check
+/// * `constraint_type`: The type of the constraint
+/// * `entity_type`: Whether the constraint applies to nodes or relationships
+/// * `label`: The label or relationship type the constraint applies to
+/// * `properties`: The properties the constraint applies to, in order
+#[cfg(feature = "tokio")]
+pub async fn assert_constraint_operational_async(
+    graph: &AsyncGraph,
+    constraint_type: ConstraintType,
+    entity_type: EntityType,
+    label: &str,
+    properties: &[&str],
+) {
+    let constraints = graph
+        .list_constraints()
+        .await
+        .expect("Could not list constraints")
+        .data;
+    if let Some(report) =
+        constraint_report(constraint_type, entity_type, label, properties, constraints)
+    {
+        panic!("{report}");
+    }
+}
+
+/// Derives a graph name which doesn't collide with any other test, in this process or any other one running concurrently,
+/// made of the test name, the process id, and a random suffix.
+/// The name is registered, so the graph can later be deleted using [`delete_registered_graphs`].
+///
+/// # Arguments
+/// * `test_name`: The name of the test, to make the graph easy to identify
+///
+/// # Returns
+/// The unique graph name
+pub fn unique_graph_name(test_name: &str) -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(GRAPH_NAME_COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+
+    let graph_name = format!(
+        "{test_name}_{}_{:08x}",
+        std::process::id(),
+        hasher.finish() as u32
+    );
+    REGISTERED_GRAPHS.lock().push(graph_name.clone());
+    graph_name
+}
+
+fn deregister(graph_name: &str) {
+    REGISTERED_GRAPHS.lock().retain(|name| name != graph_name);
+}
+
+/// Deletes every graph named by [`unique_graph_name`] which was not deleted yet, E.g. at the end of a test suite
+/// Graphs which were never created are skipped.
+///
+/// # Arguments
+/// * `client`: The client to delete the graphs with
+///
+/// # Returns
+/// The number of graphs deleted
+pub fn delete_registered_graphs(client: &FalkorSyncClient) -> usize {
+    let graph_names = std::mem::take(&mut *REGISTERED_GRAPHS.lock());
+    graph_names
+        .into_iter()
+        .filter(|graph_name| client.select_graph(graph_name).delete().is_ok())
+        .count()
+}
+
+/// Deletes every graph named by [`unique_graph_name`] which was not deleted yet, E.g. at the end of a test suite
+/// Graphs which were never created are skipped.
+///
+/// # Arguments
+/// * `client`: The client to delete the graphs with
+///
+/// # Returns
+/// The number of graphs deleted
+#[cfg(feature = "tokio")]
+pub async fn delete_registered_graphs_async(client: &FalkorAsyncClient) -> usize {
+    let graph_names = std::mem::take(&mut *REGISTERED_GRAPHS.lock());
+    let mut deleted = 0;
+    for graph_name in graph_names {
+        if client.select_graph(&graph_name).delete().await.is_ok() {
+            deleted += 1;
+        }
+    }
+    deleted
+}
+
+/// A graph with a name unique to a single test, see [`unique_graph_name`], which is deleted once dropped,
+/// even if the test panics. This dereferences to the [`SyncGraph`] itself.
+pub struct TestGraph {
+    graph: SyncGraph,
+}
+
+impl TestGraph {
+    /// Selects a new graph for a test
+    ///
+    /// # Arguments
+    /// * `client`: The client to select the graph from
+    /// * `test_name`: The name of the test, to make the graph easy to identify
+    ///
+    /// # Returns
+    /// The new [`TestGraph`]
+    pub fn new(
+        client: &FalkorSyncClient,
+        test_name: &str,
+    ) -> Self {
+        Self {
+            graph: client.select_graph(unique_graph_name(test_name)),
+        }
+    }
+}
+
+impl Deref for TestGraph {
+    type Target = SyncGraph;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl Drop for TestGraph {
+    fn drop(&mut self) {
+        self.graph.delete().ok();
+        deregister(self.graph.graph_name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_graph_name() {
+        let first = unique_graph_name("test_unique_graph_name");
+        let second = unique_graph_name("test_unique_graph_name");
+
+        assert_ne!(first, second);
+        assert!(first.starts_with(&format!("test_unique_graph_name_{}_", std::process::id())));
+        assert!(REGISTERED_GRAPHS.lock().contains(&first));
+
+        deregister(&first);
+        assert!(!REGISTERED_GRAPHS.lock().contains(&first));
+        assert!(REGISTERED_GRAPHS.lock().contains(&second));
+        deregister(&second);
+    }
+
+    #[test]
+    fn test_generate_queries() {
+        assert_eq!(
+            generate_node_exists_query("Person", &into_properties([("name", "Alice")])),
+            "MATCH (n:`Person`) WHERE n.`name` = \"Alice\" RETURN count(n)"
+        );
+        assert_eq!(
+            generate_node_exists_query("Person", &BTreeMap::new()),
+            "MATCH (n:`Person`) RETURN count(n)"
+        );
+        assert_eq!(
+            generate_edge_count_query("KNOWS"),
+            "MATCH (src)-[e:`KNOWS`]->(dst) RETURN labels(src), labels(dst), count(e)"
+        );
+    }
+
+    #[test]
+    fn test_node_mismatch_report() {
+        let expected = into_properties([
+            ("name", FalkorValue::from("Alice")),
+            ("age", FalkorValue::I64(30)),
+        ]);
+        let candidates = vec![
+            FalkorMap::from_iter([("name".to_string(), FalkorValue::from("Bob"))]),
+            FalkorMap::from_iter([
+                ("name".to_string(), FalkorValue::from("Alice")),
+                ("age".to_string(), FalkorValue::I64(31)),
+                ("city".to_string(), FalkorValue::from("Paris")),
+            ]),
+        ];
+
+        assert_eq!(
+            node_mismatch_report("Person", &expected, candidates),
+            concat!(
+                "Expected a :Person node with properties {age: 30, name: \"Alice\"}, ",
+                "the closest one differs by (- expected, + actual):\n",
+                "- age: 30\n",
+                "+ age: 31\n",
+                "  name: \"Alice\"\n",
+                "  city: \"Paris\" (not asserted)\n"
+            )
+        );
+        assert_eq!(
+            node_mismatch_report("Person", &expected, vec![]),
+            "Expected a :Person node with properties {age: 30, name: \"Alice\"}, but there are no :Person nodes"
+        );
+    }
+
+    #[test]
+    fn test_edge_count_report() {
+        let rows = vec![
+            vec![
+                FalkorValue::Array(vec![FalkorValue::from("Person")]),
+                FalkorValue::Array(vec![FalkorValue::from("Person")]),
+                FalkorValue::I64(2),
+            ],
+            vec![
+                FalkorValue::Array(vec![FalkorValue::from("Person")]),
+                FalkorValue::Array(vec![FalkorValue::from("Bot")]),
+                FalkorValue::I64(1),
+            ],
+        ];
+
+        assert_eq!(edge_count_report("KNOWS", 3, rows.clone()), None);
+        assert_eq!(
+            edge_count_report("KNOWS", 2, rows),
+            Some(
+                concat!(
+                    "Expected 2 :KNOWS edges, found 3:\n",
+                    "  (:Person)-[:KNOWS]->(:Person): 2\n",
+                    "  (:Person)-[:KNOWS]->(:Bot): 1\n"
+                )
+                .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_constraint_report() {
+        let constraint = |status| Constraint {
+            constraint_type: ConstraintType::Unique,
+            label: "Person".to_string(),
+            properties: vec!["name".to_string()],
+            entity_type: EntityType::Node,
+            status,
+        };
+        let report = |constraints| {
+            constraint_report(
+                ConstraintType::Unique,
+                EntityType::Node,
+                "Person",
+                &["name"],
+                constraints,
+            )
+        };
+
+        assert_eq!(report(vec![constraint(ConstraintStatus::Active)]), None);
+        assert_eq!(
+            report(vec![constraint(ConstraintStatus::Pending)]),
+            Some(
+                "Expected the UNIQUE constraint on Node :Person(name) to be operational, but its status is UNDER CONSTRUCTION"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            report(vec![]),
+            Some(
+                "Expected a UNIQUE constraint on Node :Person(name), the graph has no constraints"
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,343 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    transport::routing::endpoint_from_url, FalkorDBError, FalkorResult, FalkorTransport,
+    FalkorTransportProvider,
+};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tokio")]
+use crate::transport::{FalkorAsyncTransport, TransportFuture};
+
+/// How long an endpoint which could not be connected to is tried only after all others, by default
+const DEFAULT_RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The client's connections moved from one endpoint to another, reported to the hooks registered with [`FailoverProvider::on_failover`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailoverEvent {
+    /// The address of the endpoint which was active until now
+    pub from: String,
+    /// The address of the endpoint new connections are opened to
+    pub to: String,
+    /// Whether the new endpoint has a higher priority than the previous one, meaning a preferred endpoint recovered
+    pub failback: bool,
+}
+
+/// A callback invoked whenever a [`FailoverProvider`] switches endpoints, see [`FailoverProvider::on_failover`]
+pub type FailoverHook = Arc<dyn Fn(&FailoverEvent) + Send + Sync>;
+
+struct FailoverEndpoint {
+    priority: u32,
+    provider: Arc<dyn FalkorTransportProvider>,
+    failed_at: Mutex<Option<Instant>>,
+}
+
+/// A [`FalkorTransportProvider`] connecting to the highest priority endpoint which is reachable,
+/// such as a primary deployment and its standby, so the client keeps working when the active endpoint goes down.
+///
+/// Connections are not moved between endpoints while they work, once a connection fails,
+/// the pool replaces it with a new one, which is opened to the first reachable endpoint in priority order.
+/// An endpoint which could not be connected to is only tried after all others for a while, see [`FailoverProvider::with_recheck_interval`],
+/// so replacing connections does not wait on the unreachable endpoint every time, and new connections fail back once it recovers.
+pub struct FailoverProvider {
+    endpoints: Vec<FailoverEndpoint>,
+    recheck_interval: Duration,
+    active: Mutex<Option<usize>>,
+    hooks: RwLock<Vec<FailoverHook>>,
+}
+
+impl Debug for FailoverProvider {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("FailoverProvider")
+            .field("endpoints", &self.address())
+            .field("recheck_interval", &self.recheck_interval)
+            .field("active", &self.active_endpoint())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FailoverProvider {
+    /// Creates a new provider failing over between the given endpoints
+    ///
+    /// # Arguments
+    /// * `endpoints`: The priority and provider of each endpoint, must not be empty.
+    ///   Lower values are preferred, endpoints of equal priority are tried in the order they were provided.
+    ///
+    /// # Returns
+    /// The new [`FailoverProvider`], or an error if no endpoints were provided
+    pub fn new(endpoints: Vec<(u32, Arc<dyn FalkorTransportProvider>)>) -> FalkorResult<Self> {
+        if endpoints.is_empty() {
+            return Err(FalkorDBError::InvalidConnectionInfo(
+                "At least one endpoint is required for failover".to_string(),
+            ));
+        }
+
+        let mut endpoints = endpoints
+            .into_iter()
+            .map(|(priority, provider)| FailoverEndpoint {
+                priority,
+                provider,
+                failed_at: Mutex::new(None),
+            })
+            .collect::<Vec<_>>();
+        endpoints.sort_by_key(|endpoint| endpoint.priority);
+
+        Ok(Self {
+            endpoints,
+            recheck_interval: DEFAULT_RECHECK_INTERVAL,
+            active: Mutex::new(None),
+            hooks: RwLock::new(Vec::new()),
+        })
+    }
+
+    /// Creates a new provider failing over between Redis endpoints, given as connection strings
+    ///
+    /// # Arguments
+    /// * `urls`: The connection strings of the endpoints, in priority order, E.g. the primary followed by its standby
+    ///
+    /// # Returns
+    /// The new [`FailoverProvider`], or an error if any of the connection strings could not be parsed
+    pub fn from_urls<S: AsRef<str>>(urls: &[S]) -> FalkorResult<Self> {
+        Self::new(
+            urls.iter()
+                .zip(0..)
+                .map(|(url, priority)| Ok((priority, endpoint_from_url(url.as_ref())?)))
+                .collect::<FalkorResult<Vec<_>>>()?,
+        )
+    }
+
+    /// Sets how long an endpoint which could not be connected to is tried only after all others, the default is 5 seconds
+    ///
+    /// # Arguments
+    /// * `recheck_interval`: How long to prefer other endpoints after a failed connection attempt
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_recheck_interval(
+        self,
+        recheck_interval: Duration,
+    ) -> Self {
+        Self {
+            recheck_interval,
+            ..self
+        }
+    }
+
+    /// Registers a callback, invoked whenever a new connection is opened to a different endpoint than the previous one,
+    /// E.g. to alert, or emit metrics.
+    /// The callback is invoked on the thread opening the connection, so it should return quickly.
+    ///
+    /// # Arguments
+    /// * `hook`: The callback to invoke
+    pub fn on_failover<F: Fn(&FailoverEvent) + Send + Sync + 'static>(
+        &self,
+        hook: F,
+    ) {
+        self.hooks.write().push(Arc::new(hook));
+    }
+
+    /// Returns the address of the endpoint the latest connection was opened to
+    ///
+    /// # Returns
+    /// The address of the active endpoint, or [`None`] if no connection was opened yet
+    pub fn active_endpoint(&self) -> Option<String> {
+        (*self.active.lock()).map(|idx| self.endpoints[idx].provider.address())
+    }
+
+    /// The endpoints in the order they should be tried, endpoints which recently failed are moved to the back
+    fn connection_order(&self) -> Vec<usize> {
+        let (recent_failures, available): (Vec<usize>, Vec<usize>) = (0..self.endpoints.len())
+            .partition(|&idx| {
+                self.endpoints[idx]
+                    .failed_at
+                    .lock()
+                    .is_some_and(|failed_at| failed_at.elapsed() < self.recheck_interval)
+            });
+        available.into_iter().chain(recent_failures).collect()
+    }
+
+    fn record_failure(
+        &self,
+        idx: usize,
+        err: &FalkorDBError,
+    ) {
+        *self.endpoints[idx].failed_at.lock() = Some(Instant::now());
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            endpoint = self.endpoints[idx].provider.address(),
+            %err,
+            "Could not connect to failover endpoint"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = err;
+    }
+
+    fn record_success(
+        &self,
+        idx: usize,
+    ) {
+        *self.endpoints[idx].failed_at.lock() = None;
+        let previous = self.active.lock().replace(idx);
+        let Some(previous) = previous.filter(|&previous| previous != idx) else {
+            return;
+        };
+
+        let event = FailoverEvent {
+            from: self.endpoints[previous].provider.address(),
+            to: self.endpoints[idx].provider.address(),
+            failback: self.endpoints[idx].priority < self.endpoints[previous].priority,
+        };
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            from = event.from,
+            to = event.to,
+            failback = event.failback,
+            "Failed over to another endpoint"
+        );
+
+        // Clone the hooks so they run without holding any lock, allowing them to register other hooks
+        let hooks = self.hooks.read().clone();
+        for hook in hooks {
+            hook(&event);
+        }
+    }
+}
+
+impl FalkorTransportProvider for FailoverProvider {
+    fn address(&self) -> String {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.provider.address())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+        let mut last_err = FalkorDBError::NoConnection;
+        for idx in self.connection_order() {
+            match self.endpoints[idx].provider.connect() {
+                Ok(connection) => {
+                    self.record_success(idx);
+                    return Ok(connection);
+                }
+                Err(err) => {
+                    self.record_failure(idx, &err);
+                    last_err = err;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    #[cfg(feature = "tokio")]
+    fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+        Box::pin(async move {
+            let mut last_err = FalkorDBError::NoConnection;
+            for idx in self.connection_order() {
+                match self.endpoints[idx].provider.connect_async().await {
+                    Ok(connection) => {
+                        self.record_success(idx);
+                        return Ok(connection);
+                    }
+                    Err(err) => {
+                        self.record_failure(idx, &err);
+                        last_err = err;
+                    }
+                }
+            }
+            Err(last_err)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransport, FalkorClientBuilder, FalkorConnectionInfo};
+    use std::num::NonZeroU8;
+
+    fn endpoint(name: &str) -> MockTransport {
+        MockTransport::new(name, |args| match args.first().map(String::as_str) {
+            Some("GRAPH.LIST") => Ok(redis::Value::Array(vec![])),
+            _ => Ok(redis::Value::Nil),
+        })
+    }
+
+    #[test]
+    fn test_endpoints_ordered_by_priority() {
+        let provider = FailoverProvider::new(vec![
+            (1, Arc::new(endpoint("standby"))),
+            (0, Arc::new(endpoint("primary"))),
+        ])
+        .expect("Could not create provider");
+        assert_eq!(provider.address(), "primary,standby");
+        assert_eq!(provider.active_endpoint(), None);
+
+        assert!(provider.connect().is_ok());
+        assert_eq!(provider.active_endpoint(), Some("primary".to_string()));
+        assert!(FailoverProvider::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_failover_and_failback() {
+        let (primary, standby) = (endpoint("primary"), endpoint("standby"));
+        let provider = Arc::new(
+            FailoverProvider::new(vec![
+                (0, Arc::new(primary.clone())),
+                (1, Arc::new(standby.clone())),
+            ])
+            .expect("Could not create provider")
+            .with_recheck_interval(Duration::ZERO),
+        );
+        let events = Arc::new(Mutex::new(Vec::new()));
+        provider.on_failover({
+            let events = events.clone();
+            move |event| events.lock().push(event.clone())
+        });
+
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(FalkorConnectionInfo::Transport(provider.clone()))
+            .with_num_connections(NonZeroU8::new(1).expect("Could not create a perfectly fine u8"))
+            .build()
+            .expect("Could not create client over failover transport");
+        assert!(client.list_graphs().is_ok());
+
+        // The broken connection is replaced by one to the standby, so only the command in flight fails
+        primary.set_down(true);
+        assert_eq!(client.list_graphs(), Err(FalkorDBError::ConnectionDown));
+        assert!(client.list_graphs().is_ok());
+        assert_eq!(provider.active_endpoint(), Some("standby".to_string()));
+
+        primary.set_down(false);
+        standby.set_down(true);
+        assert_eq!(client.list_graphs(), Err(FalkorDBError::ConnectionDown));
+        assert!(client.list_graphs().is_ok());
+
+        assert_eq!(
+            *events.lock(),
+            vec![
+                FailoverEvent {
+                    from: "primary".to_string(),
+                    to: "standby".to_string(),
+                    failback: false,
+                },
+                FailoverEvent {
+                    from: "standby".to_string(),
+                    to: "primary".to_string(),
+                    failback: true,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,499 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    transport::{decode_resp_reply, encode_resp_command},
+    FalkorDBError, FalkorResult, FalkorTransport, FalkorTransportProvider,
+};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+};
+
+#[cfg(feature = "tokio")]
+use crate::{
+    runtime,
+    transport::{FalkorAsyncTransport, TransportFuture},
+};
+
+/// A [`FalkorTransportProvider`] which sends commands through a simple HTTP sidecar,
+/// for environments that can't speak RESP to the database directly.
+///
+/// Each command is sent as a `POST` request, whose body is the RESP-encoded command,
+/// and the sidecar is expected to respond with a `2xx` status and the RESP-encoded reply as the body.
+/// Connections are kept alive between commands, unless the sidecar asks otherwise.
+/// Only plain HTTP/1.1 sidecars are supported, gRPC sidecars are not.
+#[derive(Clone, Debug)]
+pub struct HttpSidecarProvider {
+    host: String,
+    port: u16,
+    path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl HttpSidecarProvider {
+    /// Creates a new provider for the sidecar at the given URL
+    ///
+    /// # Arguments
+    /// * `url`: The sidecar endpoint, E.g. `http://127.0.0.1:8080/command`, only plain `http` is supported
+    ///
+    /// # Returns
+    /// The new [`HttpSidecarProvider`], or an error if the URL could not be parsed
+    pub fn new(url: &str) -> FalkorResult<Self> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            FalkorDBError::InvalidConnectionInfo(format!(
+                "Sidecar URL must start with http://: {url}"
+            ))
+        })?;
+
+        let (authority, path) = rest
+            .find('/')
+            .map(|idx| rest.split_at(idx))
+            .unwrap_or((rest, "/"));
+
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, port) = bracketed.split_once(']').ok_or_else(|| {
+                    FalkorDBError::InvalidConnectionInfo(format!("Invalid sidecar host: {url}"))
+                })?;
+                (host, port.strip_prefix(':'))
+            }
+            None => match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = match port {
+            Some(port) => port.parse().map_err(|_| {
+                FalkorDBError::InvalidConnectionInfo(format!("Invalid sidecar port: {port}"))
+            })?,
+            None => 80,
+        };
+
+        if host.is_empty() {
+            return Err(FalkorDBError::InvalidConnectionInfo(format!(
+                "Missing sidecar host: {url}"
+            )));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+            headers: Vec::new(),
+        })
+    }
+
+    /// Adds a header to be sent with every request, E.g. for authenticating against the sidecar
+    ///
+    /// # Arguments
+    /// * `name`: The header name, which must be a valid HTTP token
+    /// * `value`: The header value, which must not contain line breaks
+    ///
+    /// # Returns
+    /// The consumed and modified self, or an error if the header could not be sent as-is.
+    pub fn with_header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> FalkorResult<Self> {
+        let (name, value) = (name.into(), value.into());
+        let is_token_char = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+        if name.is_empty() || !name.chars().all(is_token_char) {
+            return Err(FalkorDBError::InvalidConnectionInfo(format!(
+                "Invalid sidecar header name: {name:?}"
+            )));
+        }
+        if value.contains(['\r', '\n', '\0']) {
+            return Err(FalkorDBError::InvalidConnectionInfo(format!(
+                "Invalid value for sidecar header {name}"
+            )));
+        }
+
+        self.headers.push((name, value));
+        Ok(self)
+    }
+
+    fn authority(&self) -> String {
+        match self.host.contains(':') {
+            true => format!("[{}]:{}", self.host, self.port),
+            false => format!("{}:{}", self.host, self.port),
+        }
+    }
+
+    fn socket_address(&self) -> (&str, u16) {
+        (self.host.as_str(), self.port)
+    }
+
+    fn encode_request(
+        &self,
+        args: &[String],
+    ) -> Vec<u8> {
+        let body = encode_resp_command(args);
+        let mut request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/x-resp\r\nContent-Length: {}\r\n",
+            self.path,
+            self.authority(),
+            body.len()
+        );
+        for (name, value) in &self.headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+
+        let mut request = request.into_bytes();
+        request.extend(body);
+        request
+    }
+}
+
+impl FalkorTransportProvider for HttpSidecarProvider {
+    fn address(&self) -> String {
+        format!("http://{}{}", self.authority(), self.path)
+    }
+
+    fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+        Ok(Box::new(HttpSidecarConnection {
+            provider: self.clone(),
+            stream: None,
+        }))
+    }
+
+    #[cfg(feature = "tokio")]
+    fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+        let provider = self.clone();
+        Box::pin(async move {
+            Ok(Box::new(AsyncHttpSidecarConnection {
+                provider,
+                stream: None,
+            }) as Box<dyn FalkorAsyncTransport>)
+        })
+    }
+}
+
+fn map_io_err(_: std::io::Error) -> FalkorDBError {
+    FalkorDBError::ConnectionDown
+}
+
+/// Accumulates the status line and relevant headers of a sidecar response
+#[derive(Default)]
+struct ResponseHead {
+    status: Option<u16>,
+    content_length: Option<usize>,
+    close: bool,
+}
+
+impl ResponseHead {
+    /// Feeds a single line of the response head, returning true once the head is complete
+    fn feed(
+        &mut self,
+        line: &str,
+    ) -> FalkorResult<bool> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        if self.status.is_none() {
+            self.status = Some(
+                line.split_whitespace()
+                    .nth(1)
+                    .and_then(|status| status.parse().ok())
+                    .ok_or_else(|| {
+                        FalkorDBError::RedisParsingError(format!(
+                            "Invalid sidecar status line: {line}"
+                        ))
+                    })?,
+            );
+            return Ok(false);
+        }
+
+        if line.is_empty() {
+            return Ok(true);
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                self.content_length = Some(value.parse().map_err(|_| {
+                    FalkorDBError::RedisParsingError(format!(
+                        "Invalid sidecar content length: {value}"
+                    ))
+                })?);
+            } else if name.eq_ignore_ascii_case("connection") {
+                self.close = value.eq_ignore_ascii_case("close");
+            }
+        }
+        Ok(false)
+    }
+
+    fn body_len(&self) -> FalkorResult<usize> {
+        self.content_length.ok_or_else(|| {
+            FalkorDBError::RedisParsingError(
+                "Sidecar response is missing a Content-Length header".to_string(),
+            )
+        })
+    }
+
+    fn into_reply(
+        self,
+        body: &[u8],
+    ) -> FalkorResult<redis::Value> {
+        match self.status {
+            Some(200..=299) => decode_resp_reply(body),
+            status => Err(FalkorDBError::RedisError(format!(
+                "Sidecar responded with status {}: {}",
+                status.unwrap_or_default(),
+                String::from_utf8_lossy(body)
+            ))),
+        }
+    }
+}
+
+struct HttpSidecarConnection {
+    provider: HttpSidecarProvider,
+    stream: Option<BufReader<TcpStream>>,
+}
+
+impl HttpSidecarConnection {
+    fn round_trip(
+        &mut self,
+        request: &[u8],
+    ) -> FalkorResult<(ResponseHead, Vec<u8>)> {
+        let stream = match &mut self.stream {
+            Some(stream) => stream,
+            None => self.stream.insert(BufReader::new(
+                TcpStream::connect(self.provider.socket_address()).map_err(map_io_err)?,
+            )),
+        };
+
+        stream.get_mut().write_all(request).map_err(map_io_err)?;
+
+        let mut head = ResponseHead::default();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if stream.read_line(&mut line).map_err(map_io_err)? == 0 {
+                return Err(FalkorDBError::ConnectionDown);
+            }
+            if head.feed(&line)? {
+                break;
+            }
+        }
+
+        let mut body = vec![0; head.body_len()?];
+        stream.read_exact(&mut body).map_err(map_io_err)?;
+        Ok((head, body))
+    }
+}
+
+impl FalkorTransport for HttpSidecarConnection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value> {
+        let request = self.provider.encode_request(&args);
+        let (head, body) = self.round_trip(&request).inspect_err(|_| {
+            self.stream = None;
+        })?;
+
+        if head.close {
+            self.stream = None;
+        }
+        head.into_reply(&body)
+    }
+}
+
+#[cfg(feature = "tokio")]
+struct AsyncHttpSidecarConnection {
+    provider: HttpSidecarProvider,
+    stream: Option<tokio::io::BufReader<runtime::TcpStream>>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncHttpSidecarConnection {
+    async fn round_trip(
+        &mut self,
+        request: &[u8],
+    ) -> FalkorResult<(ResponseHead, Vec<u8>)> {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+        let stream = match &mut self.stream {
+            Some(stream) => stream,
+            None => {
+                let (host, port) = self.provider.socket_address();
+                self.stream.insert(tokio::io::BufReader::new(
+                    runtime::connect_tcp(host, port).await.map_err(map_io_err)?,
+                ))
+            }
+        };
+
+        stream
+            .get_mut()
+            .write_all(request)
+            .await
+            .map_err(map_io_err)?;
+
+        let mut head = ResponseHead::default();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if stream.read_line(&mut line).await.map_err(map_io_err)? == 0 {
+                return Err(FalkorDBError::ConnectionDown);
+            }
+            if head.feed(&line)? {
+                break;
+            }
+        }
+
+        let mut body = vec![0; head.body_len()?];
+        stream.read_exact(&mut body).await.map_err(map_io_err)?;
+        Ok((head, body))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl FalkorAsyncTransport for AsyncHttpSidecarConnection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> TransportFuture<'_, redis::Value> {
+        Box::pin(async move {
+            let request = self.provider.encode_request(&args);
+            let (head, body) = match self.round_trip(&request).await {
+                Ok(res) => res,
+                Err(err) => {
+                    self.stream = None;
+                    return Err(err);
+                }
+            };
+
+            if head.close {
+                self.stream = None;
+            }
+            head.into_reply(&body)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::TcpListener, thread};
+
+    #[test]
+    fn test_parse_sidecar_url() {
+        let provider = HttpSidecarProvider::new("http://127.0.0.1:8080/command").unwrap();
+        assert_eq!(provider.socket_address(), ("127.0.0.1", 8080));
+        assert_eq!(provider.address(), "http://127.0.0.1:8080/command");
+
+        let provider = HttpSidecarProvider::new("http://sidecar").unwrap();
+        assert_eq!(provider.socket_address(), ("sidecar", 80));
+        assert_eq!(provider.path, "/");
+
+        let provider = HttpSidecarProvider::new("http://[::1]:9000/q").unwrap();
+        assert_eq!(provider.socket_address(), ("::1", 9000));
+        assert_eq!(provider.address(), "http://[::1]:9000/q");
+
+        assert!(HttpSidecarProvider::new("https://127.0.0.1:8080").is_err());
+        assert!(HttpSidecarProvider::new("http://127.0.0.1:abc").is_err());
+        assert!(HttpSidecarProvider::new("http://:8080").is_err());
+    }
+
+    #[test]
+    fn test_encode_request() {
+        let provider = HttpSidecarProvider::new("http://localhost:8080/cmd")
+            .unwrap()
+            .with_header("Authorization", "Bearer token")
+            .unwrap();
+        let request =
+            String::from_utf8(provider.encode_request(&["GRAPH.LIST".to_string()])).unwrap();
+
+        assert!(request.starts_with("POST /cmd HTTP/1.1\r\nHost: localhost:8080\r\n"));
+        assert!(request.contains("Content-Length: 21\r\n"));
+        assert!(request.contains("Authorization: Bearer token\r\n"));
+        assert!(request.ends_with("\r\n\r\n*1\r\n$10\r\nGRAPH.LIST\r\n"));
+    }
+
+    #[test]
+    fn test_invalid_headers() {
+        let provider = HttpSidecarProvider::new("http://localhost:8080/cmd").unwrap();
+        for (name, value) in [
+            ("Authorization", "Bearer token\r\nX-Injected: 1"),
+            ("Authorization", "Bearer token\n"),
+            ("X-Injected: 1\r\nAuthorization", "Bearer token"),
+            ("Bad Name", "value"),
+            ("", "value"),
+        ] {
+            assert!(matches!(
+                provider.clone().with_header(name, value),
+                Err(FalkorDBError::InvalidConnectionInfo(_))
+            ));
+        }
+        assert!(provider.with_header("X-Request-Id", "").is_ok());
+    }
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            for reply in [&b"*1\r\n$6\r\nsocial\r\n"[..], &b"-ERR boom\r\n"[..]] {
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.strip_prefix("Content-Length: ") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let stream = reader.get_mut();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                    reply.len()
+                )
+                .unwrap();
+                stream.write_all(reply).unwrap();
+            }
+        });
+
+        let provider = HttpSidecarProvider::new(&format!("http://127.0.0.1:{port}/")).unwrap();
+        let mut conn = provider.connect().unwrap();
+        assert_eq!(
+            conn.execute(vec!["GRAPH.LIST".to_string()]),
+            Ok(redis::Value::Array(vec![redis::Value::BulkString(
+                b"social".to_vec()
+            )]))
+        );
+        assert!(matches!(
+            conn.execute(vec!["GRAPH.LIST".to_string()]),
+            Err(FalkorDBError::RedisError(_))
+        ));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_sidecar_error_status() {
+        let mut head = ResponseHead::default();
+        assert!(!head.feed("HTTP/1.1 503 Service Unavailable\r\n").unwrap());
+        assert!(!head.feed("Content-Length: 4\r\n").unwrap());
+        assert!(!head.feed("Connection: close\r\n").unwrap());
+        assert!(head.feed("\r\n").unwrap());
+        assert!(head.close);
+        assert_eq!(
+            head.into_reply(b"busy"),
+            Err(FalkorDBError::RedisError(
+                "Sidecar responded with status 503: busy".to_string()
+            ))
+        );
+    }
+}
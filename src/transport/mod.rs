@@ -0,0 +1,215 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{connection::map_redis_err, FalkorDBError, FalkorResult};
+use std::fmt::Debug;
+
+#[cfg(feature = "tokio")]
+use std::{future::Future, pin::Pin};
+
+pub(crate) mod failover;
+#[cfg(feature = "http-sidecar")]
+pub(crate) mod http;
+pub(crate) mod routing;
+#[cfg(feature = "socks5")]
+pub(crate) mod socks5;
+
+/// A boxed future, as returned by the asynchronous transport traits, and the object-safe asynchronous client and graph traits
+#[cfg(feature = "tokio")]
+pub type TransportFuture<'a, T> = Pin<Box<dyn Future<Output = FalkorResult<T>> + Send + 'a>>;
+
+/// A single connection over a user-provided transport, such as a WebSocket or HTTP gateway.
+///
+/// The transport receives the fully flattened command arguments,
+/// e.g. `["GRAPH.QUERY", "social", "MATCH (n) RETURN n", "--compact"]`,
+/// and must return the server reply as a [`redis::Value`],
+/// which allows the parsing and typed-response layers of this crate to be reused as-is.
+///
+/// Returning [`FalkorDBError::ConnectionDown`] signals the client to replace this connection with a new one.
+pub trait FalkorTransport: Send {
+    /// Sends a single command, and waits for its reply
+    ///
+    /// # Arguments
+    /// * `args`: The command name followed by its arguments
+    ///
+    /// # Returns
+    /// The raw reply, as would have been returned by the server
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value>;
+}
+
+/// The asynchronous counterpart of [`FalkorTransport`], used by [`FalkorAsyncClient`](crate::FalkorAsyncClient)
+#[cfg(feature = "tokio")]
+pub trait FalkorAsyncTransport: Send {
+    /// Sends a single command, and waits for its reply
+    ///
+    /// # Arguments
+    /// * `args`: The command name followed by its arguments
+    ///
+    /// # Returns
+    /// A future resolving to the raw reply, as would have been returned by the server
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> TransportFuture<'_, redis::Value>;
+}
+
+/// Creates connections over a user-provided transport, used by the client to fill its connection pool.
+///
+/// Pass it to the client using [`FalkorConnectionInfo::Transport`](crate::FalkorConnectionInfo::Transport).
+pub trait FalkorTransportProvider: Debug + Send + Sync {
+    /// A human-readable address of the remote end, used in logs and by [`FalkorConnectionInfo::address`](crate::FalkorConnectionInfo::address)
+    ///
+    /// # Returns
+    /// A [`String`] representation of the gateway address
+    fn address(&self) -> String;
+
+    /// Opens a new connection for the sync client
+    ///
+    /// # Returns
+    /// A boxed [`FalkorTransport`]
+    fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>>;
+
+    /// Opens a new connection for the async client.
+    /// By default, the transport is considered to be sync-only.
+    ///
+    /// # Returns
+    /// A future resolving to a boxed [`FalkorAsyncTransport`]
+    #[cfg(feature = "tokio")]
+    fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+        Box::pin(async { Err(FalkorDBError::UnavailableProvider) })
+    }
+}
+
+/// Encodes a command using the RESP protocol, for gateways that forward raw RESP frames
+///
+/// # Arguments
+/// * `args`: The command name followed by its arguments
+///
+/// # Returns
+/// The RESP-encoded bytes of this command
+pub fn encode_resp_command(args: &[String]) -> Vec<u8> {
+    let mut cmd = redis::Cmd::new();
+    for arg in args {
+        cmd.arg(arg.as_str());
+    }
+    cmd.get_packed_command()
+}
+
+/// Decodes a RESP reply received from a gateway, converting server errors to [`FalkorDBError`]
+///
+/// # Arguments
+/// * `bytes`: The raw RESP-encoded reply
+///
+/// # Returns
+/// The decoded [`redis::Value`], ready to be handed back to the client
+pub fn decode_resp_reply(bytes: &[u8]) -> FalkorResult<redis::Value> {
+    redis::parse_redis_value(bytes)
+        .map_err(|err| FalkorDBError::RedisParsingError(err.to_string()))?
+        .extract_error()
+        .map_err(map_redis_err)
+}
+
+pub(crate) fn flatten_command(
+    graph_name: Option<&str>,
+    command: &str,
+    subcommand: Option<&str>,
+    params: Option<&[&str]>,
+) -> Vec<String> {
+    [Some(command), subcommand, graph_name]
+        .into_iter()
+        .flatten()
+        .chain(params.unwrap_or_default().iter().copied())
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::MockTransport, CommandInterceptor, FalkorClientBuilder, FalkorConnectionInfo,
+        InterceptedCommand,
+    };
+    use std::sync::Arc;
+
+    fn gateway() -> MockTransport {
+        MockTransport::new("mock://gateway", |args| {
+            match args.first().map(String::as_str) {
+                Some("INFO") => Ok(redis::Value::Nil),
+                Some("GRAPH.LIST") => decode_resp_reply(b"*1\r\n$6\r\nsocial\r\n"),
+                _ => Err(FalkorDBError::InvalidDataReceived),
+            }
+        })
+    }
+
+    #[test]
+    fn test_flatten_command() {
+        assert_eq!(
+            flatten_command(
+                Some("social"),
+                "GRAPH.QUERY",
+                None,
+                Some(&["RETURN 1", "--compact"])
+            ),
+            vec!["GRAPH.QUERY", "social", "RETURN 1", "--compact"]
+        );
+        assert_eq!(
+            flatten_command(None, "INFO", Some("server"), None),
+            vec!["INFO", "server"]
+        );
+    }
+
+    #[test]
+    fn test_resp_round_trip() {
+        let encoded = encode_resp_command(&["GRAPH.LIST".to_string()]);
+        assert_eq!(encoded, b"*1\r\n$10\r\nGRAPH.LIST\r\n".to_vec());
+
+        assert_eq!(
+            decode_resp_reply(b"*2\r\n$1\r\na\r\n:5\r\n").unwrap(),
+            redis::Value::Array(vec![
+                redis::Value::BulkString(b"a".to_vec()),
+                redis::Value::Int(5)
+            ])
+        );
+        assert!(decode_resp_reply(b"-ERR boom\r\n").is_err());
+        assert!(decode_resp_reply(b"?").is_err());
+    }
+
+    #[test]
+    fn test_client_over_transport() {
+        let connection_info = FalkorConnectionInfo::Transport(Arc::new(gateway()));
+        assert_eq!(connection_info.address(), "mock://gateway");
+
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(connection_info)
+            .build()
+            .expect("Could not create client over transport");
+        assert_eq!(client.list_graphs(), Ok(vec!["social".to_string()]));
+    }
+
+    struct CachedGraphList;
+
+    impl CommandInterceptor for CachedGraphList {
+        fn before_command(
+            &self,
+            command: &mut InterceptedCommand,
+        ) -> Option<FalkorResult<redis::Value>> {
+            (command.name == "GRAPH.LIST").then(|| decode_resp_reply(b"*1\r\n$6\r\ncached\r\n"))
+        }
+    }
+
+    #[test]
+    fn test_client_interceptors() {
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(FalkorConnectionInfo::Transport(Arc::new(gateway())))
+            .with_interceptor(CachedGraphList)
+            .build()
+            .expect("Could not create client over transport");
+        assert_eq!(client.list_graphs(), Ok(vec!["cached".to_string()]));
+    }
+}
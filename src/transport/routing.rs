@@ -0,0 +1,881 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    connection::{map_connect_err, map_redis_err},
+    connection_info::format_addr,
+    FalkorConnectionInfo, FalkorDBError, FalkorResult, FalkorTransport, FalkorTransportProvider,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::{hash_map::RandomState, VecDeque},
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tokio")]
+use crate::transport::{FalkorAsyncTransport, TransportFuture};
+
+/// The default weight given to the newest sample of an endpoint, when updating its moving averages
+const DEFAULT_SMOOTHING: f64 = 0.2;
+
+/// How much a fully failing endpoint's latency is inflated by, when computing its routing weight
+const ERROR_PENALTY: f64 = 20.0;
+
+/// Latencies below this are considered equal, so a single fast reply does not starve the other endpoints
+const MIN_LATENCY: Duration = Duration::from_micros(100);
+
+/// How many recent read-only latencies are kept, to compute the hedging threshold from
+const HEDGING_WINDOW: usize = 128;
+
+/// Read-only commands are not hedged until this many latencies were observed
+#[cfg(feature = "tokio")]
+const HEDGING_MIN_SAMPLES: usize = 16;
+
+/// The command sent for read-only queries, which are the only ones safe to send twice
+#[cfg(feature = "tokio")]
+const READ_ONLY_COMMAND: &str = "GRAPH.RO_QUERY";
+
+/// A policy for hedging read-only queries sent through [`HealthAwareProvider`]
+/// When a read-only query has not been replied to within a percentile of the recently observed latencies,
+/// it is sent again to another endpoint, and whichever reply arrives first is used, bounding the tail latency.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HedgingPolicy {
+    percentile: f64,
+    min_delay: Duration,
+}
+
+impl Default for HedgingPolicy {
+    fn default() -> Self {
+        Self {
+            percentile: 0.95,
+            min_delay: Duration::from_millis(1),
+        }
+    }
+}
+
+impl HedgingPolicy {
+    /// Creates a new hedging policy, hedging queries slower than the given percentile of recent read-only latencies
+    ///
+    /// # Arguments
+    /// * `percentile`: A value between 0 and 1, clamped to that range, E.g. 0.95 to hedge the slowest 5% of queries
+    ///
+    /// # Returns
+    /// The new [`HedgingPolicy`]
+    pub fn new(percentile: f64) -> Self {
+        Self {
+            percentile: percentile.clamp(0.0, 1.0),
+            ..Default::default()
+        }
+    }
+
+    /// Never hedge before this much time has passed, regardless of the observed latencies, the default is 1ms
+    ///
+    /// # Arguments
+    /// * `min_delay`: The minimum delay before a query is hedged
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_min_delay(
+        self,
+        min_delay: Duration,
+    ) -> Self {
+        Self { min_delay, ..self }
+    }
+}
+
+/// A snapshot of the health of a single endpoint, as tracked by [`HealthAwareProvider`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EndpointHealth {
+    /// The exponentially weighted moving average of the command latency, [`None`] until the first successful reply
+    pub latency: Option<Duration>,
+    /// The exponentially weighted moving average of the connection error rate, between 0 and 1
+    pub error_rate: f64,
+    /// The relative share of commands that will be routed to this endpoint
+    pub weight: f64,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    provider: Arc<dyn FalkorTransportProvider>,
+    latency: Mutex<Option<f64>>,
+    error_rate: Mutex<f64>,
+}
+
+#[derive(Debug)]
+struct RoutingState {
+    endpoints: Vec<Endpoint>,
+    smoothing: f64,
+    hedging: Option<HedgingPolicy>,
+    read_latencies: Mutex<VecDeque<Duration>>,
+}
+
+impl RoutingState {
+    #[cfg(feature = "tokio")]
+    fn record_read_latency(
+        &self,
+        elapsed: Duration,
+    ) {
+        let mut read_latencies = self.read_latencies.lock();
+        if read_latencies.len() == HEDGING_WINDOW {
+            read_latencies.pop_front();
+        }
+        read_latencies.push_back(elapsed);
+    }
+
+    /// How long to wait for a read-only command before hedging it, [`None`] if it should not be hedged
+    #[cfg(feature = "tokio")]
+    fn hedging_delay(&self) -> Option<Duration> {
+        let policy = self.hedging?;
+        if self.endpoints.len() < 2 {
+            return None;
+        }
+
+        let mut latencies: Vec<Duration> = self.read_latencies.lock().iter().copied().collect();
+        if latencies.len() < HEDGING_MIN_SAMPLES {
+            return None;
+        }
+        latencies.sort_unstable();
+        let rank = ((latencies.len() - 1) as f64 * policy.percentile).round() as usize;
+        Some(latencies[rank].max(policy.min_delay))
+    }
+
+    /// The healthiest endpoint other than the one already chosen
+    #[cfg(feature = "tokio")]
+    fn pick_hedge(
+        &self,
+        primary: usize,
+    ) -> usize {
+        self.by_weight()
+            .into_iter()
+            .find(|idx| *idx != primary)
+            .unwrap_or(primary)
+    }
+
+    fn record(
+        &self,
+        idx: usize,
+        res: Result<Duration, ()>,
+    ) {
+        let endpoint = &self.endpoints[idx];
+        let alpha = self.smoothing;
+        let failed = if res.is_err() { 1.0 } else { 0.0 };
+        {
+            let mut error_rate = endpoint.error_rate.lock();
+            *error_rate = alpha * failed + (1.0 - alpha) * *error_rate;
+        }
+
+        if let Ok(elapsed) = res {
+            let mut latency = endpoint.latency.lock();
+            let sample = elapsed.as_secs_f64();
+            *latency = Some(match *latency {
+                Some(average) => alpha * sample + (1.0 - alpha) * average,
+                None => sample,
+            });
+        }
+    }
+
+    fn weights(&self) -> Vec<f64> {
+        let raw: Vec<Option<f64>> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                endpoint.latency.lock().map(|latency| {
+                    1.0 / (latency.max(MIN_LATENCY.as_secs_f64())
+                        * (1.0 + ERROR_PENALTY * *endpoint.error_rate.lock()))
+                })
+            })
+            .collect();
+
+        // Endpoints which never replied are weighted as the best known endpoint, so they still get explored
+        let best = raw
+            .iter()
+            .flatten()
+            .copied()
+            .fold(None, |best: Option<f64>, weight| {
+                Some(best.map_or(weight, |best| best.max(weight)))
+            });
+        self.endpoints
+            .iter()
+            .zip(raw)
+            .map(|(endpoint, weight)| {
+                weight.unwrap_or_else(|| {
+                    best.unwrap_or(1.0) / (1.0 + ERROR_PENALTY * *endpoint.error_rate.lock())
+                })
+            })
+            .collect()
+    }
+
+    fn pick(&self) -> usize {
+        let weights = self.weights();
+        let mut target = random_unit() * weights.iter().sum::<f64>();
+        for (idx, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return idx;
+            }
+            target -= weight;
+        }
+        weights.len() - 1
+    }
+
+    /// Endpoint indices, healthiest first
+    fn by_weight(&self) -> Vec<usize> {
+        let weights = self.weights();
+        let mut order: Vec<usize> = (0..weights.len()).collect();
+        order.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]));
+        order
+    }
+}
+
+fn random_unit() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Only failures of the endpoint itself count against its health, not errors returned by the server for a specific query
+fn is_endpoint_failure(err: &FalkorDBError) -> bool {
+    err.is_retryable() || matches!(err, FalkorDBError::RedisError(_))
+}
+
+/// A [`FalkorTransportProvider`] spreading commands over several endpoints, such as replicas of the same database,
+/// biased toward the healthy and fast ones.
+///
+/// Every command is routed separately, with each endpoint chosen with a probability proportional to its weight,
+/// which is derived from exponentially weighted moving averages of its latency and connection error rate.
+/// This means slow or failing endpoints receive less traffic, but are still probed, so they regain traffic once they recover.
+///
+/// All endpoints are expected to accept every command sent through the client,
+/// so read-only replicas should only be used by clients which only perform read-only queries.
+#[derive(Clone, Debug)]
+pub struct HealthAwareProvider {
+    state: Arc<RoutingState>,
+}
+
+impl HealthAwareProvider {
+    /// Creates a new provider routing between the given endpoints
+    ///
+    /// # Arguments
+    /// * `endpoints`: The providers of the individual endpoints, must not be empty
+    ///
+    /// # Returns
+    /// The new [`HealthAwareProvider`], or an error if no endpoints were provided
+    pub fn new(endpoints: Vec<Arc<dyn FalkorTransportProvider>>) -> FalkorResult<Self> {
+        if endpoints.is_empty() {
+            return Err(FalkorDBError::InvalidConnectionInfo(
+                "At least one endpoint is required for routing".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            state: Arc::new(RoutingState {
+                endpoints: endpoints
+                    .into_iter()
+                    .map(|provider| Endpoint {
+                        provider,
+                        latency: Mutex::new(None),
+                        error_rate: Mutex::new(0.0),
+                    })
+                    .collect(),
+                smoothing: DEFAULT_SMOOTHING,
+                hedging: None,
+                read_latencies: Mutex::new(VecDeque::with_capacity(HEDGING_WINDOW)),
+            }),
+        })
+    }
+
+    /// Creates a new provider routing between Redis endpoints, given as connection strings
+    ///
+    /// # Arguments
+    /// * `urls`: The connection strings of the endpoints, E.g. `falkor://10.0.0.1:6379`
+    ///
+    /// # Returns
+    /// The new [`HealthAwareProvider`], or an error if any of the connection strings could not be parsed
+    pub fn from_urls<S: AsRef<str>>(urls: &[S]) -> FalkorResult<Self> {
+        let endpoints = urls
+            .iter()
+            .map(|url| endpoint_from_url(url.as_ref()))
+            .collect::<FalkorResult<Vec<_>>>()?;
+
+        Self::new(endpoints)
+    }
+
+    /// Sets how much weight the newest sample is given when updating the moving averages, the default is 0.2
+    /// Higher values react faster to changes in health, lower values are less sensitive to spikes.
+    ///
+    /// # Arguments
+    /// * `smoothing`: A factor between 0 and 1, clamped to that range
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_smoothing(
+        self,
+        smoothing: f64,
+    ) -> Self {
+        Self {
+            state: Arc::new(RoutingState {
+                smoothing: smoothing.clamp(0.0, 1.0),
+                ..self.into_state()
+            }),
+        }
+    }
+
+    /// Hedge read-only queries according to the given policy, see [`HedgingPolicy`]
+    /// Hedging is only performed by asynchronous connections, and requires at least two endpoints.
+    ///
+    /// # Arguments
+    /// * `policy`: The [`HedgingPolicy`] to apply
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_hedging(
+        self,
+        policy: HedgingPolicy,
+    ) -> Self {
+        Self {
+            state: Arc::new(RoutingState {
+                hedging: Some(policy),
+                ..self.into_state()
+            }),
+        }
+    }
+
+    fn into_state(self) -> RoutingState {
+        Arc::try_unwrap(self.state).unwrap_or_else(|state| RoutingState {
+            endpoints: state
+                .endpoints
+                .iter()
+                .map(|endpoint| Endpoint {
+                    provider: endpoint.provider.clone(),
+                    latency: Mutex::new(*endpoint.latency.lock()),
+                    error_rate: Mutex::new(*endpoint.error_rate.lock()),
+                })
+                .collect(),
+            smoothing: state.smoothing,
+            hedging: state.hedging,
+            read_latencies: Mutex::new(state.read_latencies.lock().clone()),
+        })
+    }
+
+    /// Returns a snapshot of the tracked health of each endpoint
+    ///
+    /// # Returns
+    /// The address and [`EndpointHealth`] of each endpoint, in the order they were provided
+    pub fn health(&self) -> Vec<(String, EndpointHealth)> {
+        let weights = self.state.weights();
+        let total: f64 = weights.iter().sum();
+        self.state
+            .endpoints
+            .iter()
+            .zip(weights)
+            .map(|(endpoint, weight)| {
+                (
+                    endpoint.provider.address(),
+                    EndpointHealth {
+                        latency: endpoint.latency.lock().map(Duration::from_secs_f64),
+                        error_rate: *endpoint.error_rate.lock(),
+                        weight: weight / total,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl FalkorTransportProvider for HealthAwareProvider {
+    fn address(&self) -> String {
+        self.state
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.provider.address())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+        let mut connections: Vec<Option<Box<dyn FalkorTransport>>> =
+            self.state.endpoints.iter().map(|_| None).collect();
+
+        // Make sure at least one endpoint is reachable, the others are connected on first use
+        let mut last_err = FalkorDBError::NoConnection;
+        for idx in self.state.by_weight() {
+            match self.state.endpoints[idx].provider.connect() {
+                Ok(connection) => {
+                    connections[idx] = Some(connection);
+                    return Ok(Box::new(RoutedConnection {
+                        state: self.state.clone(),
+                        connections,
+                    }));
+                }
+                Err(err) => {
+                    self.state.record(idx, Err(()));
+                    last_err = err;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    #[cfg(feature = "tokio")]
+    fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+        Box::pin(async move {
+            let mut connections: Vec<Option<Box<dyn FalkorAsyncTransport>>> =
+                self.state.endpoints.iter().map(|_| None).collect();
+
+            let mut last_err = FalkorDBError::NoConnection;
+            for idx in self.state.by_weight() {
+                match self.state.endpoints[idx].provider.connect_async().await {
+                    Ok(connection) => {
+                        connections[idx] = Some(connection);
+                        return Ok(Box::new(AsyncRoutedConnection {
+                            state: self.state.clone(),
+                            connections,
+                        }) as Box<dyn FalkorAsyncTransport>);
+                    }
+                    Err(err) => {
+                        self.state.record(idx, Err(()));
+                        last_err = err;
+                    }
+                }
+            }
+            Err(last_err)
+        })
+    }
+}
+
+struct RoutedConnection {
+    state: Arc<RoutingState>,
+    connections: Vec<Option<Box<dyn FalkorTransport>>>,
+}
+
+impl FalkorTransport for RoutedConnection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value> {
+        let idx = self.state.pick();
+        let start = Instant::now();
+
+        let connection = match &mut self.connections[idx] {
+            Some(connection) => connection,
+            slot => match self.state.endpoints[idx].provider.connect() {
+                Ok(connection) => slot.insert(connection),
+                Err(err) => {
+                    self.state.record(idx, Err(()));
+                    return Err(err);
+                }
+            },
+        };
+
+        let res = connection.execute(args);
+        match &res {
+            Err(err) if is_endpoint_failure(err) => {
+                self.connections[idx] = None;
+                self.state.record(idx, Err(()));
+            }
+            _ => self.state.record(idx, Ok(start.elapsed())),
+        }
+        res
+    }
+}
+
+#[cfg(feature = "tokio")]
+struct AsyncRoutedConnection {
+    state: Arc<RoutingState>,
+    connections: Vec<Option<Box<dyn FalkorAsyncTransport>>>,
+}
+
+#[cfg(feature = "tokio")]
+async fn connect_slot<'a>(
+    state: &RoutingState,
+    idx: usize,
+    slot: &'a mut Option<Box<dyn FalkorAsyncTransport>>,
+) -> FalkorResult<&'a mut Box<dyn FalkorAsyncTransport>> {
+    if slot.is_none() {
+        match state.endpoints[idx].provider.connect_async().await {
+            Ok(connection) => *slot = Some(connection),
+            Err(err) => {
+                state.record(idx, Err(()));
+                return Err(err);
+            }
+        }
+    }
+    slot.as_mut().ok_or(FalkorDBError::EmptyConnection)
+}
+
+#[cfg(feature = "tokio")]
+fn settle(
+    state: &RoutingState,
+    idx: usize,
+    slot: &mut Option<Box<dyn FalkorAsyncTransport>>,
+    start: Instant,
+    res: &FalkorResult<redis::Value>,
+) {
+    match res {
+        Err(err) if is_endpoint_failure(err) => {
+            *slot = None;
+            state.record(idx, Err(()));
+        }
+        _ => state.record(idx, Ok(start.elapsed())),
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncRoutedConnection {
+    async fn execute_on(
+        &mut self,
+        idx: usize,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value> {
+        let start = Instant::now();
+        let slot = &mut self.connections[idx];
+        let res = connect_slot(&self.state, idx, slot)
+            .await?
+            .execute(args)
+            .await;
+        settle(&self.state, idx, slot, start, &res);
+        res
+    }
+
+    /// Sends the command to the primary endpoint, and if it does not reply in time, to the hedge endpoint as well
+    async fn execute_hedged(
+        &mut self,
+        primary: usize,
+        hedge: usize,
+        delay: Duration,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value> {
+        let start = Instant::now();
+        let (primary_slot, hedge_slot) = match primary < hedge {
+            true => {
+                let (left, right) = self.connections.split_at_mut(hedge);
+                (&mut left[primary], &mut right[0])
+            }
+            false => {
+                let (left, right) = self.connections.split_at_mut(primary);
+                (&mut right[0], &mut left[hedge])
+            }
+        };
+
+        let primary_connection = connect_slot(&self.state, primary, primary_slot).await?;
+        let (hedge_won, hedged, res) = {
+            let primary_fut = primary_connection.execute(args.clone());
+            tokio::pin!(primary_fut);
+
+            tokio::select! {
+                res = &mut primary_fut => (false, false, res),
+                _ = crate::runtime::sleep(delay) => {
+                    match connect_slot(&self.state, hedge, hedge_slot).await {
+                        Ok(hedge_connection) => tokio::select! {
+                            res = &mut primary_fut => (false, true, res),
+                            res = hedge_connection.execute(args) => (true, true, res),
+                        },
+                        Err(_) => (false, false, primary_fut.await),
+                    }
+                }
+            }
+        };
+
+        let (winner, winner_slot, loser, loser_slot) = match hedge_won {
+            true => (hedge, hedge_slot, primary, primary_slot),
+            false => (primary, primary_slot, hedge, hedge_slot),
+        };
+        settle(&self.state, winner, winner_slot, start, &res);
+        if hedged {
+            // The cancelled request may still be replied to, so its connection can not be reused,
+            // and the time it was given is a lower bound of its latency
+            *loser_slot = None;
+            self.state.record(loser, Ok(start.elapsed()));
+        }
+        res
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl FalkorAsyncTransport for AsyncRoutedConnection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> TransportFuture<'_, redis::Value> {
+        Box::pin(async move {
+            let idx = self.state.pick();
+            let start = Instant::now();
+
+            let read_only = args.first().map(String::as_str) == Some(READ_ONLY_COMMAND);
+            let res = match read_only.then(|| self.state.hedging_delay()).flatten() {
+                Some(delay) => {
+                    let hedge = self.state.pick_hedge(idx);
+                    self.execute_hedged(idx, hedge, delay, args).await
+                }
+                None => self.execute_on(idx, args).await,
+            };
+
+            if read_only && res.is_ok() {
+                self.state.record_read_latency(start.elapsed());
+            }
+            res
+        })
+    }
+}
+
+/// Connects to a single Redis endpoint, so it can be routed to by [`HealthAwareProvider`] or [`FailoverProvider`](crate::FailoverProvider)
+#[derive(Debug)]
+struct RedisEndpointProvider {
+    client: redis::Client,
+}
+
+/// Creates the provider of a single endpoint, given as a connection string
+pub(crate) fn endpoint_from_url(url: &str) -> FalkorResult<Arc<dyn FalkorTransportProvider>> {
+    match FalkorConnectionInfo::try_from(url)? {
+        FalkorConnectionInfo::Redis(connection_info) => Ok(Arc::new(RedisEndpointProvider {
+            client: redis::Client::open(connection_info)
+                .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
+        })),
+        FalkorConnectionInfo::Transport(provider) => Ok(provider),
+    }
+}
+
+fn args_to_cmd(args: &[String]) -> redis::Cmd {
+    let mut cmd = redis::Cmd::new();
+    for arg in args {
+        cmd.arg(arg.as_str());
+    }
+    cmd
+}
+
+impl FalkorTransportProvider for RedisEndpointProvider {
+    fn address(&self) -> String {
+        format_addr(&self.client.get_connection_info().addr)
+    }
+
+    fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+        Ok(Box::new(RedisEndpointConnection(
+            self.client
+                .get_connection()
+                .map_err(|err| map_connect_err(&self.client.get_connection_info().addr, err))?,
+        )))
+    }
+
+    #[cfg(feature = "tokio")]
+    fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+        Box::pin(async move {
+            Ok(Box::new(AsyncRedisEndpointConnection(
+                self.client
+                    .get_multiplexed_async_connection()
+                    .await
+                    .map_err(|err| map_connect_err(&self.client.get_connection_info().addr, err))?,
+            )) as Box<dyn FalkorAsyncTransport>)
+        })
+    }
+}
+
+struct RedisEndpointConnection(redis::Connection);
+
+impl FalkorTransport for RedisEndpointConnection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value> {
+        use redis::ConnectionLike as _;
+        self.0
+            .req_command(&args_to_cmd(&args))
+            .map_err(map_redis_err)
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub(crate) struct AsyncRedisEndpointConnection(pub(crate) redis::aio::MultiplexedConnection);
+
+#[cfg(feature = "tokio")]
+impl FalkorAsyncTransport for AsyncRedisEndpointConnection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> TransportFuture<'_, redis::Value> {
+        Box::pin(async move {
+            self.0
+                .send_packed_command(&args_to_cmd(&args))
+                .await
+                .map_err(map_redis_err)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransport, FalkorClientBuilder, FalkorConnectionInfo};
+
+    fn provider(endpoints: &[(&'static str, bool)]) -> HealthAwareProvider {
+        HealthAwareProvider::new(
+            endpoints
+                .iter()
+                .map(|&(name, healthy)| {
+                    Arc::new(MockTransport::new(name, move |args| {
+                        match args.first().map(String::as_str) {
+                            _ if !healthy => Err(FalkorDBError::ConnectionDown),
+                            Some("GRAPH.LIST") => Ok(redis::Value::Array(vec![])),
+                            _ => Ok(redis::Value::Nil),
+                        }
+                    })) as Arc<dyn FalkorTransportProvider>
+                })
+                .collect(),
+        )
+        .expect("Could not create provider")
+    }
+
+    #[test]
+    fn test_weights_follow_health() {
+        let provider = provider(&[("fast", true), ("slow", true), ("failing", true)]);
+        assert_eq!(provider.address(), "fast,slow,failing");
+        assert!(provider
+            .health()
+            .iter()
+            .all(|(_, health)| (health.weight - 1.0 / 3.0).abs() < 1e-9));
+
+        for _ in 0..10 {
+            provider.state.record(0, Ok(Duration::from_millis(1)));
+            provider.state.record(1, Ok(Duration::from_millis(10)));
+            provider.state.record(2, Err(()));
+        }
+
+        let health = provider.health();
+        assert_eq!(health[0].1.latency, Some(Duration::from_millis(1)));
+        assert_eq!(health[2].1.latency, None);
+        assert!(health[2].1.error_rate > 0.8);
+        assert!(health[0].1.weight > health[1].1.weight);
+        assert!(health[1].1.weight > health[2].1.weight);
+        assert!(health[2].1.weight > 0.0);
+        assert!((health.iter().map(|(_, h)| h.weight).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(provider.state.by_weight(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_routing_avoids_failing_endpoint() {
+        let provider = provider(&[("healthy", true), ("failing", false)]).with_smoothing(0.5);
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(FalkorConnectionInfo::Transport(Arc::new(provider.clone())))
+            .build()
+            .expect("Could not create client over routed transport");
+
+        let failures = (0..200).filter(|_| client.list_graphs().is_err()).count();
+        assert!(
+            failures < 50,
+            "Too many commands routed to the failing endpoint: {failures}"
+        );
+
+        let health = provider.health();
+        assert!(health[0].1.weight > 0.9);
+        assert!(health[1].1.error_rate > 0.5);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_hedging_delay() {
+        let provider = provider(&[("a", true), ("b", true)]);
+        for millis in 1..=20 {
+            provider
+                .state
+                .record_read_latency(Duration::from_millis(millis));
+        }
+        assert_eq!(provider.state.hedging_delay(), None);
+
+        let provider = provider.with_hedging(HedgingPolicy::new(0.9));
+        assert_eq!(
+            provider.state.hedging_delay(),
+            Some(Duration::from_millis(18))
+        );
+
+        let provider =
+            provider.with_hedging(HedgingPolicy::new(0.0).with_min_delay(Duration::from_millis(5)));
+        assert_eq!(
+            provider.state.hedging_delay(),
+            Some(Duration::from_millis(5))
+        );
+
+        provider.state.read_latencies.lock().truncate(10);
+        assert_eq!(provider.state.hedging_delay(), None);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(Debug)]
+    struct DelayedProvider(Duration);
+
+    #[cfg(feature = "tokio")]
+    struct DelayedTransport(Duration);
+
+    #[cfg(feature = "tokio")]
+    impl FalkorAsyncTransport for DelayedTransport {
+        fn execute(
+            &mut self,
+            _args: Vec<String>,
+        ) -> TransportFuture<'_, redis::Value> {
+            Box::pin(async move {
+                crate::runtime::sleep(self.0).await;
+                Ok(redis::Value::Int(self.0.as_millis() as i64))
+            })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl FalkorTransportProvider for DelayedProvider {
+        fn address(&self) -> String {
+            format!("{:?}", self.0)
+        }
+
+        fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+            Err(FalkorDBError::UnavailableProvider)
+        }
+
+        fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+            Box::pin(async move {
+                Ok(Box::new(DelayedTransport(self.0)) as Box<dyn FalkorAsyncTransport>)
+            })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_hedged_read_takes_first_reply() {
+        let provider = HealthAwareProvider::new(vec![
+            Arc::new(DelayedProvider(Duration::from_secs(5))),
+            Arc::new(DelayedProvider(Duration::from_millis(1))),
+        ])
+        .expect("Could not create provider")
+        .with_hedging(HedgingPolicy::default());
+
+        let mut connection = AsyncRoutedConnection {
+            state: provider.state.clone(),
+            connections: vec![None, None],
+        };
+        let start = Instant::now();
+        let res = connection
+            .execute_hedged(
+                0,
+                1,
+                Duration::from_millis(10),
+                vec![READ_ONLY_COMMAND.to_string()],
+            )
+            .await;
+        assert_eq!(res, Ok(redis::Value::Int(1)));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // The slow endpoint's cancelled connection is discarded
+        assert!(connection.connections[0].is_none());
+        assert!(connection.connections[1].is_some());
+    }
+
+    #[test]
+    fn test_empty_endpoints() {
+        assert!(HealthAwareProvider::new(vec![]).is_err());
+        assert!(HealthAwareProvider::from_urls(&["falkor://127.0.0.1:6379"]).is_ok());
+    }
+}
@@ -0,0 +1,505 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{
+    client::TcpOptions, connection::map_redis_err, transport::encode_resp_command, FalkorDBError,
+    FalkorResult, FalkorTransport, FalkorTransportProvider,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    io::{BufReader, Read, Write},
+    net::{IpAddr, TcpStream},
+};
+
+#[cfg(feature = "tokio")]
+use crate::transport::{
+    routing::AsyncRedisEndpointConnection, FalkorAsyncTransport, TransportFuture,
+};
+
+const SOCKS_VERSION: u8 = 0x05;
+const AUTH_VERSION: u8 = 0x01;
+const METHOD_NO_AUTH: u8 = 0x00;
+const METHOD_USERNAME_PASSWORD: u8 = 0x02;
+const METHOD_UNACCEPTABLE: u8 = 0xFF;
+const COMMAND_CONNECT: u8 = 0x01;
+const ADDRESS_IPV4: u8 = 0x01;
+const ADDRESS_DOMAIN: u8 = 0x03;
+const ADDRESS_IPV6: u8 = 0x04;
+
+/// A SOCKS5 proxy to connect to the database through, for environments where it is only reachable via a bastion or proxy,
+/// see [`FalkorClientBuilder::with_socks5_proxy`](crate::FalkorClientBuilder::with_socks5_proxy)
+#[derive(Clone, PartialEq, Eq)]
+pub struct Socks5Proxy {
+    host: String,
+    port: u16,
+    credentials: Option<(String, String)>,
+}
+
+impl Socks5Proxy {
+    /// Creates a new [`Socks5Proxy`], which does not require authentication
+    ///
+    /// # Arguments
+    /// * `host`: The hostname or IP address of the proxy
+    /// * `port`: The port the proxy listens on, usually 1080
+    ///
+    /// # Returns
+    /// The new [`Socks5Proxy`]
+    pub fn new<H: ToString>(
+        host: H,
+        port: u16,
+    ) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            credentials: None,
+        }
+    }
+
+    /// Authenticates with the proxy using a username and password
+    ///
+    /// # Arguments
+    /// * `username`: The username to authenticate as, at most 255 bytes long
+    /// * `password`: The user's password, at most 255 bytes long
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_auth<U: ToString, P: ToString>(
+        self,
+        username: U,
+        password: P,
+    ) -> Self {
+        Self {
+            credentials: Some((username.to_string(), password.to_string())),
+            ..self
+        }
+    }
+
+    fn address(&self) -> String {
+        match self.host.contains(':') {
+            true => format!("[{}]:{}", self.host, self.port),
+            false => format!("{}:{}", self.host, self.port),
+        }
+    }
+
+    fn unavailable(
+        &self,
+        reason: impl ToString,
+    ) -> FalkorDBError {
+        FalkorDBError::EndpointUnavailable {
+            endpoint: self.address(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// The greeting offering the authentication methods this proxy is configured for
+    fn greeting(&self) -> Vec<u8> {
+        match self.credentials {
+            Some(_) => vec![SOCKS_VERSION, 2, METHOD_NO_AUTH, METHOD_USERNAME_PASSWORD],
+            None => vec![SOCKS_VERSION, 1, METHOD_NO_AUTH],
+        }
+    }
+
+    /// Checks the method chosen by the proxy, returning the username and password sub-negotiation request, if it chose it
+    fn auth_request(
+        &self,
+        reply: [u8; 2],
+    ) -> FalkorResult<Option<Vec<u8>>> {
+        match (reply, &self.credentials) {
+            ([SOCKS_VERSION, METHOD_NO_AUTH], _) => Ok(None),
+            ([SOCKS_VERSION, METHOD_USERNAME_PASSWORD], Some((username, password))) => {
+                let (username, password) = (username.as_bytes(), password.as_bytes());
+                let (Ok(username_len), Ok(password_len)) =
+                    (u8::try_from(username.len()), u8::try_from(password.len()))
+                else {
+                    return Err(FalkorDBError::InvalidConnectionInfo(
+                        "SOCKS5 username and password must be at most 255 bytes long".to_string(),
+                    ));
+                };
+
+                let mut request = vec![AUTH_VERSION, username_len];
+                request.extend(username);
+                request.push(password_len);
+                request.extend(password);
+                Ok(Some(request))
+            }
+            ([SOCKS_VERSION, METHOD_UNACCEPTABLE], _) => Err(FalkorDBError::AuthenticationFailed(
+                "The SOCKS5 proxy accepted none of the offered authentication methods".to_string(),
+            )),
+            _ => Err(self.unavailable("Invalid SOCKS5 method selection reply")),
+        }
+    }
+
+    fn check_auth_reply(
+        &self,
+        reply: [u8; 2],
+    ) -> FalkorResult<()> {
+        match reply {
+            [AUTH_VERSION, 0] => Ok(()),
+            _ => Err(FalkorDBError::AuthenticationFailed(
+                "The SOCKS5 proxy rejected the username or password".to_string(),
+            )),
+        }
+    }
+
+    /// Checks the head of the proxy's reply to the connect request,
+    /// returning how many bytes of bound address and port follow it, which are of no use to the client
+    fn check_connect_reply(
+        &self,
+        head: [u8; 4],
+        domain_len: u8,
+    ) -> FalkorResult<usize> {
+        let [SOCKS_VERSION, status, _, address_type] = head else {
+            return Err(self.unavailable("Invalid SOCKS5 connect reply"));
+        };
+        if status != 0 {
+            return Err(self.unavailable(format!(
+                "The SOCKS5 proxy could not connect to the database: {}",
+                connect_status_reason(status)
+            )));
+        }
+        match address_type {
+            ADDRESS_IPV4 => Ok(4 + 2),
+            ADDRESS_IPV6 => Ok(16 + 2),
+            ADDRESS_DOMAIN => Ok(domain_len as usize + 2),
+            _ => Err(self.unavailable("Invalid SOCKS5 bound address type")),
+        }
+    }
+}
+
+impl Debug for Socks5Proxy {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Socks5Proxy")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field(
+                "username",
+                &self.credentials.as_ref().map(|(username, _)| username),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+fn connect_status_reason(status: u8) -> &'static str {
+    match status {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+/// The request asking the proxy to connect to the database, addressed by IP if possible, otherwise by hostname
+fn connect_request(
+    host: &str,
+    port: u16,
+) -> FalkorResult<Vec<u8>> {
+    let mut request = vec![SOCKS_VERSION, COMMAND_CONNECT, 0];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(ADDRESS_IPV4);
+            request.extend(ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(ADDRESS_IPV6);
+            request.extend(ip.octets());
+        }
+        Err(_) => {
+            let len = u8::try_from(host.len()).map_err(|_| {
+                FalkorDBError::InvalidConnectionInfo(format!("Hostname is too long: {host}"))
+            })?;
+            request.extend([ADDRESS_DOMAIN, len]);
+            request.extend(host.as_bytes());
+        }
+    }
+    request.extend(port.to_be_bytes());
+    Ok(request)
+}
+
+/// A [`FalkorTransportProvider`] connecting to a Redis endpoint through a SOCKS5 proxy,
+/// created by the client builder when a proxy is configured
+#[derive(Debug)]
+pub(crate) struct Socks5Provider {
+    proxy: Socks5Proxy,
+    host: String,
+    port: u16,
+    redis: redis::RedisConnectionInfo,
+    tcp_options: TcpOptions,
+}
+
+impl Socks5Provider {
+    pub(crate) fn new(
+        proxy: Socks5Proxy,
+        connection_info: redis::ConnectionInfo,
+        tcp_options: TcpOptions,
+    ) -> FalkorResult<Self> {
+        let redis::ConnectionAddr::Tcp(host, port) = connection_info.addr else {
+            return Err(FalkorDBError::InvalidConnectionInfo(
+                "Only plain TCP connections can be made through a SOCKS5 proxy".to_string(),
+            ));
+        };
+        Ok(Self {
+            proxy,
+            host,
+            port,
+            redis: connection_info.redis,
+            tcp_options,
+        })
+    }
+
+    /// Commands sent on every new connection, before it is handed to the client, to authenticate and select the database
+    fn setup_commands(&self) -> Vec<Vec<String>> {
+        let mut commands = Vec::new();
+        if let Some(password) = &self.redis.password {
+            let mut auth = vec!["AUTH".to_string()];
+            auth.extend(self.redis.username.clone());
+            auth.push(password.clone());
+            commands.push(auth);
+        }
+        if self.redis.db != 0 {
+            commands.push(vec!["SELECT".to_string(), self.redis.db.to_string()]);
+        }
+        commands
+    }
+
+    fn handshake(
+        &self,
+        stream: &mut TcpStream,
+    ) -> FalkorResult<()> {
+        let io_err = |err: std::io::Error| self.proxy.unavailable(err);
+
+        stream.write_all(&self.proxy.greeting()).map_err(io_err)?;
+        let mut reply = [0; 2];
+        stream.read_exact(&mut reply).map_err(io_err)?;
+        if let Some(request) = self.proxy.auth_request(reply)? {
+            stream.write_all(&request).map_err(io_err)?;
+            stream.read_exact(&mut reply).map_err(io_err)?;
+            self.proxy.check_auth_reply(reply)?;
+        }
+
+        stream
+            .write_all(&connect_request(&self.host, self.port)?)
+            .map_err(io_err)?;
+        let mut head = [0; 4];
+        stream.read_exact(&mut head).map_err(io_err)?;
+        let mut domain_len = [0; 1];
+        if head[3] == ADDRESS_DOMAIN {
+            stream.read_exact(&mut domain_len).map_err(io_err)?;
+        }
+        let mut bound = vec![0; self.proxy.check_connect_reply(head, domain_len[0])?];
+        stream.read_exact(&mut bound).map_err(io_err)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn handshake_async(
+        &self,
+        stream: &mut crate::runtime::TcpStream,
+    ) -> FalkorResult<()> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let io_err = |err: std::io::Error| self.proxy.unavailable(err);
+
+        stream
+            .write_all(&self.proxy.greeting())
+            .await
+            .map_err(io_err)?;
+        let mut reply = [0; 2];
+        stream.read_exact(&mut reply).await.map_err(io_err)?;
+        if let Some(request) = self.proxy.auth_request(reply)? {
+            stream.write_all(&request).await.map_err(io_err)?;
+            stream.read_exact(&mut reply).await.map_err(io_err)?;
+            self.proxy.check_auth_reply(reply)?;
+        }
+
+        stream
+            .write_all(&connect_request(&self.host, self.port)?)
+            .await
+            .map_err(io_err)?;
+        let mut head = [0; 4];
+        stream.read_exact(&mut head).await.map_err(io_err)?;
+        let mut domain_len = [0; 1];
+        if head[3] == ADDRESS_DOMAIN {
+            stream.read_exact(&mut domain_len).await.map_err(io_err)?;
+        }
+        let mut bound = vec![0; self.proxy.check_connect_reply(head, domain_len[0])?];
+        stream.read_exact(&mut bound).await.map_err(io_err)?;
+        Ok(())
+    }
+}
+
+impl FalkorTransportProvider for Socks5Provider {
+    fn address(&self) -> String {
+        format!(
+            "{}:{} via socks5://{}",
+            self.host,
+            self.port,
+            self.proxy.address()
+        )
+    }
+
+    fn connect(&self) -> FalkorResult<Box<dyn FalkorTransport>> {
+        let mut stream = TcpStream::connect((self.proxy.host.as_str(), self.proxy.port))
+            .map_err(|err| self.proxy.unavailable(err))?;
+        self.tcp_options
+            .apply((&stream).into())
+            .map_err(|err| self.proxy.unavailable(err))?;
+        self.handshake(&mut stream)?;
+
+        let mut connection = Socks5Connection {
+            stream: BufReader::new(stream),
+            parser: redis::Parser::new(),
+        };
+        for command in self.setup_commands() {
+            connection.execute(command)?;
+        }
+        Ok(Box::new(connection))
+    }
+
+    #[cfg(feature = "tokio")]
+    fn connect_async(&self) -> TransportFuture<'_, Box<dyn FalkorAsyncTransport>> {
+        Box::pin(async move {
+            let mut stream = crate::runtime::connect_tcp(&self.proxy.host, self.proxy.port)
+                .await
+                .map_err(|err| self.proxy.unavailable(err))?;
+            self.tcp_options
+                .apply(stream.socket())
+                .map_err(|err| self.proxy.unavailable(err))?;
+            self.handshake_async(&mut stream).await?;
+
+            // The multiplexed connection authenticates and selects the database by itself
+            let (connection, driver) = redis::aio::MultiplexedConnection::new(&self.redis, stream)
+                .await
+                .map_err(map_redis_err)?;
+            crate::runtime::spawn(driver);
+            Ok(Box::new(AsyncRedisEndpointConnection(connection)) as Box<dyn FalkorAsyncTransport>)
+        })
+    }
+}
+
+/// A connection to Redis, tunnelled through a SOCKS5 proxy
+struct Socks5Connection {
+    stream: BufReader<TcpStream>,
+    parser: redis::Parser,
+}
+
+impl FalkorTransport for Socks5Connection {
+    fn execute(
+        &mut self,
+        args: Vec<String>,
+    ) -> FalkorResult<redis::Value> {
+        self.stream
+            .get_mut()
+            .write_all(&encode_resp_command(&args))
+            .map_err(|_| FalkorDBError::ConnectionDown)?;
+        self.parser
+            .parse_value(&mut self.stream)
+            .and_then(redis::Value::extract_error)
+            .map_err(map_redis_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::TcpListener, thread};
+
+    /// Accepts a single connection, expecting username and password authentication, and replies to commands with `+OK`
+    fn spawn_mock_proxy() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind mock proxy");
+        let port = listener
+            .local_addr()
+            .expect("Could not get mock proxy address")
+            .port();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("Could not accept connection");
+            let mut greeting = [0; 4];
+            stream.read_exact(&mut greeting).ok();
+            assert_eq!(greeting, [SOCKS_VERSION, 2, 0, 2]);
+            stream.write_all(&[SOCKS_VERSION, 2]).ok();
+
+            let mut auth = [0; 13];
+            stream.read_exact(&mut auth).ok();
+            assert_eq!(&auth, b"\x01\x04user\x06secret");
+            stream.write_all(&[AUTH_VERSION, 0]).ok();
+
+            let mut connect = [0; 7 + 9];
+            stream.read_exact(&mut connect).ok();
+            assert_eq!(&connect, b"\x05\x01\x00\x03\x09falkor-db\x18\xeb");
+            stream
+                .write_all(&[SOCKS_VERSION, 0, 0, ADDRESS_IPV4, 10, 0, 0, 1, 0x18, 0xeb])
+                .ok();
+
+            let mut buf = [0; 64];
+            while stream.read(&mut buf).is_ok_and(|read| read > 0) {
+                stream.write_all(b"+OK\r\n").ok();
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_connect_request() {
+        assert_eq!(
+            connect_request("10.0.0.1", 6379).ok(),
+            Some(vec![5, 1, 0, ADDRESS_IPV4, 10, 0, 0, 1, 0x18, 0xeb])
+        );
+        assert_eq!(
+            connect_request("::1", 6379).map(|request| request[3]).ok(),
+            Some(ADDRESS_IPV6)
+        );
+        assert!(connect_request(&"a".repeat(256), 6379).is_err());
+    }
+
+    #[test]
+    fn test_handshake_replies() {
+        let proxy = Socks5Proxy::new("bastion", 1080);
+        assert_eq!(
+            proxy.auth_request([SOCKS_VERSION, METHOD_NO_AUTH]),
+            Ok(None)
+        );
+        assert!(matches!(
+            proxy.auth_request([SOCKS_VERSION, METHOD_UNACCEPTABLE]),
+            Err(FalkorDBError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            proxy.check_connect_reply([SOCKS_VERSION, 0x05, 0, ADDRESS_IPV4], 0),
+            Err(FalkorDBError::EndpointUnavailable { reason, .. }) if reason.ends_with("connection refused")
+        ));
+        assert_eq!(
+            proxy.check_connect_reply([SOCKS_VERSION, 0, 0, ADDRESS_DOMAIN], 9),
+            Ok(11)
+        );
+        assert!(!format!("{:?}", proxy.with_auth("user", "secret")).contains("secret"));
+    }
+
+    #[test]
+    fn test_connect_through_proxy() {
+        let port = spawn_mock_proxy();
+        let provider = Socks5Provider::new(
+            Socks5Proxy::new("127.0.0.1", port).with_auth("user", "secret"),
+            "redis://falkor-db:6379"
+                .parse::<redis::ConnectionInfo>()
+                .expect("Could not parse connection info"),
+            TcpOptions {
+                nodelay: Some(true),
+                keepalive: Some(std::time::Duration::from_secs(60)),
+            },
+        )
+        .expect("Could not create provider");
+
+        let mut connection = provider.connect().expect("Could not connect through proxy");
+        assert_eq!(
+            connection.execute(vec!["PING".to_string()]),
+            Ok(redis::Value::Okay)
+        );
+    }
+}
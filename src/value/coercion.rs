@@ -0,0 +1,169 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::FalkorValue;
+use std::fmt::{Display, Formatter};
+
+/// A value whose type changed between being written to the server and being read back,
+/// E.g. a float with no fractional part stored as an integer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeCoercion {
+    /// Where the coerced value resides within the written value, E.g. `[2]` or `.weights[0]`, empty for the value itself
+    pub path: String,
+    /// The [`FalkorValue`] variant which was written
+    pub written: &'static str,
+    /// The [`FalkorValue`] variant which was read back
+    pub read: &'static str,
+}
+
+impl Display for TypeCoercion {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{}written as {}, read back as {}",
+            match self.path.is_empty() {
+                true => String::new(),
+                false => format!("{}: ", self.path),
+            },
+            self.written,
+            self.read
+        )
+    }
+}
+
+/// Returns the name of the [`FalkorValue`] variant of a value
+pub(crate) fn variant_name(value: &FalkorValue) -> &'static str {
+    match value {
+        FalkorValue::Node(_) => "Node",
+        FalkorValue::Edge(_) => "Edge",
+        FalkorValue::Array(_) => "Array",
+        FalkorValue::Map(_) => "Map",
+        FalkorValue::Vec32(_) => "Vec32",
+        FalkorValue::String(_) => "String",
+        FalkorValue::Bool(_) => "Bool",
+        FalkorValue::I64(_) => "I64",
+        FalkorValue::F64(_) => "F64",
+        FalkorValue::Point(_) => "Point",
+        FalkorValue::Path(_) => "Path",
+        FalkorValue::None => "None",
+        FalkorValue::Unparseable(_) => "Unparseable",
+    }
+}
+
+fn collect_type_coercions(
+    path: String,
+    written: &FalkorValue,
+    read: &FalkorValue,
+    coercions: &mut Vec<TypeCoercion>,
+) {
+    match (written, read) {
+        (FalkorValue::Array(written), FalkorValue::Array(read)) => {
+            for (idx, written) in written.iter().enumerate() {
+                collect_type_coercions(
+                    format!("{path}[{idx}]"),
+                    written,
+                    read.get(idx).unwrap_or(&FalkorValue::None),
+                    coercions,
+                );
+            }
+        }
+        (FalkorValue::Map(written), FalkorValue::Map(read)) => {
+            for (key, written) in written {
+                collect_type_coercions(
+                    format!("{path}.{key}"),
+                    written,
+                    read.get(key).unwrap_or(&FalkorValue::None),
+                    coercions,
+                );
+            }
+        }
+        _ => {
+            let (written, read) = (variant_name(written), variant_name(read));
+            if written != read {
+                coercions.push(TypeCoercion {
+                    path,
+                    written,
+                    read,
+                });
+            }
+        }
+    }
+}
+
+/// Compares a value written to the server with the value read back, reporting every value whose type was coerced
+/// Arrays and maps are compared element by element, so a single coerced element is reported by its path.
+///
+/// # Arguments
+/// * `written`: The value which was written
+/// * `read`: The value which was read back
+///
+/// # Returns
+/// A [`Vec`] of the [`TypeCoercion`]s found, empty if the value kept its type
+pub fn find_type_coercions(
+    written: &FalkorValue,
+    read: &FalkorValue,
+) -> Vec<TypeCoercion> {
+    let mut coercions = vec![];
+    collect_type_coercions(String::new(), written, read, &mut coercions);
+    coercions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FalkorMap;
+
+    #[test]
+    fn test_find_type_coercions() {
+        assert!(find_type_coercions(&FalkorValue::I64(1), &FalkorValue::I64(2)).is_empty());
+        assert!(find_type_coercions(&FalkorValue::F64(1.0), &FalkorValue::F64(1.0)).is_empty());
+        assert_eq!(
+            find_type_coercions(&FalkorValue::F64(1.0), &FalkorValue::I64(1)),
+            vec![TypeCoercion {
+                path: String::new(),
+                written: "F64",
+                read: "I64"
+            }]
+        );
+
+        let written = FalkorValue::Array(vec![
+            FalkorValue::I64(1),
+            FalkorValue::F64(2.0),
+            FalkorValue::String("three".to_string()),
+        ]);
+        let read = FalkorValue::Array(vec![
+            FalkorValue::I64(1),
+            FalkorValue::I64(2),
+            FalkorValue::String("three".to_string()),
+        ]);
+        let coercions = find_type_coercions(&written, &read);
+        assert_eq!(coercions.len(), 1);
+        assert_eq!(coercions[0].path, "[1]");
+        assert_eq!(
+            coercions[0].to_string(),
+            "[1]: written as F64, read back as I64"
+        );
+
+        let written = FalkorValue::Map(FalkorMap::from_iter([(
+            "weights".to_string(),
+            FalkorValue::Array(vec![FalkorValue::F64(0.5)]),
+        )]));
+        let read = FalkorValue::Map(FalkorMap::from_iter([(
+            "weights".to_string(),
+            FalkorValue::Array(vec![]),
+        )]));
+        assert_eq!(
+            find_type_coercions(&written, &read),
+            vec![TypeCoercion {
+                path: ".weights[0]".to_string(),
+                written: "F64",
+                read: "None"
+            }]
+        );
+    }
+}
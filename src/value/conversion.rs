@@ -0,0 +1,172 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use crate::{Edge, FalkorDBError, FalkorMap, FalkorResult, FalkorValue, Node, Path, Point};
+
+/// Conversion from a [`FalkorValue`] into a concrete type
+pub trait FromFalkorValue: Sized {
+    /// Converts the value, failing if it holds a different type
+    fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self>;
+}
+
+macro_rules! impl_from_falkor_value {
+    ($t:ty, $variant:ident, $err:expr) => {
+        impl FromFalkorValue for $t {
+            fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self> {
+                match value {
+                    FalkorValue::$variant(inner) => Ok(inner),
+                    _ => Err($err),
+                }
+            }
+        }
+    };
+}
+
+impl_from_falkor_value!(i64, I64, FalkorDBError::ParsingI64);
+impl_from_falkor_value!(f64, F64, FalkorDBError::ParsingF64);
+impl_from_falkor_value!(bool, Bool, FalkorDBError::ParsingBool);
+impl_from_falkor_value!(String, String, FalkorDBError::ParsingString);
+impl_from_falkor_value!(Node, Node, FalkorDBError::ParsingFNode);
+impl_from_falkor_value!(Edge, Edge, FalkorDBError::ParsingFEdge);
+impl_from_falkor_value!(Path, Path, FalkorDBError::ParsingPath);
+impl_from_falkor_value!(Point, Point, FalkorDBError::ParsingFPoint);
+impl_from_falkor_value!(FalkorMap, Map, FalkorDBError::ParsingMap);
+
+impl FromFalkorValue for FalkorValue {
+    fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self> {
+        Ok(value)
+    }
+}
+
+impl<T: FromFalkorValue> FromFalkorValue for Option<T> {
+    fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self> {
+        match value {
+            FalkorValue::None => Ok(None),
+            other => T::from_falkor_value(other).map(Some),
+        }
+    }
+}
+
+impl<T: FromFalkorValue> FromFalkorValue for Vec<T> {
+    fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self> {
+        value
+            .into_vec()?
+            .into_iter()
+            .map(T::from_falkor_value)
+            .collect()
+    }
+}
+
+/// Conversion from a row of YIELD columns into a concrete type, used by typed procedure calls
+/// This is implemented for tuples of up to 8 elements, each converted from its respective column, in order.
+pub trait FromYieldRow: Sized {
+    /// The number of YIELD columns this type is converted from
+    const COLUMNS: usize;
+
+    /// Converts a single row, failing if any of its columns holds a different type
+    ///
+    /// # Arguments
+    /// * `header`: The names of the yielded columns, used for error reporting
+    /// * `row`: The values of the yielded columns, in header order
+    fn from_yield_row(
+        header: &[String],
+        row: Vec<FalkorValue>,
+    ) -> FalkorResult<Self>;
+}
+
+/// Validates a procedure's yielded header against the column count of the requested type
+pub(crate) fn check_yield_header<T: FromYieldRow>(header: &[String]) -> FalkorResult<()> {
+    match header.len() == T::COLUMNS {
+        true => Ok(()),
+        false => Err(FalkorDBError::YieldColumnMismatch(format!(
+            "expected {} columns, the procedure yielded {} ({})",
+            T::COLUMNS,
+            header.len(),
+            header.join(", ")
+        ))),
+    }
+}
+
+macro_rules! impl_from_yield_row {
+    ($count:literal; $($t:ident),+) => {
+        impl<$($t: FromFalkorValue),+> FromYieldRow for ($($t,)+) {
+            const COLUMNS: usize = $count;
+
+            fn from_yield_row(
+                header: &[String],
+                row: Vec<FalkorValue>,
+            ) -> FalkorResult<Self> {
+                if row.len() != Self::COLUMNS {
+                    return Err(FalkorDBError::YieldColumnMismatch(format!(
+                        "expected {} columns, the row contained {}",
+                        Self::COLUMNS,
+                        row.len()
+                    )));
+                }
+
+                let mut columns = header.iter().zip(row);
+                Ok(($({
+                    let (name, value) = columns.next().ok_or_else(|| {
+                        FalkorDBError::YieldColumnMismatch("the header is missing columns".to_string())
+                    })?;
+                    $t::from_falkor_value(value).map_err(|err| {
+                        FalkorDBError::YieldColumnMismatch(format!("column {name}: {err}"))
+                    })?
+                },)+))
+            }
+        }
+    };
+}
+
+impl_from_yield_row!(1; A);
+impl_from_yield_row!(2; A, B);
+impl_from_yield_row!(3; A, B, C);
+impl_from_yield_row!(4; A, B, C, D);
+impl_from_yield_row!(5; A, B, C, D, E);
+impl_from_yield_row!(6; A, B, C, D, E, F);
+impl_from_yield_row!(7; A, B, C, D, E, F, G);
+impl_from_yield_row!(8; A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_yield_row() {
+        let header = vec!["name".to_string(), "score".to_string()];
+        assert_eq!(
+            <(String, f64)>::from_yield_row(
+                &header,
+                vec![FalkorValue::from("Alice"), FalkorValue::F64(0.5)]
+            ),
+            Ok(("Alice".to_string(), 0.5))
+        );
+        assert_eq!(
+            <(String, Option<i64>)>::from_yield_row(
+                &header,
+                vec![FalkorValue::from("Alice"), FalkorValue::None]
+            ),
+            Ok(("Alice".to_string(), None))
+        );
+        assert_eq!(
+            <(String, i64)>::from_yield_row(
+                &header,
+                vec![FalkorValue::from("Alice"), FalkorValue::F64(0.5)]
+            ),
+            Err(FalkorDBError::YieldColumnMismatch(
+                "column score: Element was not of type I64".to_string()
+            ))
+        );
+        assert!(<(String,)>::from_yield_row(&header[..1], vec![]).is_err());
+
+        assert!(check_yield_header::<(String, f64)>(&header).is_ok());
+        assert_eq!(
+            check_yield_header::<(String,)>(&header),
+            Err(FalkorDBError::YieldColumnMismatch(
+                "expected 1 columns, the procedure yielded 2 (name, score)".to_string()
+            ))
+        );
+    }
+}
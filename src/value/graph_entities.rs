@@ -5,9 +5,8 @@
 
 use crate::{
     parser::{redis_value_as_int, redis_value_as_vec},
-    FalkorDBError, FalkorResult, FalkorValue, GraphSchema, SchemaType,
+    FalkorDBError, FalkorMap, FalkorResult, GraphSchema, SchemaType,
 };
-use std::collections::HashMap;
 
 /// Whether this element is a node or edge in the graph
 #[derive(Copy, Clone, Debug, Eq, PartialEq, strum::EnumString, strum::Display)]
@@ -27,8 +26,8 @@ pub struct Node {
     pub entity_id: i64,
     /// A [`Vec`] of the labels this node answers to
     pub labels: Vec<String>,
-    /// A [`HashMap`] of the properties in key-val form
-    pub properties: HashMap<String, FalkorValue>,
+    /// A [`FalkorMap`] of the properties in key-val form
+    pub properties: FalkorMap,
 }
 
 impl Node {
@@ -38,7 +37,7 @@ impl Node {
     )]
     pub(crate) fn parse(
         value: redis::Value,
-        graph_schema: &mut GraphSchema,
+        graph_schema: &GraphSchema,
     ) -> FalkorResult<Self> {
         let [entity_id, labels, properties]: [redis::Value; 3] = redis_value_as_vec(value)
             .and_then(|val_vec| {
@@ -68,8 +67,8 @@ pub struct Edge {
     pub src_node_id: i64,
     /// The entity ID of the destination node
     pub dst_node_id: i64,
-    /// A [`HashMap`] of the properties in key-val form
-    pub properties: HashMap<String, FalkorValue>,
+    /// A [`FalkorMap`] of the properties in key-val form
+    pub properties: FalkorMap,
 }
 
 impl Edge {
@@ -79,7 +78,7 @@ impl Edge {
     )]
     pub(crate) fn parse(
         value: redis::Value,
-        graph_schema: &mut GraphSchema,
+        graph_schema: &GraphSchema,
     ) -> FalkorResult<Self> {
         let [entity_id, relationship_id_raw, src_node_id, dst_node_id, properties]: [redis::Value;
             5] = redis_value_as_vec(value).and_then(|val_vec| {
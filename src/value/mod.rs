@@ -7,15 +7,29 @@ use crate::{FalkorDBError, FalkorResult};
 use graph_entities::{Edge, Node};
 use path::Path;
 use point::Point;
-use std::{collections::HashMap, fmt::Debug};
+use std::fmt::Debug;
 use vec32::Vec32;
 
+pub(crate) mod coercion;
 pub(crate) mod config;
+pub(crate) mod conversion;
 pub(crate) mod graph_entities;
 pub(crate) mod path;
 pub(crate) mod point;
 pub(crate) mod vec32;
 
+/// The map type used for [`FalkorValue::Map`], and for the properties of [`Node`]s and [`Edge`]s.
+/// By default this is a [`HashMap`](std::collections::HashMap), when the `preserve-order` feature is enabled this is an [`IndexMap`](indexmap::IndexMap) instead,
+/// which maintains the order in which the keys were returned by the server.
+#[cfg(not(feature = "preserve-order"))]
+pub type FalkorMap = std::collections::HashMap<String, FalkorValue>;
+
+/// The map type used for [`FalkorValue::Map`], and for the properties of [`Node`]s and [`Edge`]s.
+/// By default this is a [`HashMap`](std::collections::HashMap), when the `preserve-order` feature is enabled this is an [`IndexMap`](indexmap::IndexMap) instead,
+/// which maintains the order in which the keys were returned by the server.
+#[cfg(feature = "preserve-order")]
+pub type FalkorMap = indexmap::IndexMap<String, FalkorValue>;
+
 /// An enum of all the supported Falkor types
 #[derive(Clone, Debug, PartialEq)]
 pub enum FalkorValue {
@@ -25,8 +39,8 @@ pub enum FalkorValue {
     Edge(Edge),
     /// A [`Vec`] of other [`FalkorValue`]
     Array(Vec<FalkorValue>),
-    /// A [`HashMap`] of [`String`] as keys, and other [`FalkorValue`] as values
-    Map(HashMap<String, FalkorValue>),
+    /// A [`FalkorMap`] of [`String`] as keys, and other [`FalkorValue`] as values
+    Map(FalkorMap),
     /// A vector of float values used for vector search see [`Vec32`]
     Vec32(Vec32),
     /// Plain old string
@@ -68,6 +82,8 @@ impl_to_falkordb_value!(u64, Self::I64);
 impl_to_falkordb_value!(f32, Self::F64);
 impl_to_falkordb_value!(f64, Self::F64);
 
+impl_to_falkordb_value!(bool, Self::Bool);
+
 impl_to_falkordb_value!(String, Self::String);
 
 impl From<&str> for FalkorValue {
@@ -76,6 +92,12 @@ impl From<&str> for FalkorValue {
     }
 }
 
+impl<T: Into<FalkorValue>> From<Vec<T>> for FalkorValue {
+    fn from(value: Vec<T>) -> Self {
+        Self::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
 impl FalkorValue {
     /// Returns a reference to the internal [`Vec`] if this is an Array variant.
     ///
@@ -132,11 +154,11 @@ impl FalkorValue {
         }
     }
 
-    /// Returns a reference to the internal [`HashMap`] if this is an Map variant.
+    /// Returns a reference to the internal [`FalkorMap`] if this is an Map variant.
     ///
     /// # Returns
-    /// A reference to the internal [`HashMap`]
-    pub fn as_map(&self) -> Option<&HashMap<String, FalkorValue>> {
+    /// A reference to the internal [`FalkorMap`]
+    pub fn as_map(&self) -> Option<&FalkorMap> {
         match self {
             FalkorValue::Map(val) => Some(val),
             _ => None,
@@ -213,22 +235,107 @@ impl FalkorValue {
             _ => Err(FalkorDBError::ParsingString),
         }
     }
-    /// Consumes itself and returns the inner [`HashMap`] if this is a Map variant
+    /// Consumes itself and returns the inner [`FalkorMap`] if this is a Map variant
     ///
     /// # Returns
-    /// The inner [`HashMap`]
-    pub fn into_map(self) -> FalkorResult<HashMap<String, FalkorValue>> {
+    /// The inner [`FalkorMap`]
+    pub fn into_map(self) -> FalkorResult<FalkorMap> {
         match self {
             FalkorValue::Map(map) => Ok(map),
             _ => Err(FalkorDBError::ParsingMap),
         }
     }
+
+    /// Renders this value as a Cypher literal, so it can be passed as a query parameter
+    ///
+    /// # Returns
+    /// The literal, or an error for values which can't be expressed as one, such as graph entities
+    pub fn to_cypher_literal(&self) -> FalkorResult<String> {
+        Ok(match self {
+            FalkorValue::String(string) => {
+                format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            FalkorValue::Bool(boolean) => boolean.to_string(),
+            FalkorValue::I64(int) => int.to_string(),
+            FalkorValue::F64(float) if float.is_finite() => format!("{float:?}"),
+            FalkorValue::Array(array) => format!(
+                "[{}]",
+                array
+                    .iter()
+                    .map(FalkorValue::to_cypher_literal)
+                    .collect::<FalkorResult<Vec<_>>>()?
+                    .join(", ")
+            ),
+            FalkorValue::Map(map) => format!(
+                "{{{}}}",
+                map.iter()
+                    .map(|(key, val)| {
+                        val.to_cypher_literal()
+                            .map(|val| format!("`{}`: {val}", key.replace('`', "``")))
+                    })
+                    .collect::<FalkorResult<Vec<_>>>()?
+                    .join(", ")
+            ),
+            FalkorValue::Vec32(vec32) => format!(
+                "vecf32([{}])",
+                vec32
+                    .values
+                    .iter()
+                    .map(|val| format!("{val:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            FalkorValue::Point(point) => format!(
+                "point({{latitude: {:?}, longitude: {:?}}})",
+                point.latitude, point.longitude
+            ),
+            FalkorValue::None => "null".to_string(),
+            other => Err(FalkorDBError::InvalidParameter(format!("{other:?}")))?,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{collections::HashMap, f64::consts::PI};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_to_cypher_literal() {
+        assert_eq!(
+            FalkorValue::from("it's a \"quote\" \\").to_cypher_literal(),
+            Ok(r#""it's a \"quote\" \\""#.to_string())
+        );
+        assert_eq!(
+            FalkorValue::Array(vec![
+                FalkorValue::I64(1),
+                FalkorValue::F64(2.0),
+                FalkorValue::Bool(true),
+                FalkorValue::None
+            ])
+            .to_cypher_literal(),
+            Ok("[1, 2.0, true, null]".to_string())
+        );
+        assert_eq!(
+            FalkorValue::Map(FalkorMap::from([(
+                "a key".to_string(),
+                FalkorValue::I64(1)
+            )]))
+            .to_cypher_literal(),
+            Ok("{`a key`: 1}".to_string())
+        );
+        assert_eq!(
+            FalkorValue::Vec32(Vec32 {
+                values: vec![1.0, 0.5]
+            })
+            .to_cypher_literal(),
+            Ok("vecf32([1.0, 0.5])".to_string())
+        );
+        assert!(FalkorValue::F64(f64::NAN).to_cypher_literal().is_err());
+        assert!(FalkorValue::Node(Node::default())
+            .to_cypher_literal()
+            .is_err());
+    }
 
     #[test]
     fn test_as_vec() {
@@ -280,7 +387,7 @@ mod tests {
 
     #[test]
     fn test_as_map() {
-        let mut map = HashMap::new();
+        let mut map = FalkorMap::new();
         map.insert(String::from("key"), FalkorValue::I64(42));
         let map_val = FalkorValue::Map(map);
         assert!(map_val.as_map().is_some());
@@ -21,7 +21,7 @@ impl Path {
     )]
     pub(crate) fn parse(
         value: redis::Value,
-        graph_schema: &mut GraphSchema,
+        graph_schema: &GraphSchema,
     ) -> FalkorResult<Self> {
         let [nodes, relationships]: [redis::Value; 2] =
             redis_value_as_vec(value).and_then(|vec_val| {
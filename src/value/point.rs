@@ -8,6 +8,9 @@ use crate::{
     FalkorDBError, FalkorResult,
 };
 
+/// The mean radius of the earth, in meters
+const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
 /// A point in the world.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Point {
@@ -18,6 +21,41 @@ pub struct Point {
 }
 
 impl Point {
+    /// Creates a new point from its coordinates
+    ///
+    /// # Arguments
+    /// * `latitude`: The latitude, in degrees
+    /// * `longitude`: The longitude, in degrees
+    pub fn new(
+        latitude: f64,
+        longitude: f64,
+    ) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// Calculates the great-circle distance to another point, using the haversine formula
+    ///
+    /// # Arguments
+    /// * `other`: The point to measure the distance to
+    ///
+    /// # Returns
+    /// The distance, in meters
+    pub fn distance_to(
+        &self,
+        other: &Point,
+    ) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let delta_lat = lat2 - lat1;
+        let delta_long = (other.longitude - self.longitude).to_radians();
+
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (delta_long / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * a.sqrt().min(1.0).asin()
+    }
+
     /// Parses a point from a redis::Value::Array,
     /// taking the first element as an f64 latitude, and second element as an f64 longitude
     ///
@@ -49,6 +87,17 @@ impl Point {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_distance_to() {
+        let london = Point::new(51.5074, -0.1278);
+        let paris = Point::new(48.8566, 2.3522);
+
+        assert_eq!(london.distance_to(&london), 0.0);
+        assert_relative_eq!(london.distance_to(&paris), 343_560.0, max_relative = 0.001);
+        assert_relative_eq!(london.distance_to(&paris), paris.distance_to(&london));
+    }
 
     #[test]
     fn test_parse_valid_point() {